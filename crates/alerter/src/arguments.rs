@@ -0,0 +1,218 @@
+use {
+    crate::rules::RuleKind,
+    clap::Parser,
+    shared::{
+        arguments::{display_list, display_option, display_secret_option},
+        ethrpc,
+        http_client,
+    },
+    std::time::Duration,
+    url::Url,
+};
+
+#[derive(Parser)]
+pub struct Arguments {
+    #[clap(flatten)]
+    pub http_client: http_client::Arguments,
+
+    #[clap(flatten)]
+    pub ethrpc: ethrpc::Arguments,
+
+    /// Alerter update interval.
+    #[clap(
+        long,
+        env,
+        default_value = "30s",
+        value_parser = humantime::parse_duration,
+    )]
+    pub update_interval: Duration,
+
+    /// The rules to evaluate on every update.
+    #[clap(
+        long,
+        env,
+        value_enum,
+        use_value_delimiter = true,
+        default_value = "no-trades-but-matchable-order,no-settlement,auction-id-not-advancing,\
+                         no-competition-participants,api-error-rate"
+    )]
+    pub rules: Vec<RuleKind>,
+
+    /// Minimum time without a trade before alerting.
+    #[clap(
+        long,
+        env,
+        default_value = "10m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub time_without_trade: Duration,
+
+    /// Minimum time an order must have been matchable for before alerting.
+    #[clap(
+        long,
+        env,
+        default_value = "3m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub min_order_age: Duration,
+
+    /// Minimum time without a settled auction before alerting.
+    #[clap(
+        long,
+        env,
+        default_value = "30m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub time_without_settlement: Duration,
+
+    /// Minimum time the auction id must have been unchanged before alerting.
+    #[clap(
+        long,
+        env,
+        default_value = "5m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub time_without_new_auction: Duration,
+
+    /// How many auctions with user orders in a row may pass without any solver
+    /// participating before alerting.
+    #[clap(long, env, default_value = "10")]
+    pub max_auctions_without_participants: u64,
+
+    /// Maximum share of failed orderbook API requests within the error rate
+    /// window before alerting.
+    #[clap(long, env, default_value = "0.1")]
+    pub max_api_error_rate: f64,
+
+    /// The window over which the orderbook API error rate is computed.
+    #[clap(
+        long,
+        env,
+        default_value = "10m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub api_error_rate_window: Duration,
+
+    /// Do not repeat the alert of a rule more often than this.
+    #[clap(
+        long,
+        env,
+        default_value = "30m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub min_alert_interval: Duration,
+
+    /// How many errors in the update loop (fetching solvable orders or querying
+    /// prices) in a row must happen before we alert about them.
+    #[clap(long, env, default_value = "5")]
+    pub errors_in_a_row_before_alert: u32,
+
+    #[clap(long, env, default_value = "https://api.cow.fi/mainnet/")]
+    pub orderbook_api: String,
+
+    /// The Ethereum node URL to connect to. Only required for checking order
+    /// prices, which is done if a 0x API key or price estimation drivers are
+    /// configured.
+    #[clap(long, env)]
+    pub node_url: Option<Url>,
+
+    #[clap(long, env, default_value = "9588")]
+    pub metrics_port: u16,
+
+    /// Minimum time between get order requests to the api. Without this the api
+    /// can rate limit us.
+    #[clap(long, env, default_value = "200ms", value_parser = humantime::parse_duration)]
+    pub api_get_order_min_interval: Duration,
+
+    /// The 0x API key. The 0x price estimator is only used if this is set.
+    #[clap(long, env)]
+    pub zero_ex_api_key: Option<String>,
+
+    #[clap(long, env, default_value = "https://api.0x.org/")]
+    pub zero_ex_url: Url,
+
+    /// Drivers whose quote endpoint is used to check if an order can be
+    /// settled at the current market price. Allows checking prices on chains
+    /// where 0x is not available.
+    #[clap(long, env, use_value_delimiter = true)]
+    pub price_estimation_drivers: Vec<Url>,
+
+    /// Endpoints that firing alerts get POSTed to as JSON.
+    #[clap(long, env, use_value_delimiter = true)]
+    pub alert_webhook_urls: Vec<Url>,
+
+    /// Slack compatible incoming webhooks that firing alerts get posted to.
+    #[clap(long, env, use_value_delimiter = true)]
+    pub slack_webhook_urls: Vec<Url>,
+}
+
+impl std::fmt::Display for Arguments {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Arguments {
+            http_client,
+            ethrpc,
+            update_interval,
+            rules,
+            time_without_trade,
+            min_order_age,
+            time_without_settlement,
+            time_without_new_auction,
+            max_auctions_without_participants,
+            max_api_error_rate,
+            api_error_rate_window,
+            min_alert_interval,
+            errors_in_a_row_before_alert,
+            orderbook_api,
+            node_url,
+            metrics_port,
+            api_get_order_min_interval,
+            zero_ex_api_key,
+            zero_ex_url,
+            price_estimation_drivers,
+            alert_webhook_urls,
+            slack_webhook_urls,
+        } = self;
+
+        write!(f, "{}", http_client)?;
+        write!(f, "{}", ethrpc)?;
+        writeln!(f, "update_interval: {:?}", update_interval)?;
+        writeln!(f, "rules: {:?}", rules)?;
+        writeln!(f, "time_without_trade: {:?}", time_without_trade)?;
+        writeln!(f, "min_order_age: {:?}", min_order_age)?;
+        writeln!(f, "time_without_settlement: {:?}", time_without_settlement)?;
+        writeln!(
+            f,
+            "time_without_new_auction: {:?}",
+            time_without_new_auction
+        )?;
+        writeln!(
+            f,
+            "max_auctions_without_participants: {}",
+            max_auctions_without_participants
+        )?;
+        writeln!(f, "max_api_error_rate: {}", max_api_error_rate)?;
+        writeln!(f, "api_error_rate_window: {:?}", api_error_rate_window)?;
+        writeln!(f, "min_alert_interval: {:?}", min_alert_interval)?;
+        writeln!(
+            f,
+            "errors_in_a_row_before_alert: {}",
+            errors_in_a_row_before_alert
+        )?;
+        writeln!(f, "orderbook_api: {}", orderbook_api)?;
+        display_option(f, "node_url", node_url)?;
+        writeln!(f, "metrics_port: {}", metrics_port)?;
+        writeln!(
+            f,
+            "api_get_order_min_interval: {:?}",
+            api_get_order_min_interval
+        )?;
+        display_secret_option(f, "zero_ex_api_key", zero_ex_api_key)?;
+        writeln!(f, "zero_ex_url: {}", zero_ex_url)?;
+        display_list(f, "price_estimation_drivers", price_estimation_drivers)?;
+        display_list(f, "alert_webhook_urls", alert_webhook_urls)?;
+        // Slack webhook URLs contain the token needed to post messages.
+        let _intentionally_ignored = slack_webhook_urls;
+        writeln!(f, "slack_webhook_urls: SECRET")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+//! Client for the parts of the orderbook API that the alerter observes.
+
+use {
+    anyhow::{Context, Result},
+    model::{
+        auction::AuctionId,
+        order::{OrderClass, OrderKind, OrderStatus, OrderUid},
+        solver_competition::SolverCompetitionAPI,
+    },
+    number::serialization::HexOrDecimalU256,
+    primitive_types::{H160, U256},
+    reqwest::{Client, StatusCode},
+    serde::de::DeserializeOwned,
+    serde_with::serde_as,
+    std::{
+        collections::VecDeque,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+    url::Url,
+};
+
+#[serde_as]
+#[derive(Clone, Debug, serde::Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub kind: OrderKind,
+    pub buy_token: H160,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub buy_amount: U256,
+    pub sell_token: H160,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub sell_amount: U256,
+    pub uid: OrderUid,
+    pub partially_fillable: bool,
+    #[serde(flatten)]
+    pub class: OrderClass,
+    // Some if the order is fetched from api/v1/orders/{uid}
+    // None if the order is fetched from api/v1/auction
+    #[serde(default)]
+    pub status: Option<OrderStatus>,
+}
+
+impl Order {
+    pub fn is_liquidity_order(&self) -> bool {
+        matches!(self.class, OrderClass::Liquidity)
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Auction {
+    pub id: AuctionId,
+    pub orders: Vec<Order>,
+}
+
+pub struct OrderBookApi {
+    base: Url,
+    client: Client,
+    /// Outcomes of the requests sent within the error rate window.
+    requests: Mutex<VecDeque<(Instant, bool)>>,
+    error_rate_window: Duration,
+}
+
+/// How many of the requests sent within the error rate window failed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RequestStats {
+    pub total: usize,
+    pub failed: usize,
+}
+
+impl OrderBookApi {
+    pub fn new(client: Client, base_url: &str, error_rate_window: Duration) -> Self {
+        Self {
+            base: base_url.parse().unwrap(),
+            client,
+            requests: Default::default(),
+            error_rate_window,
+        }
+    }
+
+    pub async fn auction(&self) -> Result<Auction> {
+        self.get("api/v1/auction")
+            .await?
+            .context("no current auction")
+    }
+
+    pub async fn order(&self, uid: &OrderUid) -> Result<Order> {
+        self.get(&format!("api/v1/orders/{uid}"))
+            .await?
+            .context("order not found")
+    }
+
+    /// Returns `None` if no solver competition was stored yet.
+    pub async fn latest_solver_competition(&self) -> Result<Option<SolverCompetitionAPI>> {
+        self.get("api/v1/solver_competition/latest").await
+    }
+
+    /// Returns `None` if no competition was stored for the auction.
+    pub async fn solver_competition(
+        &self,
+        auction_id: AuctionId,
+    ) -> Result<Option<SolverCompetitionAPI>> {
+        self.get(&format!("api/v1/solver_competition/{auction_id}"))
+            .await
+    }
+
+    pub fn request_stats(&self) -> RequestStats {
+        let mut requests = self.requests.lock().unwrap();
+        self.prune(&mut requests);
+        RequestStats {
+            total: requests.len(),
+            failed: requests.iter().filter(|(_, ok)| !ok).count(),
+        }
+    }
+
+    /// Sends a GET request to the API and records its outcome. Returns `None`
+    /// if the resource does not exist.
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        let result = self.send(path).await;
+        let mut requests = self.requests.lock().unwrap();
+        self.prune(&mut requests);
+        requests.push_back((Instant::now(), result.is_ok()));
+        result
+    }
+
+    async fn send<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        let url = shared::url::join(&self.base, path);
+        let response = self.client.get(url).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.json().await?))
+    }
+
+    fn prune(&self, requests: &mut VecDeque<(Instant, bool)>) {
+        while requests
+            .front()
+            .is_some_and(|(sent, _)| sent.elapsed() > self.error_rate_window)
+        {
+            requests.pop_front();
+        }
+    }
+}
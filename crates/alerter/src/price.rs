@@ -0,0 +1,85 @@
+//! Checks whether an order could be settled at the current market price
+//! according to the configured price estimators.
+
+use {
+    crate::orderbook::Order,
+    anyhow::{anyhow, ensure, Context, Result},
+    model::order::{OrderKind, BUY_ETH_ADDRESS},
+    number::nonzero::U256 as NonZeroU256,
+    primitive_types::H160,
+    shared::price_estimation::{PriceEstimating, Query},
+    std::sync::Arc,
+};
+
+pub struct PriceChecker {
+    estimators: Vec<(String, Arc<dyn PriceEstimating>)>,
+    native_token: H160,
+}
+
+impl PriceChecker {
+    pub fn new(
+        estimators: Vec<(String, Arc<dyn PriceEstimating>)>,
+        native_token: H160,
+    ) -> Result<Self> {
+        ensure!(
+            !estimators.is_empty(),
+            "checking prices requires a 0x api key or price estimation drivers"
+        );
+        Ok(Self {
+            estimators,
+            native_token,
+        })
+    }
+
+    /// An order can be settled if any estimator finds a price that satisfies
+    /// its limit price. Errors only if all estimators fail.
+    pub async fn can_be_settled(&self, order: &Order) -> Result<bool> {
+        let amount = match order.kind {
+            OrderKind::Buy => order.buy_amount,
+            OrderKind::Sell => order.sell_amount,
+        };
+        let query = Arc::new(Query {
+            sell_token: order.sell_token,
+            buy_token: self.convert_eth_to_native_token(order.buy_token),
+            in_amount: NonZeroU256::new(amount).context("zero order amount")?,
+            kind: order.kind,
+            verification: None,
+            block_dependent: false,
+        });
+
+        let mut succeeded = false;
+        let mut error = None;
+        for (name, estimator) in &self.estimators {
+            let estimate = match estimator.estimate(query.clone()).await {
+                Ok(estimate) => estimate,
+                Err(err) => {
+                    tracing::debug!(estimator = name, ?err, "price estimation failed");
+                    error = Some(anyhow!(err).context(format!("estimator {name}")));
+                    continue;
+                }
+            };
+            succeeded = true;
+            let (sell_amount, buy_amount) = estimate.amounts(&query);
+            tracing::debug!(estimator = name, ?query, ?estimate, "price estimate");
+            if sell_amount <= order.sell_amount && buy_amount >= order.buy_amount {
+                tracing::debug!(%order.uid, estimator = name, "marking order as settleable");
+                return Ok(true);
+            }
+        }
+
+        match error {
+            Some(err) if !succeeded => Err(err),
+            _ => Ok(false),
+        }
+    }
+
+    // Converts the eth placeholder address to the native token. Leaves other
+    // addresses untouched.
+    fn convert_eth_to_native_token(&self, token: H160) -> H160 {
+        if token == BUY_ETH_ADDRESS {
+            self.native_token
+        } else {
+            token
+        }
+    }
+}
@@ -0,0 +1,494 @@
+//! Conditions that indicate that the protocol is not working as expected.
+
+use {
+    crate::{
+        orderbook::{Auction, Order, OrderBookApi},
+        price::PriceChecker,
+    },
+    anyhow::{Context, Result},
+    model::{
+        auction::AuctionId,
+        order::{OrderClass, OrderStatus, OrderUid},
+        solver_competition::SolverCompetitionAPI,
+    },
+    std::{
+        collections::{HashMap, VecDeque},
+        sync::Arc,
+        time::{Duration, Instant},
+    },
+};
+
+/// The state of the orderbook API fetched once per update and shared by all
+/// rules. Fields are `None` if fetching them failed.
+#[derive(Debug, Default)]
+pub struct Observation {
+    pub auction: Option<Auction>,
+    pub latest_competition: Option<SolverCompetitionAPI>,
+}
+
+#[async_trait::async_trait]
+pub trait Rule: Send {
+    /// Identifies the rule in metrics and alert payloads.
+    fn name(&self) -> &'static str;
+
+    /// Returns a description of the problem if the alert condition holds.
+    async fn evaluate(&mut self, observation: &Observation) -> Result<Option<String>>;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, clap::ValueEnum)]
+pub enum RuleKind {
+    NoTradesButMatchableOrder,
+    NoSettlement,
+    AuctionIdNotAdvancing,
+    NoCompetitionParticipants,
+    ApiErrorRate,
+}
+
+/// Alerts if no trades happened for some time even though there is an order
+/// that could be settled at the current market price.
+pub struct NoTradesButMatchableOrder {
+    orderbook: Arc<OrderBookApi>,
+    prices: PriceChecker,
+    // Alert if no trades have been observed for this long.
+    time_without_trade: Duration,
+    // Give the solver some time to settle an order after it has become solvable before we alert.
+    min_order_solvable_time: Duration,
+    // Minimum time between get order requests to the api.
+    api_get_order_min_interval: Duration,
+    last_observed_trade: Instant,
+    // order and for how long it has been matchable
+    open_orders: HashMap<OrderUid, (Order, Option<Instant>)>,
+}
+
+impl NoTradesButMatchableOrder {
+    pub fn new(
+        orderbook: Arc<OrderBookApi>,
+        prices: PriceChecker,
+        time_without_trade: Duration,
+        min_order_solvable_time: Duration,
+        api_get_order_min_interval: Duration,
+    ) -> Self {
+        Self {
+            orderbook,
+            prices,
+            time_without_trade,
+            min_order_solvable_time,
+            api_get_order_min_interval,
+            last_observed_trade: Instant::now(),
+            open_orders: HashMap::new(),
+        }
+    }
+
+    async fn update_open_orders(&mut self, auction: &Auction) -> Result<()> {
+        let mut orders = auction
+            .orders
+            .iter()
+            .filter(|order| !order.is_liquidity_order() && !order.partially_fillable)
+            .map(|order| {
+                let existing_time = self.open_orders.get(&order.uid).and_then(|o| o.1);
+                (order.uid, (order.clone(), existing_time))
+            })
+            .collect::<HashMap<_, _>>();
+
+        tracing::debug!("found {} open orders", orders.len());
+
+        std::mem::swap(&mut self.open_orders, &mut orders);
+        let mut closed_orders: Vec<Order> = orders.into_values().map(|(order, _)| order).collect();
+        // Keep only orders that were open last update and are not open this update.
+        closed_orders.retain(|order| !self.open_orders.contains_key(&order.uid));
+        // We're trying to find an order that has been filled. Try market orders first
+        // because they are more likely to be.
+        closed_orders.sort_unstable_by_key(|order| match order.class {
+            OrderClass::Market => 0u8,
+            OrderClass::Limit => 1,
+            OrderClass::Liquidity => 2,
+        });
+        for order in closed_orders {
+            let uid = &order.uid;
+            tracing::debug!(order =% uid, "found closed order");
+            let start = Instant::now();
+            let api_order = self.orderbook.order(uid).await.context("get order")?;
+            if api_order.status == Some(OrderStatus::Fulfilled) {
+                tracing::debug!(
+                    "updating last observed trade because order {} was fulfilled",
+                    uid
+                );
+                self.last_observed_trade = Instant::now();
+                return Ok(());
+            }
+            tokio::time::sleep_until((start + self.api_get_order_min_interval).into()).await;
+        }
+        tracing::debug!("found no fulfilled orders");
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Rule for NoTradesButMatchableOrder {
+    fn name(&self) -> &'static str {
+        "no_trades_but_matchable_order"
+    }
+
+    async fn evaluate(&mut self, observation: &Observation) -> Result<Option<String>> {
+        let Some(auction) = &observation.auction else {
+            return Ok(None);
+        };
+        self.update_open_orders(auction).await?;
+        if self.last_observed_trade.elapsed() <= self.time_without_trade {
+            // Delete all matchable timestamps.
+            //
+            // If we didn't do this what could happen is that first we mark an order as
+            // matchable at t0. Then a trade happens so we skip the matchable
+            // update loop below because if there was a recent trade we don't
+            // want to alert anyway. Then no trade happens for long enough that
+            // we want to alert and the order is again matchable. In this case
+            // we would alert immediately even though it could be the case that the
+            // order wasn't matchable and just now became matchable again. We would wrongly
+            // assume it has been matchable since t0 but we did not check this
+            // between now and then.
+            for (_, instant) in self.open_orders.values_mut() {
+                *instant = None;
+            }
+            return Ok(None);
+        }
+
+        for (order, last_solvable) in self.open_orders.values_mut() {
+            let can_be_settled = self
+                .prices
+                .can_be_settled(order)
+                .await
+                .context("can_be_settled")?;
+            if !can_be_settled {
+                *last_solvable = None;
+                continue;
+            }
+            let now = Instant::now();
+            let solvable_since = *last_solvable.get_or_insert(now);
+            if now.duration_since(solvable_since) > self.min_order_solvable_time {
+                return Ok(Some(format!(
+                    "No orders have been settled in the last {} seconds even though order {} is \
+                     solvable and has a price that allows it to be settled.",
+                    self.time_without_trade.as_secs(),
+                    order.uid,
+                )));
+            }
+            return Ok(None);
+        }
+
+        Ok(None)
+    }
+}
+
+/// Alerts if no auction got settled for some time.
+pub struct NoSettlement {
+    orderbook: Arc<OrderBookApi>,
+    max_time_without_settlement: Duration,
+    last_settlement: Instant,
+    // The most recent settled competition. Observing it again doesn't count as
+    // a new settlement.
+    last_settled: Option<AuctionId>,
+    // Recent competitions that were not settled yet when we last checked.
+    unsettled: VecDeque<AuctionId>,
+}
+
+impl NoSettlement {
+    /// How many unsettled competitions get checked again on every update.
+    /// Settlements usually get mined within a few auctions.
+    const MAX_UNSETTLED_COMPETITIONS: usize = 5;
+
+    pub fn new(orderbook: Arc<OrderBookApi>, max_time_without_settlement: Duration) -> Self {
+        Self {
+            orderbook,
+            max_time_without_settlement,
+            last_settlement: Instant::now(),
+            last_settled: None,
+            unsettled: Default::default(),
+        }
+    }
+
+    fn settled(&mut self, auction_id: AuctionId) {
+        if self.last_settled.is_some_and(|last| last >= auction_id) {
+            return;
+        }
+        self.last_settled = Some(auction_id);
+        self.last_settlement = Instant::now();
+    }
+}
+
+#[async_trait::async_trait]
+impl Rule for NoSettlement {
+    fn name(&self) -> &'static str {
+        "no_settlement"
+    }
+
+    async fn evaluate(&mut self, observation: &Observation) -> Result<Option<String>> {
+        if let Some(competition) = &observation.latest_competition {
+            if competition.transaction_hash.is_some() {
+                self.settled(competition.auction_id);
+            } else if self
+                .unsettled
+                .back()
+                .map_or(true, |id| *id < competition.auction_id)
+            {
+                self.unsettled.push_back(competition.auction_id);
+                if self.unsettled.len() > Self::MAX_UNSETTLED_COMPETITIONS {
+                    self.unsettled.pop_front();
+                }
+            }
+        }
+
+        // The latest competition usually only gets settled after a newer one
+        // was stored, so check the previous ones again.
+        let mut unsettled = VecDeque::new();
+        for auction_id in std::mem::take(&mut self.unsettled) {
+            let competition = self
+                .orderbook
+                .solver_competition(auction_id)
+                .await
+                .context("get solver competition")?;
+            match competition {
+                Some(competition) if competition.transaction_hash.is_some() => {
+                    self.settled(auction_id)
+                }
+                _ => unsettled.push_back(auction_id),
+            }
+        }
+        self.unsettled = unsettled;
+
+        let elapsed = self.last_settlement.elapsed();
+        Ok((elapsed > self.max_time_without_settlement).then(|| {
+            format!(
+                "No auction has been settled in the last {} seconds.",
+                elapsed.as_secs()
+            )
+        }))
+    }
+}
+
+/// Alerts if the autopilot stops cutting new auctions.
+pub struct AuctionIdNotAdvancing {
+    max_time: Duration,
+    last_auction: Option<(AuctionId, Instant)>,
+}
+
+impl AuctionIdNotAdvancing {
+    pub fn new(max_time: Duration) -> Self {
+        Self {
+            max_time,
+            last_auction: None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Rule for AuctionIdNotAdvancing {
+    fn name(&self) -> &'static str {
+        "auction_id_not_advancing"
+    }
+
+    async fn evaluate(&mut self, observation: &Observation) -> Result<Option<String>> {
+        let Some(auction) = &observation.auction else {
+            return Ok(None);
+        };
+        let (id, since) = match self.last_auction {
+            Some((id, since)) if id == auction.id => (id, since),
+            _ => *self.last_auction.insert((auction.id, Instant::now())),
+        };
+        let elapsed = since.elapsed();
+        Ok((elapsed > self.max_time).then(|| {
+            format!(
+                "The auction id has been {id} for the last {} seconds.",
+                elapsed.as_secs()
+            )
+        }))
+    }
+}
+
+/// Alerts if no solver participated in the recent auctions. The autopilot only
+/// stores competitions with at least one solution so this compares the current
+/// auction with the latest stored competition.
+pub struct NoCompetitionParticipants {
+    max_auctions: u64,
+}
+
+impl NoCompetitionParticipants {
+    pub fn new(max_auctions: u64) -> Self {
+        Self { max_auctions }
+    }
+}
+
+#[async_trait::async_trait]
+impl Rule for NoCompetitionParticipants {
+    fn name(&self) -> &'static str {
+        "no_competition_participants"
+    }
+
+    async fn evaluate(&mut self, observation: &Observation) -> Result<Option<String>> {
+        let (Some(auction), Some(competition)) =
+            (&observation.auction, &observation.latest_competition)
+        else {
+            return Ok(None);
+        };
+        // Auctions without user orders don't get solved.
+        if auction.orders.iter().all(Order::is_liquidity_order) {
+            return Ok(None);
+        }
+        let auctions = auction.id.saturating_sub(competition.auction_id);
+        if competition.common.solutions.is_empty() {
+            return Ok(Some(format!(
+                "No solver participated in auction {}.",
+                competition.auction_id
+            )));
+        }
+        Ok((auctions > self.max_auctions as i64).then(|| {
+            format!(
+                "No solver participated in the last {auctions} auctions, the latest competition \
+                 was auction {}.",
+                competition.auction_id
+            )
+        }))
+    }
+}
+
+/// Alerts if too many of the alerter's own requests to the orderbook API fail.
+pub struct ApiErrorRate {
+    orderbook: Arc<OrderBookApi>,
+    max_error_rate: f64,
+}
+
+impl ApiErrorRate {
+    /// Don't alert on a handful of failed requests after a restart.
+    const MIN_REQUESTS: usize = 10;
+
+    pub fn new(orderbook: Arc<OrderBookApi>, max_error_rate: f64) -> Self {
+        Self {
+            orderbook,
+            max_error_rate,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Rule for ApiErrorRate {
+    fn name(&self) -> &'static str {
+        "api_error_rate"
+    }
+
+    async fn evaluate(&mut self, _: &Observation) -> Result<Option<String>> {
+        let stats = self.orderbook.request_stats();
+        if stats.total < Self::MIN_REQUESTS {
+            return Ok(None);
+        }
+        let error_rate = stats.failed as f64 / stats.total as f64;
+        Ok((error_rate > self.max_error_rate).then(|| {
+            format!(
+                "{} of the last {} orderbook API requests failed.",
+                stats.failed, stats.total
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        model::{
+            order::OrderKind,
+            solver_competition::{SolverCompetitionDB, SolverSettlement},
+        },
+    };
+
+    fn order(class: OrderClass) -> Order {
+        Order {
+            kind: OrderKind::Sell,
+            buy_token: Default::default(),
+            buy_amount: 1.into(),
+            sell_token: Default::default(),
+            sell_amount: 1.into(),
+            uid: Default::default(),
+            partially_fillable: false,
+            class,
+            status: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn auction_id_not_advancing() {
+        let mut rule = AuctionIdNotAdvancing::new(Duration::ZERO);
+        let observation = |id| Observation {
+            auction: Some(Auction { id, orders: vec![] }),
+            latest_competition: None,
+        };
+
+        assert!(rule.evaluate(&Default::default()).await.unwrap().is_none());
+        rule.evaluate(&observation(1)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(rule.evaluate(&observation(1)).await.unwrap().is_some());
+
+        rule.max_time = Duration::from_secs(60);
+        assert!(rule.evaluate(&observation(2)).await.unwrap().is_none());
+        assert_eq!(rule.last_auction.unwrap().0, 2);
+    }
+
+    #[tokio::test]
+    async fn no_settlement_ignores_same_settled_competition() {
+        let orderbook = Arc::new(OrderBookApi::new(
+            Default::default(),
+            "http://localhost",
+            Duration::from_secs(60),
+        ));
+        let mut rule = NoSettlement::new(orderbook, Duration::ZERO);
+        let observation = |auction_id| Observation {
+            auction: None,
+            latest_competition: Some(SolverCompetitionAPI {
+                auction_id,
+                transaction_hash: Some(Default::default()),
+                ..Default::default()
+            }),
+        };
+
+        rule.evaluate(&observation(1)).await.unwrap();
+        // The same settled competition keeps being the latest one.
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(rule.evaluate(&observation(1)).await.unwrap().is_some());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(rule.evaluate(&observation(1)).await.unwrap().is_some());
+
+        rule.max_time_without_settlement = Duration::from_secs(60);
+        assert!(rule.evaluate(&observation(2)).await.unwrap().is_none());
+        assert_eq!(rule.last_settled, Some(2));
+    }
+
+    #[tokio::test]
+    async fn no_competition_participants() {
+        let mut rule = NoCompetitionParticipants::new(2);
+        let observation = |auction_id, orders, solutions| Observation {
+            auction: Some(Auction {
+                id: auction_id,
+                orders,
+            }),
+            latest_competition: Some(SolverCompetitionAPI {
+                auction_id: 10,
+                common: SolverCompetitionDB {
+                    solutions,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        };
+        let solutions = || vec![SolverSettlement::default()];
+
+        let ok = observation(12, vec![order(OrderClass::Market)], solutions());
+        assert!(rule.evaluate(&ok).await.unwrap().is_none());
+
+        let stale = observation(13, vec![order(OrderClass::Limit)], solutions());
+        assert!(rule.evaluate(&stale).await.unwrap().is_some());
+
+        let no_user_orders = observation(13, vec![order(OrderClass::Liquidity)], solutions());
+        assert!(rule.evaluate(&no_user_orders).await.unwrap().is_none());
+
+        let empty = observation(10, vec![order(OrderClass::Market)], vec![]);
+        assert!(rule.evaluate(&empty).await.unwrap().is_some());
+    }
+}
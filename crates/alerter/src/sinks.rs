@@ -0,0 +1,164 @@
+//! Destinations that alerts get delivered to.
+
+use {
+    anyhow::Result,
+    chrono::{DateTime, Utc},
+    prometheus::{IntGauge, IntGaugeVec, Opts},
+    reqwest::Client,
+    serde::Serialize,
+    url::Url,
+};
+
+#[derive(Clone, Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Alert {
+    pub rule: &'static str,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[async_trait::async_trait]
+pub trait Sink: Send + Sync {
+    /// Records the outcome of a rule evaluation. Called on every update.
+    fn observe(&self, _rule: &'static str, _firing: bool) {}
+
+    /// Delivers a firing alert. Called at most once per minimum alert interval
+    /// and rule.
+    async fn alert(&self, alert: &Alert) -> Result<()>;
+}
+
+/// Exposes whether each rule is currently firing as a gauge so that we can use
+/// our Grafana alert infrastructure.
+pub struct Prometheus {
+    firing: IntGaugeVec,
+    /// The gauge that existed before there were multiple rules. It is kept so
+    /// that existing dashboards and alerts keep working.
+    no_trades_but_matchable_order: IntGauge,
+}
+
+impl Prometheus {
+    pub fn new() -> Self {
+        let firing = IntGaugeVec::new(
+            Opts::new(
+                "alert_firing",
+                "0 or 1 depending on whether the rule is firing",
+            ),
+            &["rule"],
+        )
+        .unwrap();
+        let no_trades_but_matchable_order =
+            IntGauge::new("no_trades_but_matchable_order", "0 or 1").unwrap();
+        let registry = observe::metrics::get_registry();
+        registry.register(Box::new(firing.clone())).unwrap();
+        registry
+            .register(Box::new(no_trades_but_matchable_order.clone()))
+            .unwrap();
+        Self {
+            firing,
+            no_trades_but_matchable_order,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Sink for Prometheus {
+    fn observe(&self, rule: &'static str, firing: bool) {
+        self.firing.with_label_values(&[rule]).set(firing.into());
+        if rule == "no_trades_but_matchable_order" {
+            self.no_trades_but_matchable_order.set(firing.into());
+        }
+    }
+
+    async fn alert(&self, _: &Alert) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// POSTs the alert as JSON to an arbitrary endpoint.
+pub struct Webhook {
+    client: Client,
+    url: Url,
+}
+
+impl Webhook {
+    pub fn new(client: Client, url: Url) -> Self {
+        Self { client, url }
+    }
+}
+
+#[async_trait::async_trait]
+impl Sink for Webhook {
+    async fn alert(&self, alert: &Alert) -> Result<()> {
+        self.client
+            .post(self.url.clone())
+            .json(alert)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// POSTs the alert to a Slack compatible incoming webhook.
+pub struct Slack {
+    client: Client,
+    url: Url,
+}
+
+impl Slack {
+    pub fn new(client: Client, url: Url) -> Self {
+        Self { client, url }
+    }
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+struct SlackMessage {
+    text: String,
+}
+
+impl From<&Alert> for SlackMessage {
+    fn from(alert: &Alert) -> Self {
+        Self {
+            text: format!(":rotating_light: *{}*: {}", alert.rule, alert.message),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Sink for Slack {
+    async fn alert(&self, alert: &Alert) -> Result<()> {
+        self.client
+            .post(self.url.clone())
+            .json(&SlackMessage::from(alert))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, chrono::TimeZone, serde_json::json};
+
+    #[test]
+    fn payloads() {
+        let alert = Alert {
+            rule: "auction_id_not_advancing",
+            message: "stuck".to_string(),
+            timestamp: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+        };
+        assert_eq!(
+            serde_json::to_value(&alert).unwrap(),
+            json!({
+                "rule": "auction_id_not_advancing",
+                "message": "stuck",
+                "timestamp": "2023-11-14T22:13:20Z",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(SlackMessage::from(&alert)).unwrap(),
+            json!({ "text": ":rotating_light: *auction_id_not_advancing*: stuck" })
+        );
+    }
+}
@@ -39,6 +39,24 @@ pub struct Arguments {
     #[clap(long, env)]
     pub ethflow_indexing_start: Option<u64>,
 
+    /// Address of the ComposableCoW contract. If specified, conditional orders
+    /// created through the contract get watched and their tradeable orders
+    /// get posted to the orderbook.
+    #[clap(long, env)]
+    pub composable_cow_contract: Option<H160>,
+
+    /// Block at which we should start indexing ComposableCoW contract events.
+    /// If there are already events in the database for a block later than
+    /// this, then this block is ignored and can be omitted. Defaults to the
+    /// latest block, i.e. previously created conditional orders are ignored.
+    #[clap(long, env)]
+    pub composable_cow_indexing_start: Option<u64>,
+
+    /// Url of the orderbook to which the tradeable orders of conditional orders
+    /// get posted.
+    #[clap(long, env, default_value = "http://localhost:8080")]
+    pub orderbook_url: Url,
+
     /// A tracing Ethereum node URL to connect to, allowing a separate node URL
     /// to be used exclusively for tracing calls.
     #[clap(long, env)]
@@ -165,6 +183,13 @@ pub struct Arguments {
     #[clap(long, env, default_value = "5")]
     pub additional_deadline_for_rewards: usize,
 
+    /// The maximum number of winners per auction. Winning solutions are
+    /// selected greedily by score and must not settle the same orders or use
+    /// the same liquidity. Solutions with custom interactions must not touch
+    /// the same tokens either, since their liquidity is unknown.
+    #[clap(long, env, default_value = "1")]
+    pub max_winners_per_auction: usize,
+
     /// Cap used for CIP20 score calculation. Defaults to 0.01 ETH.
     #[clap(long, env, default_value = "0.01", value_parser = shared::arguments::wei_from_ether)]
     pub score_cap: U256,
@@ -200,9 +225,9 @@ pub struct Arguments {
     )]
     pub solve_deadline: Duration,
 
-    /// Describes how the protocol fee should be calculated.
+    /// Describes how the protocol fees should be calculated.
     #[clap(flatten)]
-    pub fee_policy: FeePolicy,
+    pub fee_policies: FeePolicies,
 
     /// Arguments for uploading information to S3.
     #[clap(flatten)]
@@ -217,6 +242,11 @@ pub struct Arguments {
     /// `order_events` database table.
     #[clap(long, env, default_value = "30d", value_parser = humantime::parse_duration)]
     pub order_events_cleanup_threshold: Duration,
+
+    /// Time interval between checks for auctions whose solver rewards can be
+    /// computed, i.e. auctions whose block deadline is final.
+    #[clap(long, env, default_value = "1m", value_parser = humantime::parse_duration)]
+    pub solver_rewards_update_interval: Duration,
 }
 
 impl std::fmt::Display for Arguments {
@@ -230,6 +260,9 @@ impl std::fmt::Display for Arguments {
             tracing_node_url,
             ethflow_contract,
             ethflow_indexing_start,
+            composable_cow_contract,
+            composable_cow_indexing_start,
+            orderbook_url,
             metrics_address,
             skip_event_sync,
             allowed_tokens,
@@ -247,12 +280,14 @@ impl std::fmt::Display for Arguments {
             drivers,
             submission_deadline,
             additional_deadline_for_rewards,
+            max_winners_per_auction,
             score_cap,
             shadow,
             solve_deadline,
-            fee_policy,
+            fee_policies,
             order_events_cleanup_interval,
             order_events_cleanup_threshold,
+            solver_rewards_update_interval,
             db_url,
             insert_batch_size,
             native_price_estimation_results_required,
@@ -269,6 +304,13 @@ impl std::fmt::Display for Arguments {
         display_option(f, "tracing_node_url", tracing_node_url)?;
         writeln!(f, "ethflow_contract: {:?}", ethflow_contract)?;
         writeln!(f, "ethflow_indexing_start: {:?}", ethflow_indexing_start)?;
+        writeln!(f, "composable_cow_contract: {:?}", composable_cow_contract)?;
+        writeln!(
+            f,
+            "composable_cow_indexing_start: {:?}",
+            composable_cow_indexing_start
+        )?;
+        writeln!(f, "orderbook_url: {}", orderbook_url)?;
         writeln!(f, "metrics_address: {}", metrics_address)?;
         let _intentionally_ignored = db_url;
         writeln!(f, "db_url: SECRET")?;
@@ -308,10 +350,11 @@ impl std::fmt::Display for Arguments {
             "additional_deadline_for_rewards: {}",
             additional_deadline_for_rewards
         )?;
+        writeln!(f, "max_winners_per_auction: {}", max_winners_per_auction)?;
         writeln!(f, "score_cap: {}", score_cap)?;
         display_option(f, "shadow", shadow)?;
         writeln!(f, "solve_deadline: {:?}", solve_deadline)?;
-        writeln!(f, "fee_policy: {:?}", fee_policy)?;
+        writeln!(f, "fee_policies: {:?}", fee_policies)?;
         writeln!(
             f,
             "order_events_cleanup_interval: {:?}",
@@ -322,6 +365,11 @@ impl std::fmt::Display for Arguments {
             "order_events_cleanup_threshold: {:?}",
             order_events_cleanup_threshold
         )?;
+        writeln!(
+            f,
+            "solver_rewards_update_interval: {:?}",
+            solver_rewards_update_interval
+        )?;
         writeln!(f, "insert_batch_size: {}", insert_batch_size)?;
         writeln!(
             f,
@@ -340,8 +388,11 @@ impl std::fmt::Display for Arguments {
 }
 
 #[derive(clap::Parser, Debug, Clone)]
-pub struct FeePolicy {
-    /// Type of fee policy to use. Examples:
+pub struct FeePolicies {
+    /// List of protocol fee policies. Every policy whose selectors all match
+    /// an order is applied to it, so an order can end up with several
+    /// protocol fees. Each policy has the format
+    /// `<KIND>[;<SELECTOR>]*` with the following kinds:
     ///
     /// - Surplus without cap
     /// surplus:0.5:1.0
@@ -350,23 +401,133 @@ pub struct FeePolicy {
     /// surplus:0.5:0.06
     ///
     /// - Price improvement without cap:
-    /// price_improvement:0.5:1.0
+    /// priceImprovement:0.5:1.0
     ///
     /// - Price improvement with cap:
-    /// price_improvement:0.5:0.06
+    /// priceImprovement:0.5:0.06
     ///
     /// - Volume based:
     /// volume:0.1
-    #[clap(long, env, default_value = "surplus:0.0:1.0")]
-    pub fee_policy_kind: FeePolicyKind,
+    ///
+    /// and the following selectors:
+    ///
+    /// - Order class: `class=market`, `class=limit` or `class=liquidity`
+    /// - Sell or buy token: `sellToken=<ADDRESS>`, `buyToken=<ADDRESS>`
+    /// - Order owner: `owner=<ADDRESS>`
+    /// - App code of the order's app data: `appCode=<STRING>`
+    /// - Orders whose limit price at creation time suggests they can not be
+    ///   immediately filled: `outsideMarketPrice`
+    ///
+    /// For example, limit orders paying half of their surplus (capped at 1% of
+    /// the volume) and orders placed through `MyApp` additionally paying 0.1%
+    /// of the volume:
+    /// surplus:0.5:0.01;class=limit|volume:0.001;appCode=MyApp
+    ///
+    /// Policies are separated by `|` so that selector values like app codes
+    /// may contain commas.
+    #[clap(
+        long,
+        env,
+        value_delimiter = '|',
+        default_value = "surplus:0.0:1.0;class=limit;outsideMarketPrice"
+    )]
+    pub fee_policies: Vec<FeePolicy>,
 
-    /// Should protocol fees be collected or skipped for orders whose
-    /// limit price at order creation time suggests they can be immediately
-    /// filled.
-    #[clap(long, env, action = clap::ArgAction::Set, default_value = "true")]
+    /// Deprecated, use `--fee-policies` instead. A single fee policy applied
+    /// to limit orders only.
+    #[clap(long, env, hide = true, conflicts_with = "fee_policies")]
+    pub fee_policy_kind: Option<FeePolicyKind>,
+
+    /// Deprecated, use `--fee-policies` with the `outsideMarketPrice` selector
+    /// instead. Only used together with `--fee-policy-kind`: should surplus
+    /// fees be skipped for limit orders whose limit price at order creation
+    /// time suggests they can be immediately filled.
+    #[clap(
+        long,
+        env,
+        hide = true,
+        action = clap::ArgAction::Set,
+        default_value = "true"
+    )]
     pub fee_policy_skip_market_orders: bool,
 }
 
+impl FeePolicies {
+    /// The configured fee policies, translating the deprecated single policy
+    /// arguments into the equivalent selector based policy if they are used.
+    pub fn policies(&self) -> Vec<FeePolicy> {
+        let Some(kind) = &self.fee_policy_kind else {
+            return self.fee_policies.clone();
+        };
+        let mut selectors = vec![FeePolicySelector::Class(model::order::OrderClass::Limit)];
+        if matches!(kind, FeePolicyKind::Surplus { .. }) && self.fee_policy_skip_market_orders {
+            selectors.push(FeePolicySelector::OutsideMarketPrice);
+        }
+        vec![FeePolicy {
+            kind: kind.clone(),
+            selectors,
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FeePolicy {
+    pub kind: FeePolicyKind,
+    /// Conditions an order has to fulfill for the policy to be applied. An
+    /// empty list applies the policy to all orders.
+    pub selectors: Vec<FeePolicySelector>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeePolicySelector {
+    Class(model::order::OrderClass),
+    SellToken(H160),
+    BuyToken(H160),
+    Owner(H160),
+    AppCode(String),
+    OutsideMarketPrice,
+}
+
+impl FromStr for FeePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(';');
+        let kind = parts
+            .next()
+            .ok_or("missing fee policy kind")?
+            .parse::<FeePolicyKind>()?;
+        let selectors = parts
+            .map(FeePolicySelector::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { kind, selectors })
+    }
+}
+
+impl FromStr for FeePolicySelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_address = |value: &str| {
+            H160::from_str(value).map_err(|e| format!("invalid address {value}: {e}"))
+        };
+        match s.split_once('=') {
+            None if s == "outsideMarketPrice" => Ok(Self::OutsideMarketPrice),
+            Some(("class", value)) => match value {
+                "market" => Ok(Self::Class(model::order::OrderClass::Market)),
+                "limit" => Ok(Self::Class(model::order::OrderClass::Limit)),
+                "liquidity" => Ok(Self::Class(model::order::OrderClass::Liquidity)),
+                _ => Err(format!("invalid order class: {value}")),
+            },
+            Some(("sellToken", value)) => parse_address(value).map(Self::SellToken),
+            Some(("buyToken", value)) => parse_address(value).map(Self::BuyToken),
+            Some(("owner", value)) => parse_address(value).map(Self::Owner),
+            Some(("appCode", value)) => Ok(Self::AppCode(value.to_string())),
+            _ => Err(format!("invalid fee policy selector: {s}")),
+        }
+    }
+}
+
 #[derive(clap::Parser, Debug, Clone)]
 pub enum FeePolicyKind {
     /// How much of the order's surplus should be taken as a protocol fee.
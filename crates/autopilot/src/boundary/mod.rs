@@ -1,7 +1,7 @@
 pub use {
     crate::database::{
         competition::Competition,
-        order_events::{store_order_events, OrderEventLabel},
+        order_events::{store_order_events, OrderEventLabel, OrderFilterReason},
     },
     database,
     model::{
@@ -21,7 +21,10 @@ pub use {
         signature::{EcdsaSignature, Signature, SigningScheme},
         DomainSeparator,
     },
-    shared::order_validation::{is_order_outside_market_price, Amounts},
+    shared::{
+        app_data,
+        order_validation::{is_order_outside_market_price, Amounts},
+    },
 };
 use {crate::domain, ethrpc::Web3, std::collections::HashMap, url::Url};
 
@@ -29,18 +32,28 @@ pub mod events;
 pub mod order;
 
 /// Builds a web3 client that bufferes requests and sends them in a
-/// batch call.
-pub fn buffered_web3_client(ethrpc: &Url) -> Web3 {
+/// batch call. Requests fail over to the fallback nodes if the main node is
+/// unhealthy.
+pub fn buffered_web3_client(
+    ethrpc: &Url,
+    fallbacks: &[Url],
+    multi: &ethrpc::multi::Configuration,
+) -> Web3 {
     let ethrpc_args = shared::ethrpc::Arguments {
         ethrpc_max_batch_size: 20,
         ethrpc_max_concurrent_requests: 10,
         ethrpc_batch_delay: Default::default(),
+        ethrpc_fallback_urls: fallbacks.to_vec(),
+        ethrpc_max_block_lag: multi.max_block_lag,
+        ethrpc_max_error_rate: multi.max_error_rate,
+        ethrpc_health_check_interval: multi.health_check_interval,
+        ethrpc_hedge_delay: multi.hedge_delay,
     };
     let http_factory =
         shared::http_client::HttpClientFactory::new(&shared::http_client::Arguments {
             http_timeout: std::time::Duration::from_secs(10),
         });
-    shared::ethrpc::web3(&ethrpc_args, &http_factory, ethrpc, "base")
+    shared::ethrpc::web3_with_fallbacks(&ethrpc_args, &http_factory, ethrpc, "base")
 }
 
 pub struct SolvableOrders {
@@ -0,0 +1,549 @@
+//! Watch-tower for conditional orders (e.g. TWAP, stop-loss or good-after-time
+//! orders) created through the ComposableCoW contract.
+//!
+//! On every new block the active conditional orders that are due get polled by
+//! asking the contract for their currently tradeable order. Tradeable orders
+//! get posted to the orderbook as EIP-1271 orders of the conditional order's
+//! owner. When no order is tradeable, the contract reverts with a custom error
+//! telling us when the conditional order should be polled again, which is
+//! stored in the database so that we don't needlessly poll it before then.
+
+use {
+    crate::database::Postgres,
+    anyhow::{anyhow, Context, Result},
+    contracts::ComposableCoW,
+    database::{
+        byte_array::ByteArray,
+        conditional_orders::{ConditionalOrder, PollUpdate, Status},
+    },
+    ethcontract::{
+        common::abi::{self, ParamType, Token},
+        tokens::Tokenize,
+        Bytes,
+        H160,
+        U256,
+    },
+    ethrpc::current_block::{self, BlockInfo, CurrentBlockStream},
+    futures::StreamExt,
+    hex_literal::hex,
+    model::{
+        app_data::AppDataHash,
+        order::{
+            BuyTokenDestination,
+            OrderCreation,
+            OrderCreationAppData,
+            OrderData,
+            OrderKind,
+            OrderUid,
+            SellTokenSource,
+        },
+        signature::Signature,
+        DomainSeparator,
+    },
+    serde::Deserialize,
+    shared::ethrpc::Web3,
+    url::Url,
+    web3::types::{BlockId, CallRequest},
+};
+
+/// How many conditional orders get polled concurrently.
+const MAX_CONCURRENT_POLLS: usize = 10;
+
+/// How many blocks to wait before polling a conditional order again after
+/// an unexpected error (e.g. the orderbook rejected its order).
+const ERROR_BACKOFF_BLOCKS: u64 = 10;
+
+/// After how many consecutive unexpected errors a conditional order no longer
+/// gets polled.
+const MAX_FAILED_POLLS: i32 = 10;
+
+pub struct WatchTower {
+    db: Postgres,
+    web3: Web3,
+    contract: ComposableCoW,
+    orderbook: Orderbook,
+    domain_separator: DomainSeparator,
+}
+
+impl WatchTower {
+    pub fn new(
+        db: Postgres,
+        web3: Web3,
+        contract: H160,
+        orderbook: Orderbook,
+        domain_separator: DomainSeparator,
+    ) -> Self {
+        Self {
+            contract: ComposableCoW::at(&web3, contract),
+            db,
+            web3,
+            orderbook,
+            domain_separator,
+        }
+    }
+
+    pub async fn run_forever(self, current_block: CurrentBlockStream) -> ! {
+        let mut blocks = current_block::into_stream(current_block);
+        while let Some(block) = blocks.next().await {
+            if let Err(err) = self.update(&block).await {
+                tracing::warn!(
+                    ?err,
+                    block = block.number,
+                    "failed to poll conditional orders"
+                );
+            }
+        }
+        panic!("block stream unexpectedly dropped");
+    }
+
+    async fn update(&self, block: &BlockInfo) -> Result<()> {
+        let orders = self
+            .db
+            .due_conditional_orders(block.number, block.timestamp)
+            .await?;
+        tracing::debug!(count = orders.len(), "polling conditional orders");
+        futures::stream::iter(orders)
+            .for_each_concurrent(MAX_CONCURRENT_POLLS, |order| async move {
+                self.update_order(&order, block).await
+            })
+            .await;
+        Ok(())
+    }
+
+    async fn update_order(&self, order: &ConditionalOrder, block: &BlockInfo) {
+        let poll = match self.poll(order, block).await {
+            Ok(poll) => poll,
+            Err(err) => {
+                // Most likely a node issue, so we simply try again on the next
+                // block.
+                tracing::warn!(?err, owner = ?order.owner, "failed to poll conditional order");
+                return;
+            }
+        };
+        Metrics::get()
+            .polls
+            .with_label_values(&[poll.label()])
+            .inc();
+        let update = poll.into_poll_update(order, block);
+        if let Err(err) = self.db.update_conditional_order_poll(order, &update).await {
+            tracing::warn!(?err, "failed to store conditional order poll");
+        }
+    }
+
+    async fn poll(&self, order: &ConditionalOrder, block: &BlockInfo) -> Result<Poll> {
+        let owner = H160(order.owner.0);
+        let Ok(salt) = order.salt.as_slice().try_into() else {
+            tracing::warn!(?owner, "conditional order salt has wrong number of bytes");
+            return Ok(Poll::Failed);
+        };
+        let params = (
+            H160(order.handler.0),
+            Bytes(salt),
+            Bytes(order.static_input.clone()),
+        );
+        let method = self.contract.get_tradeable_order_with_signature(
+            owner,
+            params,
+            Bytes(Default::default()),
+            Default::default(),
+        );
+        let call = CallRequest {
+            to: Some(self.contract.address()),
+            data: method.tx.data,
+            ..Default::default()
+        };
+        let output = match self
+            .web3
+            .eth()
+            .call(call, Some(BlockId::Number(block.number.into())))
+            .await
+        {
+            Ok(output) => output,
+            Err(err) => {
+                return match revert_data(&err) {
+                    Some(data) => Ok(Poll::from_revert(&data)),
+                    None => Err(err.into()),
+                }
+            }
+        };
+
+        // The contract returned something that isn't a valid order. Retrying
+        // on the next block likely won't help, so count it as a failure.
+        let (data, signature) = match decode_tradeable_order(&output.0) {
+            Ok(order) => order,
+            Err(err) => {
+                tracing::warn!(?err, ?owner, "failed to decode tradeable order");
+                return Ok(Poll::Failed);
+            }
+        };
+        let creation = OrderCreation {
+            sell_token: data.sell_token,
+            buy_token: data.buy_token,
+            receiver: data.receiver,
+            sell_amount: data.sell_amount,
+            buy_amount: data.buy_amount,
+            valid_to: data.valid_to,
+            fee_amount: data.fee_amount,
+            kind: data.kind,
+            partially_fillable: data.partially_fillable,
+            sell_token_balance: data.sell_token_balance,
+            buy_token_balance: data.buy_token_balance,
+            from: Some(owner),
+            signature: Signature::Eip1271(signature),
+            quote_id: None,
+            app_data: OrderCreationAppData::Hash {
+                hash: data.app_data,
+            },
+        };
+        let uid = data.uid(&self.domain_separator, &owner);
+        if order.last_order_uid == Some(ByteArray(uid.0)) {
+            // The order didn't change since the last poll and was already
+            // posted.
+            return Ok(Poll::Posted(uid));
+        }
+
+        match self.orderbook.post(&creation).await {
+            Ok(()) => {
+                tracing::debug!(?uid, "posted conditional order");
+                Ok(Poll::Posted(uid))
+            }
+            Err(err) => {
+                tracing::debug!(?err, ?uid, "failed to post conditional order");
+                Ok(Poll::Failed)
+            }
+        }
+    }
+}
+
+/// The outcome of polling a conditional order.
+#[derive(Debug, Eq, PartialEq)]
+enum Poll {
+    /// The tradeable order was posted (or already existed).
+    Posted(OrderUid),
+    TryNextBlock,
+    TryAtBlock(u64),
+    TryAtEpoch(u64),
+    Never,
+    /// The contract or orderbook failed unexpectedly. The conditional order
+    /// gets retried until it failed too many times in a row.
+    Failed,
+}
+
+impl Poll {
+    /// Interprets the custom errors the contract reverts with if no order is
+    /// tradeable.
+    fn from_revert(data: &[u8]) -> Self {
+        const ORDER_NOT_VALID: [u8; 4] = hex!("c8fc2725");
+        const POLL_TRY_NEXT_BLOCK: [u8; 4] = hex!("d05f3065");
+        const POLL_TRY_AT_BLOCK: [u8; 4] = hex!("1fe8506e");
+        const POLL_TRY_AT_EPOCH: [u8; 4] = hex!("7e334637");
+        const POLL_NEVER: [u8; 4] = hex!("981b64cd");
+
+        let (selector, data) = data.split_at(data.len().min(4));
+        let at = || {
+            abi::decode(&[ParamType::Uint(256), ParamType::String], data)
+                .ok()
+                .and_then(|tokens| tokens.into_iter().next()?.into_uint())
+                .and_then(|value| value.try_into().ok())
+        };
+        match selector {
+            // The order is not valid right now but might become valid later.
+            s if s == ORDER_NOT_VALID || s == POLL_TRY_NEXT_BLOCK => Self::TryNextBlock,
+            s if s == POLL_TRY_AT_BLOCK => at().map_or(Self::Failed, Self::TryAtBlock),
+            s if s == POLL_TRY_AT_EPOCH => at().map_or(Self::Failed, Self::TryAtEpoch),
+            s if s == POLL_NEVER => Self::Never,
+            _ => Self::Failed,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Posted(_) => "posted",
+            Self::TryNextBlock => "try_next_block",
+            Self::TryAtBlock(_) => "try_at_block",
+            Self::TryAtEpoch(_) => "try_at_epoch",
+            Self::Never => "never",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn into_poll_update(self, order: &ConditionalOrder, block: &BlockInfo) -> PollUpdate {
+        let active = |next_poll_block: Option<u64>, next_poll_timestamp: Option<u64>| PollUpdate {
+            status: Status::Active,
+            next_poll_block: next_poll_block.map(|block| block as i64),
+            next_poll_timestamp: next_poll_timestamp.map(|timestamp| timestamp as i64),
+            last_order_uid: order.last_order_uid,
+            failed_polls: 0,
+        };
+        match self {
+            Self::Posted(uid) => PollUpdate {
+                last_order_uid: Some(ByteArray(uid.0)),
+                ..active(Some(block.number + 1), None)
+            },
+            Self::TryNextBlock => active(Some(block.number + 1), None),
+            Self::TryAtBlock(number) => active(Some(number), None),
+            Self::TryAtEpoch(timestamp) => active(None, Some(timestamp)),
+            Self::Never => PollUpdate {
+                status: Status::Done,
+                ..active(None, None)
+            },
+            Self::Failed => {
+                let failed_polls = order.failed_polls.saturating_add(1);
+                let status = if failed_polls >= MAX_FAILED_POLLS {
+                    tracing::debug!(owner = ?order.owner, "giving up on failing conditional order");
+                    Status::Done
+                } else {
+                    Status::Active
+                };
+                PollUpdate {
+                    status,
+                    failed_polls,
+                    ..active(Some(block.number + ERROR_BACKOFF_BLOCKS), None)
+                }
+            }
+        }
+    }
+}
+
+/// Extracts the revert data from the error of an `eth_call`. Nodes return it
+/// as a hex string in the data field of the JSON RPC error.
+fn revert_data(err: &web3::Error) -> Option<Vec<u8>> {
+    let web3::Error::Rpc(err) = err else {
+        return None;
+    };
+    let data = err.data.as_ref()?.as_str()?;
+    // Some nodes prefix the data with a message like "Reverted 0x...".
+    let hex = &data[data.find("0x")? + 2..];
+    hex::decode(hex).ok()
+}
+
+/// The `GPv2Order.Data` struct as returned by the contract.
+type EncodedOrder = (
+    H160,            // sellToken
+    H160,            // buyToken
+    H160,            // receiver
+    U256,            // sellAmount
+    U256,            // buyAmount
+    u32,             // validTo
+    Bytes<[u8; 32]>, // appData
+    U256,            // feeAmount
+    Bytes<[u8; 32]>, // kind
+    bool,            // partiallyFillable
+    Bytes<[u8; 32]>, // sellTokenBalance
+    Bytes<[u8; 32]>, // buyTokenBalance
+);
+
+fn decode_tradeable_order(output: &[u8]) -> Result<(OrderData, Vec<u8>)> {
+    let function = ComposableCoW::raw_contract()
+        .abi
+        .function("getTradeableOrderWithSignature")
+        .unwrap();
+    let tokens = function.decode_output(output).context("decode")?;
+    let (order, signature): (EncodedOrder, Bytes<Vec<u8>>) =
+        Tokenize::from_token(Token::Tuple(tokens))?;
+    let receiver = match order.2 {
+        H160(bytes) if bytes == [0u8; 20] => None,
+        receiver => Some(receiver),
+    };
+    let data = OrderData {
+        sell_token: order.0,
+        buy_token: order.1,
+        receiver,
+        sell_amount: order.3,
+        buy_amount: order.4,
+        valid_to: order.5,
+        app_data: AppDataHash(order.6 .0),
+        fee_amount: order.7,
+        kind: OrderKind::from_contract_bytes(order.8 .0)?,
+        partially_fillable: order.9,
+        sell_token_balance: SellTokenSource::from_contract_bytes(order.10 .0)?,
+        buy_token_balance: BuyTokenDestination::from_contract_bytes(order.11 .0)?,
+    };
+    Ok((data, signature.0))
+}
+
+/// A client posting the tradeable orders of conditional orders to the
+/// orderbook.
+pub struct Orderbook {
+    client: reqwest::Client,
+    url: Url,
+}
+
+impl Orderbook {
+    pub fn new(client: reqwest::Client, url: Url) -> Self {
+        Self { client, url }
+    }
+
+    /// Posts the order. Succeeds if the order already exists.
+    async fn post(&self, order: &OrderCreation) -> Result<()> {
+        let response = self
+            .client
+            .post(shared::url::join(&self.url, "api/v1/orders"))
+            .json(order)
+            .send()
+            .await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.text().await?;
+        if is_duplicated_order(&body) {
+            return Ok(());
+        }
+        Err(anyhow!("orderbook responded with {status}: {body}"))
+    }
+}
+
+/// Whether the orderbook rejected the order because it already exists.
+fn is_duplicated_order(body: &str) -> bool {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Error {
+        error_type: String,
+    }
+
+    serde_json::from_str::<Error>(body).is_ok_and(|err| err.error_type == "DuplicatedOrder")
+}
+
+#[derive(prometheus_metric_storage::MetricStorage)]
+#[metric(subsystem = "conditional_orders")]
+struct Metrics {
+    /// Results of polling conditional orders.
+    #[metric(labels("result"))]
+    polls: prometheus::IntCounterVec,
+}
+
+impl Metrics {
+    fn get() -> &'static Self {
+        Self::instance(observe::metrics::get_storage_registry()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revert(selector: [u8; 4], tokens: &[Token]) -> Vec<u8> {
+        [selector.as_slice(), &abi::encode(tokens)].concat()
+    }
+
+    #[test]
+    fn interprets_reverts() {
+        let reason = Token::String("reason".to_string());
+        assert_eq!(
+            Poll::from_revert(&revert(hex!("d05f3065"), &[reason.clone()])),
+            Poll::TryNextBlock
+        );
+        assert_eq!(
+            Poll::from_revert(&revert(hex!("c8fc2725"), &[reason.clone()])),
+            Poll::TryNextBlock
+        );
+        assert_eq!(
+            Poll::from_revert(&revert(
+                hex!("1fe8506e"),
+                &[Token::Uint(U256::from(42)), reason.clone()]
+            )),
+            Poll::TryAtBlock(42)
+        );
+        assert_eq!(
+            Poll::from_revert(&revert(
+                hex!("7e334637"),
+                &[Token::Uint(U256::from(1_700_000_000)), reason.clone()]
+            )),
+            Poll::TryAtEpoch(1_700_000_000)
+        );
+        assert_eq!(
+            Poll::from_revert(&revert(hex!("981b64cd"), &[reason])),
+            Poll::Never
+        );
+        assert_eq!(Poll::from_revert(&hex!("08c379a0")), Poll::Failed);
+        assert_eq!(Poll::from_revert(&[]), Poll::Failed);
+    }
+
+    #[test]
+    fn extracts_revert_data() {
+        let error = |data: &str| {
+            web3::Error::Rpc(ethcontract::jsonrpc::Error {
+                code: ethcontract::jsonrpc::ErrorCode::ServerError(3),
+                message: "execution reverted".to_string(),
+                data: Some(serde_json::Value::String(data.to_string())),
+            })
+        };
+        assert_eq!(revert_data(&error("0x01ff")), Some(vec![1, 255]));
+        assert_eq!(revert_data(&error("Reverted 0x01ff")), Some(vec![1, 255]));
+        assert_eq!(revert_data(&error("nonsense")), None);
+        assert_eq!(revert_data(&web3::Error::Unreachable), None);
+    }
+
+    #[test]
+    fn poll_updates() {
+        let order = ConditionalOrder {
+            last_order_uid: Some(ByteArray([1; 56])),
+            ..Default::default()
+        };
+        let block = BlockInfo {
+            number: 100,
+            ..Default::default()
+        };
+        assert_eq!(
+            Poll::Posted(OrderUid([2; 56])).into_poll_update(&order, &block),
+            PollUpdate {
+                status: Status::Active,
+                next_poll_block: Some(101),
+                next_poll_timestamp: None,
+                last_order_uid: Some(ByteArray([2; 56])),
+                failed_polls: 0,
+            }
+        );
+        assert_eq!(
+            Poll::TryAtEpoch(1_000).into_poll_update(&order, &block),
+            PollUpdate {
+                status: Status::Active,
+                next_poll_block: None,
+                next_poll_timestamp: Some(1_000),
+                last_order_uid: Some(ByteArray([1; 56])),
+                failed_polls: 0,
+            }
+        );
+        assert_eq!(
+            Poll::Never.into_poll_update(&order, &block).status,
+            Status::Done
+        );
+        assert_eq!(
+            Poll::Failed.into_poll_update(&order, &block),
+            PollUpdate {
+                status: Status::Active,
+                next_poll_block: Some(110),
+                next_poll_timestamp: None,
+                last_order_uid: Some(ByteArray([1; 56])),
+                failed_polls: 1,
+            }
+        );
+
+        let failing = ConditionalOrder {
+            failed_polls: MAX_FAILED_POLLS - 1,
+            ..order.clone()
+        };
+        assert_eq!(
+            Poll::Failed.into_poll_update(&failing, &block).status,
+            Status::Done
+        );
+        assert_eq!(
+            Poll::TryNextBlock
+                .into_poll_update(&failing, &block)
+                .failed_polls,
+            0
+        );
+    }
+
+    #[test]
+    fn detects_duplicated_orders() {
+        assert!(is_duplicated_order(
+            r#"{"errorType": "DuplicatedOrder", "description": "order already exists"}"#
+        ));
+        assert!(!is_duplicated_order(
+            r#"{"errorType": "InsufficientFee", "description": "DuplicatedOrder"}"#
+        ));
+        assert!(!is_duplicated_order("DuplicatedOrder"));
+    }
+}
@@ -7,14 +7,17 @@ use {
 mod auction;
 pub mod auction_prices;
 pub mod competition;
+pub mod conditional_orders;
 pub mod ethflow_events;
 pub mod events;
 pub mod fee_policies;
+mod native_prices;
 pub mod on_settlement_event_updater;
 pub mod onchain_order_events;
 pub mod order_events;
 mod quotes;
 pub mod recent_settlements;
+pub mod solver_rewards;
 
 #[derive(Debug, Clone)]
 pub struct Config {
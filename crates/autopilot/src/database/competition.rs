@@ -4,6 +4,7 @@ use {
         auction::AuctionId,
         auction_participants::Participant,
         auction_prices::AuctionPrice,
+        auction_winners,
         byte_array::ByteArray,
         settlement_call_data::SettlementCallData,
         settlement_scores::Score,
@@ -19,9 +20,8 @@ use {
 #[derivative(Debug)]
 pub struct Competition {
     pub auction_id: AuctionId,
-    pub winner: H160,
-    pub winning_score: U256,
-    pub reference_score: U256,
+    /// The winners of the auction, best ranked first.
+    pub winners: Vec<Winner>,
     /// Addresses to which the CIP20 participation rewards will be payed out.
     /// Usually the same as the solver addresses.
     pub participants: HashSet<H160>,
@@ -31,15 +31,24 @@ pub struct Competition {
     /// chain before this block height.
     pub block_deadline: u64,
     pub competition_simulation_block: u64,
-    /// Winner settlement call data
+    /// Best ranked winner settlement call data
     #[derivative(Debug(format_with = "shared::debug_bytes"))]
     pub call_data: Vec<u8>,
-    /// Uninternalized winner settlement call data
+    /// Uninternalized best ranked winner settlement call data
     #[derivative(Debug(format_with = "shared::debug_bytes"))]
     pub uninternalized_call_data: Vec<u8>,
     pub competition_table: SolverCompetitionDB,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct Winner {
+    pub solver: H160,
+    pub score: U256,
+    /// The score other solutions would have contributed in place of this
+    /// winner's solution had the winner not participated.
+    pub reference_score: U256,
+}
+
 impl super::Postgres {
     pub async fn save_competition(&self, competition: &Competition) -> anyhow::Result<()> {
         let _timer = super::Metrics::get()
@@ -49,6 +58,8 @@ impl super::Postgres {
 
         let json = &serde_json::to_value(&competition.competition_table)?;
 
+        let best = competition.winners.first().context("no winner")?;
+
         let mut ex = self.pool.begin().await.context("begin")?;
 
         database::solver_competition::save(&mut ex, competition.auction_id, json)
@@ -59,9 +70,9 @@ impl super::Postgres {
             &mut ex,
             Score {
                 auction_id: competition.auction_id,
-                winner: ByteArray(competition.winner.0),
-                winning_score: u256_to_big_decimal(&competition.winning_score),
-                reference_score: u256_to_big_decimal(&competition.reference_score),
+                winner: ByteArray(best.solver.0),
+                winning_score: u256_to_big_decimal(&best.score),
+                reference_score: u256_to_big_decimal(&best.reference_score),
                 block_deadline: competition
                     .block_deadline
                     .try_into()
@@ -75,6 +86,26 @@ impl super::Postgres {
         .await
         .context("settlement_scores::insert")?;
 
+        database::auction_winners::delete(&mut ex, competition.auction_id)
+            .await
+            .context("auction_winners::delete")?;
+        database::auction_winners::insert(
+            &mut ex,
+            competition
+                .winners
+                .iter()
+                .map(|winner| auction_winners::Winner {
+                    auction_id: competition.auction_id,
+                    winner: ByteArray(winner.solver.0),
+                    score: u256_to_big_decimal(&winner.score),
+                    reference_score: u256_to_big_decimal(&winner.reference_score),
+                })
+                .collect::<Vec<_>>()
+                .as_slice(),
+        )
+        .await
+        .context("auction_winners::insert")?;
+
         database::auction_participants::insert(
             &mut ex,
             competition
@@ -0,0 +1,60 @@
+//! A component that listens exclusively for `ConditionalOrderCreated` events of
+//! the ComposableCoW contract.
+use {
+    ethcontract::{contract::AllEventsBuilder, transport::DynTransport, BlockNumber, H160, H256},
+    ethrpc::current_block::{block_number_to_block_number_hash, BlockNumberHash},
+    hex_literal::hex,
+    shared::{ethrpc::Web3, event_handling::EventRetrieving},
+};
+
+const CONDITIONAL_ORDER_CREATED_TOPIC: H256 = H256(hex!(
+    "2cceac5555b0ca45a3744ced542f54b56ad2eb45e521962372eef212a2cbf361"
+));
+
+pub struct ConditionalOrderRetriever {
+    web3: Web3,
+    address: H160,
+}
+
+impl ConditionalOrderRetriever {
+    pub fn new(web3: Web3, address: H160) -> Self {
+        Self { web3, address }
+    }
+}
+
+impl EventRetrieving for ConditionalOrderRetriever {
+    type Event = contracts::composable_cow::Event;
+
+    fn get_events(&self) -> AllEventsBuilder<DynTransport, Self::Event> {
+        let mut events = AllEventsBuilder::new(self.web3.clone(), self.address, None);
+        // The contract emits other events (e.g. for merkle roots of conditional
+        // orders) which we don't index.
+        events.filter = events
+            .filter
+            .topic0(vec![CONDITIONAL_ORDER_CREATED_TOPIC].into());
+        events
+    }
+}
+
+/// Determines the block from which on ComposableCoW events get indexed. The
+/// deployment block of the contract is not known, so without a configured
+/// indexing start only conditional orders created from now on are indexed.
+pub async fn determine_composable_cow_indexing_start(
+    skip_event_sync_start: &Option<BlockNumberHash>,
+    indexing_start: Option<u64>,
+    web3: &Web3,
+) -> BlockNumberHash {
+    if let Some(block_number_hash) = skip_event_sync_start {
+        return *block_number_hash;
+    }
+    let block = match indexing_start {
+        Some(block_number) => block_number.into(),
+        None => {
+            tracing::warn!("no ComposableCoW indexing start configured, using the latest block");
+            BlockNumber::Latest
+        }
+    };
+    block_number_to_block_number_hash(web3, block)
+        .await
+        .expect("Should be able to find block at ComposableCoW indexing start")
+}
@@ -0,0 +1,84 @@
+//! Implements the logic for indexing `ConditionalOrderCreated` events of the
+//! ComposableCoW contract.
+use {
+    crate::database::Postgres,
+    anyhow::{Context, Result},
+    database::{
+        byte_array::ByteArray,
+        conditional_orders::{ConditionalOrder, Status},
+    },
+    ethrpc::current_block::RangeInclusive,
+    shared::event_handling::EventStoring,
+};
+
+type ComposableCoWEvent = contracts::composable_cow::Event;
+
+fn get_conditional_orders(
+    events: Vec<ethcontract::Event<ComposableCoWEvent>>,
+) -> Result<Vec<ConditionalOrder>> {
+    events
+        .into_iter()
+        .map(|event| {
+            let meta = event.meta.context("event without metadata")?;
+            let ComposableCoWEvent::ConditionalOrderCreated(created) = event.data;
+            let (handler, salt, static_input) = created.params;
+            Ok(ConditionalOrder {
+                block_number: meta.block_number.try_into()?,
+                log_index: meta.log_index.try_into()?,
+                owner: ByteArray(created.owner.0),
+                handler: ByteArray(handler.0),
+                salt: salt.0.to_vec(),
+                static_input: static_input.0,
+                status: Status::Active,
+                next_poll_block: None,
+                next_poll_timestamp: None,
+                last_order_uid: None,
+            })
+        })
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl EventStoring<ComposableCoWEvent> for Postgres {
+    async fn last_event_block(&self) -> Result<u64> {
+        let mut ex = self.pool.acquire().await?;
+        let block = database::conditional_orders::last_indexed_block(&mut ex).await?;
+        Ok(block.unwrap_or_default() as u64)
+    }
+
+    async fn append_events(
+        &mut self,
+        events: Vec<ethcontract::Event<ComposableCoWEvent>>,
+    ) -> Result<()> {
+        let orders = match get_conditional_orders(events)? {
+            orders if !orders.is_empty() => orders,
+            _ => return Ok(()),
+        };
+        let _timer = crate::database::Metrics::get()
+            .database_queries
+            .with_label_values(&["append_conditional_order_events"])
+            .start_timer();
+        let mut ex = self.pool.begin().await?;
+        database::conditional_orders::insert(&mut ex, &orders).await?;
+        ex.commit().await?;
+        Ok(())
+    }
+
+    async fn replace_events(
+        &mut self,
+        events: Vec<ethcontract::Event<ComposableCoWEvent>>,
+        range: RangeInclusive<u64>,
+    ) -> Result<()> {
+        let orders = get_conditional_orders(events)?;
+        let _timer = crate::database::Metrics::get()
+            .database_queries
+            .with_label_values(&["replace_conditional_order_events"])
+            .start_timer();
+        let mut ex = self.pool.begin().await?;
+        database::conditional_orders::delete(&mut ex, *range.start() as i64, *range.end() as i64)
+            .await?;
+        database::conditional_orders::insert(&mut ex, &orders).await?;
+        ex.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+//! Contains the components to index conditional orders created through the
+//! ComposableCoW contract and to keep track of polling them.
+pub mod event_retriever;
+pub mod event_storing;
+
+use {
+    anyhow::Context,
+    database::conditional_orders::{ConditionalOrder, PollUpdate},
+};
+
+impl super::Postgres {
+    /// Active conditional orders that should be polled at the given block.
+    pub async fn due_conditional_orders(
+        &self,
+        block_number: u64,
+        timestamp: u64,
+    ) -> anyhow::Result<Vec<ConditionalOrder>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["due_conditional_orders"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await.context("acquire")?;
+        database::conditional_orders::due(&mut ex, block_number.try_into()?, timestamp.try_into()?)
+            .await
+            .context("due")
+    }
+
+    pub async fn update_conditional_order_poll(
+        &self,
+        order: &ConditionalOrder,
+        update: &PollUpdate,
+    ) -> anyhow::Result<()> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["update_conditional_order_poll"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await.context("acquire")?;
+        database::conditional_orders::update_poll(
+            &mut ex,
+            order.block_number,
+            order.log_index,
+            update,
+        )
+        .await
+        .context("update_poll")
+    }
+}
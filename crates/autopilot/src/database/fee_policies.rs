@@ -24,7 +24,7 @@ pub async fn insert_batch(
     let mut query_builder = QueryBuilder::new(
         "INSERT INTO fee_policies (auction_id, order_uid, kind, surplus_factor, \
          surplus_max_volume_factor, volume_factor, price_improvement_factor, \
-         price_improvement_max_volume_factor)",
+         price_improvement_max_volume_factor, partner_recipient)",
     );
 
     query_builder.push_values(fee_policies, |mut b, fee_policy| {
@@ -35,7 +35,8 @@ pub async fn insert_batch(
             .push_bind(fee_policy.surplus_max_volume_factor)
             .push_bind(fee_policy.volume_factor)
             .push_bind(fee_policy.price_improvement_factor)
-            .push_bind(fee_policy.price_improvement_max_volume_factor);
+            .push_bind(fee_policy.price_improvement_max_volume_factor)
+            .push_bind(fee_policy.partner_recipient);
     });
 
     query_builder.build().execute(ex).await.map(|_| ())
@@ -63,7 +64,7 @@ pub async fn fetch(
 
 #[cfg(test)]
 mod tests {
-    use {super::*, database::byte_array::ByteArray, sqlx::Connection};
+    use {super::*, database::byte_array::ByteArray, primitive_types::H160, sqlx::Connection};
 
     #[tokio::test]
     #[ignore]
@@ -97,7 +98,18 @@ mod tests {
                 fee: 1.into(),
             },
         };
-        let input_policies = vec![fee_policy_1, fee_policy_2, fee_policy_3, fee_policy_4];
+        // partner fee policy
+        let fee_policy_5 = domain::fee::Policy::Partner {
+            factor: 0.01,
+            recipient: H160([2; 20]),
+        };
+        let input_policies = vec![
+            fee_policy_1,
+            fee_policy_2,
+            fee_policy_3,
+            fee_policy_4,
+            fee_policy_5,
+        ];
 
         insert_batch(
             &mut db,
@@ -117,6 +129,7 @@ mod tests {
             volume_factor: None,
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            partner_recipient: None,
         };
         // surplus fee policy with caps
         let fee_policy_2 = dto::FeePolicy {
@@ -128,6 +141,7 @@ mod tests {
             volume_factor: None,
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            partner_recipient: None,
         };
         // volume based fee policy
         let fee_policy_3 = dto::FeePolicy {
@@ -139,6 +153,7 @@ mod tests {
             volume_factor: Some(0.06),
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            partner_recipient: None,
         };
         // price improvement fee policy
         let fee_policy_4 = dto::FeePolicy {
@@ -150,8 +165,27 @@ mod tests {
             volume_factor: None,
             price_improvement_factor: Some(0.1),
             price_improvement_max_volume_factor: Some(1.0),
+            partner_recipient: None,
+        };
+        // partner fee policy
+        let fee_policy_5 = dto::FeePolicy {
+            auction_id,
+            order_uid,
+            kind: dto::fee_policy::FeePolicyKind::Partner,
+            surplus_factor: None,
+            surplus_max_volume_factor: None,
+            volume_factor: Some(0.01),
+            price_improvement_factor: None,
+            price_improvement_max_volume_factor: None,
+            partner_recipient: Some(ByteArray([2; 20])),
         };
-        let expected = vec![fee_policy_1, fee_policy_2, fee_policy_3, fee_policy_4];
+        let expected = vec![
+            fee_policy_1,
+            fee_policy_2,
+            fee_policy_3,
+            fee_policy_4,
+            fee_policy_5,
+        ];
 
         let output = fetch(&mut db, 1, order_uid).await.unwrap();
         assert_eq!(output, expected);
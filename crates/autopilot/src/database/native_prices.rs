@@ -0,0 +1,36 @@
+use {
+    super::Postgres,
+    anyhow::{Context, Result},
+    chrono::{DateTime, Utc},
+    shared::price_estimation::native_price_cache::{NativePriceStoring, StoredNativePrice},
+};
+
+#[async_trait::async_trait]
+impl NativePriceStoring for Postgres {
+    async fn store_native_prices(&self, prices: Vec<StoredNativePrice>) -> Result<()> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["store_native_prices"])
+            .start_timer();
+
+        let prices = prices.into_iter().map(Into::into).collect::<Vec<_>>();
+        database::native_prices::store(&self.pool, &prices)
+            .await
+            .context("native_prices::store")
+    }
+
+    async fn load_native_prices(
+        &self,
+        updated_after: DateTime<Utc>,
+    ) -> Result<Vec<StoredNativePrice>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["load_native_prices"])
+            .start_timer();
+
+        let prices = database::native_prices::load_updated_after(&self.pool, updated_after)
+            .await
+            .context("native_prices::load_updated_after")?;
+        Ok(prices.into_iter().map(Into::into).collect())
+    }
+}
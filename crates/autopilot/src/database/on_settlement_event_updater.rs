@@ -1,5 +1,6 @@
 use {
     anyhow::{Context, Result},
+    chrono::{DateTime, Utc},
     database::{byte_array::ByteArray, settlement_observations::Observation},
     ethcontract::U256,
     model::order::OrderUid,
@@ -25,6 +26,8 @@ pub struct SettlementUpdate {
     pub block_number: i64,
     pub log_index: i64,
     pub auction_id: AuctionId,
+    /// Timestamp of the block the settlement happened in.
+    pub block_timestamp: Option<DateTime<Utc>>,
     /// Only set if the auction is for this environment.
     pub auction_data: Option<AuctionData>,
 }
@@ -49,6 +52,16 @@ impl super::Postgres {
         .await
         .context("insert_settlement_tx_info")?;
 
+        if let Some(block_timestamp) = settlement_update.block_timestamp {
+            database::settlements::update_block_timestamp(
+                ex,
+                settlement_update.block_number,
+                block_timestamp,
+            )
+            .await
+            .context("update_block_timestamp")?;
+        }
+
         if let Some(auction_data) = settlement_update.auction_data {
             database::settlement_observations::upsert(
                 ex,
@@ -1,4 +1,4 @@
-pub use database::order_events::OrderEventLabel;
+pub use database::order_events::{OrderEventLabel, OrderFilterReason};
 use {
     crate::domain,
     anyhow::{Context, Result},
@@ -17,18 +17,21 @@ impl super::Postgres {
     }
 }
 
+/// Stores an event with the given label for each order. Orders can optionally
+/// come with a reason describing why the event happened.
 pub async fn store_order_events(
     db: &super::Postgres,
-    order_uids: Vec<domain::OrderUid>,
+    events: Vec<(domain::OrderUid, Option<OrderFilterReason>)>,
     label: OrderEventLabel,
     timestamp: DateTime<Utc>,
 ) -> Result<()> {
     let mut ex = db.pool.begin().await.context("begin transaction")?;
-    for uid in order_uids {
+    for (uid, reason) in events {
         let event = OrderEvent {
             order_uid: ByteArray(uid.0),
             timestamp,
             label,
+            reason,
         };
 
         order_events::insert_order_event(&mut ex, &event).await?
@@ -0,0 +1,39 @@
+use {
+    anyhow::Context,
+    database::solver_rewards::{Outcome, Reward},
+};
+
+impl super::Postgres {
+    pub async fn unrewarded_outcomes(
+        &self,
+        max_block_deadline: u64,
+        max_auctions: i64,
+    ) -> anyhow::Result<Vec<Outcome>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["unrewarded_outcomes"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await.context("acquire")?;
+        database::solver_rewards::unrewarded_outcomes(
+            &mut ex,
+            max_block_deadline.try_into()?,
+            max_auctions,
+        )
+        .await
+        .context("unrewarded_outcomes")
+    }
+
+    pub async fn save_solver_rewards(&self, rewards: &[Reward]) -> anyhow::Result<()> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["save_solver_rewards"])
+            .start_timer();
+
+        let mut ex = self.pool.begin().await.context("begin")?;
+        database::solver_rewards::insert(&mut ex, rewards)
+            .await
+            .context("insert")?;
+        ex.commit().await.context("commit")
+    }
+}
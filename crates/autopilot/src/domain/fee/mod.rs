@@ -2,7 +2,8 @@
 //!
 //! The protocol fee is a fee that is defined by the protocol and for each order
 //! we define the way to calculate the protocol fee based on the configuration
-//! parameters.
+//! parameters. Multiple fee policies can be configured and every policy whose
+//! selectors match an order is applied to it.
 
 mod policy;
 
@@ -13,35 +14,57 @@ use {
         domain,
     },
     itertools::Itertools,
-    primitive_types::U256,
+    primitive_types::{H160, U256},
 };
 
 /// Constructs fee policies based on the current configuration.
 pub struct ProtocolFee {
-    policy: policy::Policy,
+    policies: Vec<policy::Policy>,
 }
 
 impl ProtocolFee {
-    pub fn new(fee_policy_args: arguments::FeePolicy) -> Self {
+    pub fn new(fee_policies_args: arguments::FeePolicies) -> Self {
         Self {
-            policy: fee_policy_args.into(),
+            policies: fee_policies_args
+                .policies()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }
     }
 
     /// Converts an order from the boundary layer to the domain layer, applying
-    /// protocol fees if necessary.
+    /// all matching protocol fees in the order they were configured followed
+    /// by the partner fee requested in the order's app data.
     pub fn apply(&self, order: boundary::Order, quote: &domain::Quote) -> domain::Order {
-        let protocol_fees = match &self.policy {
-            policy::Policy::Surplus(variant) => variant.apply(&order, quote),
-            policy::Policy::PriceImprovement(variant) => variant.apply(&order, quote),
-            policy::Policy::Volume(variant) => variant.apply(&order),
-        }
-        .into_iter()
-        .collect_vec();
+        let protocol_fees = self
+            .policies
+            .iter()
+            .filter_map(|policy| policy.apply(&order, quote))
+            .chain(partner_fee(&order))
+            .collect_vec();
         boundary::order::to_domain(order, protocol_fees)
     }
 }
 
+/// Partner fees are taken as a share of the order's volume on top of the
+/// protocol fees. Fees above the maximum the orderbook accepts are capped, and
+/// fees without a recipient are ignored since they couldn't be paid out.
+fn partner_fee(order: &boundary::Order) -> Option<Policy> {
+    let full_app_data = order.metadata.full_app_data.as_ref()?;
+    let partner_fee = boundary::app_data::parse(full_app_data.as_bytes())
+        .ok()?
+        .partner_fee?;
+    if partner_fee.recipient.is_zero() {
+        return None;
+    }
+    let bps = partner_fee.bps.min(boundary::app_data::MAX_PARTNER_FEE_BPS);
+    Some(Policy::Partner {
+        factor: bps as f64 / 10_000.,
+        recipient: partner_fee.recipient,
+    })
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Policy {
     /// If the order receives more than limit price, take the protocol fee as a
@@ -75,6 +98,14 @@ pub enum Policy {
         /// fee.
         factor: f64,
     },
+    /// A fee requested by the integrator in the order's app data. It is taken
+    /// like a [`Policy::Volume`] fee and later paid out to the recipient.
+    Partner {
+        /// Percentage of the order's volume that is taken as a partner fee.
+        factor: f64,
+        /// The address the partner fee is paid out to.
+        recipient: H160,
+    },
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -96,3 +127,87 @@ impl From<domain::Quote> for Quote {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, model::order::OrderMetadata};
+
+    fn order(full_app_data: &str) -> boundary::Order {
+        boundary::Order {
+            metadata: OrderMetadata {
+                full_app_data: Some(full_app_data.to_owned()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn partner_fee_app_data(bps: u64, recipient: H160) -> String {
+        format!(r#"{{"metadata":{{"partnerFee":{{"bps":{bps},"recipient":"{recipient:?}"}}}}}}"#)
+    }
+
+    #[test]
+    fn partner_fee_keeps_recipient() {
+        let recipient = H160([1; 20]);
+        assert_eq!(
+            partner_fee(&order(&partner_fee_app_data(50, recipient))),
+            Some(Policy::Partner {
+                factor: 0.005,
+                recipient,
+            })
+        );
+    }
+
+    #[test]
+    fn partner_fee_is_capped() {
+        let recipient = H160([1; 20]);
+        assert_eq!(
+            partner_fee(&order(&partner_fee_app_data(1_000, recipient))),
+            Some(Policy::Partner {
+                factor: 0.01,
+                recipient,
+            })
+        );
+    }
+
+    #[test]
+    fn partner_fee_requires_recipient() {
+        assert_eq!(
+            partner_fee(&order(&partner_fee_app_data(50, H160::zero()))),
+            None
+        );
+        assert_eq!(partner_fee(&order("{}")), None);
+    }
+
+    #[test]
+    fn partner_fee_is_applied_after_protocol_fees() {
+        let protocol_fee = ProtocolFee::new(arguments::FeePolicies {
+            fee_policies: vec!["surplus:0.5:0.01;class=limit".parse().unwrap()],
+            fee_policy_kind: None,
+            fee_policy_skip_market_orders: true,
+        });
+        let recipient = H160([1; 20]);
+        let mut order = order(&partner_fee_app_data(50, recipient));
+        order.metadata.class = boundary::OrderClass::Limit;
+        let quote = domain::Quote {
+            order_uid: domain::OrderUid([0; 56]),
+            sell_amount: U256::zero(),
+            buy_amount: U256::zero(),
+            fee: U256::zero(),
+        };
+
+        assert_eq!(
+            protocol_fee.apply(order, &quote).protocol_fees,
+            vec![
+                Policy::Surplus {
+                    factor: 0.5,
+                    max_volume_factor: 0.01,
+                },
+                Policy::Partner {
+                    factor: 0.005,
+                    recipient,
+                },
+            ]
+        );
+    }
+}
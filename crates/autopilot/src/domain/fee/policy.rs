@@ -1,110 +1,213 @@
-use crate::{arguments, boundary, domain};
+use {
+    crate::{arguments, boundary, domain},
+    primitive_types::H160,
+};
 
-pub enum Policy {
-    Surplus(Surplus),
-    PriceImprovement(PriceImprovement),
-    Volume(Volume),
+pub struct Policy {
+    kind: Kind,
+    selectors: Vec<Selector>,
 }
 
-pub struct Surplus {
-    factor: f64,
-    max_volume_factor: f64,
-    skip_market_orders: bool,
+enum Kind {
+    Surplus { factor: f64, max_volume_factor: f64 },
+    PriceImprovement { factor: f64, max_volume_factor: f64 },
+    Volume { factor: f64 },
 }
 
-pub struct PriceImprovement {
-    factor: f64,
-    max_volume_factor: f64,
-}
-
-pub struct Volume {
-    factor: f64,
+/// Condition an order has to fulfill for a policy to apply to it.
+enum Selector {
+    Class(boundary::OrderClass),
+    SellToken(H160),
+    BuyToken(H160),
+    Owner(H160),
+    AppCode(String),
+    OutsideMarketPrice,
 }
 
 impl From<arguments::FeePolicy> for Policy {
     fn from(policy_arg: arguments::FeePolicy) -> Self {
-        match policy_arg.fee_policy_kind {
+        let kind = match policy_arg.kind {
             arguments::FeePolicyKind::Surplus {
                 factor,
                 max_volume_factor,
-            } => Policy::Surplus(Surplus {
+            } => Kind::Surplus {
                 factor,
                 max_volume_factor,
-                skip_market_orders: policy_arg.fee_policy_skip_market_orders,
-            }),
+            },
             arguments::FeePolicyKind::PriceImprovement {
                 factor,
                 max_volume_factor,
-            } => Policy::PriceImprovement(PriceImprovement {
+            } => Kind::PriceImprovement {
                 factor,
                 max_volume_factor,
-            }),
-            arguments::FeePolicyKind::Volume { factor } => Policy::Volume(Volume { factor }),
-        }
+            },
+            arguments::FeePolicyKind::Volume { factor } => Kind::Volume { factor },
+        };
+        let selectors = policy_arg
+            .selectors
+            .into_iter()
+            .map(|selector| match selector {
+                arguments::FeePolicySelector::Class(class) => Selector::Class(class),
+                arguments::FeePolicySelector::SellToken(token) => Selector::SellToken(token),
+                arguments::FeePolicySelector::BuyToken(token) => Selector::BuyToken(token),
+                arguments::FeePolicySelector::Owner(owner) => Selector::Owner(owner),
+                arguments::FeePolicySelector::AppCode(app_code) => Selector::AppCode(app_code),
+                arguments::FeePolicySelector::OutsideMarketPrice => Selector::OutsideMarketPrice,
+            })
+            .collect();
+        Self { kind, selectors }
     }
 }
 
-impl Surplus {
+impl Policy {
+    /// Returns the protocol fee for the order if all of the policy's
+    /// selectors match it.
     pub fn apply(
         &self,
         order: &boundary::Order,
         quote: &domain::Quote,
     ) -> Option<domain::fee::Policy> {
-        match order.metadata.class {
-            boundary::OrderClass::Market => None,
-            boundary::OrderClass::Liquidity => None,
-            boundary::OrderClass::Limit => {
-                let policy = domain::fee::Policy::Surplus {
-                    factor: self.factor,
-                    max_volume_factor: self.max_volume_factor,
+        if !self
+            .selectors
+            .iter()
+            .all(|selector| selector.matches(order, quote))
+        {
+            return None;
+        }
+        let policy = match self.kind {
+            Kind::Surplus {
+                factor,
+                max_volume_factor,
+            } => domain::fee::Policy::Surplus {
+                factor,
+                max_volume_factor,
+            },
+            Kind::PriceImprovement {
+                factor,
+                max_volume_factor,
+            } => domain::fee::Policy::PriceImprovement {
+                factor,
+                max_volume_factor,
+                quote: quote.clone().into(),
+            },
+            Kind::Volume { factor } => domain::fee::Policy::Volume { factor },
+        };
+        Some(policy)
+    }
+}
+
+impl Selector {
+    fn matches(&self, order: &boundary::Order, quote: &domain::Quote) -> bool {
+        match self {
+            Self::Class(class) => order.metadata.class == *class,
+            Self::SellToken(token) => order.data.sell_token == *token,
+            Self::BuyToken(token) => order.data.buy_token == *token,
+            Self::Owner(owner) => order.metadata.owner == *owner,
+            Self::AppCode(app_code) => app_code_of(order).as_deref() == Some(app_code.as_str()),
+            Self::OutsideMarketPrice => {
+                let order_ = boundary::Amounts {
+                    sell: order.data.sell_amount,
+                    buy: order.data.buy_amount,
+                    fee: order.data.fee_amount,
+                };
+                let quote_ = boundary::Amounts {
+                    sell: quote.sell_amount,
+                    buy: quote.buy_amount,
+                    fee: quote.fee,
                 };
-                if !self.skip_market_orders {
-                    Some(policy)
-                } else {
-                    let order_ = boundary::Amounts {
-                        sell: order.data.sell_amount,
-                        buy: order.data.buy_amount,
-                        fee: order.data.fee_amount,
-                    };
-                    let quote_ = boundary::Amounts {
-                        sell: quote.sell_amount,
-                        buy: quote.buy_amount,
-                        fee: quote.fee,
-                    };
-
-                    boundary::is_order_outside_market_price(&order_, &quote_).then_some(policy)
-                }
+                boundary::is_order_outside_market_price(&order_, &quote_)
             }
         }
     }
 }
 
-impl PriceImprovement {
-    pub fn apply(
-        &self,
-        order: &boundary::Order,
-        quote: &domain::Quote,
-    ) -> Option<domain::fee::Policy> {
-        match order.metadata.class {
-            boundary::OrderClass::Market => None,
-            boundary::OrderClass::Liquidity => None,
-            boundary::OrderClass::Limit => Some(domain::fee::Policy::PriceImprovement {
-                factor: self.factor,
-                max_volume_factor: self.max_volume_factor,
-                quote: quote.clone().into(),
-            }),
-        }
+/// Extracts the `appCode` field from the order's full app data, if known.
+fn app_code_of(order: &boundary::Order) -> Option<String> {
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct AppData {
+        app_code: Option<String>,
     }
+
+    let full_app_data = order.metadata.full_app_data.as_ref()?;
+    serde_json::from_str::<AppData>(full_app_data)
+        .ok()?
+        .app_code
 }
 
-impl Volume {
-    pub fn apply(&self, order: &boundary::Order) -> Option<domain::fee::Policy> {
-        match order.metadata.class {
-            boundary::OrderClass::Market => None,
-            boundary::OrderClass::Liquidity => None,
-            boundary::OrderClass::Limit => Some(domain::fee::Policy::Volume {
-                factor: self.factor,
-            }),
+#[cfg(test)]
+mod tests {
+    use {super::*, model::order::OrderBuilder};
+
+    fn quote() -> domain::Quote {
+        domain::Quote {
+            order_uid: domain::OrderUid([0; 56]),
+            sell_amount: 100.into(),
+            buy_amount: 100.into(),
+            fee: 0.into(),
         }
     }
+
+    fn limit_order(buy_amount: u64) -> boundary::Order {
+        OrderBuilder::default()
+            .with_class(boundary::OrderClass::Limit)
+            .with_sell_amount(100.into())
+            .with_buy_amount(buy_amount.into())
+            .build()
+    }
+
+    fn policy(s: &str) -> Policy {
+        s.parse::<arguments::FeePolicy>().unwrap().into()
+    }
+
+    #[test]
+    fn selectors_must_all_match() {
+        let policy = policy("volume:0.1;class=limit;outsideMarketPrice");
+
+        // limit price is better than the quote
+        assert_eq!(
+            policy.apply(&limit_order(200), &quote()),
+            Some(domain::fee::Policy::Volume { factor: 0.1 })
+        );
+        // limit price is worse than the quote
+        assert_eq!(policy.apply(&limit_order(50), &quote()), None);
+        // wrong class
+        let market_order = OrderBuilder::default()
+            .with_class(boundary::OrderClass::Market)
+            .with_sell_amount(100.into())
+            .with_buy_amount(200.into())
+            .build();
+        assert_eq!(policy.apply(&market_order, &quote()), None);
+    }
+
+    #[test]
+    fn policy_without_selectors_applies_to_all_orders() {
+        let policy = policy("surplus:0.5:0.01");
+        let market_order = OrderBuilder::default()
+            .with_class(boundary::OrderClass::Market)
+            .build();
+        assert_eq!(
+            policy.apply(&market_order, &quote()),
+            Some(domain::fee::Policy::Surplus {
+                factor: 0.5,
+                max_volume_factor: 0.01,
+            })
+        );
+    }
+
+    #[test]
+    fn selects_by_app_code() {
+        let policy = policy("volume:0.001;appCode=MyApp");
+        let order = OrderBuilder::default()
+            .with_full_app_data(r#"{"appCode":"MyApp"}"#.to_string())
+            .build();
+        assert!(policy.apply(&order, &quote()).is_some());
+        let order = OrderBuilder::default()
+            .with_full_app_data(r#"{"appCode":"OtherApp"}"#.to_string())
+            .build();
+        assert!(policy.apply(&order, &quote()).is_none());
+        assert!(policy
+            .apply(&OrderBuilder::default().build(), &quote())
+            .is_none());
+    }
 }
@@ -36,9 +36,13 @@ pub struct Rpc {
 
 impl Rpc {
     /// Instantiate an RPC client to an Ethereum (or Ethereum-compatible) node
-    /// at the specifed URL.
-    pub async fn new(url: &url::Url) -> Result<Self, Error> {
-        let web3 = boundary::buffered_web3_client(url);
+    /// at the specifed URL that fails over to the fallback nodes.
+    pub async fn new(
+        url: &url::Url,
+        fallbacks: &[url::Url],
+        multi: &ethrpc::multi::Configuration,
+    ) -> Result<Self, Error> {
+        let web3 = boundary::buffered_web3_client(url, fallbacks, multi);
         let chain = web3.eth().chain_id().await?.into();
 
         Ok(Self { web3, chain })
@@ -120,6 +124,14 @@ impl Ethereum {
             .await
             .map_err(Into::into)
     }
+
+    pub async fn block(&self, number: u64) -> Result<Option<web3::types::Block<H256>>, Error> {
+        self.web3
+            .eth()
+            .block(web3::types::BlockNumber::Number(number.into()).into())
+            .await
+            .map_err(Into::into)
+    }
 }
 
 #[derive(Debug, Error)]
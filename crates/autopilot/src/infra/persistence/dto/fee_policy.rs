@@ -10,6 +10,7 @@ pub struct FeePolicy {
     pub volume_factor: Option<f64>,
     pub price_improvement_factor: Option<f64>,
     pub price_improvement_max_volume_factor: Option<f64>,
+    pub partner_recipient: Option<boundary::database::Address>,
 }
 
 impl FeePolicy {
@@ -31,6 +32,7 @@ impl FeePolicy {
                 volume_factor: None,
                 price_improvement_factor: None,
                 price_improvement_max_volume_factor: None,
+                partner_recipient: None,
             },
             domain::fee::Policy::Volume { factor } => Self {
                 auction_id,
@@ -41,6 +43,7 @@ impl FeePolicy {
                 volume_factor: Some(factor),
                 price_improvement_factor: None,
                 price_improvement_max_volume_factor: None,
+                partner_recipient: None,
             },
             domain::fee::Policy::PriceImprovement {
                 factor,
@@ -55,6 +58,18 @@ impl FeePolicy {
                 volume_factor: None,
                 price_improvement_factor: Some(factor),
                 price_improvement_max_volume_factor: Some(max_volume_factor),
+                partner_recipient: None,
+            },
+            domain::fee::Policy::Partner { factor, recipient } => Self {
+                auction_id,
+                order_uid: boundary::database::byte_array::ByteArray(order_uid.0),
+                kind: FeePolicyKind::Partner,
+                surplus_factor: None,
+                surplus_max_volume_factor: None,
+                volume_factor: Some(factor),
+                price_improvement_factor: None,
+                price_improvement_max_volume_factor: None,
+                partner_recipient: Some(boundary::database::byte_array::ByteArray(recipient.0)),
             },
         }
     }
@@ -66,4 +81,5 @@ pub enum FeePolicyKind {
     Surplus,
     Volume,
     PriceImprovement,
+    Partner,
 }
@@ -310,6 +310,9 @@ impl From<domain::fee::Policy> for FeePolicy {
                 },
             },
             domain::fee::Policy::Volume { factor } => Self::Volume { factor },
+            // Partner fees are taken like volume fees. Their recipient is only
+            // needed for paying them out and is persisted in the fee policies.
+            domain::fee::Policy::Partner { factor, .. } => Self::Volume { factor },
         }
     }
 }
@@ -96,13 +96,36 @@ impl Persistence {
         &self,
         order_uids: Vec<domain::OrderUid>,
         label: boundary::OrderEventLabel,
+    ) {
+        let events = order_uids.into_iter().map(|uid| (uid, None)).collect();
+        self.spawn_store_order_events(events, label);
+    }
+
+    /// Like [`Self::store_order_events`] but additionally records why each
+    /// order got filtered or marked as invalid.
+    pub fn store_filtered_order_events(
+        &self,
+        events: Vec<(domain::OrderUid, boundary::OrderFilterReason)>,
+        label: boundary::OrderEventLabel,
+    ) {
+        let events = events
+            .into_iter()
+            .map(|(uid, reason)| (uid, Some(reason)))
+            .collect();
+        self.spawn_store_order_events(events, label);
+    }
+
+    fn spawn_store_order_events(
+        &self,
+        events: Vec<(domain::OrderUid, Option<boundary::OrderFilterReason>)>,
+        label: boundary::OrderEventLabel,
     ) {
         let db = self.postgres.clone();
         tokio::spawn(
             async move {
                 let start = Instant::now();
-                let events_count = order_uids.len();
-                match boundary::store_order_events(&db, order_uids, label, Utc::now()).await {
+                let events_count = events.len();
+                match boundary::store_order_events(&db, events, label, Utc::now()).await {
                     Ok(_) => {
                         tracing::debug!(elapsed=?start.elapsed(), ?events_count, "stored order events");
                     }
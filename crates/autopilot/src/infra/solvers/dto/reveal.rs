@@ -11,6 +11,8 @@ pub struct Request {
     /// Unique ID of the solution (per driver competition), to reveal.
     #[serde_as(as = "serde_with::DisplayFromStr")]
     pub solution_id: u64,
+    /// The auction the solution was computed for.
+    pub auction_id: i64,
 }
 
 #[serde_as]
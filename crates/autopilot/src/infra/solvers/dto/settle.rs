@@ -12,6 +12,10 @@ pub struct Request {
     /// Unique ID of the solution (per driver competition), to settle.
     #[serde_as(as = "serde_with::DisplayFromStr")]
     pub solution_id: u64,
+    /// The auction the solution was computed for.
+    pub auction_id: i64,
+    /// The last block in which the settlement may be included.
+    pub submission_deadline_latest_block: u64,
 }
 
 #[serde_as]
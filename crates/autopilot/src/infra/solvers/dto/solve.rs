@@ -103,6 +103,13 @@ pub struct Solution {
     pub orders: HashMap<boundary::OrderUid, TradedAmounts>,
     #[serde_as(as = "HashMap<_, HexOrDecimalU256>")]
     pub clearing_prices: HashMap<H160, U256>,
+    /// Addresses of the liquidity pools used by the solution.
+    #[serde(default)]
+    pub liquidity: HashSet<H160>,
+    /// Whether the solution contains custom interactions whose liquidity is
+    /// not part of `liquidity`.
+    #[serde(default)]
+    pub custom_interactions: bool,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
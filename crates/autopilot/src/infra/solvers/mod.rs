@@ -75,11 +75,38 @@ impl Driver {
             .context("body")?;
         let text = String::from_utf8_lossy(&body);
         tracing::trace!(%status, body=%text, "solver response");
-        let context = || format!("url {url}, body {text:?}");
         if status != 200 {
-            return Err(anyhow!("bad status {status}, {}", context()));
+            return Err(BadStatus {
+                status,
+                url,
+                body: text.into_owned(),
+            }
+            .into());
         }
-        serde_json::from_slice(&body).with_context(|| format!("bad json {}", context()))
+        serde_json::from_slice(&body).with_context(|| format!("bad json url {url}, body {text:?}"))
+    }
+}
+
+/// A non-200 response from the driver.
+#[derive(Debug, thiserror::Error)]
+#[error("bad status {status}, url {url}, body {body:?}")]
+pub struct BadStatus {
+    pub status: u16,
+    pub url: Url,
+    pub body: String,
+}
+
+impl BadStatus {
+    /// The error kind reported by the driver, if the body contains one.
+    pub fn kind(&self) -> Option<String> {
+        #[derive(serde::Deserialize)]
+        struct Error {
+            kind: String,
+        }
+
+        serde_json::from_str::<Error>(&self.body)
+            .ok()
+            .map(|error| error.kind)
     }
 }
 
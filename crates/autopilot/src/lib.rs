@@ -1,5 +1,6 @@
 pub mod arguments;
 pub mod boundary;
+pub mod conditional_orders;
 pub mod database;
 pub mod decoded_settlement;
 pub mod domain;
@@ -11,6 +12,7 @@ pub mod run;
 pub mod run_loop;
 pub mod shadow;
 pub mod solvable_orders;
+pub mod solver_rewards;
 pub mod util;
 
 pub use self::run::{run, start};
@@ -27,6 +27,12 @@
 // transaction and insert them into the database (settlement_observations
 // table).
 
+// 3. Backfilling block timestamps
+//
+// Settlements indexed before block timestamps were recorded get their
+// timestamp backfilled, most recent first, whenever there are no new
+// settlement events to process.
+
 use {
     crate::{
         database::{
@@ -37,7 +43,8 @@ use {
         infra,
     },
     anyhow::{Context, Result},
-    database::PgTransaction,
+    chrono::{DateTime, TimeZone, Utc},
+    database::{byte_array::ByteArray, PgTransaction},
     primitive_types::H256,
     shared::external_prices::ExternalPrices,
     sqlx::PgConnection,
@@ -132,7 +139,7 @@ impl Inner {
             .context("get_settlement_event_without_tx_info")?
         {
             Some(event) => event,
-            None => return Ok(false),
+            None => return self.backfill_block_timestamp(ex).await,
         };
 
         let hash = H256(event.tx_hash.0);
@@ -160,10 +167,13 @@ impl Inner {
                 ),
             };
 
+        let block_timestamp = self.block_timestamp(event.block_number).await?;
+
         let update = SettlementUpdate {
             block_number: event.block_number,
             log_index: event.log_index,
             auction_id,
+            block_timestamp,
             auction_data,
         };
 
@@ -176,6 +186,44 @@ impl Inner {
         Ok(true)
     }
 
+    /// Records the block timestamp of the most recent settlement that was
+    /// indexed before block timestamps were recorded.
+    ///
+    /// Returns whether an update was performed.
+    async fn backfill_block_timestamp(&self, mut ex: PgTransaction<'_>) -> Result<bool> {
+        let Some(block_number) = database::settlements::block_without_timestamp(&mut ex)
+            .await
+            .context("block_without_timestamp")?
+        else {
+            return Ok(false);
+        };
+        let Some(block_timestamp) = self.block_timestamp(block_number).await? else {
+            return Ok(false);
+        };
+        tracing::debug!(
+            block_number,
+            ?block_timestamp,
+            "backfilling block timestamp"
+        );
+        database::settlements::update_block_timestamp(&mut ex, block_number, block_timestamp)
+            .await
+            .context("update_block_timestamp")?;
+        ex.commit().await?;
+        Ok(true)
+    }
+
+    /// Fetches the timestamp of the block a settlement happened in. It is only
+    /// used for filtering trades by time so a missing block is not an error.
+    async fn block_timestamp(&self, block_number: i64) -> Result<Option<DateTime<Utc>>> {
+        let block_number = u64::try_from(block_number).context("negative block number")?;
+        let Some(block) = self.eth.block(block_number).await? else {
+            tracing::warn!(block_number, "no block found, reorg happened");
+            return Ok(None);
+        };
+        let timestamp = i64::try_from(block.timestamp.as_u64()).context("block timestamp")?;
+        Ok(Utc.timestamp_opt(timestamp, 0).single())
+    }
+
     async fn fetch_auction_data(
         &self,
         hash: H256,
@@ -272,8 +320,13 @@ impl Inner {
         };
 
         let score = database::settlement_scores::fetch(ex, auction_id).await?;
+        let winners = database::auction_winners::fetch(ex, auction_id).await?;
+        // Auctions from before multiple winners were supported only have a score.
+        let is_winner = |score: &database::settlement_scores::Score| {
+            score.winner.0 == tx_from.0 || winners.iter().any(|w| w.winner.0 == tx_from.0)
+        };
         let data_already_recorded =
-            database::settlements::already_processed(ex, auction_id).await?;
+            database::settlements::already_processed(ex, auction_id, &ByteArray(tx_from.0)).await?;
         match (score, data_already_recorded) {
             (None, _) => {
                 tracing::debug!(
@@ -282,12 +335,12 @@ impl Inner {
                 );
                 Ok(AuctionIdRecoveryStatus::DoNotAddAuctionData(auction_id))
             }
-            (Some(score), _) if score.winner.0 != tx_from.0 => {
+            (Some(score), _) if !is_winner(&score) => {
                 tracing::warn!(
                     auction_id,
                     ?tx_from,
                     winner = ?score.winner,
-                    "solution submitted by solver other than the winner"
+                    "solution submitted by solver other than a winner"
                 );
                 Ok(AuctionIdRecoveryStatus::DoNotAddAuctionData(auction_id))
             }
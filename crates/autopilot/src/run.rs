@@ -3,6 +3,10 @@ use {
         arguments::Arguments,
         boundary,
         database::{
+            conditional_orders::event_retriever::{
+                determine_composable_cow_indexing_start,
+                ConditionalOrderRetriever,
+            },
             ethflow_events::event_retriever::EthFlowRefundRetriever,
             onchain_order_events::{
                 ethflow_events::{determine_ethflow_indexing_start, EthFlowOnchainOrderParser},
@@ -26,6 +30,7 @@ use {
     model::DomainSeparator,
     shared::{
         account_balances,
+        arguments::PoolDiscovery,
         bad_token::{
             cache::CachingDetector,
             instrumented::InstrumentedBadTokenDetectorExt,
@@ -48,7 +53,7 @@ use {
                 BalancerPoolFetcher,
             },
             uniswap_v2::{pool_cache::PoolCache, UniV2BaselineSourceParameters},
-            uniswap_v3::pool_fetching::UniswapV3PoolFetcher,
+            uniswap_v3::pool_fetching::{FactoryEventsDiscovery, UniswapV3PoolFetcher},
             BaselineSource,
             PoolAggregator,
         },
@@ -90,8 +95,8 @@ impl Liveness {
     }
 }
 
-async fn ethrpc(url: &Url) -> infra::blockchain::Rpc {
-    infra::blockchain::Rpc::new(url)
+async fn ethrpc(args: &shared::ethrpc::Arguments, url: &Url) -> infra::blockchain::Rpc {
+    infra::blockchain::Rpc::new(url, &args.ethrpc_fallback_urls, &args.multi())
         .await
         .expect("connect ethereum RPC")
 }
@@ -135,7 +140,7 @@ pub async fn run(args: Arguments) {
     crate::database::run_database_metrics_work(db.clone());
 
     let http_factory = HttpClientFactory::new(&args.http_client);
-    let web3 = shared::ethrpc::web3(
+    let web3 = shared::ethrpc::web3_with_fallbacks(
         &args.shared.ethrpc,
         &http_factory,
         &args.shared.node_url,
@@ -158,7 +163,7 @@ pub async fn run(args: Arguments) {
         );
     }
 
-    let ethrpc = ethrpc(&args.shared.node_url).await;
+    let ethrpc = ethrpc(&args.shared.ethrpc, &args.shared.node_url).await;
     let contracts = infra::blockchain::contracts::Addresses {
         settlement: args.shared.settlement_contract_address,
         weth: args.shared.native_token_address,
@@ -326,8 +331,8 @@ pub async fn run(args: Arguments) {
             .clone()
             .unwrap_or_else(|| BalancerFactoryKind::for_chain(chain_id));
         let contracts = BalancerContracts::new(&web3, factories).await.unwrap();
-        match BalancerPoolFetcher::new(
-            &args.shared.graph_api_base_url,
+        let fetcher = BalancerPoolFetcher::from_args(
+            &args.shared,
             chain_id,
             block_retriever.clone(),
             token_info_fetcher.clone(),
@@ -336,10 +341,9 @@ pub async fn run(args: Arguments) {
             http_factory.create(),
             web3.clone(),
             &contracts,
-            args.shared.balancer_pool_deny_list.clone(),
         )
-        .await
-        {
+        .await;
+        match fetcher {
             Ok(fetcher) => Some(Arc::new(fetcher)),
             Err(err) => {
                 tracing::error!(
@@ -355,16 +359,50 @@ pub async fn run(args: Arguments) {
         None
     };
     let uniswap_v3_pool_fetcher = if baseline_sources.contains(&BaselineSource::UniswapV3) {
-        match UniswapV3PoolFetcher::new(
-            &args.shared.graph_api_base_url,
-            chain_id,
-            web3.clone(),
-            http_factory.create(),
-            block_retriever,
-            args.shared.max_pools_to_initialize_cache,
-        )
-        .await
-        {
+        let fetcher = match args.shared.uniswap_v3_pool_discovery {
+            PoolDiscovery::Subgraph => {
+                UniswapV3PoolFetcher::new(
+                    &args.shared.graph_api_base_url,
+                    chain_id,
+                    web3.clone(),
+                    http_factory.create(),
+                    block_retriever,
+                    args.shared.max_pools_to_initialize_cache,
+                )
+                .await
+            }
+            PoolDiscovery::FactoryEvents => {
+                match args
+                    .shared
+                    .uniswap_v3_factory_address
+                    .or(uniswapv3_factory.as_ref().map(|factory| factory.address()))
+                {
+                    Some(factory) => {
+                        async {
+                            let config = FactoryEventsDiscovery::new(
+                                factory,
+                                args.shared.uniswap_v3_factory_start_block,
+                                args.shared.uniswap_v3_pool_checkpoint_path.clone(),
+                                chain_id,
+                            )
+                            .await?;
+                            UniswapV3PoolFetcher::from_factory_events(
+                                config,
+                                web3.clone(),
+                                block_retriever,
+                                token_info_fetcher.clone(),
+                            )
+                            .await
+                        }
+                        .await
+                    }
+                    None => Err(anyhow::anyhow!(
+                        "UniswapV3 factory is not deployed on this network"
+                    )),
+                }
+            }
+        };
+        match fetcher {
             Ok(fetcher) => Some(Arc::new(fetcher)),
             Err(err) => {
                 tracing::error!(
@@ -422,6 +460,7 @@ pub async fn run(args: Arguments) {
                 &args.order_quoting.price_estimation_legacy_solvers,
             ),
             args.native_price_estimation_results_required,
+            Arc::new(db.clone()),
         )
         .unwrap();
     let price_estimator = price_estimator_factory
@@ -514,7 +553,7 @@ pub async fn run(args: Arguments) {
                 // interface called CoWSwapOnchainOrders.
                 CoWSwapOnchainOrdersContract::new(web3.clone(), ethflow_contract),
                 onchain_order_event_parser,
-                block_retriever,
+                block_retriever.clone(),
                 start_block,
             )
             .await
@@ -522,6 +561,38 @@ pub async fn run(args: Arguments) {
         );
         maintainers.push(broadcaster_event_updater);
     }
+    if let Some(composable_cow) = args.composable_cow_contract {
+        let start_block = determine_composable_cow_indexing_start(
+            &skip_event_sync_start,
+            args.composable_cow_indexing_start,
+            &web3,
+        )
+        .await;
+        let conditional_order_updater = Arc::new(
+            EventUpdater::new_skip_blocks_before(
+                ConditionalOrderRetriever::new(web3.clone(), composable_cow),
+                db.clone(),
+                block_retriever,
+                start_block,
+            )
+            .await
+            .unwrap(),
+        );
+        maintainers.push(conditional_order_updater);
+
+        let watch_tower = crate::conditional_orders::WatchTower::new(
+            db.clone(),
+            web3.clone(),
+            composable_cow,
+            crate::conditional_orders::Orderbook::new(http_factory.create(), args.orderbook_url),
+            DomainSeparator::new(chain_id, eth.contracts().settlement().address()),
+        );
+        tokio::task::spawn(
+            watch_tower
+                .run_forever(eth.current_block().clone())
+                .instrument(tracing::info_span!("conditional_orders")),
+        );
+    }
     if let Some(uniswap_v3) = uniswap_v3_pool_fetcher {
         maintainers.push(uniswap_v3);
     }
@@ -552,7 +623,7 @@ pub async fn run(args: Arguments) {
         args.limit_order_price_factor
             .try_into()
             .expect("limit order price factor can't be converted to BigDecimal"),
-        domain::ProtocolFee::new(args.fee_policy.clone()),
+        domain::ProtocolFee::new(args.fee_policies.clone()),
     );
     solvable_orders_cache
         .update(block)
@@ -566,8 +637,10 @@ pub async fn run(args: Arguments) {
         args.order_events_cleanup_interval,
         args.order_events_cleanup_threshold,
     );
-    let order_events_cleaner =
-        crate::periodic_db_cleanup::OrderEventsCleaner::new(order_events_cleaner_config, db);
+    let order_events_cleaner = crate::periodic_db_cleanup::OrderEventsCleaner::new(
+        order_events_cleaner_config,
+        db.clone(),
+    );
 
     tokio::task::spawn(
         order_events_cleaner
@@ -575,6 +648,18 @@ pub async fn run(args: Arguments) {
             .instrument(tracing::info_span!("order_events_cleaner")),
     );
 
+    let solver_rewards = crate::solver_rewards::RewardCalculator::new(
+        eth.clone(),
+        db,
+        args.solver_rewards_update_interval,
+        args.score_cap,
+    );
+    tokio::task::spawn(
+        solver_rewards
+            .run_forever()
+            .instrument(tracing::info_span!("solver_rewards")),
+    );
+
     let market_makable_token_list_configuration = TokenListConfiguration {
         url: args.trusted_tokens_url,
         update_interval: args.trusted_tokens_update_interval,
@@ -597,6 +682,7 @@ pub async fn run(args: Arguments) {
         market_makable_token_list,
         submission_deadline: args.submission_deadline as u64,
         additional_deadline_for_rewards: args.additional_deadline_for_rewards as u64,
+        max_winners_per_auction: args.max_winners_per_auction,
         score_cap: args.score_cap,
         max_settlement_transaction_wait: args.max_settlement_transaction_wait,
         solve_deadline: args.solve_deadline,
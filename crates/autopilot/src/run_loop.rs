@@ -1,6 +1,6 @@
 use {
     crate::{
-        database::competition::Competition,
+        database::competition::{Competition, Winner},
         domain::{self, auction::order::Class, OrderUid},
         infra::{
             self,
@@ -12,6 +12,7 @@ use {
     ::observe::metrics,
     anyhow::Result,
     database::order_events::OrderEventLabel,
+    futures::StreamExt,
     model::solver_competition::{
         CompetitionAuction,
         Order,
@@ -42,10 +43,11 @@ pub struct RunLoop {
     pub market_makable_token_list: AutoUpdatingTokenList,
     pub submission_deadline: u64,
     pub additional_deadline_for_rewards: u64,
+    pub max_winners_per_auction: usize,
     pub score_cap: U256,
     pub max_settlement_transaction_wait: Duration,
     pub solve_deadline: Duration,
-    pub in_flight_orders: Arc<Mutex<Option<InFlightOrders>>>,
+    pub in_flight_orders: Arc<Mutex<Vec<InFlightOrders>>>,
     pub liveness: Arc<Liveness>,
 }
 
@@ -128,141 +130,93 @@ impl RunLoop {
             solutions.sort_unstable_by_key(|participant| participant.solution.score);
             solutions
         };
+        let solutions = &solutions;
+        let ranked = solutions
+            .iter()
+            .map(|participant| &participant.solution)
+            .collect::<Vec<_>>();
         let competition_simulation_block = self.eth.current_block().borrow().number;
-
-        // TODO: Keep going with other solutions until some deadline.
-        if let Some(Participant { driver, solution }) = solutions.last() {
-            tracing::info!(driver = %driver.name, solution = %solution.id, "winner");
-
-            let revealed = match self.reveal(driver, auction_id, solution.id).await {
-                Ok(result) => {
-                    Metrics::reveal_ok(driver);
-                    result
-                }
-                Err(err) => {
-                    Metrics::reveal_err(driver, &err);
-                    tracing::warn!(driver = %driver.name, ?err, "failed to reveal winning solution");
-                    return;
-                }
-            };
-
-            let order_uids = solution.order_ids().copied().collect();
-            self.persistence
-                .store_order_events(order_uids, OrderEventLabel::Considered);
-
-            let winner = solution.account;
-            let winning_score = solution.score.get();
-            let reference_score = solutions
-                .iter()
-                .nth_back(1)
-                .map(|participant| participant.solution.score.get())
-                .unwrap_or_default();
-            let participants = solutions
+        let submission_deadline = competition_simulation_block + self.submission_deadline;
+
+        // Select the best non-overlapping solutions as winners and fall back to
+        // the next best ones whenever a winner fails to reveal or settle its
+        // solution. Solutions are identified by their index in `solutions`.
+        let mut skipped = HashMap::new();
+        let mut revealed = HashMap::new();
+        let mut settled = Vec::new();
+        // Winners whose settlement was handed to the driver, including those
+        // that failed but might still end up on chain.
+        let mut submitted = Vec::new();
+        let mut in_flight = Vec::new();
+        let mut stored_fee_policies = HashSet::new();
+        loop {
+            if self.eth.current_block().borrow().number > submission_deadline {
+                tracing::info!("submission deadline reached, not trying remaining solutions");
+                break;
+            }
+            let winners = select_winners(
+                &ranked,
+                &submitted,
+                |index| skipped.contains_key(&index),
+                self.max_winners_per_auction,
+            );
+            let pending = winners
                 .iter()
-                .map(|participant| participant.solution.account)
-                .collect::<HashSet<_>>();
-
-            let mut prices = BTreeMap::new();
-            let mut fee_policies = Vec::new();
-            let block_deadline = competition_simulation_block
-                + self.submission_deadline
-                + self.additional_deadline_for_rewards;
-            let call_data = revealed.calldata.internalized.clone();
-            let uninternalized_call_data = revealed.calldata.uninternalized.clone();
-
-            for order_id in solution.order_ids() {
-                let auction_order = auction
-                    .orders
+                .copied()
+                .filter(|index| !submitted.contains(index))
+                .collect::<Vec<_>>();
+            if pending.is_empty() {
+                break;
+            }
+
+            let reveals = futures::future::join_all(
+                pending
                     .iter()
-                    .find(|auction_order| &auction_order.uid == order_id);
-                match auction_order {
-                    Some(auction_order) => {
-                        fee_policies.push((auction_order.uid, auction_order.protocol_fees.clone()));
-                        if let Some(price) = auction.prices.get(&auction_order.sell_token) {
-                            prices.insert(auction_order.sell_token, *price);
-                        } else {
-                            tracing::error!(
-                                sell_token = ?auction_order.sell_token,
-                                "sell token price is missing in auction"
-                            );
-                        }
-                        if let Some(price) = auction.prices.get(&auction_order.buy_token) {
-                            prices.insert(auction_order.buy_token, *price);
-                        } else {
-                            tracing::error!(
-                                buy_token = ?auction_order.buy_token,
-                                "buy token price is missing in auction"
-                            );
-                        }
+                    .copied()
+                    .filter(|index| !revealed.contains_key(index))
+                    .map(|index| async move {
+                        let Participant { driver, solution } = &solutions[index];
+                        tracing::info!(driver = %driver.name, solution = %solution.id, "winner");
+                        (index, self.reveal(driver, auction_id, solution.id).await)
+                    }),
+            )
+            .await;
+            let mut reveal_failed = false;
+            for (index, result) in reveals {
+                let driver = solutions[index].driver;
+                match result {
+                    Ok(response) => {
+                        Metrics::reveal_ok(driver);
+                        revealed.insert(index, response);
                     }
-                    None => {
-                        tracing::debug!(?order_id, "order not found in auction");
+                    Err(err) => {
+                        Metrics::reveal_err(driver, &err);
+                        tracing::warn!(driver = %driver.name, ?err, "failed to reveal winning solution");
+                        skipped.insert(index, format!("reveal failed: {err}"));
+                        reveal_failed = true;
                     }
                 }
             }
+            if reveal_failed {
+                // Select new winners in place of the ones that failed.
+                continue;
+            }
 
-            let competition_table = SolverCompetitionDB {
-                auction_start_block: auction.block,
-                competition_simulation_block,
-                auction: CompetitionAuction {
-                    orders: auction
-                        .orders
-                        .iter()
-                        .map(|order| order.uid.into())
-                        .collect(),
-                    prices: auction.prices.clone(),
-                },
-                solutions: solutions
-                    .iter()
-                    .enumerate()
-                    .map(|(index, participant)| {
-                        let is_winner = solutions.len() - index == 1;
-                        let mut settlement = SolverSettlement {
-                            solver: participant.driver.name.clone(),
-                            solver_address: participant.solution.account,
-                            score: Some(Score::Solver(participant.solution.score.get())),
-                            ranking: solutions.len() - index,
-                            orders: participant
-                                .solution
-                                .orders()
-                                .iter()
-                                .map(|(id, order)| Order::Colocated {
-                                    id: (*id).into(),
-                                    sell_amount: order.sell_amount,
-                                    buy_amount: order.buy_amount,
-                                })
-                                .collect(),
-                            clearing_prices: participant
-                                .solution
-                                .clearing_prices
-                                .iter()
-                                .map(|(token, price)| (*token, *price))
-                                .collect(),
-                            call_data: None,
-                            uninternalized_call_data: None,
-                        };
-                        if is_winner {
-                            settlement.call_data = Some(revealed.calldata.internalized.clone());
-                            settlement.uninternalized_call_data =
-                                Some(revealed.calldata.uninternalized.clone());
-                        }
-                        settlement
-                    })
-                    .collect(),
-            };
-            let competition = Competition {
+            for index in &pending {
+                let order_uids = solutions[*index].solution.order_ids().copied().collect();
+                self.persistence
+                    .store_order_events(order_uids, OrderEventLabel::Considered);
+            }
+
+            let competition = self.competition_record(
                 auction_id,
-                winner,
-                winning_score,
-                reference_score,
-                participants,
-                prices,
-                block_deadline,
+                &auction,
+                solutions,
+                &winners,
+                &revealed,
+                &skipped,
                 competition_simulation_block,
-                call_data,
-                uninternalized_call_data,
-                competition_table,
-            };
+            );
 
             tracing::info!(?competition, "saving competition");
             if let Err(err) = self.persistence.save_competition(&competition).await {
@@ -270,6 +224,15 @@ impl RunLoop {
                 return;
             }
 
+            // Fee policies of orders that were part of a previously tried solution
+            // have already been stored.
+            let fee_policies = pending
+                .iter()
+                .flat_map(|index| solutions[*index].solution.order_ids())
+                .filter(|uid| stored_fee_policies.insert(**uid))
+                .filter_map(|uid| auction.orders.iter().find(|order| &order.uid == uid))
+                .map(|order| (order.uid, order.protocol_fees.clone()))
+                .collect();
             tracing::info!("saving fee policies");
             if let Err(err) = self
                 .persistence
@@ -280,21 +243,220 @@ impl RunLoop {
                 tracing::warn!(?err, "failed to save fee policies");
             }
 
-            tracing::info!(driver = %driver.name, "settling");
-            let submission_start = Instant::now();
-            match self.settle(driver, solution).await {
-                Ok(()) => Metrics::settle_ok(driver, submission_start.elapsed()),
-                Err(err) => {
-                    Metrics::settle_err(driver, &err, submission_start.elapsed());
-                    tracing::warn!(?err, driver = %driver.name, "settlement failed");
+            let settlements =
+                futures::future::join_all(pending.iter().copied().map(|index| async move {
+                    let Participant { driver, solution } = &solutions[index];
+                    tracing::info!(driver = %driver.name, "settling");
+                    let submission_start = Instant::now();
+                    // `max_settlement_transaction_wait` is usually longer than
+                    // the submission deadline, so stop waiting for the driver
+                    // once the deadline has passed.
+                    let settle = self.settle(driver, auction_id, solution, submission_deadline);
+                    let result = tokio::select! {
+                        result = settle => result,
+                        _ = self.wait_past_block(submission_deadline) => Err(SettleError::Timeout),
+                    };
+                    (index, result, submission_start.elapsed())
+                }))
+                .await;
+            let mut settle_failed = false;
+            for (index, result, elapsed) in settlements {
+                let Participant { driver, solution } = &solutions[index];
+                match result {
+                    Ok(tx_hash) => {
+                        Metrics::settle_ok(driver, elapsed);
+                        settled.push(index);
+                        submitted.push(index);
+                        in_flight.push(InFlightOrders {
+                            tx_hash,
+                            orders: solution.order_ids().copied().collect(),
+                        });
+                    }
+                    Err(err) => {
+                        Metrics::settle_err(driver, &err, elapsed);
+                        tracing::warn!(?err, driver = %driver.name, "settlement failed");
+                        // Only fall back to other solutions if this one can no
+                        // longer end up on chain, otherwise both might settle
+                        // the same orders.
+                        if matches!(err, SettleError::NotSubmitted(_)) {
+                            skipped.insert(index, format!("settle failed: {err}"));
+                            settle_failed = true;
+                        } else {
+                            submitted.push(index);
+                        }
+                    }
                 }
             }
-            let unsettled_orders: HashSet<_> = solutions
+            if !in_flight.is_empty() {
+                *self.in_flight_orders.lock().await = in_flight.clone();
+            }
+            if !settle_failed {
+                break;
+            }
+        }
+
+        let Some(best) = settled.iter().max() else {
+            tracing::warn!(skipped = skipped.len(), "no solution was settled");
+            return;
+        };
+        let settled_orders: HashSet<_> = settled
+            .iter()
+            .flat_map(|index| solutions[*index].solution.order_ids())
+            .collect();
+        let unsettled_orders: HashSet<_> = solutions
+            .iter()
+            .flat_map(|p| p.solution.order_ids())
+            .filter(|uid| !settled_orders.contains(uid))
+            .collect();
+        Metrics::matched_unsettled(solutions[*best].driver, unsettled_orders);
+    }
+
+    /// Builds the competition record for the auction with the participants at
+    /// the `winners` indices into the ranked `solutions` settling the auction.
+    #[allow(clippy::too_many_arguments)]
+    fn competition_record(
+        &self,
+        auction_id: domain::auction::Id,
+        auction: &domain::Auction,
+        solutions: &[Participant<'_>],
+        winners: &[usize],
+        revealed: &HashMap<usize, reveal::Response>,
+        skipped: &HashMap<usize, String>,
+        competition_simulation_block: u64,
+    ) -> Competition {
+        let ranked = solutions
+            .iter()
+            .map(|participant| &participant.solution)
+            .collect::<Vec<_>>();
+        let reference_scores = winners
+            .iter()
+            .map(|winner| {
+                let reference_score = reference_score(
+                    &ranked,
+                    winners,
+                    *winner,
+                    |index| skipped.contains_key(&index),
+                    self.max_winners_per_auction,
+                );
+                (*winner, reference_score)
+            })
+            .collect::<HashMap<_, _>>();
+        let participants = solutions
+            .iter()
+            .map(|participant| participant.solution.account)
+            .collect::<HashSet<_>>();
+
+        let mut prices = BTreeMap::new();
+        let block_deadline = competition_simulation_block
+            + self.submission_deadline
+            + self.additional_deadline_for_rewards;
+        // `winners` are sorted best ranked first.
+        let best = &revealed[&winners[0]];
+        let call_data = best.calldata.internalized.clone();
+        let uninternalized_call_data = best.calldata.uninternalized.clone();
+
+        for order_id in winners
+            .iter()
+            .flat_map(|winner| solutions[*winner].solution.order_ids())
+        {
+            let auction_order = auction
+                .orders
                 .iter()
-                .flat_map(|p| p.solution.orders.keys())
-                .filter(|uid| !solution.orders.contains_key(uid))
-                .collect();
-            Metrics::matched_unsettled(driver, unsettled_orders);
+                .find(|auction_order| &auction_order.uid == order_id);
+            match auction_order {
+                Some(auction_order) => {
+                    if let Some(price) = auction.prices.get(&auction_order.sell_token) {
+                        prices.insert(auction_order.sell_token, *price);
+                    } else {
+                        tracing::error!(
+                            sell_token = ?auction_order.sell_token,
+                            "sell token price is missing in auction"
+                        );
+                    }
+                    if let Some(price) = auction.prices.get(&auction_order.buy_token) {
+                        prices.insert(auction_order.buy_token, *price);
+                    } else {
+                        tracing::error!(
+                            buy_token = ?auction_order.buy_token,
+                            "buy token price is missing in auction"
+                        );
+                    }
+                }
+                None => {
+                    tracing::debug!(?order_id, "order not found in auction");
+                }
+            }
+        }
+
+        let competition_table = SolverCompetitionDB {
+            auction_start_block: auction.block,
+            competition_simulation_block,
+            auction: CompetitionAuction {
+                orders: auction
+                    .orders
+                    .iter()
+                    .map(|order| order.uid.into())
+                    .collect(),
+                prices: auction.prices.clone(),
+            },
+            solutions: solutions
+                .iter()
+                .enumerate()
+                .map(|(index, participant)| {
+                    let is_winner = winners.contains(&index);
+                    let mut settlement = SolverSettlement {
+                        solver: participant.driver.name.clone(),
+                        solver_address: participant.solution.account,
+                        score: Some(Score::Solver(participant.solution.score.get())),
+                        ranking: solutions.len() - index,
+                        orders: participant
+                            .solution
+                            .orders()
+                            .iter()
+                            .map(|(id, order)| Order::Colocated {
+                                id: (*id).into(),
+                                sell_amount: order.sell_amount,
+                                buy_amount: order.buy_amount,
+                            })
+                            .collect(),
+                        clearing_prices: participant
+                            .solution
+                            .clearing_prices
+                            .iter()
+                            .map(|(token, price)| (*token, *price))
+                            .collect(),
+                        call_data: None,
+                        uninternalized_call_data: None,
+                        is_winner,
+                        reference_score: reference_scores.get(&index).copied(),
+                        skip_reason: skipped.get(&index).cloned(),
+                    };
+                    if let Some(revealed) = revealed.get(&index).filter(|_| is_winner) {
+                        settlement.call_data = Some(revealed.calldata.internalized.clone());
+                        settlement.uninternalized_call_data =
+                            Some(revealed.calldata.uninternalized.clone());
+                    }
+                    settlement
+                })
+                .collect(),
+        };
+        Competition {
+            auction_id,
+            winners: winners
+                .iter()
+                .map(|winner| Winner {
+                    solver: solutions[*winner].solution.account,
+                    score: solutions[*winner].solution.score.get(),
+                    reference_score: reference_scores[winner],
+                })
+                .collect(),
+            participants,
+            prices,
+            block_deadline,
+            competition_simulation_block,
+            call_data,
+            uninternalized_call_data,
+            competition_table,
         }
     }
 
@@ -382,6 +544,8 @@ impl RunLoop {
                         .map(|(o, amounts)| (o.into(), amounts))
                         .collect(),
                     clearing_prices: solution.clearing_prices,
+                    liquidity: solution.liquidity,
+                    custom_interactions: solution.custom_interactions,
                 })
             })
             .collect())
@@ -395,7 +559,10 @@ impl RunLoop {
         solution_id: u64,
     ) -> Result<reveal::Response, RevealError> {
         let response = driver
-            .reveal(&reveal::Request { solution_id })
+            .reveal(&reveal::Request {
+                solution_id,
+                auction_id: auction,
+            })
             .await
             .map_err(RevealError::Failure)?;
         if !response
@@ -409,69 +576,88 @@ impl RunLoop {
         Ok(response)
     }
 
-    /// Execute the solver's solution. Returns Ok when the corresponding
-    /// transaction has been mined.
-    async fn settle(&self, driver: &infra::Driver, solved: &Solution) -> Result<(), SettleError> {
+    /// Execute the solver's solution. Returns the hash of the corresponding
+    /// transaction once it has been mined.
+    async fn settle(
+        &self,
+        driver: &infra::Driver,
+        auction: domain::auction::Id,
+        solved: &Solution,
+        submission_deadline: u64,
+    ) -> Result<H256, SettleError> {
         let order_ids = solved.order_ids().copied().collect();
         self.persistence
             .store_order_events(order_ids, OrderEventLabel::Executing);
 
         let request = settle::Request {
             solution_id: solved.id,
+            auction_id: auction,
+            submission_deadline_latest_block: submission_deadline,
         };
 
         let tx_hash = driver
             .settle(&request, self.max_settlement_transaction_wait)
             .await
-            .map_err(SettleError::Failure)?
+            .map_err(|err| {
+                if never_submitted(&err) {
+                    SettleError::NotSubmitted(err)
+                } else {
+                    SettleError::Failure(err)
+                }
+            })?
             .tx_hash;
 
-        *self.in_flight_orders.lock().await = Some(InFlightOrders {
-            tx_hash,
-            orders: solved.orders.keys().copied().collect(),
-        });
-
         let order_uids = solved.orders.keys().copied().collect();
         self.persistence
             .store_order_events(order_uids, OrderEventLabel::Traded);
         tracing::debug!(?tx_hash, "solution settled");
 
-        Ok(())
+        Ok(tx_hash)
+    }
+
+    /// Resolves once the chain has advanced past the given block.
+    async fn wait_past_block(&self, block: u64) {
+        let mut blocks = ethrpc::current_block::into_stream(self.eth.current_block().clone());
+        while let Some(current) = blocks.next().await {
+            if current.number > block {
+                return;
+            }
+        }
+        futures::future::pending().await
     }
 
     /// Removes orders that are currently being settled to avoid solvers trying
     /// to fill an order a second time.
     async fn remove_in_flight_orders(&self, mut auction: domain::Auction) -> domain::Auction {
-        let Some(in_flight) = &*self.in_flight_orders.lock().await else {
-            return auction;
-        };
-
-        let tx_receipt = self.eth.transaction_receipt(in_flight.tx_hash).await;
-
-        let prev_settlement_block = match tx_receipt {
-            Ok(Some(TransactionReceipt {
-                block_number: Some(number),
-                ..
-            })) => number.0[0],
-            // Could not find the block of the previous settlement, let's be
-            // conservative and assume all orders are still in-flight.
-            _ => u64::MAX,
-        };
+        for in_flight in self.in_flight_orders.lock().await.iter() {
+            let tx_receipt = self.eth.transaction_receipt(in_flight.tx_hash).await;
+
+            let prev_settlement_block = match tx_receipt {
+                Ok(Some(TransactionReceipt {
+                    block_number: Some(number),
+                    ..
+                })) => number.0[0],
+                // Could not find the block of the previous settlement, let's be
+                // conservative and assume all orders are still in-flight.
+                _ => u64::MAX,
+            };
 
-        if auction.latest_settlement_block < prev_settlement_block {
-            // Auction was built before the in-flight orders were processed.
-            auction
-                .orders
-                .retain(|o| !in_flight.orders.contains(&o.uid));
-            tracing::debug!(orders = ?in_flight.orders, "filtered out in-flight orders");
+            if auction.latest_settlement_block < prev_settlement_block {
+                // Auction was built before the in-flight orders were processed.
+                auction
+                    .orders
+                    .retain(|o| !in_flight.orders.contains(&o.uid));
+                tracing::debug!(orders = ?in_flight.orders, "filtered out in-flight orders");
+            }
         }
 
         auction
     }
 }
 
-/// Orders settled in the previous auction that might still be in-flight.
-#[derive(Default)]
+/// Orders settled in the previous auction that might still be in-flight. There
+/// is one entry per winner of the auction.
+#[derive(Clone, Default)]
 pub struct InFlightOrders {
     /// The transaction that these orders where settled in.
     tx_hash: H256,
@@ -489,6 +675,10 @@ struct Solution {
     score: NonZeroU256,
     orders: HashMap<domain::OrderUid, solve::TradedAmounts>,
     clearing_prices: HashMap<H160, U256>,
+    liquidity: HashSet<H160>,
+    /// Whether the solution contains custom interactions whose liquidity is
+    /// unknown.
+    custom_interactions: bool,
 }
 
 impl Solution {
@@ -499,6 +689,82 @@ impl Solution {
     pub fn orders(&self) -> &HashMap<domain::OrderUid, solve::TradedAmounts> {
         &self.orders
     }
+
+    /// Whether both solutions settle the same order or use the same
+    /// liquidity. Overlapping solutions can't both win an auction. Sharing
+    /// tokens is fine, since both settlements can execute independently.
+    ///
+    /// The liquidity used by custom interactions is unknown, so solutions
+    /// containing them are considered to overlap with any solution touching
+    /// the same tokens.
+    pub fn overlaps(&self, other: &Solution) -> bool {
+        let touches_same_tokens = || {
+            self.clearing_prices
+                .keys()
+                .any(|token| other.clearing_prices.contains_key(token))
+        };
+        self.order_ids().any(|uid| other.orders.contains_key(uid))
+            || !self.liquidity.is_disjoint(&other.liquidity)
+            || ((self.custom_interactions || other.custom_interactions) && touches_same_tokens())
+    }
+}
+
+/// Greedily selects the winners among the ranked `solutions` (sorted by score,
+/// best last). Starting with the best one, solutions are selected as long as
+/// they don't overlap with an already selected one. The `fixed` solutions are
+/// always selected (e.g. because they have already been settled) and
+/// `excluded` ones never are. Returns the indices of the winners, best ranked
+/// first.
+fn select_winners(
+    solutions: &[&Solution],
+    fixed: &[usize],
+    excluded: impl Fn(usize) -> bool,
+    max_winners: usize,
+) -> Vec<usize> {
+    let mut winners = fixed.to_vec();
+    for index in (0..solutions.len()).rev() {
+        if winners.len() >= max_winners {
+            break;
+        }
+        if winners.contains(&index) || excluded(index) {
+            continue;
+        }
+        if winners
+            .iter()
+            .all(|winner| !solutions[*winner].overlaps(solutions[index]))
+        {
+            winners.push(index);
+        }
+    }
+    winners.sort_unstable_by(|a, b| b.cmp(a));
+    winners
+}
+
+/// The score other solutions would have contributed in place of the `winner`
+/// had it not participated. With a single winner this is the score of the
+/// runner up.
+fn reference_score(
+    solutions: &[&Solution],
+    winners: &[usize],
+    winner: usize,
+    excluded: impl Fn(usize) -> bool,
+    max_winners: usize,
+) -> U256 {
+    let total = |winners: &[usize]| {
+        winners
+            .iter()
+            .filter(|index| **index != winner)
+            .fold(U256::zero(), |total, index| {
+                total.saturating_add(solutions[*index].score.get())
+            })
+    };
+    let without_winner = select_winners(
+        solutions,
+        &[],
+        |index| index == winner || excluded(index),
+        max_winners,
+    );
+    total(&without_winner).saturating_sub(total(winners))
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -525,10 +791,30 @@ enum RevealError {
 
 #[derive(Debug, thiserror::Error)]
 enum SettleError {
+    /// The driver did not submit the settlement, so it can't end up on chain.
+    #[error("not submitted: {0}")]
+    NotSubmitted(anyhow::Error),
+    #[error("submission deadline reached")]
+    Timeout,
     #[error(transparent)]
     Failure(anyhow::Error),
 }
 
+/// Whether the error proves that the driver never submitted the settlement,
+/// either because the request never reached it or because it didn't have the
+/// solution.
+fn never_submitted(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(reqwest::Error::is_connect)
+            || cause
+                .downcast_ref::<infra::solvers::BadStatus>()
+                .and_then(infra::solvers::BadStatus::kind)
+                .is_some_and(|kind| kind == "SolutionNotAvailable")
+    })
+}
+
 #[derive(prometheus_metric_storage::MetricStorage)]
 #[metric(subsystem = "runloop")]
 struct Metrics {
@@ -635,6 +921,8 @@ impl Metrics {
 
     fn settle_err(driver: &infra::Driver, err: &SettleError, time: Duration) {
         let label = match err {
+            SettleError::NotSubmitted(_) => "not_submitted",
+            SettleError::Timeout => "timeout",
             SettleError::Failure(_) => "error",
         };
         Self::get()
@@ -696,3 +984,133 @@ pub mod observe {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A solution with the given score settling orders and using liquidity
+    /// identified by a single byte.
+    fn solution(score: u64, orders: &[u8], liquidity: &[u8]) -> Solution {
+        Solution {
+            id: 0,
+            account: H160::zero(),
+            score: NonZeroU256::new(score.into()).unwrap(),
+            orders: orders
+                .iter()
+                .map(|order| {
+                    (
+                        domain::OrderUid([*order; 56]),
+                        solve::TradedAmounts {
+                            sell_amount: 1.into(),
+                            buy_amount: 1.into(),
+                        },
+                    )
+                })
+                .collect(),
+            // all solutions trade the same token which doesn't make them overlap
+            clearing_prices: [(H160([0xff; 20]), 1.into())].into_iter().collect(),
+            liquidity: liquidity.iter().map(|pool| H160([*pool; 20])).collect(),
+            custom_interactions: false,
+        }
+    }
+
+    #[test]
+    fn solutions_with_custom_interactions_overlap_on_tokens() {
+        let mut custom = solution(10, &[1], &[1]);
+        custom.custom_interactions = true;
+        let other = solution(20, &[2], &[2]);
+        assert!(!solution(10, &[1], &[1]).overlaps(&other));
+        assert!(custom.overlaps(&other));
+        assert!(other.overlaps(&custom));
+
+        let mut unrelated = solution(30, &[3], &[3]);
+        unrelated.clearing_prices = [(H160([0xee; 20]), 1.into())].into_iter().collect();
+        assert!(!custom.overlaps(&unrelated));
+    }
+
+    #[test]
+    fn selects_non_overlapping_winners() {
+        // ranked worst to best
+        let solutions = [
+            solution(10, &[4], &[7, 8]),
+            solution(20, &[3], &[5, 6]),
+            solution(30, &[2], &[2, 3]),
+            solution(40, &[1], &[1, 3]),
+            solution(50, &[1], &[1, 2]),
+        ];
+        let ranked = solutions.iter().collect::<Vec<_>>();
+
+        // a single winner is the best solution
+        assert_eq!(select_winners(&ranked, &[], |_| false, 1), vec![4]);
+        // solutions 3 and 2 overlap with the best one
+        assert_eq!(select_winners(&ranked, &[], |_| false, 2), vec![4, 1]);
+        assert_eq!(select_winners(&ranked, &[], |_| false, 5), vec![4, 1, 0]);
+        // without the best solution, the second best can win
+        assert_eq!(
+            select_winners(&ranked, &[], |index| index == 4, 5),
+            vec![3, 1, 0]
+        );
+        // fixed solutions always win
+        assert_eq!(
+            select_winners(&ranked, &[2], |index| index == 4, 5),
+            vec![2, 1, 0]
+        );
+    }
+
+    #[test]
+    fn reference_scores() {
+        // ranked worst to best
+        let solutions = [
+            solution(10, &[3], &[5]),
+            solution(20, &[2], &[3, 4]),
+            solution(30, &[1], &[1, 3]),
+            solution(40, &[1], &[1, 2]),
+        ];
+        let ranked = solutions.iter().collect::<Vec<_>>();
+
+        // a single winner gets the runner up score as reference
+        let winners = select_winners(&ranked, &[], |_| false, 1);
+        assert_eq!(winners, vec![3]);
+        assert_eq!(
+            reference_score(&ranked, &winners, 3, |_| false, 1),
+            30.into()
+        );
+
+        let winners = select_winners(&ranked, &[], |_| false, 3);
+        assert_eq!(winners, vec![3, 1, 0]);
+        // without the best solution, solution 2 would have won instead of
+        // solution 1 (which overlaps with it)
+        assert_eq!(
+            reference_score(&ranked, &winners, 3, |_| false, 3),
+            10.into()
+        );
+        // nothing would have replaced the other winners
+        assert_eq!(
+            reference_score(&ranked, &winners, 1, |_| false, 3),
+            0.into()
+        );
+        assert_eq!(
+            reference_score(&ranked, &winners, 0, |_| false, 3),
+            0.into()
+        );
+    }
+
+    #[test]
+    fn detects_settlements_that_were_never_submitted() {
+        let rejected = |kind: &str| {
+            anyhow::Error::from(infra::solvers::BadStatus {
+                status: 400,
+                url: "http://driver/settle".parse().unwrap(),
+                body: format!(r#"{{"kind":"{kind}","description":""}}"#),
+            })
+        };
+
+        assert!(never_submitted(&rejected("SolutionNotAvailable")));
+        assert!(never_submitted(
+            &rejected("SolutionNotAvailable").context("settle")
+        ));
+        assert!(!never_submitted(&rejected("FailedToSubmit")));
+        assert!(!never_submitted(&anyhow::anyhow!("timeout")));
+    }
+}
@@ -231,7 +231,10 @@ impl RunLoop {
         let score = NonZeroU256::new(score).ok_or(Error::ZeroScore)?;
 
         let revealed = driver
-            .reveal(&reveal::Request { solution_id })
+            .reveal(&reveal::Request {
+                solution_id,
+                auction_id: request.id,
+            })
             .await
             .map_err(Error::Reveal)?;
         if !revealed
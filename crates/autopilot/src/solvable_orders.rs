@@ -5,7 +5,7 @@ use {
     },
     anyhow::Result,
     bigdecimal::BigDecimal,
-    database::order_events::OrderEventLabel,
+    database::order_events::{OrderEventLabel, OrderFilterReason},
     ethrpc::current_block::CurrentBlockStream,
     indexmap::IndexSet,
     itertools::Itertools,
@@ -145,21 +145,21 @@ impl SolvableOrdersCache {
         let db_solvable_orders = self.persistence.solvable_orders(min_valid_to).await?;
 
         let mut counter = OrderFilterCounter::new(self.metrics, &db_solvable_orders.orders);
-        let mut invalid_order_uids = Vec::new();
+        let mut invalid_order_events = Vec::new();
         let mut filtered_order_events = Vec::new();
 
         let orders = filter_banned_user_orders(db_solvable_orders.orders, &self.banned_users).await;
-        let removed = counter.checkpoint("banned_user", &orders);
-        invalid_order_uids.extend(removed);
+        let removed = counter.checkpoint(OrderFilterReason::BannedUser, &orders);
+        invalid_order_events.extend(removed);
 
         let orders =
             filter_invalid_signature_orders(orders, self.signature_validator.as_ref()).await;
-        let removed = counter.checkpoint("invalid_signature", &orders);
-        invalid_order_uids.extend(removed);
+        let removed = counter.checkpoint(OrderFilterReason::InvalidSignature, &orders);
+        invalid_order_events.extend(removed);
 
         let orders = filter_unsupported_tokens(orders, self.bad_token_detector.as_ref()).await?;
-        let removed = counter.checkpoint("unsupported_token", &orders);
-        invalid_order_uids.extend(removed);
+        let removed = counter.checkpoint(OrderFilterReason::UnsupportedToken, &orders);
+        invalid_order_events.extend(removed);
 
         let missing_queries: Vec<_> = orders.iter().map(Query::from_order).collect();
         let fetched_balances = self.balance_fetcher.get_balances(&missing_queries).await;
@@ -182,11 +182,11 @@ impl SolvableOrdersCache {
             .collect::<HashMap<_, _>>();
 
         let orders = orders_with_balance(orders, &balances);
-        let removed = counter.checkpoint("insufficient_balance", &orders);
-        invalid_order_uids.extend(removed);
+        let removed = counter.checkpoint(OrderFilterReason::InsufficientBalance, &orders);
+        invalid_order_events.extend(removed);
 
         let orders = filter_dust_orders(orders, &balances);
-        let removed = counter.checkpoint("dust_order", &orders);
+        let removed = counter.checkpoint(OrderFilterReason::DustOrder, &orders);
         filtered_order_events.extend(removed);
 
         // create auction
@@ -208,11 +208,11 @@ impl SolvableOrdersCache {
             entry.insert(weth_price);
         }
 
-        let removed = counter.checkpoint("missing_price", &orders);
+        let removed = counter.checkpoint(OrderFilterReason::MissingPrice, &orders);
         filtered_order_events.extend(removed);
 
         let orders = filter_mispriced_limit_orders(orders, &prices, &self.limit_order_price_factor);
-        let removed = counter.checkpoint("out_of_market", &orders);
+        let removed = counter.checkpoint(OrderFilterReason::OutOfMarket, &orders);
         filtered_order_events.extend(removed);
 
         let removed = counter.record(&orders);
@@ -220,17 +220,17 @@ impl SolvableOrdersCache {
 
         // spawning a background task since `order_events` table insert operation takes
         // a while and the result is ignored.
-        self.persistence.store_order_events(
-            invalid_order_uids
+        self.persistence.store_filtered_order_events(
+            invalid_order_events
                 .iter()
-                .map(|id| domain::OrderUid(id.0))
+                .map(|(id, reason)| (domain::OrderUid(id.0), *reason))
                 .collect(),
             OrderEventLabel::Invalid,
         );
-        self.persistence.store_order_events(
+        self.persistence.store_filtered_order_events(
             filtered_order_events
                 .iter()
-                .map(|id| domain::OrderUid(id.0))
+                .map(|(id, reason)| (domain::OrderUid(id.0), *reason))
                 .collect(),
             OrderEventLabel::Filtered,
         );
@@ -623,7 +623,7 @@ struct OrderFilterCounter {
     counts: HashMap<Reason, usize>,
 }
 
-type Reason = &'static str;
+type Reason = OrderFilterReason;
 
 impl OrderFilterCounter {
     fn new(metrics: &'static Metrics, orders: &[Order]) -> Self {
@@ -652,7 +652,9 @@ impl OrderFilterCounter {
     }
 
     /// Creates a new checkpoint from the current remaining orders.
-    fn checkpoint(&mut self, reason: Reason, orders: &[Order]) -> Vec<OrderUid> {
+    /// Returns the orders that got filtered since the last checkpoint together
+    /// with the given reason.
+    fn checkpoint(&mut self, reason: Reason, orders: &[Order]) -> Vec<(OrderUid, Reason)> {
         let filtered_orders = orders
             .iter()
             .fold(self.orders.clone(), |mut order_uids, order| {
@@ -665,17 +667,20 @@ impl OrderFilterCounter {
             self.orders.remove(order_uid).unwrap();
         }
         if !filtered_orders.is_empty() {
-            tracing::debug!(%reason, orders = ?filtered_orders, "filtered orders");
+            tracing::debug!(reason = reason.as_str(), orders = ?filtered_orders, "filtered orders");
         }
-        filtered_orders.into_keys().collect()
+        filtered_orders
+            .into_keys()
+            .map(|order_uid| (order_uid, reason))
+            .collect()
     }
 
     /// Records the filter counter to metrics.
     /// If there are orders that have been filtered out since the last
     /// checkpoint these orders will get recorded with the readon "other".
     /// Returns these catch-all orders.
-    fn record(mut self, orders: &[Order]) -> Vec<OrderUid> {
-        let removed = self.checkpoint("other", orders);
+    fn record(mut self, orders: &[Order]) -> Vec<(OrderUid, Reason)> {
+        let removed = self.checkpoint(OrderFilterReason::Other, orders);
 
         self.metrics.auction_creations.inc();
 
@@ -691,7 +696,7 @@ impl OrderFilterCounter {
         for (reason, count) in self.counts {
             self.metrics
                 .auction_filtered_orders
-                .with_label_values(&[reason])
+                .with_label_values(&[reason.as_str()])
                 .set(count as _);
         }
 
@@ -0,0 +1,171 @@
+//! Computes the rewards of auction winners according to CIP-20 once the
+//! deadline for settling an auction has passed.
+//!
+//! Winners that settled their solution in time get rewarded with the
+//! difference between their score and their reference score. Winners that
+//! failed to settle get penalised with their reference score, which is the
+//! same as if they had settled a solution with a score of zero. Both rewards
+//! and penalties are capped by the score cap.
+
+use {
+    crate::{database::Postgres, infra},
+    anyhow::{Context, Result},
+    bigdecimal::BigDecimal,
+    chrono::{DateTime, TimeZone, Utc},
+    database::solver_rewards::{Outcome, Reward},
+    itertools::Itertools,
+    number::conversions::u256_to_big_decimal,
+    primitive_types::U256,
+    shared::event_handling::MAX_REORG_BLOCK_COUNT,
+    std::{collections::HashMap, time::Duration},
+    tokio::time,
+};
+
+/// The maximum number of auctions whose rewards get computed at once.
+const MAX_AUCTIONS_PER_UPDATE: i64 = 100;
+
+pub struct RewardCalculator {
+    eth: infra::Ethereum,
+    db: Postgres,
+    update_interval: Duration,
+    score_cap: BigDecimal,
+}
+
+impl RewardCalculator {
+    pub fn new(
+        eth: infra::Ethereum,
+        db: Postgres,
+        update_interval: Duration,
+        score_cap: U256,
+    ) -> Self {
+        Self {
+            eth,
+            db,
+            update_interval,
+            score_cap: u256_to_big_decimal(&score_cap),
+        }
+    }
+
+    pub async fn run_forever(self) -> ! {
+        let mut interval = time::interval(self.update_interval);
+        loop {
+            interval.tick().await;
+            // Catch up on all auctions whose deadline passed before moving on
+            // to the next interval.
+            loop {
+                match self.update().await {
+                    Ok(0) => break,
+                    Ok(auctions) => {
+                        tracing::debug!(auctions, "computed solver rewards");
+                        Metrics::get().rewarded_auctions.inc_by(auctions as u64);
+                    }
+                    Err(err) => {
+                        tracing::warn!(?err, "failed to compute solver rewards");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Computes and stores the rewards of the next batch of auctions whose
+    /// deadline is final. Returns the number of rewarded auctions.
+    async fn update(&self) -> Result<usize> {
+        let current_block = self.eth.current_block().borrow().number;
+        let outcomes = self
+            .db
+            .unrewarded_outcomes(
+                current_block.saturating_sub(MAX_REORG_BLOCK_COUNT),
+                MAX_AUCTIONS_PER_UPDATE,
+            )
+            .await?;
+
+        let mut timestamps = HashMap::new();
+        for block in outcomes
+            .iter()
+            .map(|outcome| outcome.block_deadline)
+            .unique()
+        {
+            timestamps.insert(block, self.block_timestamp(block).await?);
+        }
+
+        let rewards = outcomes
+            .into_iter()
+            .map(|outcome| Reward {
+                reward: reward(&outcome, &self.score_cap),
+                block_timestamp: timestamps[&outcome.block_deadline],
+                auction_id: outcome.auction_id,
+                solver: outcome.solver,
+                block_deadline: outcome.block_deadline,
+                settled: outcome.settled,
+                score: outcome.score,
+                reference_score: outcome.reference_score,
+            })
+            .collect::<Vec<_>>();
+        self.db.save_solver_rewards(&rewards).await?;
+
+        Ok(rewards
+            .iter()
+            .map(|reward| reward.auction_id)
+            .unique()
+            .count())
+    }
+
+    async fn block_timestamp(&self, block: i64) -> Result<DateTime<Utc>> {
+        let block = self
+            .eth
+            .block(block.try_into()?)
+            .await?
+            .with_context(|| format!("missing block {block}"))?;
+        Utc.timestamp_opt(block.timestamp.as_u64().try_into()?, 0)
+            .single()
+            .context("invalid block timestamp")
+    }
+}
+
+/// The reward of a winner, in wei. Negative rewards are penalties.
+fn reward(outcome: &Outcome, score_cap: &BigDecimal) -> BigDecimal {
+    let score = if outcome.settled {
+        outcome.score.clone()
+    } else {
+        BigDecimal::from(0)
+    };
+    (score - &outcome.reference_score).clamp(-score_cap.clone(), score_cap.clone())
+}
+
+#[derive(prometheus_metric_storage::MetricStorage)]
+struct Metrics {
+    /// The total number of auctions whose solver rewards were computed.
+    rewarded_auctions: prometheus::IntCounter,
+}
+
+impl Metrics {
+    fn get() -> &'static Self {
+        Metrics::instance(observe::metrics::get_storage_registry()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, database::byte_array::ByteArray};
+
+    #[test]
+    fn rewards() {
+        let outcome = |settled, score: u32, reference_score: u32| Outcome {
+            auction_id: 1,
+            solver: ByteArray([1; 20]),
+            block_deadline: 1,
+            settled,
+            score: score.into(),
+            reference_score: reference_score.into(),
+        };
+        let cap = BigDecimal::from(10);
+
+        assert_eq!(reward(&outcome(true, 15, 10), &cap), 5.into());
+        assert_eq!(reward(&outcome(true, 30, 10), &cap), 10.into());
+        assert_eq!(reward(&outcome(true, 8, 0), &cap), 8.into());
+        assert_eq!(reward(&outcome(false, 15, 5), &cap), (-5).into());
+        assert_eq!(reward(&outcome(false, 30, 20), &cap), (-10).into());
+        assert_eq!(reward(&outcome(false, 8, 0), &cap), 0.into());
+    }
+}
@@ -65,6 +65,9 @@ fn main() {
     generate_contract_with_config("CoWSwapOnchainOrders", |builder| {
         builder.contract_mod_override("cowswap_onchain_orders")
     });
+    generate_contract_with_config("ComposableCoW", |builder| {
+        builder.contract_mod_override("composable_cow")
+    });
     generate_contract_with_config("BalancerV2Authorizer", |builder| {
         builder.contract_mod_override("balancer_v2_authorizer")
     });
@@ -560,6 +563,9 @@ fn main() {
             .add_network_str(GNOSIS, "0x01DcB88678aedD0C4cC9552B20F4718550250574")
             .add_network_str(SEPOLIA, "0x01DcB88678aedD0C4cC9552B20F4718550250574")
     });
+    generate_contract("ICurveCryptoPool");
+    generate_contract("ICurveLegacyPool");
+    generate_contract("ICurvePool");
     generate_contract("IUniswapLikeRouter");
     generate_contract("IUniswapLikePair");
     // EIP-1271 contract - SignatureValidator
@@ -618,7 +624,14 @@ fn main() {
     generate_contract_with_config("IUniswapV3Factory", |builder| {
         // <https://github.com/Uniswap/v3-periphery/blob/697c2474757ea89fec12a4e6db16a574fe259610/deploys.md>
         builder
-            .add_network_str(MAINNET, "0x1F98431c8aD98523631AE4a59f267346ea31F984")
+            .add_network(
+                MAINNET,
+                Network {
+                    address: addr("0x1F98431c8aD98523631AE4a59f267346ea31F984"),
+                    // <https://etherscan.io/address/0x1F98431c8aD98523631AE4a59f267346ea31F984>
+                    deployment_information: Some(DeploymentInformation::BlockNumber(12369621)),
+                },
+            )
             .add_network_str(GOERLI, "0x1F98431c8aD98523631AE4a59f267346ea31F984")
             .add_network_str(SEPOLIA, "0x1F98431c8aD98523631AE4a59f267346ea31F984")
         // Not available on Gnosis Chain
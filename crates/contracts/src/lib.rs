@@ -40,6 +40,7 @@ include_contracts! {
     BaoswapRouter;
     CoWSwapEthFlow;
     CoWSwapOnchainOrders;
+    ComposableCoW;
     CowProtocolToken;
     ERC1271SignatureValidator;
     ERC20;
@@ -52,6 +53,9 @@ include_contracts! {
     GnosisSafeProxyFactory;
     HoneyswapRouter;
     HooksTrampoline;
+    ICurveCryptoPool;
+    ICurveLegacyPool;
+    ICurvePool;
     ISwaprPair;
     IUniswapLikePair;
     IUniswapLikeRouter;
@@ -1,4 +1,7 @@
-use sqlx::{types::JsonValue, PgConnection};
+use {
+    crate::OrderUid,
+    sqlx::{types::JsonValue, PgConnection},
+};
 
 pub type AuctionId = i64;
 
@@ -24,6 +27,24 @@ LIMIT 1
     sqlx::query_as(QUERY).fetch_optional(ex).await
 }
 
+/// Returns the id of the most recent auction and whether it includes the
+/// order.
+pub async fn most_recent_includes_order(
+    ex: &mut PgConnection,
+    order_uid: &OrderUid,
+) -> Result<Option<(AuctionId, bool)>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT id, COALESCE(json->'orders' @> jsonb_build_array(jsonb_build_object('uid', '0x' || encode($1, 'hex'))), false)
+FROM auctions
+ORDER BY id DESC
+LIMIT 1
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(order_uid)
+        .fetch_optional(ex)
+        .await
+}
+
 pub async fn delete_all_auctions(ex: &mut PgConnection) -> Result<(), sqlx::Error> {
     const QUERY: &str = "TRUNCATE auctions;";
     sqlx::query(QUERY).execute(ex).await.map(|_| ())
@@ -31,7 +52,37 @@ pub async fn delete_all_auctions(ex: &mut PgConnection) -> Result<(), sqlx::Erro
 
 #[cfg(test)]
 mod tests {
-    use {super::*, sqlx::Connection};
+    use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_most_recent_includes_order() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let uid = ByteArray([1; 56]);
+        assert_eq!(
+            most_recent_includes_order(&mut db, &uid).await.unwrap(),
+            None
+        );
+
+        let auction = |uid: &OrderUid| {
+            format!(r#"{{"orders": [{{"uid": "{uid:?}", "kind": "sell"}}]}}"#)
+                .parse::<JsonValue>()
+                .unwrap()
+        };
+        let id = save(&mut db, &auction(&uid)).await.unwrap();
+        assert_eq!(
+            most_recent_includes_order(&mut db, &uid).await.unwrap(),
+            Some((id, true))
+        );
+        let id = save(&mut db, &auction(&ByteArray([2; 56]))).await.unwrap();
+        assert_eq!(
+            most_recent_includes_order(&mut db, &uid).await.unwrap(),
+            Some((id, false))
+        );
+    }
 
     #[tokio::test]
     #[ignore]
@@ -15,8 +15,7 @@ pub async fn insert(
     ex: &mut PgTransaction<'_>,
     participants: &[Participant],
 ) -> Result<(), sqlx::Error> {
-    const QUERY: &str =
-        r#"INSERT INTO auction_participants (auction_id, participant) VALUES ($1, $2);"#;
+    const QUERY: &str = r#"INSERT INTO auction_participants (auction_id, participant) VALUES ($1, $2) ON CONFLICT DO NOTHING;"#;
     for participant in participants {
         sqlx::query(QUERY)
             .bind(participant.auction_id)
@@ -17,8 +17,7 @@ pub async fn insert(
     ex: &mut PgTransaction<'_>,
     prices: &[AuctionPrice],
 ) -> Result<(), sqlx::Error> {
-    const QUERY: &str =
-        r#"INSERT INTO auction_prices (auction_id, token, price) VALUES ($1, $2, $3);"#;
+    const QUERY: &str = r#"INSERT INTO auction_prices (auction_id, token, price) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;"#;
     for price in prices {
         sqlx::query(QUERY)
             .bind(price.auction_id)
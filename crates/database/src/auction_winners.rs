@@ -0,0 +1,88 @@
+use {
+    crate::{auction::AuctionId, Address, PgTransaction},
+    bigdecimal::BigDecimal,
+    sqlx::PgConnection,
+    std::ops::DerefMut,
+};
+
+/// One of possibly multiple winners of a solver competition for a given
+/// auction.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Winner {
+    pub auction_id: AuctionId,
+    pub winner: Address,
+    pub score: BigDecimal,
+    pub reference_score: BigDecimal,
+}
+
+pub async fn insert(ex: &mut PgTransaction<'_>, winners: &[Winner]) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO auction_winners (auction_id, winner, score, reference_score)
+VALUES ($1, $2, $3, $4)
+    ;"#;
+    for winner in winners {
+        sqlx::query(QUERY)
+            .bind(winner.auction_id)
+            .bind(winner.winner)
+            .bind(&winner.score)
+            .bind(&winner.reference_score)
+            .execute(ex.deref_mut())
+            .await?;
+    }
+    Ok(())
+}
+
+/// Removes the winners of an auction, e.g. because the winner selection changed
+/// after a winner failed to settle its solution.
+pub async fn delete(ex: &mut PgTransaction<'_>, auction_id: AuctionId) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"DELETE FROM auction_winners WHERE auction_id = $1"#;
+    sqlx::query(QUERY)
+        .bind(auction_id)
+        .execute(ex.deref_mut())
+        .await?;
+    Ok(())
+}
+
+pub async fn fetch(
+    ex: &mut PgConnection,
+    auction_id: AuctionId,
+) -> Result<Vec<Winner>, sqlx::Error> {
+    const QUERY: &str =
+        r#"SELECT * FROM auction_winners WHERE auction_id = $1 ORDER BY score DESC"#;
+    sqlx::query_as(QUERY).bind(auction_id).fetch_all(ex).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let input = vec![
+            Winner {
+                auction_id: 1,
+                winner: ByteArray([2; 20]),
+                score: 10.into(),
+                reference_score: 5.into(),
+            },
+            Winner {
+                auction_id: 1,
+                winner: ByteArray([3; 20]),
+                score: 8.into(),
+                reference_score: 0.into(),
+            },
+        ];
+        insert(&mut db, &input).await.unwrap();
+        let output = fetch(&mut db, 1).await.unwrap();
+        assert_eq!(input, output);
+
+        delete(&mut db, 1).await.unwrap();
+        let output = fetch(&mut db, 1).await.unwrap();
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+//! Conditional orders registered with the ComposableCoW contract together with
+//! the state of polling them for tradeable orders.
+
+use {
+    crate::{Address, OrderUid},
+    sqlx::PgConnection,
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "ConditionalOrderStatus")]
+#[sqlx(rename_all = "lowercase")]
+pub enum Status {
+    /// The conditional order gets polled for tradeable orders.
+    #[default]
+    Active,
+    /// The conditional order will never produce tradeable orders again.
+    Done,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, sqlx::FromRow)]
+pub struct ConditionalOrder {
+    pub block_number: i64,
+    pub log_index: i64,
+    pub owner: Address,
+    pub handler: Address,
+    pub salt: Vec<u8>,
+    pub static_input: Vec<u8>,
+    pub status: Status,
+    pub next_poll_block: Option<i64>,
+    pub next_poll_timestamp: Option<i64>,
+    pub last_order_uid: Option<OrderUid>,
+    pub failed_polls: i32,
+}
+
+/// The result of polling a conditional order.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PollUpdate {
+    pub status: Status,
+    pub next_poll_block: Option<i64>,
+    pub next_poll_timestamp: Option<i64>,
+    pub last_order_uid: Option<OrderUid>,
+    /// Number of consecutive polls that failed unexpectedly.
+    pub failed_polls: i32,
+}
+
+pub async fn last_indexed_block(ex: &mut PgConnection) -> Result<Option<i64>, sqlx::Error> {
+    const QUERY: &str = "SELECT MAX(block_number) FROM conditional_orders;";
+    sqlx::query_scalar(QUERY).fetch_one(ex).await
+}
+
+pub async fn insert(ex: &mut PgConnection, orders: &[ConditionalOrder]) -> Result<(), sqlx::Error> {
+    for order in orders {
+        insert_order(ex, order).await?;
+    }
+    Ok(())
+}
+
+async fn insert_order(ex: &mut PgConnection, order: &ConditionalOrder) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO conditional_orders (block_number, log_index, owner, handler, salt, static_input, status, next_poll_block, next_poll_timestamp, last_order_uid)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+ON CONFLICT DO NOTHING
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(order.block_number)
+        .bind(order.log_index)
+        .bind(order.owner)
+        .bind(order.handler)
+        .bind(&order.salt)
+        .bind(&order.static_input)
+        .bind(order.status)
+        .bind(order.next_poll_block)
+        .bind(order.next_poll_timestamp)
+        .bind(order.last_order_uid)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Deletes the conditional orders created in the inclusive block range.
+pub async fn delete(
+    ex: &mut PgConnection,
+    from_block: i64,
+    to_block: i64,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+DELETE FROM conditional_orders
+WHERE block_number >= $1 AND block_number <= $2
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(from_block)
+        .bind(to_block)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Fetches the active conditional orders that are due to be polled at the
+/// given block.
+pub async fn due(
+    ex: &mut PgConnection,
+    block_number: i64,
+    timestamp: i64,
+) -> Result<Vec<ConditionalOrder>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT * FROM conditional_orders
+WHERE
+    status = 'active' AND
+    block_number <= $1 AND
+    (next_poll_block IS NULL OR next_poll_block <= $1) AND
+    (next_poll_timestamp IS NULL OR next_poll_timestamp <= $2)
+ORDER BY block_number, log_index
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(block_number)
+        .bind(timestamp)
+        .fetch_all(ex)
+        .await
+}
+
+pub async fn update_poll(
+    ex: &mut PgConnection,
+    block_number: i64,
+    log_index: i64,
+    update: &PollUpdate,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE conditional_orders
+SET status = $3, next_poll_block = $4, next_poll_timestamp = $5, last_order_uid = $6, failed_polls = $7
+WHERE block_number = $1 AND log_index = $2
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(block_number)
+        .bind(log_index)
+        .bind(update.status)
+        .bind(update.next_poll_block)
+        .bind(update.next_poll_timestamp)
+        .bind(update.last_order_uid)
+        .bind(update.failed_polls)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        assert_eq!(last_indexed_block(&mut db).await.unwrap(), None);
+
+        let order = |block_number, log_index| ConditionalOrder {
+            block_number,
+            log_index,
+            owner: ByteArray([1; 20]),
+            handler: ByteArray([2; 20]),
+            salt: vec![3; 32],
+            static_input: vec![4, 5, 6],
+            ..Default::default()
+        };
+        insert(&mut db, &[order(1, 0), order(2, 0), order(2, 1)])
+            .await
+            .unwrap();
+        // Inserting the same event again is a no-op.
+        insert(&mut db, &[order(1, 0)]).await.unwrap();
+        assert_eq!(last_indexed_block(&mut db).await.unwrap(), Some(2));
+        assert_eq!(due(&mut db, 1, 0).await.unwrap(), vec![order(1, 0)]);
+        assert_eq!(
+            due(&mut db, 2, 0).await.unwrap(),
+            vec![order(1, 0), order(2, 0), order(2, 1)],
+        );
+
+        let try_at_block = PollUpdate {
+            next_poll_block: Some(10),
+            last_order_uid: Some(ByteArray([7; 56])),
+            ..Default::default()
+        };
+        update_poll(&mut db, 1, 0, &try_at_block).await.unwrap();
+        let try_at_epoch = PollUpdate {
+            next_poll_timestamp: Some(1_000),
+            failed_polls: 1,
+            ..Default::default()
+        };
+        update_poll(&mut db, 2, 0, &try_at_epoch).await.unwrap();
+        let done = PollUpdate {
+            status: Status::Done,
+            ..Default::default()
+        };
+        update_poll(&mut db, 2, 1, &done).await.unwrap();
+
+        assert_eq!(due(&mut db, 9, 999).await.unwrap(), vec![]);
+        assert_eq!(
+            due(&mut db, 10, 999).await.unwrap(),
+            vec![ConditionalOrder {
+                next_poll_block: Some(10),
+                last_order_uid: Some(ByteArray([7; 56])),
+                ..order(1, 0)
+            }],
+        );
+        assert_eq!(
+            due(&mut db, 9, 1_000).await.unwrap(),
+            vec![ConditionalOrder {
+                next_poll_timestamp: Some(1_000),
+                failed_polls: 1,
+                ..order(2, 0)
+            }],
+        );
+
+        delete(&mut db, 2, 2).await.unwrap();
+        assert_eq!(last_indexed_block(&mut db).await.unwrap(), Some(1));
+    }
+}
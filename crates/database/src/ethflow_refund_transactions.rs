@@ -0,0 +1,217 @@
+//! Stores the refunding transactions sent by the refunder throughout their
+//! lifecycle, from their first broadcast until they got mined or dropped.
+
+use {
+    crate::{Address, OrderUid, TransactionHash},
+    bigdecimal::BigDecimal,
+    sqlx::{
+        types::chrono::{DateTime, Utc},
+        PgConnection,
+    },
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "RefundTransactionStatus")]
+#[sqlx(rename_all = "lowercase")]
+pub enum Status {
+    /// The transaction was broadcast but is not mined yet.
+    Pending,
+    /// One of the broadcasts of the transaction got mined.
+    Mined,
+    /// The nonce of the transaction got used by a different transaction.
+    Dropped,
+}
+
+#[derive(Clone, Debug, PartialEq, sqlx::FromRow)]
+pub struct RefundTransaction {
+    pub sender: Address,
+    pub nonce: i64,
+    pub ethflow_contract: Address,
+    pub order_uids: Vec<OrderUid>,
+    /// The hashes of all broadcasts, the latest one last.
+    pub tx_hashes: Vec<TransactionHash>,
+    pub max_fee_per_gas: BigDecimal,
+    pub max_priority_fee_per_gas: BigDecimal,
+    pub created: DateTime<Utc>,
+    pub last_broadcast: DateTime<Utc>,
+    pub status: Status,
+    pub mined_tx_hash: Option<TransactionHash>,
+    pub mined_block_number: Option<i64>,
+}
+
+pub async fn insert(ex: &mut PgConnection, tx: &RefundTransaction) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO ethflow_refund_transactions (sender, nonce, ethflow_contract, order_uids, tx_hashes, max_fee_per_gas, max_priority_fee_per_gas, created, last_broadcast, status, mined_tx_hash, mined_block_number)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(tx.sender)
+        .bind(tx.nonce)
+        .bind(tx.ethflow_contract)
+        .bind(&tx.order_uids)
+        .bind(&tx.tx_hashes)
+        .bind(&tx.max_fee_per_gas)
+        .bind(&tx.max_priority_fee_per_gas)
+        .bind(tx.created)
+        .bind(tx.last_broadcast)
+        .bind(tx.status)
+        .bind(tx.mined_tx_hash)
+        .bind(tx.mined_block_number)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Fetches the pending transactions of the sender ordered by nonce.
+pub async fn pending(
+    ex: &mut PgConnection,
+    sender: &Address,
+) -> Result<Vec<RefundTransaction>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT * FROM ethflow_refund_transactions
+WHERE sender = $1 AND status = 'pending'
+ORDER BY nonce
+    ;"#;
+    sqlx::query_as(QUERY).bind(sender).fetch_all(ex).await
+}
+
+/// Records that the transaction was broadcast again, with the given hash and
+/// fees.
+pub async fn record_broadcast(
+    ex: &mut PgConnection,
+    sender: &Address,
+    nonce: i64,
+    tx_hash: &TransactionHash,
+    max_fee_per_gas: &BigDecimal,
+    max_priority_fee_per_gas: &BigDecimal,
+    timestamp: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE ethflow_refund_transactions
+SET
+    tx_hashes = CASE WHEN $3 = ANY(tx_hashes) THEN tx_hashes ELSE array_append(tx_hashes, $3) END,
+    max_fee_per_gas = $4,
+    max_priority_fee_per_gas = $5,
+    last_broadcast = $6
+WHERE sender = $1 AND nonce = $2
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(sender)
+        .bind(nonce)
+        .bind(tx_hash)
+        .bind(max_fee_per_gas)
+        .bind(max_priority_fee_per_gas)
+        .bind(timestamp)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+pub async fn mark_mined(
+    ex: &mut PgConnection,
+    sender: &Address,
+    nonce: i64,
+    tx_hash: &TransactionHash,
+    block_number: i64,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE ethflow_refund_transactions
+SET status = 'mined', mined_tx_hash = $3, mined_block_number = $4
+WHERE sender = $1 AND nonce = $2
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(sender)
+        .bind(nonce)
+        .bind(tx_hash)
+        .bind(block_number)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+pub async fn mark_dropped(
+    ex: &mut PgConnection,
+    sender: &Address,
+    nonce: i64,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE ethflow_refund_transactions
+SET status = 'dropped'
+WHERE sender = $1 AND nonce = $2
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(sender)
+        .bind(nonce)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::byte_array::ByteArray,
+        sqlx::{types::chrono::TimeZone, Connection},
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let sender = ByteArray([1; 20]);
+        let tx = |nonce| RefundTransaction {
+            sender,
+            nonce,
+            ethflow_contract: ByteArray([2; 20]),
+            order_uids: vec![ByteArray([3; 56]), ByteArray([4; 56])],
+            tx_hashes: vec![ByteArray([nonce as u8; 32])],
+            max_fee_per_gas: 10.into(),
+            max_priority_fee_per_gas: 1.into(),
+            created: Utc.timestamp_opt(1_000, 0).unwrap(),
+            last_broadcast: Utc.timestamp_opt(1_000, 0).unwrap(),
+            status: Status::Pending,
+            mined_tx_hash: None,
+            mined_block_number: None,
+        };
+        insert(&mut db, &tx(1)).await.unwrap();
+        insert(&mut db, &tx(2)).await.unwrap();
+        insert(&mut db, &tx(3)).await.unwrap();
+        assert_eq!(
+            pending(&mut db, &sender).await.unwrap(),
+            vec![tx(1), tx(2), tx(3)]
+        );
+
+        let timestamp = Utc.timestamp_opt(2_000, 0).unwrap();
+        record_broadcast(
+            &mut db,
+            &sender,
+            1,
+            &ByteArray([5; 32]),
+            &20.into(),
+            &2.into(),
+            timestamp,
+        )
+        .await
+        .unwrap();
+        mark_mined(&mut db, &sender, 2, &ByteArray([2; 32]), 100)
+            .await
+            .unwrap();
+        mark_dropped(&mut db, &sender, 3).await.unwrap();
+
+        let pending = pending(&mut db, &sender).await.unwrap();
+        assert_eq!(
+            pending,
+            vec![RefundTransaction {
+                tx_hashes: vec![ByteArray([1; 32]), ByteArray([5; 32])],
+                max_fee_per_gas: 20.into(),
+                max_priority_fee_per_gas: 2.into(),
+                last_broadcast: timestamp,
+                ..tx(1)
+            }]
+        );
+    }
+}
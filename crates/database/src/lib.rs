@@ -2,9 +2,13 @@ pub mod app_data;
 pub mod auction;
 pub mod auction_participants;
 pub mod auction_prices;
+pub mod auction_winners;
 pub mod byte_array;
+pub mod conditional_orders;
 pub mod ethflow_orders;
+pub mod ethflow_refund_transactions;
 pub mod events;
+pub mod native_prices;
 pub mod onchain_broadcasted_orders;
 pub mod onchain_invalidations;
 pub mod order_events;
@@ -16,6 +20,7 @@ pub mod settlement_observations;
 pub mod settlement_scores;
 pub mod settlements;
 pub mod solver_competition;
+pub mod solver_rewards;
 pub mod trades;
 
 use {
@@ -63,7 +68,12 @@ pub const TABLES: &[&str] = &[
     "settlement_observations",
     "auction_prices",
     "auction_participants",
+    "auction_winners",
     "app_data",
+    "native_prices",
+    "solver_rewards",
+    "ethflow_refund_transactions",
+    "conditional_orders",
 ];
 
 /// The names of potentially big volume tables we use in the db.
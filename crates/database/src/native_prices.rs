@@ -0,0 +1,113 @@
+use {
+    crate::{Address, PgTransaction},
+    sqlx::{
+        types::chrono::{DateTime, Utc},
+        PgConnection,
+        PgPool,
+    },
+    std::ops::DerefMut,
+};
+
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct NativePrice {
+    pub token: Address,
+    pub price: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Stores the prices, replacing older prices of the same tokens.
+pub async fn upsert(ex: &mut PgTransaction<'_>, prices: &[NativePrice]) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO native_prices (token, price, updated_at)
+VALUES ($1, $2, $3)
+ON CONFLICT (token) DO UPDATE
+SET price = EXCLUDED.price, updated_at = EXCLUDED.updated_at
+WHERE native_prices.updated_at < EXCLUDED.updated_at
+    ;"#;
+    for price in prices {
+        sqlx::query(QUERY)
+            .bind(price.token)
+            .bind(price.price)
+            .bind(price.updated_at)
+            .execute(ex.deref_mut())
+            .await?;
+    }
+    Ok(())
+}
+
+/// Fetches all prices that were updated after `updated_after`.
+pub async fn fetch_updated_after(
+    ex: &mut PgConnection,
+    updated_after: DateTime<Utc>,
+) -> Result<Vec<NativePrice>, sqlx::Error> {
+    const QUERY: &str = r#"SELECT * FROM native_prices WHERE updated_at > $1"#;
+    sqlx::query_as(QUERY)
+        .bind(updated_after)
+        .fetch_all(ex)
+        .await
+}
+
+/// Stores the prices in a single transaction, replacing older prices of the
+/// same tokens.
+pub async fn store(pool: &PgPool, prices: &[NativePrice]) -> Result<(), sqlx::Error> {
+    let mut ex = pool.begin().await?;
+    upsert(&mut ex, prices).await?;
+    ex.commit().await
+}
+
+/// Loads all prices that were updated after `updated_after`.
+pub async fn load_updated_after(
+    pool: &PgPool,
+    updated_after: DateTime<Utc>,
+) -> Result<Vec<NativePrice>, sqlx::Error> {
+    let mut ex = pool.acquire().await?;
+    fetch_updated_after(&mut ex, updated_after).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::byte_array::ByteArray,
+        chrono::{Duration, TimeZone},
+        sqlx::Connection,
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let old = NativePrice {
+            token: ByteArray([1; 20]),
+            price: 1.,
+            updated_at: now - Duration::minutes(1),
+        };
+        let new = NativePrice {
+            token: ByteArray([2; 20]),
+            price: 2.,
+            updated_at: now,
+        };
+        upsert(&mut db, &[old.clone(), new.clone()]).await.unwrap();
+
+        let output = fetch_updated_after(&mut db, now - Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(output, vec![new.clone()]);
+
+        // older prices don't replace newer ones
+        let outdated = NativePrice {
+            price: 3.,
+            updated_at: now - Duration::minutes(2),
+            ..new.clone()
+        };
+        upsert(&mut db, &[outdated]).await.unwrap();
+        let output = fetch_updated_after(&mut db, now - Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(output, vec![new]);
+    }
+}
@@ -2,7 +2,7 @@
 //! This information gets used to compuate service level indicators.
 
 use {
-    crate::OrderUid,
+    crate::{Address, OrderUid},
     chrono::Utc,
     sqlx::{types::chrono::DateTime, PgConnection, PgPool},
 };
@@ -32,6 +32,45 @@ pub enum OrderEventLabel {
     Cancelled,
 }
 
+/// Describes why an order was filtered from an auction or is considered
+/// invalid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, sqlx::Type)]
+#[sqlx(type_name = "OrderFilterReason")]
+#[sqlx(rename_all = "snake_case")]
+pub enum OrderFilterReason {
+    /// The owner or receiver of the order is banned.
+    BannedUser,
+    /// The PreSign or EIP-1271 signature of the order is not valid.
+    InvalidSignature,
+    /// The order buys or sells a token that can not be traded.
+    UnsupportedToken,
+    /// The owner doesn't have enough sell tokens or allowance.
+    InsufficientBalance,
+    /// The remaining amount of the partially fillable order is too small.
+    DustOrder,
+    /// No native price could be estimated for one of the tokens.
+    MissingPrice,
+    /// The limit price is too far from the market price.
+    OutOfMarket,
+    /// The order was dropped for some other reason.
+    Other,
+}
+
+impl OrderFilterReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BannedUser => "banned_user",
+            Self::InvalidSignature => "invalid_signature",
+            Self::UnsupportedToken => "unsupported_token",
+            Self::InsufficientBalance => "insufficient_balance",
+            Self::DustOrder => "dust_order",
+            Self::MissingPrice => "missing_price",
+            Self::OutOfMarket => "out_of_market",
+            Self::Other => "other",
+        }
+    }
+}
+
 /// Contains a single event of the life cycle of an order and when it was
 /// registered.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type, sqlx::FromRow)]
@@ -43,39 +82,107 @@ pub struct OrderEvent {
     pub timestamp: DateTime<Utc>,
     /// What kind of event happened
     pub label: OrderEventLabel,
+    /// Why the order was filtered or is invalid
+    pub reason: Option<OrderFilterReason>,
+}
+
+/// An order event together with the owner of the order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::FromRow)]
+pub struct OwnedOrderEvent {
+    pub owner: Address,
+    #[sqlx(flatten)]
+    pub event: OrderEvent,
 }
 
 /// Inserts a row into the `order_events` table only if the latest event for the
-/// corresponding order UID has a different label than the provided event..
+/// corresponding order UID has a different label or reason than the provided
+/// event.
 pub async fn insert_order_event(
     ex: &mut PgConnection,
     event: &OrderEvent,
 ) -> Result<(), sqlx::Error> {
     const QUERY: &str = r#"
         WITH cte AS (
-            SELECT label
+            SELECT label, reason
             FROM order_events
             WHERE order_uid = $1
             ORDER BY timestamp DESC
             LIMIT 1
         )
-        INSERT INTO order_events (order_uid, timestamp, label)
-        SELECT $1, $2, $3
+        INSERT INTO order_events (order_uid, timestamp, label, reason)
+        SELECT $1, $2, $3, $4
         WHERE NOT EXISTS (
             SELECT 1
             FROM cte
-            WHERE label = $3
+            WHERE label = $3 AND reason IS NOT DISTINCT FROM $4
         )
     "#;
     sqlx::query(QUERY)
         .bind(event.order_uid)
         .bind(event.timestamp)
         .bind(event.label)
+        .bind(event.reason)
         .execute(ex)
         .await
         .map(|_| ())
 }
 
+/// Fetches the events of an order registered after the provided timestamp
+/// ordered from oldest to newest. All events are returned if no timestamp is
+/// provided.
+pub async fn fetch_order_events(
+    ex: &mut PgConnection,
+    order_uid: &OrderUid,
+    after: Option<DateTime<Utc>>,
+) -> Result<Vec<OrderEvent>, sqlx::Error> {
+    const QUERY: &str = r#"
+        SELECT order_uid, timestamp, label, reason
+        FROM order_events
+        WHERE order_uid = $1 AND ($2 IS NULL OR timestamp > $2)
+        ORDER BY timestamp
+    "#;
+    sqlx::query_as(QUERY)
+        .bind(order_uid)
+        .bind(after)
+        .fetch_all(ex)
+        .await
+}
+
+/// Fetches the most recent event of an order.
+pub async fn fetch_latest_order_event(
+    ex: &mut PgConnection,
+    order_uid: &OrderUid,
+) -> Result<Option<OrderEvent>, sqlx::Error> {
+    const QUERY: &str = r#"
+        SELECT order_uid, timestamp, label, reason
+        FROM order_events
+        WHERE order_uid = $1
+        ORDER BY timestamp DESC
+        LIMIT 1
+    "#;
+    sqlx::query_as(QUERY)
+        .bind(order_uid)
+        .fetch_optional(ex)
+        .await
+}
+
+/// Fetches the events of all orders registered at or after the provided
+/// timestamp ordered from oldest to newest. Events of orders that are not in
+/// the `orders` table are skipped.
+pub async fn fetch_order_events_since(
+    ex: &mut PgConnection,
+    since: DateTime<Utc>,
+) -> Result<Vec<OwnedOrderEvent>, sqlx::Error> {
+    const QUERY: &str = r#"
+        SELECT o.owner, oe.order_uid, oe.timestamp, oe.label, oe.reason
+        FROM order_events oe
+        JOIN orders o ON o.uid = oe.order_uid
+        WHERE oe.timestamp >= $1
+        ORDER BY oe.timestamp
+    "#;
+    sqlx::query_as(QUERY).bind(since).fetch_all(ex).await
+}
+
 /// Deletes rows before the provided timestamp from the `order_events` table.
 pub async fn delete_order_events_before(
     pool: &PgPool,
@@ -117,24 +224,28 @@ mod tests {
             order_uid: uid_a,
             timestamp: now - chrono::Duration::milliseconds(300),
             label: OrderEventLabel::Created,
+            reason: None,
         };
         insert_order_event(&mut ex, &event_a).await.unwrap();
         let event_b = OrderEvent {
             order_uid: uid_a,
             timestamp: now - chrono::Duration::milliseconds(200),
             label: OrderEventLabel::Invalid,
+            reason: None,
         };
         insert_order_event(&mut ex, &event_b).await.unwrap();
         let event_c = OrderEvent {
             order_uid: uid_b,
             timestamp: now - chrono::Duration::milliseconds(100),
             label: OrderEventLabel::Invalid,
+            reason: None,
         };
         insert_order_event(&mut ex, &event_c).await.unwrap();
         let event_d = OrderEvent {
             order_uid: uid_a,
             timestamp: now,
             label: OrderEventLabel::Invalid,
+            reason: None,
         };
         insert_order_event(&mut ex, &event_d).await.unwrap();
 
@@ -151,6 +262,101 @@ mod tests {
         assert_eq!(ids[2].label, OrderEventLabel::Invalid);
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_fetch_order_events() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let owner = ByteArray([3; 20]);
+        let order = crate::orders::Order {
+            owner,
+            ..Default::default()
+        };
+        crate::orders::insert_order(&mut db, &order).await.unwrap();
+
+        let now = Utc::now();
+        let created = OrderEvent {
+            order_uid: order.uid,
+            timestamp: now - chrono::Duration::milliseconds(100),
+            label: OrderEventLabel::Created,
+            reason: None,
+        };
+        insert_order_event(&mut db, &created).await.unwrap();
+        let ready = OrderEvent {
+            order_uid: order.uid,
+            timestamp: now,
+            label: OrderEventLabel::Ready,
+            reason: None,
+        };
+        insert_order_event(&mut db, &ready).await.unwrap();
+        let other = OrderEvent {
+            order_uid: ByteArray([4; 56]),
+            timestamp: now,
+            label: OrderEventLabel::Created,
+            reason: None,
+        };
+        insert_order_event(&mut db, &other).await.unwrap();
+
+        let events = fetch_order_events(&mut db, &order.uid, None).await.unwrap();
+        assert_eq!(events, vec![created, ready]);
+        let latest = fetch_latest_order_event(&mut db, &order.uid).await.unwrap();
+        assert_eq!(latest, Some(ready));
+        let latest = fetch_latest_order_event(&mut db, &ByteArray([5; 56]))
+            .await
+            .unwrap();
+        assert_eq!(latest, None);
+        let events = fetch_order_events(&mut db, &order.uid, Some(created.timestamp))
+            .await
+            .unwrap();
+        assert_eq!(events, vec![ready]);
+
+        let events = fetch_order_events_since(&mut db, created.timestamp)
+            .await
+            .unwrap();
+        let owned = |event| OwnedOrderEvent { owner, event };
+        assert_eq!(events, vec![owned(created), owned(ready)]);
+        let events = fetch_order_events_since(&mut db, ready.timestamp)
+            .await
+            .unwrap();
+        assert_eq!(events, vec![owned(ready)]);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_order_event_reasons() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let now = Utc::now();
+        let uid = ByteArray([1; 56]);
+        let event = |millis, reason| OrderEvent {
+            order_uid: uid,
+            timestamp: now + chrono::Duration::milliseconds(millis),
+            label: OrderEventLabel::Filtered,
+            reason,
+        };
+        let missing_price = event(0, Some(OrderFilterReason::MissingPrice));
+        insert_order_event(&mut db, &missing_price).await.unwrap();
+        // Same label and reason as the latest event gets skipped.
+        insert_order_event(&mut db, &event(100, Some(OrderFilterReason::MissingPrice)))
+            .await
+            .unwrap();
+        // Same label with a different reason gets stored.
+        let out_of_market = event(200, Some(OrderFilterReason::OutOfMarket));
+        insert_order_event(&mut db, &out_of_market).await.unwrap();
+        let no_reason = event(300, None);
+        insert_order_event(&mut db, &no_reason).await.unwrap();
+        insert_order_event(&mut db, &event(400, None))
+            .await
+            .unwrap();
+
+        let events = fetch_order_events(&mut db, &uid, None).await.unwrap();
+        assert_eq!(events, vec![missing_price, out_of_market, no_reason]);
+    }
+
     async fn all_order_events(ex: &mut PgConnection) -> Vec<OrderEvent> {
         const QUERY: &str = r#"
                 SELECT *
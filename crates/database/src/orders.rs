@@ -113,6 +113,7 @@ pub async fn insert_orders_and_ignore_conflicts(
                 label: OrderEventLabel::Created,
                 timestamp: order.creation_timestamp,
                 order_uid: order.uid,
+                reason: None,
             },
         )
         .await?;
@@ -159,11 +160,53 @@ pub async fn insert_order_and_ignore_conflicts(
     insert_order_execute_sqlx(QUERY, ex, order).await
 }
 
+/// Inserts the orders that don't exist yet together with their creation
+/// events and returns the uids of the newly inserted ones. Orders that already
+/// exist, e.g. because they were inserted concurrently or appear multiple
+/// times in `orders`, are left untouched.
+pub async fn insert_new_orders(
+    ex: &mut PgConnection,
+    orders: &[Order],
+) -> Result<Vec<OrderUid>, sqlx::Error> {
+    const QUERY: &str = const_format::concatcp!(
+        INSERT_ORDER_QUERY,
+        "ON CONFLICT (uid) DO NOTHING RETURNING uid"
+    );
+    let mut inserted = Vec::new();
+    for order in orders {
+        let row = bind_order(QUERY, order).fetch_optional(&mut *ex).await?;
+        if row.is_none() {
+            continue;
+        }
+        insert_order_event(
+            ex,
+            &OrderEvent {
+                label: OrderEventLabel::Created,
+                timestamp: order.creation_timestamp,
+                order_uid: order.uid,
+                reason: None,
+            },
+        )
+        .await?;
+        inserted.push(order.uid);
+    }
+    Ok(inserted)
+}
+
 async fn insert_order_execute_sqlx(
     query_str: &str,
     ex: &mut PgConnection,
     order: &Order,
 ) -> Result<(), sqlx::Error> {
+    bind_order(query_str, order).execute(ex).await?;
+    Ok(())
+}
+
+/// Binds the order's fields to the parameters of [`INSERT_ORDER_QUERY`].
+fn bind_order<'q>(
+    query_str: &'q str,
+    order: &'q Order,
+) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
     sqlx::query(query_str)
         .bind(order.uid)
         .bind(order.owner)
@@ -186,9 +229,6 @@ async fn insert_order_execute_sqlx(
         .bind(&order.full_fee_amount)
         .bind(order.cancellation_timestamp)
         .bind(order.class)
-        .execute(ex)
-        .await?;
-    Ok(())
 }
 
 pub async fn insert_order(ex: &mut PgConnection, order: &Order) -> Result<(), sqlx::Error> {
@@ -1146,6 +1186,27 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_insert_new_orders_returns_inserted_uids() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let existing = Order::default();
+        insert_order(&mut db, &existing).await.unwrap();
+        let new = Order {
+            uid: ByteArray([1; 56]),
+            ..Default::default()
+        };
+
+        let inserted = insert_new_orders(&mut db, &[existing.clone(), new.clone(), new.clone()])
+            .await
+            .unwrap();
+        assert_eq!(inserted, vec![new.uid]);
+        assert_eq!(read_order(&mut db, &new.uid).await.unwrap().unwrap(), new);
+    }
+
     #[tokio::test]
     #[ignore]
     async fn postgres_quote_roundtrip_updating_on_conflict() {
@@ -8,7 +8,12 @@ pub struct SettlementCallData {
 }
 
 pub async fn insert(ex: &mut PgConnection, row: SettlementCallData) -> Result<(), sqlx::Error> {
-    const QUERY: &str = r#"INSERT INTO settlement_call_data (auction_id, call_data, uninternalized_call_data) VALUES ($1, $2, $3);"#;
+    const QUERY: &str = r#"
+INSERT INTO settlement_call_data (auction_id, call_data, uninternalized_call_data)
+VALUES ($1, $2, $3)
+ON CONFLICT (auction_id) DO UPDATE
+SET call_data = EXCLUDED.call_data, uninternalized_call_data = EXCLUDED.uninternalized_call_data
+    ;"#;
     sqlx::query(QUERY)
         .bind(row.auction_id)
         .bind(row.call_data.as_slice())
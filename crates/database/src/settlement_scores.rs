@@ -16,7 +16,13 @@ pub struct Score {
 }
 
 pub async fn insert(ex: &mut PgTransaction<'_>, score: Score) -> Result<(), sqlx::Error> {
-    const QUERY: &str = r#"INSERT INTO settlement_scores (auction_id, winner, winning_score, reference_score, block_deadline, simulation_block) VALUES ($1, $2, $3, $4, $5, $6);"#;
+    const QUERY: &str = r#"
+INSERT INTO settlement_scores (auction_id, winner, winning_score, reference_score, block_deadline, simulation_block)
+VALUES ($1, $2, $3, $4, $5, $6)
+ON CONFLICT (auction_id) DO UPDATE
+SET winner = EXCLUDED.winner, winning_score = EXCLUDED.winning_score, reference_score = EXCLUDED.reference_score,
+    block_deadline = EXCLUDED.block_deadline, simulation_block = EXCLUDED.simulation_block
+    ;"#;
     sqlx::query(QUERY)
         .bind(score.auction_id)
         .bind(score.winner)
@@ -40,6 +46,55 @@ pub async fn fetch(
         .await
 }
 
+/// Filters the scores of past auctions. Fields that are `None` don't restrict
+/// the result.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ScoresFilter {
+    /// Solver that must have submitted a solution to the auction.
+    pub solver: Option<Address>,
+    /// First block (inclusive) the solutions can have been simulated at.
+    pub from_block: Option<i64>,
+    /// Last block (inclusive) the solutions can have been simulated at.
+    pub to_block: Option<i64>,
+    /// Only match auctions won by `solver`.
+    pub winner_only: bool,
+}
+
+/// Fetches the scores of the auctions matching the filter, newest auction
+/// first. Pages are requested by passing the oldest auction of the previous
+/// page as `before`, so the query never has to skip over rows.
+pub async fn fetch_filtered(
+    ex: &mut PgConnection,
+    filter: &ScoresFilter,
+    before: Option<AuctionId>,
+    limit: i64,
+) -> Result<Vec<Score>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT ss.*
+FROM settlement_scores ss
+JOIN solver_competitions sc ON sc.id = ss.auction_id
+WHERE
+    ($1 IS NULL OR sc.json->'solutions' @> jsonb_build_array(
+        jsonb_build_object('solverAddress', '0x' || encode($1, 'hex'))
+    ))
+    AND ($2 IS NULL OR ss.simulation_block >= $2)
+    AND ($3 IS NULL OR ss.simulation_block <= $3)
+    AND (NOT $4 OR ss.winner = $1)
+    AND ($5 IS NULL OR ss.auction_id < $5)
+ORDER BY ss.auction_id DESC
+LIMIT $6
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(filter.solver)
+        .bind(filter.from_block)
+        .bind(filter.to_block)
+        .bind(filter.winner_only)
+        .bind(before)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
@@ -63,5 +118,109 @@ mod tests {
 
         let output = fetch(&mut db, 1).await.unwrap().unwrap();
         assert_eq!(input, output);
+
+        // a fallback winner replaces the original one
+        let input = Score {
+            winner: ByteArray([3; 20]),
+            winning_score: 9.into(),
+            reference_score: 8.into(),
+            ..input
+        };
+        insert(&mut db, input.clone()).await.unwrap();
+
+        let output = fetch(&mut db, 1).await.unwrap().unwrap();
+        assert_eq!(input, output);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_fetch_filtered() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let solver_a = ByteArray([1; 20]);
+        let solver_b = ByteArray([2; 20]);
+        let mut scores = Vec::new();
+        for (auction_id, winner, participants) in [
+            (1, solver_a, vec![solver_a]),
+            (2, solver_b, vec![solver_a, solver_b]),
+            (3, solver_b, vec![solver_b]),
+        ] {
+            let solutions = participants
+                .iter()
+                .map(|solver| format!(r#"{{"solverAddress": "{solver:?}"}}"#))
+                .collect::<Vec<_>>()
+                .join(",");
+            let competition = format!(r#"{{"solutions": [{solutions}]}}"#)
+                .parse::<sqlx::types::JsonValue>()
+                .unwrap();
+            crate::solver_competition::save(&mut db, auction_id, &competition)
+                .await
+                .unwrap();
+            let score = Score {
+                auction_id,
+                winner,
+                winning_score: 10.into(),
+                reference_score: 9.into(),
+                block_deadline: auction_id + 10,
+                simulation_block: auction_id,
+            };
+            insert(&mut db, score.clone()).await.unwrap();
+            scores.push(score);
+        }
+        // An auction without a recorded competition doesn't match.
+        insert(
+            &mut db,
+            Score {
+                auction_id: 4,
+                ..scores[0].clone()
+            },
+        )
+        .await
+        .unwrap();
+
+        async fn fetch(
+            ex: &mut PgConnection,
+            filter: ScoresFilter,
+            before: Option<AuctionId>,
+            limit: i64,
+        ) -> Vec<AuctionId> {
+            fetch_filtered(ex, &filter, before, limit)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|score| score.auction_id)
+                .collect()
+        }
+
+        assert_eq!(
+            fetch(&mut db, Default::default(), None, 10).await,
+            vec![3, 2, 1]
+        );
+        assert_eq!(
+            fetch(&mut db, Default::default(), Some(3), 1).await,
+            vec![2]
+        );
+        assert_eq!(
+            fetch(&mut db, Default::default(), Some(1), 10).await,
+            vec![]
+        );
+        let filter = ScoresFilter {
+            solver: Some(solver_a),
+            ..Default::default()
+        };
+        assert_eq!(fetch(&mut db, filter.clone(), None, 10).await, vec![2, 1]);
+        let winner_only = ScoresFilter {
+            winner_only: true,
+            ..filter
+        };
+        assert_eq!(fetch(&mut db, winner_only, None, 10).await, vec![1]);
+        let blocks = ScoresFilter {
+            from_block: Some(2),
+            to_block: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(fetch(&mut db, blocks, None, 10).await, vec![2]);
     }
 }
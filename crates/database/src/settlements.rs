@@ -1,5 +1,6 @@
 use {
-    crate::{events::EventIndex, PgTransaction, TransactionHash},
+    crate::{events::EventIndex, Address, PgTransaction, TransactionHash},
+    chrono::{DateTime, Utc},
     sqlx::{Executor, PgConnection},
     std::ops::Range,
 };
@@ -60,13 +61,19 @@ LIMIT 1
     sqlx::query_as(QUERY).fetch_optional(ex).await
 }
 
+/// Whether a settlement of the given solver has already been linked to the
+/// auction. An auction can have multiple winners that each settle their own
+/// solution.
 pub async fn already_processed(
     ex: &mut PgConnection,
     auction_id: i64,
+    solver: &Address,
 ) -> Result<bool, sqlx::Error> {
-    const QUERY: &str = r#"SELECT COUNT(*) FROM settlements WHERE auction_id = $1;"#;
+    const QUERY: &str =
+        r#"SELECT COUNT(*) FROM settlements WHERE auction_id = $1 AND solver = $2;"#;
     let count: i64 = sqlx::query_scalar(QUERY)
         .bind(auction_id)
+        .bind(solver)
         .fetch_one(ex)
         .await?;
     Ok(count >= 1)
@@ -92,6 +99,39 @@ WHERE block_number = $2 AND log_index = $3
         .map(|_| ())
 }
 
+/// Records the timestamp of the block the settlements of a block happened in.
+pub async fn update_block_timestamp(
+    ex: &mut PgConnection,
+    block_number: i64,
+    block_timestamp: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE settlements
+SET block_timestamp = $1
+WHERE block_number = $2
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(block_timestamp)
+        .bind(block_number)
+        .execute(ex)
+        .await
+        .map(|_| ())
+}
+
+/// Returns the most recent block with a settlement whose block timestamp is
+/// not known yet. Used to backfill the timestamps of settlements indexed
+/// before they were recorded.
+pub async fn block_without_timestamp(ex: &mut PgConnection) -> Result<Option<i64>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT block_number
+FROM settlements
+WHERE block_timestamp IS NULL
+ORDER BY block_number DESC
+LIMIT 1
+    "#;
+    sqlx::query_scalar(QUERY).fetch_optional(ex).await
+}
+
 pub async fn delete(
     ex: &mut PgTransaction<'_>,
     delete_from_block_number: u64,
@@ -205,4 +245,35 @@ mod tests {
 
         assert!(settlement.is_none());
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_block_without_timestamp() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        assert_eq!(block_without_timestamp(&mut db).await.unwrap(), None);
+
+        for block_number in [1, 2] {
+            let event = EventIndex {
+                block_number,
+                log_index: 0,
+            };
+            crate::events::insert_settlement(&mut db, &event, &Default::default())
+                .await
+                .unwrap();
+        }
+        assert_eq!(block_without_timestamp(&mut db).await.unwrap(), Some(2));
+
+        update_block_timestamp(&mut db, 2, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(block_without_timestamp(&mut db).await.unwrap(), Some(1));
+
+        update_block_timestamp(&mut db, 1, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(block_without_timestamp(&mut db).await.unwrap(), None);
+    }
 }
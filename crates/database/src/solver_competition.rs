@@ -1,5 +1,5 @@
 use {
-    crate::{auction::AuctionId, TransactionHash},
+    crate::{auction::AuctionId, OrderUid, TransactionHash},
     sqlx::{types::JsonValue, PgConnection},
 };
 
@@ -11,6 +11,8 @@ pub async fn save(
     const QUERY: &str = r#"
 INSERT INTO solver_competitions (id, json)
 VALUES ($1, $2)
+ON CONFLICT (id) DO UPDATE
+SET json = EXCLUDED.json
     ;"#;
     sqlx::query(QUERY).bind(id).bind(data).execute(ex).await?;
     Ok(())
@@ -64,6 +66,52 @@ WHERE s.tx_hash = $1
     sqlx::query_as(QUERY).bind(tx_hash).fetch_optional(ex).await
 }
 
+/// Loads the competitions of the given auctions, newest first.
+pub async fn load_by_ids(
+    ex: &mut PgConnection,
+    ids: &[AuctionId],
+) -> Result<Vec<LoadCompetition>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT sc.json, sc.id, s.tx_hash
+FROM solver_competitions sc
+-- outer joins because the data might not have been indexed yet
+LEFT OUTER JOIN settlements s ON sc.id = s.auction_id
+WHERE sc.id = ANY($1)
+ORDER BY sc.id DESC
+    ;"#;
+    sqlx::query_as(QUERY).bind(ids).fetch_all(ex).await
+}
+
+/// Loads up to `limit` competitions whose auction contained the order, newest
+/// first. Only the competitions of the `max_auctions` most recent auctions are
+/// considered.
+pub async fn load_recent_by_order(
+    ex: &mut PgConnection,
+    order_uid: &OrderUid,
+    max_auctions: i64,
+    limit: i64,
+) -> Result<Vec<LoadCompetition>, sqlx::Error> {
+    // The containment check is served by the `solver_competitions_auction_orders`
+    // index so only the json of matching competitions gets read.
+    const QUERY: &str = r#"
+SELECT sc.json, sc.id, s.tx_hash
+FROM solver_competitions sc
+-- outer joins because the data might not have been indexed yet
+LEFT OUTER JOIN settlements s ON sc.id = s.auction_id
+WHERE
+    sc.json->'auction'->'orders' ? ('0x' || encode($1, 'hex')) AND
+    sc.id > (SELECT COALESCE(MAX(id), 0) FROM solver_competitions) - $2
+ORDER BY sc.id DESC
+LIMIT $3
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(order_uid)
+        .bind(max_auctions)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -91,6 +139,62 @@ mod tests {
         assert!(load_by_id(&mut db, 1).await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_load_by_ids() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let value = JsonValue::Bool(true);
+        for id in 1..=3 {
+            save(&mut db, id, &value).await.unwrap();
+        }
+
+        let competitions = load_by_ids(&mut db, &[1, 3, 4]).await.unwrap();
+        let ids = competitions
+            .iter()
+            .map(|competition| competition.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![3, 1]);
+        assert!(load_by_ids(&mut db, &[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_load_recent_by_order() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let uid = ByteArray([1; 56]);
+        let competition = |uid: &OrderUid| {
+            format!(r#"{{"auction": {{"orders": ["{uid:?}"]}}}}"#)
+                .parse::<JsonValue>()
+                .unwrap()
+        };
+        save(&mut db, 1, &competition(&uid)).await.unwrap();
+        save(&mut db, 2, &competition(&ByteArray([2; 56])))
+            .await
+            .unwrap();
+        save(&mut db, 3, &competition(&uid)).await.unwrap();
+
+        let ids = |competitions: Vec<LoadCompetition>| {
+            competitions
+                .into_iter()
+                .map(|competition| competition.id)
+                .collect::<Vec<_>>()
+        };
+        let competitions = load_recent_by_order(&mut db, &uid, 10, 10).await.unwrap();
+        assert_eq!(ids(competitions), vec![3, 1]);
+        // Only the most recent auctions are considered.
+        let competitions = load_recent_by_order(&mut db, &uid, 2, 10).await.unwrap();
+        assert_eq!(ids(competitions), vec![3]);
+        // At most `limit` competitions are returned.
+        let competitions = load_recent_by_order(&mut db, &uid, 10, 1).await.unwrap();
+        assert_eq!(ids(competitions), vec![3]);
+    }
+
     #[tokio::test]
     #[ignore]
     async fn postgres_by_hash() {
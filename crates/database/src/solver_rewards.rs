@@ -0,0 +1,318 @@
+use {
+    crate::{auction::AuctionId, Address, PgTransaction},
+    bigdecimal::BigDecimal,
+    chrono::{DateTime, Utc},
+    sqlx::{PgConnection, QueryBuilder},
+    std::ops::DerefMut,
+};
+
+/// The outcome of an auction for one of its winners, for which no reward has
+/// been computed yet.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Outcome {
+    pub auction_id: AuctionId,
+    pub solver: Address,
+    pub block_deadline: i64,
+    /// Whether the solver settled the auction no later than the deadline.
+    pub settled: bool,
+    pub score: BigDecimal,
+    pub reference_score: BigDecimal,
+}
+
+/// Fetches the outcomes of the oldest auctions whose block deadline is not
+/// after `max_block_deadline` and that don't have rewards yet.
+///
+/// Winners are read from `auction_winners` and fall back to the single winner
+/// in `settlement_scores` for auctions that predate it. Auctions are only
+/// considered once all settlements up to their deadline have been linked to
+/// an auction.
+pub async fn unrewarded_outcomes(
+    ex: &mut PgConnection,
+    max_block_deadline: i64,
+    max_auctions: i64,
+) -> Result<Vec<Outcome>, sqlx::Error> {
+    const QUERY: &str = r#"
+WITH auctions AS (
+    SELECT ss.*
+    FROM settlement_scores ss
+    WHERE
+        ss.block_deadline <= $1 AND
+        NOT EXISTS (SELECT 1 FROM solver_rewards sr WHERE sr.auction_id = ss.auction_id) AND
+        NOT EXISTS (
+            SELECT 1 FROM settlements s
+            WHERE s.auction_id IS NULL AND s.block_number <= ss.block_deadline
+        )
+    ORDER BY ss.auction_id
+    LIMIT $2
+),
+winners AS (
+    SELECT
+        a.auction_id,
+        COALESCE(aw.winner, a.winner) AS solver,
+        a.block_deadline,
+        COALESCE(aw.score, a.winning_score) AS score,
+        COALESCE(aw.reference_score, a.reference_score) AS reference_score
+    FROM auctions a
+    LEFT OUTER JOIN auction_winners aw ON aw.auction_id = a.auction_id
+)
+SELECT
+    w.*,
+    EXISTS (
+        SELECT 1 FROM settlements s
+        WHERE
+            s.auction_id = w.auction_id AND
+            s.solver = w.solver AND
+            s.block_number <= w.block_deadline
+    ) AS settled
+FROM winners w
+ORDER BY w.auction_id, w.solver
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(max_block_deadline)
+        .bind(max_auctions)
+        .fetch_all(ex)
+        .await
+}
+
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Reward {
+    pub auction_id: AuctionId,
+    pub solver: Address,
+    pub block_deadline: i64,
+    pub block_timestamp: DateTime<Utc>,
+    pub settled: bool,
+    pub score: BigDecimal,
+    pub reference_score: BigDecimal,
+    /// The reward in wei, negative for penalties.
+    pub reward: BigDecimal,
+}
+
+pub async fn insert(ex: &mut PgTransaction<'_>, rewards: &[Reward]) -> Result<(), sqlx::Error> {
+    if rewards.is_empty() {
+        return Ok(());
+    }
+
+    let mut query_builder = QueryBuilder::new(
+        "INSERT INTO solver_rewards (auction_id, solver, block_deadline, block_timestamp, \
+         settled, score, reference_score, reward) ",
+    );
+    query_builder.push_values(rewards, |mut row, reward| {
+        row.push_bind(reward.auction_id)
+            .push_bind(reward.solver)
+            .push_bind(reward.block_deadline)
+            .push_bind(reward.block_timestamp)
+            .push_bind(reward.settled)
+            .push_bind(&reward.score)
+            .push_bind(&reward.reference_score)
+            .push_bind(&reward.reward);
+    });
+    query_builder.push(" ON CONFLICT DO NOTHING");
+    query_builder.build().execute(ex.deref_mut()).await?;
+    Ok(())
+}
+
+/// Fetches the rewards of a solver whose deadline block timestamp is in
+/// `[from, to)`, ordered by auction.
+pub async fn fetch(
+    ex: &mut PgConnection,
+    solver: &Address,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Reward>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT * FROM solver_rewards
+WHERE solver = $1 AND block_timestamp >= $2 AND block_timestamp < $3
+ORDER BY auction_id
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(solver)
+        .bind(from)
+        .bind(to)
+        .fetch_all(ex)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction_winners::{self, Winner},
+            byte_array::ByteArray,
+            events::{self, EventIndex, Settlement},
+            settlement_scores::{self, Score},
+            settlements,
+        },
+        chrono::TimeZone,
+        sqlx::Connection,
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let score = |auction_id, winner, block_deadline| Score {
+            auction_id,
+            winner: ByteArray([winner; 20]),
+            winning_score: 10.into(),
+            reference_score: 5.into(),
+            block_deadline,
+            simulation_block: 0,
+        };
+        settlement_scores::insert(&mut db, score(1, 1, 10))
+            .await
+            .unwrap();
+        settlement_scores::insert(&mut db, score(2, 2, 20))
+            .await
+            .unwrap();
+        settlement_scores::insert(&mut db, score(3, 3, 30))
+            .await
+            .unwrap();
+        // auction 2 has multiple winners
+        auction_winners::insert(
+            &mut db,
+            &[
+                Winner {
+                    auction_id: 2,
+                    winner: ByteArray([2; 20]),
+                    score: 10.into(),
+                    reference_score: 5.into(),
+                },
+                Winner {
+                    auction_id: 2,
+                    winner: ByteArray([4; 20]),
+                    score: 8.into(),
+                    reference_score: 0.into(),
+                },
+            ],
+        )
+        .await
+        .unwrap();
+
+        // solver 1 settled auction 1 in time, solver 2 settled auction 2 too
+        // late
+        let settle = |block_number, solver| {
+            (
+                EventIndex {
+                    block_number,
+                    log_index: 0,
+                },
+                Settlement {
+                    solver: ByteArray([solver; 20]),
+                    transaction_hash: ByteArray([solver; 32]),
+                },
+            )
+        };
+        for (auction_id, (index, settlement)) in [(1, settle(9, 1)), (2, settle(21, 2))] {
+            events::insert_settlement(&mut db, &index, &settlement)
+                .await
+                .unwrap();
+            settlements::update_settlement_auction(
+                &mut db,
+                index.block_number,
+                index.log_index,
+                auction_id,
+            )
+            .await
+            .unwrap();
+        }
+
+        let outcomes = unrewarded_outcomes(&mut db, 25, 10).await.unwrap();
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome {
+                    auction_id: 1,
+                    solver: ByteArray([1; 20]),
+                    block_deadline: 10,
+                    settled: true,
+                    score: 10.into(),
+                    reference_score: 5.into(),
+                },
+                Outcome {
+                    auction_id: 2,
+                    solver: ByteArray([2; 20]),
+                    block_deadline: 20,
+                    settled: false,
+                    score: 10.into(),
+                    reference_score: 5.into(),
+                },
+                Outcome {
+                    auction_id: 2,
+                    solver: ByteArray([4; 20]),
+                    block_deadline: 20,
+                    settled: false,
+                    score: 8.into(),
+                    reference_score: 0.into(),
+                },
+            ]
+        );
+
+        // settlements that are not linked to an auction yet block the
+        // computation of later rewards
+        events::insert_settlement(&mut db, &settle(15, 5).0, &settle(15, 5).1)
+            .await
+            .unwrap();
+        let outcomes = unrewarded_outcomes(&mut db, 25, 10).await.unwrap();
+        assert_eq!(outcomes.len(), 1);
+
+        let reward = Reward {
+            auction_id: 1,
+            solver: ByteArray([1; 20]),
+            block_deadline: 10,
+            block_timestamp: Utc.timestamp_opt(1_000, 0).unwrap(),
+            settled: true,
+            score: 10.into(),
+            reference_score: 5.into(),
+            reward: 5.into(),
+        };
+        let other_reward = Reward {
+            auction_id: 2,
+            solver: ByteArray([2; 20]),
+            block_deadline: 20,
+            settled: false,
+            reward: (-5).into(),
+            ..reward.clone()
+        };
+        insert(&mut db, &[reward.clone(), other_reward.clone()])
+            .await
+            .unwrap();
+        // already inserted rewards are ignored
+        insert(&mut db, &[reward.clone()]).await.unwrap();
+        let outcomes = unrewarded_outcomes(&mut db, 25, 10).await.unwrap();
+        assert!(outcomes.is_empty());
+
+        let rewards = fetch(
+            &mut db,
+            &ByteArray([1; 20]),
+            Utc.timestamp_opt(1_000, 0).unwrap(),
+            Utc.timestamp_opt(2_000, 0).unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(rewards, vec![reward]);
+
+        let rewards = fetch(
+            &mut db,
+            &ByteArray([2; 20]),
+            Utc.timestamp_opt(1_000, 0).unwrap(),
+            Utc.timestamp_opt(2_000, 0).unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(rewards, vec![other_reward]);
+
+        let rewards = fetch(
+            &mut db,
+            &ByteArray([1; 20]),
+            Utc.timestamp_opt(0, 0).unwrap(),
+            Utc.timestamp_opt(1_000, 0).unwrap(),
+        )
+        .await
+        .unwrap();
+        assert!(rewards.is_empty());
+    }
+}
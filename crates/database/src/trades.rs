@@ -1,6 +1,7 @@
 use {
-    crate::{Address, OrderUid, TransactionHash},
+    crate::{events::EventIndex, Address, OrderUid, TransactionHash},
     bigdecimal::BigDecimal,
+    chrono::{DateTime, Utc},
     futures::stream::BoxStream,
     sqlx::PgConnection,
 };
@@ -19,10 +20,35 @@ pub struct TradesQueryRow {
     pub tx_hash: Option<TransactionHash>,
 }
 
+/// Filters trades. Fields that are `None` don't restrict the result.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TradesFilter {
+    pub owner: Option<Address>,
+    pub order_uid: Option<OrderUid>,
+    pub sell_token: Option<Address>,
+    pub buy_token: Option<Address>,
+    /// First block (inclusive) a trade can have happened in.
+    pub from_block: Option<i64>,
+    /// Last block (inclusive) a trade can have happened in.
+    pub to_block: Option<i64>,
+    /// Earliest time (inclusive) a trade can have been settled at. Trades whose
+    /// settlement timestamp is not known don't match.
+    pub from_timestamp: Option<DateTime<Utc>>,
+    /// Latest time (exclusive) a trade can have been settled at. Trades whose
+    /// settlement timestamp is not known don't match.
+    pub to_timestamp: Option<DateTime<Utc>>,
+    pub tx_hash: Option<TransactionHash>,
+}
+
+/// Fetches trades matching the filter ordered by their event index. Only
+/// trades after the `after` event index are returned which allows paginating
+/// through the trades with the index of the last trade of the previous page as
+/// a cursor.
 pub fn trades<'a>(
     ex: &'a mut PgConnection,
-    owner_filter: Option<&'a Address>,
-    order_uid_filter: Option<&'a OrderUid>,
+    filter: &'a TradesFilter,
+    after: Option<EventIndex>,
+    limit: Option<i64>,
 ) -> BoxStream<'a, Result<TradesQueryRow, sqlx::Error>> {
     const COMMON_QUERY: &str = r#"
 SELECT
@@ -37,30 +63,76 @@ SELECT
     o.sell_token,
     settlement.tx_hash
 FROM trades t
+JOIN orders o
+ON o.uid = t.order_uid"#;
+    const SETTLEMENT_JOIN: &str = r#"
 LEFT OUTER JOIN LATERAL (
-    SELECT tx_hash FROM settlements s
+    SELECT tx_hash, block_timestamp FROM settlements s
     WHERE s.block_number = t.block_number
     AND   s.log_index > t.log_index
     ORDER BY s.log_index ASC
     LIMIT 1
-) AS settlement ON true
-JOIN orders o
-ON o.uid = t.order_uid"#;
+) AS settlement ON true"#;
+    // The time and transaction filters get translated to block numbers so
+    // that they can make use of the trades' primary key. The block range only
+    // narrows down the trades. Trades whose settlement has no known timestamp
+    // don't match a time filter.
+    const COMMON_FILTER: &str = r#"
+    AND ($2 IS NULL OR o.uid = $2)
+    AND ($3 IS NULL OR o.sell_token = $3)
+    AND ($4 IS NULL OR o.buy_token = $4)
+    AND ($5 IS NULL OR t.block_number >= $5)
+    AND ($6 IS NULL OR t.block_number <= $6)
+    AND ($7 IS NULL OR t.block_number >= (
+        SELECT MIN(block_number) FROM settlements WHERE block_timestamp >= $7
+    ))
+    AND ($8 IS NULL OR t.block_number <= (
+        SELECT MAX(block_number) FROM settlements WHERE block_timestamp < $8
+    ))
+    AND ($9 IS NULL OR t.block_number IN (
+        SELECT block_number FROM settlements WHERE tx_hash = $9
+    ))
+    AND ($10 IS NULL OR (t.block_number, t.log_index) > ($10, $11))
+    AND ($9 IS NULL OR settlement.tx_hash = $9)
+    AND ($7 IS NULL OR settlement.block_timestamp >= $7)
+    AND ($8 IS NULL OR settlement.block_timestamp < $8)
+ORDER BY t.block_number, t.log_index
+LIMIT $12"#;
+    // Both branches are limited on their own so that each can stop scanning
+    // the trades' primary key early instead of the union having to collect all
+    // matching trades first. The second branch excludes trades the first one
+    // already returned so the branches can be combined with `UNION ALL`.
     const QUERY: &str = const_format::concatcp!(
+        "(",
         COMMON_QUERY,
+        SETTLEMENT_JOIN,
         " WHERE ($1 IS NULL OR o.owner = $1)",
-        " AND ($2 IS NULL OR o.uid = $2)",
-        "UNION",
+        COMMON_FILTER,
+        ") UNION ALL (",
         COMMON_QUERY,
-        " LEFT OUTER JOIN onchain_placed_orders onchain_o",
+        " JOIN onchain_placed_orders onchain_o",
         " ON onchain_o.uid = t.order_uid",
-        " WHERE onchain_o.sender = $1",
-        " AND ($2 IS NULL OR o.uid = $2)",
+        SETTLEMENT_JOIN,
+        " WHERE $1 IS NOT NULL AND onchain_o.sender = $1 AND o.owner != $1",
+        COMMON_FILTER,
+        r#")
+ORDER BY block_number, log_index
+LIMIT $12"#,
     );
 
     sqlx::query_as(QUERY)
-        .bind(owner_filter)
-        .bind(order_uid_filter)
+        .bind(filter.owner)
+        .bind(filter.order_uid)
+        .bind(filter.sell_token)
+        .bind(filter.buy_token)
+        .bind(filter.from_block)
+        .bind(filter.to_block)
+        .bind(filter.from_timestamp)
+        .bind(filter.to_timestamp)
+        .bind(filter.tx_hash)
+        .bind(after.map(|index| index.block_number))
+        .bind(after.map(|index| index.log_index))
+        .bind(limit)
         .fetch(ex)
 }
 
@@ -139,7 +211,12 @@ mod tests {
         order_uid_filter: Option<&OrderUid>,
         expected: &[TradesQueryRow],
     ) {
-        let filtered = trades(db, owner_filter, order_uid_filter)
+        let filter = TradesFilter {
+            owner: owner_filter.copied(),
+            order_uid: order_uid_filter.copied(),
+            ..Default::default()
+        };
+        let filtered = trades(db, &filter, None, None)
             .try_collect::<Vec<_>>()
             .await
             .unwrap();
@@ -170,7 +247,7 @@ mod tests {
         };
         let trade_b =
             add_order_and_trade(&mut db, owners[0], order_ids[1], event_index_b, None).await;
-        assert_trades(&mut db, None, None, &[trade_b, trade_a]).await;
+        assert_trades(&mut db, None, None, &[trade_a, trade_b]).await;
     }
 
     #[tokio::test]
@@ -211,7 +288,11 @@ mod tests {
         }
 
         let now = std::time::Instant::now();
-        trades(&mut db, Some(&ByteArray([2u8; 20])), None)
+        let filter = TradesFilter {
+            owner: Some(ByteArray([2u8; 20])),
+            ..Default::default()
+        };
+        trades(&mut db, &filter, None, None)
             .try_collect::<Vec<_>>()
             .await
             .unwrap();
@@ -270,6 +351,28 @@ mod tests {
         assert_trades(&mut db, Some(&owners[3]), None, &[trade_0]).await;
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_trades_of_onchain_order_placed_by_owner_are_not_duplicated() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let (owners, order_ids) = generate_owners_and_order_ids(1, 1).await;
+        let event_index = EventIndex::default();
+        let trade = add_order_and_trade(&mut db, owners[0], order_ids[0], event_index, None).await;
+        let onchain_order = OnchainOrderPlacement {
+            order_uid: ByteArray(order_ids[0].0),
+            sender: owners[0],
+            placement_error: None,
+        };
+        insert_onchain_order(&mut db, &event_index, &onchain_order)
+            .await
+            .unwrap();
+
+        assert_trades(&mut db, Some(&owners[0]), None, &[trade]).await;
+    }
+
     #[tokio::test]
     #[ignore]
     async fn postgres_trades_with_order_uid_filter() {
@@ -495,4 +598,169 @@ mod tests {
         .await;
         assert_trades(&mut db, None, None, &[trade_a, trade_b]).await;
     }
+
+    async fn fetch(
+        db: &mut PgConnection,
+        filter: TradesFilter,
+        after: Option<EventIndex>,
+        limit: Option<i64>,
+    ) -> Vec<TradesQueryRow> {
+        trades(db, &filter, after, limit)
+            .try_collect()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_trades_with_filters_and_pagination() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let (owners, order_ids) = generate_owners_and_order_ids(1, 3).await;
+        let token_a = ByteArray([0xa; 20]);
+        let token_b = ByteArray([0xb; 20]);
+        let mut expected = Vec::new();
+        for (i, order_uid) in order_ids.iter().enumerate() {
+            let (sell_token, buy_token) = if i == 1 {
+                (token_b, token_a)
+            } else {
+                (token_a, token_b)
+            };
+            let order = Order {
+                uid: *order_uid,
+                owner: owners[0],
+                sell_token,
+                buy_token,
+                ..Default::default()
+            };
+            crate::orders::insert_order(&mut db, &order).await.unwrap();
+            let block_number = i as i64;
+            let tx_hash = ByteArray([i as u8; 32]);
+            let trade = add_trade(
+                &mut db,
+                owners[0],
+                *order_uid,
+                EventIndex {
+                    block_number,
+                    log_index: 0,
+                },
+                Some(tx_hash),
+            )
+            .await;
+            add_settlement(
+                &mut db,
+                EventIndex {
+                    block_number,
+                    log_index: 1,
+                },
+                Default::default(),
+                tx_hash,
+            )
+            .await;
+            // The timestamp of the first settlement is not known.
+            if i > 0 {
+                crate::settlements::update_block_timestamp(
+                    &mut db,
+                    block_number,
+                    chrono::TimeZone::timestamp_opt(&Utc, block_number * 12, 0).unwrap(),
+                )
+                .await
+                .unwrap();
+            }
+            expected.push(TradesQueryRow {
+                sell_token,
+                buy_token,
+                ..trade
+            });
+        }
+
+        let result = fetch(
+            &mut db,
+            TradesFilter {
+                sell_token: Some(token_a),
+                ..Default::default()
+            },
+            None,
+            None,
+        )
+        .await;
+        assert_eq!(result, [expected[0].clone(), expected[2].clone()]);
+
+        let result = fetch(
+            &mut db,
+            TradesFilter {
+                buy_token: Some(token_a),
+                ..Default::default()
+            },
+            None,
+            None,
+        )
+        .await;
+        assert_eq!(result, [expected[1].clone()]);
+
+        let result = fetch(
+            &mut db,
+            TradesFilter {
+                from_block: Some(1),
+                to_block: Some(1),
+                ..Default::default()
+            },
+            None,
+            None,
+        )
+        .await;
+        assert_eq!(result, [expected[1].clone()]);
+
+        let result = fetch(
+            &mut db,
+            TradesFilter {
+                from_timestamp: Some(chrono::TimeZone::timestamp_opt(&Utc, 12, 0).unwrap()),
+                to_timestamp: Some(chrono::TimeZone::timestamp_opt(&Utc, 24, 0).unwrap()),
+                ..Default::default()
+            },
+            None,
+            None,
+        )
+        .await;
+        assert_eq!(result, [expected[1].clone()]);
+
+        let result = fetch(
+            &mut db,
+            TradesFilter {
+                to_timestamp: Some(chrono::TimeZone::timestamp_opt(&Utc, 36, 0).unwrap()),
+                ..Default::default()
+            },
+            None,
+            None,
+        )
+        .await;
+        assert_eq!(result, [expected[1].clone(), expected[2].clone()]);
+
+        let result = fetch(
+            &mut db,
+            TradesFilter {
+                tx_hash: Some(ByteArray([2; 32])),
+                ..Default::default()
+            },
+            None,
+            None,
+        )
+        .await;
+        assert_eq!(result, [expected[2].clone()]);
+
+        let filter = TradesFilter {
+            owner: Some(owners[0]),
+            ..Default::default()
+        };
+        let page = fetch(&mut db, filter.clone(), None, Some(2)).await;
+        assert_eq!(page, expected[..2]);
+        let cursor = EventIndex {
+            block_number: page[1].block_number,
+            log_index: page[1].log_index,
+        };
+        let page = fetch(&mut db, filter, Some(cursor), Some(2)).await;
+        assert_eq!(page, expected[2..]);
+    }
 }
@@ -176,22 +176,38 @@ async fn init_liquidity(
         web3: web3.clone(),
     })));
 
-    let balancer_pool_fetcher = Arc::new(
-        BalancerPoolFetcher::new(
-            &config.graph_api_base_url,
-            eth.network().0,
-            block_retriever.clone(),
-            token_info_fetcher.clone(),
-            boundary::liquidity::cache_config(),
-            block_stream.clone(),
-            boundary::liquidity::http_client(),
-            web3.clone(),
-            &contracts,
-            config.pool_deny_list.clone(),
-        )
-        .await
-        .context("failed to create balancer pool fetcher")?,
-    );
+    let balancer_pool_fetcher = match &config.factory_events {
+        Some(factory_events) => {
+            BalancerPoolFetcher::from_factory_events(
+                factory_events.start_block,
+                block_retriever.clone(),
+                token_info_fetcher.clone(),
+                boundary::liquidity::cache_config(),
+                block_stream.clone(),
+                web3.clone(),
+                &contracts,
+                config.pool_deny_list.clone(),
+            )
+            .await
+        }
+        None => {
+            BalancerPoolFetcher::new(
+                &config.graph_api_base_url,
+                eth.network().0,
+                block_retriever.clone(),
+                token_info_fetcher.clone(),
+                boundary::liquidity::cache_config(),
+                block_stream.clone(),
+                boundary::liquidity::http_client(),
+                web3.clone(),
+                &contracts,
+                config.pool_deny_list.clone(),
+            )
+            .await
+        }
+    };
+    let balancer_pool_fetcher =
+        Arc::new(balancer_pool_fetcher.context("failed to create balancer pool fetcher")?);
 
     Ok(BalancerV2Liquidity::new(
         web3,
@@ -0,0 +1,93 @@
+use {
+    crate::{
+        boundary::{self, Result},
+        domain::{
+            eth,
+            liquidity::{self, curve},
+        },
+        infra::{self, blockchain::Ethereum},
+    },
+    contracts::ICurvePool,
+    shared::{
+        interaction::Interaction,
+        sources::curve::pool_fetching::{CurvePoolFetcher, POOL_MEAN_GAS},
+        token_info::{CachedTokenInfoFetcher, TokenInfoFetcher},
+    },
+    solver::{
+        interactions::CurveInteraction,
+        liquidity::{curve::CurveLiquidity, CurvePoolOrder},
+        liquidity_collector::LiquidityCollecting,
+    },
+    std::sync::Arc,
+};
+
+pub fn to_domain(id: liquidity::Id, pool: CurvePoolOrder) -> Result<liquidity::Liquidity> {
+    Ok(liquidity::Liquidity {
+        id,
+        gas: POOL_MEAN_GAS.into(),
+        kind: liquidity::Kind::Curve(curve::Pool {
+            address: pool.pool.address.into(),
+            reserves: curve::Reserves::new(
+                pool.pool
+                    .tokens
+                    .iter()
+                    .map(|token| curve::Reserve {
+                        asset: eth::Asset {
+                            token: token.address.into(),
+                            amount: token.balance.into(),
+                        },
+                        decimals: token.decimals,
+                    })
+                    .collect(),
+            )?,
+            amplification_parameter: curve::AmplificationParameter(
+                pool.pool.amplification_parameter,
+            ),
+            fee: curve::Fee::from_raw(pool.pool.fee)?,
+        }),
+    })
+}
+
+pub fn to_interaction(
+    pool: &liquidity::curve::Pool,
+    index_in: usize,
+    index_out: usize,
+    input: &liquidity::MaxInput,
+    output: &liquidity::ExactOutput,
+) -> eth::Interaction {
+    let web3 = ethrpc::dummy::web3();
+
+    let interaction = CurveInteraction {
+        pool: ICurvePool::at(&web3, pool.address.0),
+        index_in,
+        index_out,
+        amount_in: input.0.amount,
+        min_amount_out: output.0.amount,
+    };
+
+    let encoded = interaction.encode();
+    eth::Interaction {
+        target: eth::Address(encoded.0),
+        value: eth::Ether(encoded.1),
+        call_data: crate::util::Bytes(encoded.2 .0),
+    }
+}
+
+pub async fn collector(
+    eth: &Ethereum,
+    config: &infra::liquidity::config::Curve,
+) -> Result<Box<dyn LiquidityCollecting>> {
+    let eth = eth.with_metric_label("curve".into());
+    let web3 = boundary::web3(&eth);
+    let token_infos = Arc::new(CachedTokenInfoFetcher::new(Arc::new(TokenInfoFetcher {
+        web3: web3.clone(),
+    })));
+    let pools = config.pools.iter().map(|pool| pool.0).collect::<Vec<_>>();
+    let pool_fetcher = CurvePoolFetcher::new(web3.clone(), &pools, token_infos).await?;
+
+    Ok(Box::new(CurveLiquidity::new(
+        web3,
+        eth.contracts().settlement(),
+        Arc::new(pool_fetcher),
+    )))
+}
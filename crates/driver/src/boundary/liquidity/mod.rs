@@ -27,6 +27,7 @@ use {
 };
 
 pub mod balancer;
+pub mod curve;
 pub mod swapr;
 pub mod uniswap;
 mod zeroex;
@@ -98,6 +99,14 @@ impl Fetcher {
             .map(|config| uniswap::v3::collector(eth, block_retriever.clone(), config))
             .collect();
 
+        let curve: Vec<_> = future::try_join_all(
+            config
+                .curve
+                .iter()
+                .map(|config| curve::collector(eth, config)),
+        )
+        .await?;
+
         let zeroex: Vec<_> = future::try_join_all(
             config
                 .zeroex
@@ -121,7 +130,7 @@ impl Fetcher {
         Ok(Self {
             blocks: block_stream,
             inner: LiquidityCollector {
-                liquidity_sources: [uni_v2, swapr, bal_v2, uni_v3, zeroex]
+                liquidity_sources: [uni_v2, swapr, bal_v2, uni_v3, curve, zeroex]
                     .into_iter()
                     .flatten()
                     .collect(),
@@ -171,6 +180,7 @@ impl Fetcher {
                     Liquidity::BalancerStable(pool) => balancer::v2::stable::to_domain(id, pool),
                     Liquidity::LimitOrder(_) => unreachable!(),
                     Liquidity::Concentrated(pool) => uniswap::v3::to_domain(id, pool),
+                    Liquidity::Curve(pool) => curve::to_domain(id, pool),
                 }
                 // Ignore "bad" liquidity - this allows the driver to continue
                 // solving with the other good stuff.
@@ -16,7 +16,8 @@ use {
     shared::{
         http_solver::model::TokenAmount,
         interaction::Interaction,
-        sources::uniswap_v3::pool_fetching::UniswapV3PoolFetcher,
+        sources::uniswap_v3::pool_fetching::{FactoryEventsDiscovery, UniswapV3PoolFetcher},
+        token_info::{CachedTokenInfoFetcher, TokenInfoFetcher},
     },
     solver::{
         interactions::allowances::Allowances,
@@ -128,18 +129,39 @@ async fn init_liquidity(
     let web3 = boundary::web3(eth);
     let router = UniswapV3SwapRouter::at(&web3, config.router.0);
 
-    let pool_fetcher = Arc::new(
-        UniswapV3PoolFetcher::new(
-            &config.graph_api_base_url,
-            eth.network().0,
-            web3.clone(),
-            boundary::liquidity::http_client(),
-            block_retriever,
-            config.max_pools_to_initialize,
-        )
-        .await
-        .context("failed to initialise UniswapV3 liquidity")?,
-    );
+    let pool_fetcher = match &config.factory_events {
+        Some(factory_events) => {
+            let token_info = Arc::new(CachedTokenInfoFetcher::new(Arc::new(TokenInfoFetcher {
+                web3: web3.clone(),
+            })));
+            let discovery = FactoryEventsDiscovery::new(
+                factory_events.factory.0,
+                factory_events.start_block,
+                factory_events.checkpoint_path.clone(),
+                eth.network().0,
+            )
+            .await?;
+            UniswapV3PoolFetcher::from_factory_events(
+                discovery,
+                web3.clone(),
+                block_retriever,
+                token_info,
+            )
+            .await
+        }
+        None => {
+            UniswapV3PoolFetcher::new(
+                &config.graph_api_base_url,
+                eth.network().0,
+                web3.clone(),
+                boundary::liquidity::http_client(),
+                block_retriever,
+                config.max_pools_to_initialize,
+            )
+            .await
+        }
+    };
+    let pool_fetcher = Arc::new(pool_fetcher.context("failed to initialise UniswapV3 liquidity")?);
 
     Ok(UniswapV3Liquidity::new(
         router,
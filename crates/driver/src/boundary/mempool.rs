@@ -55,6 +55,15 @@ pub enum Kind {
         additional_tip_percentage: f64,
         use_soft_cancellations: bool,
     },
+    /// Private submission of single transaction bundles to block builder
+    /// relays via `eth_sendBundle`. Only supported by the native submission
+    /// logic.
+    Bundle {
+        relays: Vec<reqwest::Url>,
+        /// Key for authenticating requests to relays that require it, such as
+        /// the Flashbots relay.
+        signer: Option<secp256k1::SecretKey>,
+    },
 }
 
 impl Kind {
@@ -63,6 +72,7 @@ impl Kind {
         match self {
             Kind::Public(_) => "PublicMempool",
             Kind::MEVBlocker { .. } => "MEVBlocker",
+            Kind::Bundle { .. } => "Bundle",
         }
     }
 }
@@ -134,6 +144,9 @@ impl Mempool {
                 config,
                 eth,
             },
+            Kind::Bundle { .. } => {
+                anyhow::bail!("bundle mempools are only supported by the native submission logic")
+            }
         })
     }
 
@@ -165,7 +178,7 @@ impl Mempool {
                     true,
                 ) => *additional_tip_percentage,
                 (Kind::MEVBlocker { .. }, false) => 0.,
-                (Kind::Public(_), _) => 0.,
+                (Kind::Public(_) | Kind::Bundle { .. }, _) => 0.,
             },
             max_additional_tip: match (&self.config.kind, settlement.boundary.revertable()) {
                 (
@@ -175,11 +188,11 @@ impl Mempool {
                     true,
                 ) => max_additional_tip.to_f64_lossy(),
                 (Kind::MEVBlocker { .. }, false) => 0.,
-                (Kind::Public(_), _) => 0.,
+                (Kind::Public(_) | Kind::Bundle { .. }, _) => 0.,
             },
         };
         let use_soft_cancellations = match self.config.kind {
-            Kind::Public(_) => false,
+            Kind::Public(_) | Kind::Bundle { .. } => false,
             Kind::MEVBlocker {
                 use_soft_cancellations,
                 ..
@@ -49,25 +49,41 @@ fn web3(eth: &Ethereum) -> Web3 {
 }
 
 /// Builds a web3 client that buffers requests and sends them in a
-/// batch call.
-pub fn buffered_web3_client(ethrpc: &Url) -> Web3 {
-    web3_client(ethrpc, 20, 10)
+/// batch call. Requests fail over to the fallback nodes if the main node is
+/// unhealthy.
+pub fn buffered_web3_client(
+    ethrpc: &Url,
+    fallbacks: &[Url],
+    multi: &ethrpc::multi::Configuration,
+) -> Web3 {
+    web3_client(ethrpc, fallbacks, multi, 20, 10)
 }
 
 /// Builds a web3 client that sends requests one by one.
 pub fn unbuffered_web3_client(ethrpc: &Url) -> Web3 {
-    web3_client(ethrpc, 0, 0)
+    web3_client(ethrpc, &[], &Default::default(), 0, 0)
 }
 
-fn web3_client(ethrpc: &Url, max_batch_size: usize, max_concurrent_requests: usize) -> Web3 {
+fn web3_client(
+    ethrpc: &Url,
+    fallbacks: &[Url],
+    multi: &ethrpc::multi::Configuration,
+    max_batch_size: usize,
+    max_concurrent_requests: usize,
+) -> Web3 {
     let ethrpc_args = shared::ethrpc::Arguments {
         ethrpc_max_batch_size: max_batch_size,
         ethrpc_max_concurrent_requests: max_concurrent_requests,
         ethrpc_batch_delay: Default::default(),
+        ethrpc_fallback_urls: fallbacks.to_vec(),
+        ethrpc_max_block_lag: multi.max_block_lag,
+        ethrpc_max_error_rate: multi.max_error_rate,
+        ethrpc_health_check_interval: multi.health_check_interval,
+        ethrpc_hedge_delay: multi.hedge_delay,
     };
     let http_factory =
         shared::http_client::HttpClientFactory::new(&shared::http_client::Arguments {
             http_timeout: std::time::Duration::from_secs(10),
         });
-    shared::ethrpc::web3(&ethrpc_args, &http_factory, ethrpc, "base")
+    shared::ethrpc::web3_with_fallbacks(&ethrpc_args, &http_factory, ethrpc, "base")
 }
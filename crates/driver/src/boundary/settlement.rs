@@ -451,6 +451,9 @@ pub fn to_boundary_interaction(
                 liquidity::Kind::BalancerV2Weighted(pool) => pool
                     .swap(&input, &output, &settlement_contract.into())
                     .context("invalid balancer v2 weighted execution")?,
+                liquidity::Kind::Curve(pool) => pool
+                    .swap(&input, &output)
+                    .context("invalid curve execution")?,
                 liquidity::Kind::Swapr(pool) => pool
                     .swap(&input, &output, &settlement_contract.into())
                     .context("invalid swapr execution")?,
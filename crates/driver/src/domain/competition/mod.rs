@@ -9,7 +9,8 @@ use {
         infra::{
             self,
             blockchain::Ethereum,
-            notify, observe,
+            notify,
+            observe,
             solver::{self, Solver},
             Simulator,
         },
@@ -19,7 +20,6 @@ use {
     itertools::Itertools,
     std::{
         collections::{HashMap, HashSet},
-        sync::Mutex,
         time::Duration,
     },
     tap::TapFallible,
@@ -28,6 +28,7 @@ use {
 pub mod auction;
 pub mod order;
 pub mod score;
+pub mod settlements;
 pub mod solution;
 
 pub use {
@@ -37,14 +38,20 @@ pub use {
         risk::{ObjectiveValue, SuccessProbability},
         Score,
     },
+    settlements::Settlements,
     solution::{Solution, SolverScore},
 };
 
+/// The ID of the solution the driver proposes for an auction. The driver
+/// currently proposes a single solution per auction.
+pub const SOLUTION_ID: u64 = 0;
+
 /// An ongoing competition. There is one competition going on per solver at any
 /// time. The competition stores settlements to solutions generated by the
-/// driver, and allows them to be executed onchain when requested later. The
-/// solutions expire after a certain amount of time, at which point trying to
-/// use them will return an `[Error::InvalidSolutionId]`.
+/// driver for recent auctions, and allows them to be executed onchain when
+/// requested later. The solutions expire after a certain amount of time, at
+/// which point trying to use them will return an
+/// `[Error::SolutionNotAvailable]`.
 #[derive(Debug)]
 pub struct Competition {
     pub solver: Solver,
@@ -52,7 +59,7 @@ pub struct Competition {
     pub liquidity: infra::liquidity::Fetcher,
     pub simulator: Simulator,
     pub mempools: Mempools,
-    pub settlement: Mutex<Option<Settlement>>,
+    pub settlements: Settlements,
 }
 
 impl Competition {
@@ -230,19 +237,26 @@ impl Competition {
                         score,
                         trades: settlement.orders(),
                         prices: settlement.prices(),
+                        liquidity: settlement.liquidity(),
+                        custom_interactions: settlement.has_custom_interactions(),
                     },
                     settlement,
                 )
             })
             .unzip();
 
-        *self.settlement.lock().unwrap() = settlement.clone();
-
         let settlement = match settlement {
             Some(settlement) => settlement,
             // Don't wait for the deadline because we can't produce a solution anyway.
-            None => return Ok(score),
+            None => {
+                if let Some(id) = auction.id() {
+                    self.settlements.clear_latest(id);
+                }
+                return Ok(score);
+            }
         };
+        self.settlements
+            .insert(settlement.auction_id, SOLUTION_ID, settlement.clone());
 
         // Re-simulate the solution on every new block until the deadline ends to make
         // sure we actually submit a working solution close to when the winner
@@ -258,7 +272,8 @@ impl Competition {
                     {
                         observe::winner_voided(block, &err);
                         *score_ref = None;
-                        *self.settlement.lock().unwrap() = None;
+                        self.settlements
+                            .take(Some(settlement.auction_id), SOLUTION_ID);
                         if let Some(id) = settlement.notify_id() {
                             notify::simulation_failed(
                                 &self.solver,
@@ -278,13 +293,17 @@ impl Competition {
         Ok(score)
     }
 
-    pub async fn reveal(&self) -> Result<Revealed, Error> {
+    /// Reveal the calldata of a solution generated as part of this
+    /// competition. If no auction ID is specified, the solution of the most
+    /// recent auction is revealed.
+    pub async fn reveal(
+        &self,
+        auction_id: Option<auction::Id>,
+        solution_id: u64,
+    ) -> Result<Revealed, Error> {
         let settlement = self
-            .settlement
-            .lock()
-            .unwrap()
-            .as_ref()
-            .cloned()
+            .settlements
+            .get(auction_id, solution_id)
             .ok_or(Error::SolutionNotAvailable)?;
         Ok(Revealed {
             internalized_calldata: settlement
@@ -303,16 +322,23 @@ impl Competition {
     }
 
     /// Execute the solution generated as part of this competition. Use
-    /// [`Competition::solve`] to generate the solution.
-    pub async fn settle(&self) -> Result<Settled, Error> {
+    /// [`Competition::solve`] to generate the solution. If no auction ID is
+    /// specified, the solution of the most recent auction is executed.
+    pub async fn settle(
+        &self,
+        auction_id: Option<auction::Id>,
+        solution_id: u64,
+        submission_deadline: Option<u64>,
+    ) -> Result<Settled, Error> {
         let settlement = self
-            .settlement
-            .lock()
-            .unwrap()
-            .take()
+            .settlements
+            .take(auction_id, solution_id)
             .ok_or(Error::SolutionNotAvailable)?;
 
-        let executed = self.mempools.execute(&self.solver, &settlement).await;
+        let executed = self
+            .mempools
+            .execute(&self.solver, &settlement, submission_deadline)
+            .await;
         notify::executed(
             &self.solver,
             settlement.auction_id,
@@ -340,13 +366,9 @@ impl Competition {
         }
     }
 
-    /// The ID of the auction being competed on.
+    /// The ID of the most recent auction being competed on.
     pub fn auction_id(&self) -> Option<auction::Id> {
-        self.settlement
-            .lock()
-            .unwrap()
-            .as_ref()
-            .map(|s| s.auction_id)
+        self.settlements.latest_auction_id()
     }
 
     /// Returns whether the settlement can be executed or would revert.
@@ -418,6 +440,10 @@ pub struct Solved {
     pub score: Score,
     pub trades: HashMap<order::Uid, Amounts>,
     pub prices: HashMap<eth::TokenAddress, eth::TokenAmount>,
+    /// See [`Settlement::liquidity`].
+    pub liquidity: HashSet<eth::Address>,
+    /// See [`Settlement::has_custom_interactions`].
+    pub custom_interactions: bool,
 }
 
 #[derive(Debug, Default)]
@@ -453,8 +479,8 @@ pub struct Settled {
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(
-        "no solution is available yet, this might mean that /settle was called before /solve \
-         returned"
+        "no solution is available for the requested ID, this might mean that /settle was called \
+         before /solve returned or that the solution expired"
     )]
     SolutionNotAvailable,
     #[error("{0:?}")]
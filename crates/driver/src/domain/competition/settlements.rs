@@ -0,0 +1,181 @@
+use {
+    super::{auction, Settlement},
+    std::{
+        collections::VecDeque,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+};
+
+/// The maximum number of settlements kept around at any time. The oldest
+/// settlement gets evicted when this limit is exceeded.
+const MAX_SETTLEMENTS: usize = 10;
+
+/// How long a settlement can be revealed or executed after it was computed.
+const SETTLEMENT_EXPIRY: Duration = Duration::from_secs(300);
+
+/// Settlements computed for recent auctions. They are keyed by auction ID and
+/// solution ID so that the protocol can reveal and execute the settlement of
+/// one auction while the next auction is already being solved.
+#[derive(Debug)]
+pub struct Settlements<T = Settlement>(Mutex<Inner<T>>);
+
+#[derive(Debug)]
+struct Inner<T> {
+    entries: VecDeque<Entry<T>>,
+    /// The most recent auction that was solved, even if no settlement was
+    /// found for it.
+    latest: Option<auction::Id>,
+}
+
+#[derive(Debug)]
+struct Entry<T> {
+    auction_id: auction::Id,
+    solution_id: u64,
+    settlement: T,
+    created: Instant,
+}
+
+impl<T> Entry<T> {
+    fn matches(&self, auction_id: auction::Id, solution_id: u64) -> bool {
+        self.auction_id == auction_id && self.solution_id == solution_id
+    }
+}
+
+impl<T> Default for Settlements<T> {
+    fn default() -> Self {
+        Self(Mutex::new(Inner {
+            entries: Default::default(),
+            latest: None,
+        }))
+    }
+}
+
+impl<T: Clone> Settlements<T> {
+    /// Stores a settlement, replacing any previous settlement with the same
+    /// auction and solution ID. The auction becomes the most recent one.
+    pub fn insert(&self, auction_id: auction::Id, solution_id: u64, settlement: T) {
+        let mut inner = self.lock();
+        inner
+            .entries
+            .retain(|entry| !entry.matches(auction_id, solution_id));
+        inner.entries.push_back(Entry {
+            auction_id,
+            solution_id,
+            settlement,
+            created: Instant::now(),
+        });
+        while inner.entries.len() > MAX_SETTLEMENTS {
+            inner.entries.pop_front();
+        }
+        inner.latest = Some(auction_id);
+    }
+
+    /// Marks the auction as the most recent one without storing a settlement
+    /// for it, so that the settlements of previous auctions are no longer
+    /// used when no auction ID is specified.
+    pub fn clear_latest(&self, auction_id: auction::Id) {
+        self.lock().latest = Some(auction_id);
+    }
+
+    /// Returns a copy of the settlement with the specified ID. If no auction ID
+    /// is specified, the most recent auction is assumed.
+    pub fn get(&self, auction_id: Option<auction::Id>, solution_id: u64) -> Option<T> {
+        let inner = self.lock();
+        let index = inner.position(auction_id, solution_id)?;
+        Some(inner.entries[index].settlement.clone())
+    }
+
+    /// Removes the settlement with the specified ID and returns it. If no
+    /// auction ID is specified, the most recent auction is assumed.
+    pub fn take(&self, auction_id: Option<auction::Id>, solution_id: u64) -> Option<T> {
+        let mut inner = self.lock();
+        let index = inner.position(auction_id, solution_id)?;
+        inner.entries.remove(index).map(|entry| entry.settlement)
+    }
+
+    /// The ID of the most recent auction that was solved.
+    pub fn latest_auction_id(&self) -> Option<auction::Id> {
+        self.lock().latest
+    }
+
+    /// Locks the settlements, evicting the ones that expired.
+    fn lock(&self) -> std::sync::MutexGuard<Inner<T>> {
+        let mut inner = self.0.lock().unwrap();
+        inner
+            .entries
+            .retain(|entry| entry.created.elapsed() < SETTLEMENT_EXPIRY);
+        inner
+    }
+}
+
+impl<T> Inner<T> {
+    fn position(&self, auction_id: Option<auction::Id>, solution_id: u64) -> Option<usize> {
+        let auction_id = auction_id.or(self.latest)?;
+        self.entries
+            .iter()
+            .position(|entry| entry.matches(auction_id, solution_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_latest_auction() {
+        let settlements = Settlements::default();
+        assert_eq!(settlements.get(None, 0), None);
+
+        settlements.insert(auction::Id(1), 0, "first");
+        settlements.insert(auction::Id(2), 0, "second");
+        assert_eq!(settlements.latest_auction_id(), Some(auction::Id(2)));
+        assert_eq!(settlements.get(None, 0), Some("second"));
+        assert_eq!(settlements.get(Some(auction::Id(1)), 0), Some("first"));
+        assert_eq!(settlements.get(None, 1), None);
+    }
+
+    #[test]
+    fn take_removes_settlement() {
+        let settlements = Settlements::default();
+        settlements.insert(auction::Id(1), 0, "first");
+        settlements.insert(auction::Id(2), 0, "second");
+
+        assert_eq!(settlements.take(Some(auction::Id(1)), 0), Some("first"));
+        assert_eq!(settlements.take(Some(auction::Id(1)), 0), None);
+        assert_eq!(settlements.take(None, 0), Some("second"));
+        assert_eq!(settlements.get(None, 0), None);
+    }
+
+    #[test]
+    fn replaces_settlement_with_same_id() {
+        let settlements = Settlements::default();
+        settlements.insert(auction::Id(1), 0, "old");
+        settlements.insert(auction::Id(1), 0, "new");
+
+        assert_eq!(settlements.take(None, 0), Some("new"));
+        assert_eq!(settlements.take(None, 0), None);
+    }
+
+    #[test]
+    fn evicts_oldest_settlements() {
+        let settlements = Settlements::default();
+        for id in 0..=MAX_SETTLEMENTS as i64 {
+            settlements.insert(auction::Id(id), 0, id);
+        }
+
+        assert_eq!(settlements.get(Some(auction::Id(0)), 0), None);
+        assert_eq!(settlements.get(Some(auction::Id(1)), 0), Some(1));
+    }
+
+    #[test]
+    fn clearing_latest_keeps_previous_settlements() {
+        let settlements = Settlements::default();
+        settlements.insert(auction::Id(1), 0, "first");
+        settlements.clear_latest(auction::Id(2));
+
+        assert_eq!(settlements.latest_auction_id(), Some(auction::Id(2)));
+        assert_eq!(settlements.get(None, 0), None);
+        assert_eq!(settlements.get(Some(auction::Id(1)), 0), Some("first"));
+    }
+}
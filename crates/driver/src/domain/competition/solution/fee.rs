@@ -38,9 +38,22 @@ use {
 };
 
 impl Fulfillment {
-    /// Applies the protocol fee to the existing fulfillment creating a new one.
-    pub fn with_protocol_fee(&self, prices: ClearingPrices) -> Result<Self, Error> {
-        let protocol_fee = self.protocol_fee_in_sell_token(prices)?;
+    /// Applies the protocol fees to the existing fulfillment creating a new
+    /// one. Multiple fee policies are applied one after another in the order
+    /// they are attached to the order, each to the fulfillment resulting from
+    /// the previous ones.
+    pub fn with_protocol_fees(&self, prices: ClearingPrices) -> Result<Self, Error> {
+        let mut fulfillment = self.clone();
+        for policy in &self.order().protocol_fees {
+            fulfillment = fulfillment.with_protocol_fee(prices, policy)?;
+        }
+        Ok(fulfillment)
+    }
+
+    /// Applies a single protocol fee to the existing fulfillment creating a
+    /// new one.
+    fn with_protocol_fee(&self, prices: ClearingPrices, policy: &FeePolicy) -> Result<Self, Error> {
+        let protocol_fee = self.protocol_fee_in_sell_token(prices, policy)?;
 
         // Increase the fee by the protocol fee
         let fee = match self.surplus_fee() {
@@ -73,28 +86,27 @@ impl Fulfillment {
     }
 
     /// Computed protocol fee in surplus token.
-    fn protocol_fee(&self, prices: ClearingPrices) -> Result<eth::TokenAmount, Error> {
-        // TODO: support multiple fee policies
-        if self.order().protocol_fees.len() > 1 {
-            return Err(Error::MultipleFeePolicies);
-        }
-
-        match self.order().protocol_fees.first() {
-            Some(FeePolicy::Surplus {
+    fn protocol_fee(
+        &self,
+        prices: ClearingPrices,
+        policy: &FeePolicy,
+    ) -> Result<eth::TokenAmount, Error> {
+        match policy {
+            FeePolicy::Surplus {
                 factor,
                 max_volume_factor,
-            }) => self.calculate_fee(
+            } => self.calculate_fee(
                 self.order().sell.amount.0,
                 self.order().buy.amount.0,
                 prices,
                 *factor,
                 *max_volume_factor,
             ),
-            Some(FeePolicy::PriceImprovement {
+            FeePolicy::PriceImprovement {
                 factor,
                 max_volume_factor,
                 quote,
-            }) => {
+            } => {
                 let (sell_amount, buy_amount) = adjust_quote_to_order_limits(
                     self.order().sell.amount.0,
                     self.order().buy.amount.0,
@@ -105,8 +117,7 @@ impl Fulfillment {
                 )?;
                 self.calculate_fee(sell_amount, buy_amount, prices, *factor, *max_volume_factor)
             }
-            Some(FeePolicy::Volume { factor }) => self.fee_from_volume(prices, *factor),
-            None => Ok(0.into()),
+            FeePolicy::Volume { factor } => self.fee_from_volume(prices, *factor),
         }
     }
 
@@ -169,11 +180,12 @@ impl Fulfillment {
     fn protocol_fee_in_sell_token(
         &self,
         prices: ClearingPrices,
+        policy: &FeePolicy,
     ) -> Result<eth::TokenAmount, Error> {
         let fee_in_sell_token = match self.order().side {
-            Side::Buy => self.protocol_fee(prices)?,
+            Side::Buy => self.protocol_fee(prices, policy)?,
             Side::Sell => self
-                .protocol_fee(prices)?
+                .protocol_fee(prices, policy)?
                 .0
                 .checked_mul(prices.buy)
                 .ok_or(Math::Overflow)?
@@ -240,8 +252,6 @@ fn adjust_quote_to_order_limits(
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error("multiple fee policies are not supported yet")]
-    MultipleFeePolicies,
     #[error("orders with non solver determined gas cost fees are not supported")]
     ProtocolFeeOnStaticOrder,
     #[error(transparent)]
@@ -84,7 +84,7 @@ impl Solution {
                             buy: solution.prices
                                 [&fulfillment.order().buy.token.wrap(solution.weth)],
                         };
-                        let fulfillment = fulfillment.with_protocol_fee(prices)?;
+                        let fulfillment = fulfillment.with_protocol_fees(prices)?;
                         trades.push(Trade::Fulfillment(fulfillment))
                     }
                     order::Kind::Liquidity => {
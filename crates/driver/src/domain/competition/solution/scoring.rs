@@ -139,72 +139,120 @@ impl Trade {
 
     /// Protocol fee is defined by fee policies attached to the order.
     ///
+    /// The policies were applied one after another, so their fees are
+    /// recovered in reverse order: each fee is computed on the trade as it was
+    /// right after its policy got applied, i.e. with the fees of all later
+    /// policies added back.
+    ///
     /// Denominated in SURPLUS token
     fn protocol_fee(&self) -> Result<eth::Asset, Error> {
-        // TODO: support multiple fee policies
-        if self.policies.len() > 1 {
-            return Err(Error::MultipleFeePolicies);
+        let mut current = self.clone();
+        let mut total = eth::U256::zero();
+        for policy in self.policies.iter().rev() {
+            let fee = current.policy_fee(policy)?;
+            total = total.checked_add(fee.0).ok_or(Math::Overflow)?;
+            current.custom_price = self.custom_prices_before(total)?;
         }
+        Ok(eth::Asset {
+            token: self.surplus_token(),
+            amount: total.into(),
+        })
+    }
 
-        let protocol_fee = |policy: &order::FeePolicy| {
-            match policy {
-                order::FeePolicy::Surplus {
-                    factor,
-                    max_volume_factor,
-                } => Ok(std::cmp::min(
-                    {
-                        // If the surplus after all fees is X, then the original surplus before
-                        // protocol fee is X / (1 - factor)
-                        let surplus = self
-                            .surplus()
-                            .ok_or(Error::Surplus(self.sell, self.buy))?
-                            .amount;
-                        surplus
-                            .apply_factor(factor / (1.0 - factor))
-                            .ok_or(Error::Factor(surplus, *factor))?
-                    },
-                    {
-                        // Convert the executed amount to surplus token so it can be compared
-                        // with the surplus
-                        let executed_in_surplus_token: eth::TokenAmount = match self.side {
-                            Side::Sell => self
-                                .executed
-                                .0
-                                .checked_mul(self.custom_price.sell)
-                                .ok_or(Math::Overflow)?
-                                .checked_div(self.custom_price.buy)
-                                .ok_or(Math::DivisionByZero)?,
-                            Side::Buy => self
-                                .executed
-                                .0
-                                .checked_mul(self.custom_price.buy)
-                                .ok_or(Math::Overflow)?
-                                .checked_div(self.custom_price.sell)
-                                .ok_or(Math::DivisionByZero)?,
-                        }
-                        .into();
-                        let factor = match self.side {
-                            Side::Sell => max_volume_factor / (1.0 - max_volume_factor),
-                            Side::Buy => max_volume_factor / (1.0 + max_volume_factor),
-                        };
-                        executed_in_surplus_token
-                            .apply_factor(factor)
-                            .ok_or(Error::Factor(executed_in_surplus_token, factor))?
-                    },
-                )),
-                order::FeePolicy::PriceImprovement {
-                    factor: _,
-                    max_volume_factor: _,
-                    quote: _,
-                } => Err(Error::UnimplementedFeePolicy),
-                order::FeePolicy::Volume { factor: _ } => Err(Error::UnimplementedFeePolicy),
-            }
+    /// The fee taken by a single policy, given that it was the last policy
+    /// applied to the trade.
+    ///
+    /// Denominated in SURPLUS token
+    fn policy_fee(&self, policy: &order::FeePolicy) -> Result<eth::TokenAmount, Error> {
+        match policy {
+            order::FeePolicy::Surplus {
+                factor,
+                max_volume_factor,
+            } => Ok(std::cmp::min(
+                {
+                    // If the surplus after all fees is X, then the original surplus before
+                    // protocol fee is X / (1 - factor)
+                    let surplus = self
+                        .surplus()
+                        .ok_or(Error::Surplus(self.sell, self.buy))?
+                        .amount;
+                    surplus
+                        .apply_factor(factor / (1.0 - factor))
+                        .ok_or(Error::Factor(surplus, *factor))?
+                },
+                self.volume_fee(*max_volume_factor)?,
+            )),
+            order::FeePolicy::PriceImprovement {
+                factor: _,
+                max_volume_factor: _,
+                quote: _,
+            } => Err(Error::UnimplementedFeePolicy),
+            order::FeePolicy::Volume { factor } => self.volume_fee(*factor),
+        }
+    }
+
+    /// The fee taken as a factor of the order's volume before the fee.
+    ///
+    /// Denominated in SURPLUS token
+    fn volume_fee(&self, factor: f64) -> Result<eth::TokenAmount, Error> {
+        // Convert the executed amount to surplus token so it can be compared
+        // with the surplus
+        let executed_in_surplus_token: eth::TokenAmount = match self.side {
+            Side::Sell => self
+                .executed
+                .0
+                .checked_mul(self.custom_price.sell)
+                .ok_or(Math::Overflow)?
+                .checked_div(self.custom_price.buy)
+                .ok_or(Math::DivisionByZero)?,
+            Side::Buy => self
+                .executed
+                .0
+                .checked_mul(self.custom_price.buy)
+                .ok_or(Math::Overflow)?
+                .checked_div(self.custom_price.sell)
+                .ok_or(Math::DivisionByZero)?,
+        }
+        .into();
+        let factor = match self.side {
+            Side::Sell => factor / (1.0 - factor),
+            Side::Buy => factor / (1.0 + factor),
         };
+        executed_in_surplus_token
+            .apply_factor(factor)
+            .ok_or(Error::Factor(executed_in_surplus_token, factor))
+    }
 
-        let protocol_fee = self.policies.first().map(protocol_fee).transpose();
-        Ok(eth::Asset {
-            token: self.surplus_token(),
-            amount: protocol_fee?.unwrap_or(0.into()),
+    /// The custom clearing prices the trade would have had without the given
+    /// protocol fee.
+    fn custom_prices_before(&self, protocol_fee: eth::U256) -> Result<CustomClearingPrices, Error> {
+        Ok(match self.side {
+            // The fee is taken from the bought amount.
+            Side::Sell => CustomClearingPrices {
+                sell: self
+                    .executed
+                    .0
+                    .checked_mul(self.custom_price.sell)
+                    .ok_or(Math::Overflow)?
+                    .checked_div(self.custom_price.buy)
+                    .ok_or(Math::DivisionByZero)?
+                    .checked_add(protocol_fee)
+                    .ok_or(Math::Overflow)?,
+                buy: self.executed.0,
+            },
+            // The fee is added to the sold amount.
+            Side::Buy => CustomClearingPrices {
+                sell: self.executed.0,
+                buy: self
+                    .executed
+                    .0
+                    .checked_mul(self.custom_price.buy)
+                    .ok_or(Math::Overflow)?
+                    .checked_div(self.custom_price.sell)
+                    .ok_or(Math::DivisionByZero)?
+                    .checked_sub(protocol_fee)
+                    .ok_or(Math::Overflow)?,
+            },
         })
     }
 
@@ -242,8 +290,6 @@ pub struct CustomClearingPrices {
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error("multiple fee policies are not supported yet")]
-    MultipleFeePolicies,
     #[error("fee policy not implemented yet")]
     UnimplementedFeePolicy,
     #[error("failed to calculate surplus for trade sell {0:?} buy {1:?}")]
@@ -255,3 +301,66 @@ pub enum Error {
     #[error(transparent)]
     Math(#[from] Math),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protocol_fees_are_recovered_in_reverse_order() {
+        let asset = |token: u64, amount: u64| eth::Asset {
+            token: eth::TokenAddress(eth::ContractAddress(eth::H160::from_low_u64_be(token))),
+            amount: eth::U256::from(amount).into(),
+        };
+        // A sell order that received 400 before fees. The first policy took
+        // half of that and the second policy half of the remaining 200, so 100
+        // were left for the user.
+        let trade = Trade::new(
+            asset(1, 100),
+            asset(2, 50),
+            Side::Sell,
+            order::TargetAmount(100.into()),
+            CustomClearingPrices {
+                sell: 100.into(),
+                buy: 100.into(),
+            },
+            vec![
+                order::FeePolicy::Volume { factor: 0.5 },
+                order::FeePolicy::Volume { factor: 0.5 },
+            ],
+        );
+
+        assert_eq!(trade.protocol_fee().unwrap(), asset(2, 300));
+    }
+
+    #[test]
+    fn surplus_and_volume_fees_are_combined() {
+        let asset = |token: u64, amount: u64| eth::Asset {
+            token: eth::TokenAddress(eth::ContractAddress(eth::H160::from_low_u64_be(token))),
+            amount: eth::U256::from(amount).into(),
+        };
+        // A sell order with a limit of 50 that received 172 before fees. The
+        // surplus fee took half of the surplus of 122 and the volume fee (e.g.
+        // a partner fee) 10% of the remaining 111, so 100 were left for the
+        // user.
+        let trade = Trade::new(
+            asset(1, 100),
+            asset(2, 50),
+            Side::Sell,
+            order::TargetAmount(100.into()),
+            CustomClearingPrices {
+                sell: 100.into(),
+                buy: 100.into(),
+            },
+            vec![
+                order::FeePolicy::Surplus {
+                    factor: 0.5,
+                    max_volume_factor: 0.5,
+                },
+                order::FeePolicy::Volume { factor: 0.1 },
+            ],
+        );
+
+        assert_eq!(trade.protocol_fee().unwrap(), asset(2, 72));
+    }
+}
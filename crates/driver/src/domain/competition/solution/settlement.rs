@@ -359,6 +359,30 @@ impl Settlement {
             })
     }
 
+    /// The addresses of the liquidity pools used by this settlement. Custom
+    /// interactions are not included since they can't be attributed to a
+    /// liquidity source.
+    pub fn liquidity(&self) -> HashSet<eth::Address> {
+        self.solutions
+            .values()
+            .flat_map(|solution| solution.interactions())
+            .filter_map(|interaction| match interaction {
+                solution::Interaction::Liquidity(liquidity) => liquidity.liquidity.kind.address(),
+                solution::Interaction::Custom(_) => None,
+            })
+            .collect()
+    }
+
+    /// Whether any of the settled solutions contains custom interactions. The
+    /// liquidity those use is unknown, so [`Settlement::liquidity`] is not
+    /// complete for such settlements.
+    pub fn has_custom_interactions(&self) -> bool {
+        self.solutions
+            .values()
+            .flat_map(|solution| solution.interactions())
+            .any(|interaction| matches!(interaction, solution::Interaction::Custom(_)))
+    }
+
     /// The uniform price vector this settlement proposes
     pub fn prices(&self) -> HashMap<eth::TokenAddress, eth::TokenAmount> {
         self.boundary.clearing_prices()
@@ -0,0 +1,131 @@
+use {
+    crate::{
+        boundary,
+        domain::{eth, liquidity},
+    },
+    itertools::Itertools,
+};
+
+/// Liquidity data tied to a Curve StableSwap pool [^1].
+///
+/// Only "plain" pools are modelled, i.e. pools holding a fixed set of ERC20
+/// coins that are traded via `exchange(i, j, dx, min_dy)`.
+///
+/// [^1]: <https://classic.curve.fi/whitepaper>
+#[derive(Clone, Debug)]
+pub struct Pool {
+    pub address: eth::ContractAddress,
+    pub reserves: Reserves,
+    pub amplification_parameter: AmplificationParameter,
+    pub fee: Fee,
+}
+
+impl Pool {
+    /// Encodes a pool swap as an interaction. Returns `Err` if the swap
+    /// parameters are invalid for the pool, specifically if the input and
+    /// output tokens are not coins of the pool.
+    ///
+    /// Note that Curve pools only support swapping exact input amounts, so the
+    /// encoded swap sells the entire maximum input and only guarantees that at
+    /// least the requested output is received.
+    pub fn swap(
+        &self,
+        input: &liquidity::MaxInput,
+        output: &liquidity::ExactOutput,
+    ) -> Result<eth::Interaction, liquidity::InvalidSwap> {
+        let (Some(i), Some(j)) = (
+            self.reserves.index_of(&input.0.token),
+            self.reserves.index_of(&output.0.token),
+        ) else {
+            return Err(liquidity::InvalidSwap);
+        };
+        if i == j {
+            return Err(liquidity::InvalidSwap);
+        }
+
+        Ok(boundary::liquidity::curve::to_interaction(
+            self, i, j, input, output,
+        ))
+    }
+}
+
+/// Curve pool reserves.
+///
+/// This is an ordered collection of coins, where the position of a reserve
+/// corresponds to the coin index in the pool contract.
+#[derive(Clone, Debug)]
+pub struct Reserves(Vec<Reserve>);
+
+impl Reserves {
+    /// Creates new Curve reserves, returns `Err` if the specified reserves are
+    /// invalid, specifically, if there are fewer than two or duplicate coins.
+    pub fn new(reserves: Vec<Reserve>) -> Result<Self, InvalidReserves> {
+        if reserves.len() < 2 || !reserves.iter().map(|r| r.asset.token).all_unique() {
+            return Err(InvalidReserves);
+        }
+
+        Ok(Self(reserves))
+    }
+
+    /// Returns the coin index of the specified token.
+    fn index_of(&self, token: &eth::TokenAddress) -> Option<usize> {
+        self.tokens().position(|t| &t == token)
+    }
+
+    /// Returns an iterator over the reserve tokens, in coin index order.
+    pub fn tokens(&self) -> impl Iterator<Item = eth::TokenAddress> + '_ {
+        self.iter().map(|r| r.asset.token)
+    }
+
+    /// Returns an iterator over the reserves, in coin index order.
+    pub fn iter(&self) -> impl Iterator<Item = Reserve> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid Curve token reserves; fewer than two or duplicate coins")]
+pub struct InvalidReserves;
+
+/// Curve pool reserve for a single coin.
+#[derive(Clone, Copy, Debug)]
+pub struct Reserve {
+    pub asset: eth::Asset,
+    /// The number of decimals of the coin, used for normalizing balances to
+    /// 18 decimals in the StableSwap invariant.
+    pub decimals: u8,
+}
+
+/// The Curve amplification coefficient `A`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AmplificationParameter(pub eth::U256);
+
+/// A Curve swap fee.
+///
+/// Internally, this is represented in units of `1e-10`, matching the pool
+/// contracts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Fee(eth::U256);
+
+impl Fee {
+    /// The fee denominator used by Curve pools.
+    pub const DENOMINATOR: u64 = 10_000_000_000;
+
+    /// Creates a new fee from its raw pool contract representation. Returns
+    /// `Err` for fees of 100% or more.
+    pub fn from_raw(fee: eth::U256) -> Result<Self, InvalidFee> {
+        if fee >= Self::DENOMINATOR.into() {
+            return Err(InvalidFee);
+        }
+        Ok(Self(fee))
+    }
+
+    /// Returns the raw fee value in units of `1e-10`.
+    pub fn as_raw(&self) -> eth::U256 {
+        self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid Curve fee of 100% or more")]
+pub struct InvalidFee;
@@ -4,6 +4,7 @@
 use {crate::domain::eth, std::cmp::Ordering};
 
 pub mod balancer;
+pub mod curve;
 pub mod swapr;
 pub mod uniswap;
 pub mod zeroex;
@@ -57,10 +58,27 @@ pub enum Kind {
     UniswapV3(uniswap::v3::Pool),
     BalancerV2Stable(balancer::v2::stable::Pool),
     BalancerV2Weighted(balancer::v2::weighted::Pool),
+    Curve(curve::Pool),
     Swapr(swapr::Pool),
     ZeroEx(zeroex::LimitOrder),
 }
 
+impl Kind {
+    /// The address of the pool providing this liquidity. Two settlements using
+    /// the same pool can influence each other's execution.
+    pub fn address(&self) -> Option<eth::Address> {
+        match self {
+            Kind::UniswapV2(pool) => Some(pool.address),
+            Kind::UniswapV3(pool) => Some(pool.address.into()),
+            Kind::BalancerV2Stable(pool) => Some(pool.id.address().into()),
+            Kind::BalancerV2Weighted(pool) => Some(pool.id.address().into()),
+            Kind::Curve(pool) => Some(pool.address.into()),
+            Kind::Swapr(pool) => Some(pool.base.address),
+            Kind::ZeroEx(_) => None,
+        }
+    }
+}
+
 impl From<&Kind> for &'static str {
     fn from(val: &Kind) -> &'static str {
         match *val {
@@ -68,6 +86,7 @@ impl From<&Kind> for &'static str {
             Kind::UniswapV3(_) => "UniswapV3",
             Kind::BalancerV2Stable(_) => "BalancerV2Stable",
             Kind::BalancerV2Weighted(_) => "BalancerV2Weighted",
+            Kind::Curve(_) => "Curve",
             Kind::Swapr(_) => "Swapr",
             Kind::ZeroEx(_) => "ZeroExLimitOrder",
         }
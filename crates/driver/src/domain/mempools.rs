@@ -33,11 +33,14 @@ impl Mempools {
         }
     }
 
-    /// Publish a settlement to the mempools.
+    /// Publish a settlement to the mempools. If a submission deadline is
+    /// specified, settlements which aren't included by that block get
+    /// cancelled.
     pub async fn execute(
         &self,
         solver: &Solver,
         settlement: &Settlement,
+        submission_deadline: Option<u64>,
     ) -> Result<eth::TxId, Error> {
         let (tx_hash, _remaining_futures) =
             select_ok(self.mempools.iter().cloned().map(|mempool| {
@@ -47,7 +50,7 @@ impl Mempools {
                             mempool.execute(solver, settlement.clone()).await
                         }
                         infra::Mempool::Native(inner) => {
-                            self.submit(inner, solver, settlement)
+                            self.submit(inner, solver, settlement, submission_deadline)
                                 .instrument(tracing::info_span!(
                                     "mempool",
                                     kind = inner.to_string()
@@ -85,6 +88,7 @@ impl Mempools {
         mempool: &infra::mempool::Inner,
         solver: &Solver,
         settlement: &Settlement,
+        submission_deadline: Option<u64>,
     ) -> Result<eth::TxId, Error> {
         // Don't submit risky transactions if revert protection is
         // enabled and the settlement may revert in this mempool.
@@ -112,17 +116,16 @@ impl Mempools {
         let mut block_stream = into_stream(self.ethereum.current_block().clone());
         block_stream.next().await;
 
-        let hash = mempool.submit(tx.clone(), settlement.gas, solver).await?;
+        let submission = mempool.submit(tx.clone(), settlement.gas, solver).await?;
+        let hash = submission.hash().clone();
         loop {
             // Wait for the next block to be mined or we time out.
-            if tokio::time::timeout_at(deadline, block_stream.next())
-                .await
-                .is_err()
-            {
+            let Ok(block) = tokio::time::timeout_at(deadline, block_stream.next()).await else {
                 tracing::info!(?hash, "tx not confirmed in time, cancelling");
-                self.cancel(mempool, settlement.gas.price, solver).await?;
+                self.cancel(mempool, &submission, settlement.gas.price, solver)
+                    .await?;
                 return Err(Error::Expired);
-            }
+            };
             tracing::debug!(?hash, "checking if tx is confirmed");
 
             let receipt = self
@@ -137,6 +140,16 @@ impl Mempools {
                 TxStatus::Executed => return Ok(hash),
                 TxStatus::Reverted => return Err(Error::Revert(hash)),
                 TxStatus::Pending => {
+                    // The transaction can't be included by the deadline anymore.
+                    if let (Some(block), Some(submission_deadline)) = (&block, submission_deadline)
+                    {
+                        if block.number >= submission_deadline {
+                            tracing::info!(?hash, "submission deadline reached, cancelling");
+                            self.cancel(mempool, &submission, settlement.gas.price, solver)
+                                .await?;
+                            return Err(Error::Expired);
+                        }
+                    }
                     // Check if transaction still simulates
                     if let Err(err) = self.ethereum.estimate_gas(tx.clone()).await {
                         if err.is_revert() {
@@ -145,25 +158,35 @@ impl Mempools {
                                 ?err,
                                 "tx started failing in mempool, cancelling"
                             );
-                            self.cancel(mempool, settlement.gas.price, solver).await?;
+                            self.cancel(mempool, &submission, settlement.gas.price, solver)
+                                .await?;
                             return Err(Error::SimulationRevert);
                         } else {
                             tracing::warn!(?hash, ?err, "couldn't re-simulate tx");
                         }
                     }
+                    if let Err(err) = mempool.resubmit(&submission).await {
+                        tracing::warn!(?hash, ?err, "failed to resubmit tx");
+                    }
                 }
             }
         }
     }
 
     /// Cancel a pending settlement by sending a transaction to self with a
-    /// slightly higher gas price than the existing one.
+    /// slightly higher gas price than the existing one. Bundles are private,
+    /// so they are cancelled with the relays instead.
     async fn cancel(
         &self,
         mempool: &infra::mempool::Inner,
+        submission: &infra::mempool::Submission,
         pending: eth::GasPrice,
         solver: &Solver,
     ) -> Result<(), Error> {
+        if mempool.cancel_bundle(submission).await? {
+            return Ok(());
+        }
+
         let cancellation = eth::Tx {
             from: solver.address(),
             to: solver.address(),
@@ -4,12 +4,15 @@ use {
             competition::{Auction, Solution},
             eth,
             liquidity::{
-                self, balancer,
+                self,
+                balancer,
+                curve,
                 uniswap::{
                     self,
                     v3::{Liquidity as V3Liquidity, LiquidityNet, SqrtPrice, Tick},
                 },
-                zeroex, Kind,
+                zeroex,
+                Kind,
             },
             Liquidity,
         },
@@ -184,6 +187,38 @@ impl GasRequest {
                         fee: decimal_to_v3_fee(dto_pool.fee)?,
                     }),
                 }),
+                DTOLiquidity::Curve(dto_pool) => {
+                    let reserves = curve::Reserves::new(
+                        dto_pool
+                            .tokens
+                            .into_iter()
+                            .map(|(t, r)| curve::Reserve {
+                                asset: eth::Asset {
+                                    token: t.into(),
+                                    amount: r.balance.into(),
+                                },
+                                decimals: r.decimals,
+                            })
+                            .collect(),
+                    )
+                    .map_err(|err| {
+                        observe::invalid_dto(&err, "curve reserves");
+                        Error::LiquidityError
+                    })?;
+                    Ok(Liquidity {
+                        id: dto_pool.id.into(),
+                        gas: dto_pool.gas_estimate.into(),
+                        kind: Kind::Curve(curve::Pool {
+                            address: dto_pool.address.into(),
+                            reserves,
+                            amplification_parameter: curve::AmplificationParameter(
+                                decimal_to_u256(dto_pool.amplification_parameter, 0)?,
+                            ),
+                            fee: curve::Fee::from_raw(decimal_to_u256(dto_pool.fee, 10)?)
+                                .map_err(|_| Error::LiquidityError)?,
+                        }),
+                    })
+                }
                 DTOLiquidity::LimitOrder(dto_pool) => Ok(Liquidity {
                     id: dto_pool.id.into(),
                     gas: dto_pool.gas_estimate.into(),
@@ -246,6 +281,11 @@ fn decimal_to_amp_param(
     Ok(amp_param)
 }
 
+fn decimal_to_u256(value: bigdecimal::BigDecimal, scale: i64) -> Result<eth::U256, Error> {
+    let (value, _) = value.with_scale(scale).into_bigint_and_exponent();
+    U256Ext::from_big_int(&value).map_err(|_| Error::LiquidityError)
+}
+
 fn decimal_to_v3_fee(fee: bigdecimal::BigDecimal) -> Result<uniswap::v3::Fee, Error> {
     let (value, _) = fee.with_scale(6).into_bigint_and_exponent();
     let fee_u256: eth::U256 = U256Ext::from_big_int(&value).map_err(|_| Error::LiquidityError)?;
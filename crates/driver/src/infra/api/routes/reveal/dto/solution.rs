@@ -1,11 +1,24 @@
-use {serde::Deserialize, serde_with::serde_as};
+use {crate::domain::competition::auction, serde::Deserialize, serde_with::serde_as};
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Solution {
-    #[allow(dead_code)]
     /// Unique ID of the solution (per driver competition), to reveal.
     #[serde_as(as = "serde_with::DisplayFromStr")]
     solution_id: u64,
+    /// The auction the solution was computed for. If not specified, the
+    /// solution of the most recent auction is used.
+    #[serde(default)]
+    auction_id: Option<i64>,
+}
+
+impl Solution {
+    pub fn solution_id(&self) -> u64 {
+        self.solution_id
+    }
+
+    pub fn auction_id(&self) -> Option<auction::Id> {
+        self.auction_id.map(auction::Id)
+    }
 }
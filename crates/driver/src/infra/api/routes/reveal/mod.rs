@@ -14,13 +14,18 @@ pub(in crate::infra::api) fn reveal(router: axum::Router<State>) -> axum::Router
 
 async fn route(
     state: axum::extract::State<State>,
-    _: axum::Json<dto::Solution>,
+    solution: axum::Json<dto::Solution>,
 ) -> Result<axum::Json<dto::Revealed>, (hyper::StatusCode, axum::Json<Error>)> {
     let competition = state.competition();
-    let auction_id = competition.auction_id().map(|id| id.0);
+    let auction_id = solution
+        .auction_id()
+        .or_else(|| competition.auction_id())
+        .map(|id| id.0);
     let handle_request = async {
         observe::revealing();
-        let result = competition.reveal().await;
+        let result = competition
+            .reveal(solution.auction_id(), solution.solution_id())
+            .await;
         observe::revealed(state.solver().name(), &result);
         let result = result?;
         Ok(axum::Json(dto::Revealed::new(result)))
@@ -1,11 +1,33 @@
-use {serde::Deserialize, serde_with::serde_as};
+use {crate::domain::competition::auction, serde::Deserialize, serde_with::serde_as};
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Solution {
-    #[allow(dead_code)]
     /// Unique ID of the solution (per driver competition), to settle.
     #[serde_as(as = "serde_with::DisplayFromStr")]
     solution_id: u64,
+    /// The auction the solution was computed for. If not specified, the
+    /// solution of the most recent auction is used.
+    #[serde(default)]
+    auction_id: Option<i64>,
+    /// The last block in which the settlement may be included. Several
+    /// winners of an auction settle in parallel, so each of them has to
+    /// respect the deadline of the auction on its own.
+    #[serde(default)]
+    submission_deadline_latest_block: Option<u64>,
+}
+
+impl Solution {
+    pub fn solution_id(&self) -> u64 {
+        self.solution_id
+    }
+
+    pub fn auction_id(&self) -> Option<auction::Id> {
+        self.auction_id.map(auction::Id)
+    }
+
+    pub fn submission_deadline_latest_block(&self) -> Option<u64> {
+        self.submission_deadline_latest_block
+    }
 }
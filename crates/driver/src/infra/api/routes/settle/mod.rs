@@ -14,13 +14,22 @@ pub(in crate::infra::api) fn settle(router: axum::Router<State>) -> axum::Router
 
 async fn route(
     state: axum::extract::State<State>,
-    _: axum::Json<dto::Solution>,
+    solution: axum::Json<dto::Solution>,
 ) -> Result<axum::Json<dto::Settled>, (hyper::StatusCode, axum::Json<Error>)> {
     let competition = state.competition();
-    let auction_id = competition.auction_id().map(|id| id.0);
+    let auction_id = solution
+        .auction_id()
+        .or_else(|| competition.auction_id())
+        .map(|id| id.0);
     let handle_request = async {
         observe::settling();
-        let result = competition.settle().await;
+        let result = competition
+            .settle(
+                solution.auction_id(),
+                solution.solution_id(),
+                solution.submission_deadline_latest_block(),
+            )
+            .await;
         observe::settled(state.solver().name(), &result);
         let calldata = result?;
         Ok(axum::Json(dto::Settled::new(calldata)))
@@ -13,7 +13,7 @@ impl Solved {
     pub fn new(solved: Option<competition::Solved>, solver: &Solver) -> Self {
         let solutions = solved
             .into_iter()
-            .map(|solved| Solution::new(0, solved, solver))
+            .map(|solved| Solution::new(competition::SOLUTION_ID, solved, solver))
             .collect();
         Self { solutions }
     }
@@ -50,6 +50,12 @@ impl Solution {
                 .into_iter()
                 .map(|(k, v)| (k.into(), v.into()))
                 .collect(),
+            liquidity: solved
+                .liquidity
+                .into_iter()
+                .map(|address| address.0)
+                .collect(),
+            custom_interactions: solved.custom_interactions,
         }
     }
 }
@@ -83,4 +89,9 @@ pub struct Solution {
     orders: HashMap<OrderId, TradedAmounts>,
     #[serde_as(as = "HashMap<_, serialize::U256>")]
     clearing_prices: HashMap<eth::H160, eth::U256>,
+    /// Addresses of the liquidity pools used by the solution.
+    liquidity: Vec<eth::H160>,
+    /// Whether the solution contains custom interactions whose liquidity is
+    /// not part of `liquidity`.
+    custom_interactions: bool,
 }
@@ -24,9 +24,13 @@ pub struct Rpc {
 
 impl Rpc {
     /// Instantiate an RPC client to an Ethereum (or Ethereum-compatible) node
-    /// at the specifed URL.
-    pub async fn new(url: &url::Url) -> Result<Self, Error> {
-        let web3 = boundary::buffered_web3_client(url);
+    /// at the specifed URL that fails over to the fallback nodes.
+    pub async fn new(
+        url: &url::Url,
+        fallbacks: &[url::Url],
+        multi: &ethrpc::multi::Configuration,
+    ) -> Result<Self, Error> {
+        let web3 = boundary::buffered_web3_client(url, fallbacks, multi);
         let chain = web3.eth().chain_id().await?.into();
 
         Ok(Self { web3, chain })
@@ -119,6 +123,12 @@ impl Ethereum {
         Ok(!code.0.is_empty())
     }
 
+    /// Returns the underlying web3 client, for components that need direct
+    /// access to the node state.
+    pub(in crate::infra) fn web3(&self) -> &DynWeb3 {
+        &self.web3
+    }
+
     /// Returns a type that monitors the block chain to inform about the current
     /// block.
     pub fn current_block(&self) -> &CurrentBlockStream {
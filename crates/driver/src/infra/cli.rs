@@ -1,6 +1,6 @@
 use {
     reqwest::Url,
-    std::{net::SocketAddr, path::PathBuf},
+    std::{net::SocketAddr, path::PathBuf, time::Duration},
 };
 
 #[derive(Debug, clap::Parser)]
@@ -21,9 +21,47 @@ pub struct Args {
     #[clap(long, env)]
     pub ethrpc: Url,
 
+    /// Additional node RPC API endpoints to fail over to when the main node is
+    /// unhealthy or can't be reached.
+    #[clap(long, env, use_value_delimiter = true)]
+    pub ethrpc_fallbacks: Vec<Url>,
+
+    /// How many blocks a node can lag behind the most advanced node before it
+    /// is considered unhealthy. Only used when fallback nodes are configured.
+    #[clap(long, env, default_value = "3")]
+    pub ethrpc_max_block_lag: u64,
+
+    /// Share of failed recent requests above which a node is considered
+    /// unhealthy. Only used when fallback nodes are configured.
+    #[clap(long, env, default_value = "0.5")]
+    pub ethrpc_max_error_rate: f64,
+
+    /// How often the health of the nodes gets checked. Only used when fallback
+    /// nodes are configured.
+    #[clap(long, env, value_parser = humantime::parse_duration, default_value = "5s")]
+    pub ethrpc_health_check_interval: Duration,
+
+    /// How long to wait for a response to `eth_call` and `eth_estimateGas`
+    /// requests before also sending them to the next node. Hedging is disabled
+    /// if not specified. Only used when fallback nodes are configured.
+    #[clap(long, env, value_parser = humantime::parse_duration)]
+    pub ethrpc_hedge_delay: Option<Duration>,
+
     /// Path to the driver configuration file. This file should be in TOML
     /// format. For an example see
     /// https://github.com/cowprotocol/services/blob/main/crates/driver/example.toml.
     #[clap(long, env)]
     pub config: PathBuf,
 }
+
+impl Args {
+    /// Configuration of the transport that fails over to the fallback nodes.
+    pub fn multi_node(&self) -> ethrpc::multi::Configuration {
+        ethrpc::multi::Configuration {
+            max_block_lag: self.ethrpc_max_block_lag,
+            max_error_rate: self.ethrpc_max_error_rate,
+            health_check_interval: self.ethrpc_health_check_interval,
+            hedge_delay: self.ethrpc_hedge_delay,
+        }
+    }
+}
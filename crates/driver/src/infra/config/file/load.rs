@@ -167,8 +167,11 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                     file::UniswapV3Config::Preset {
                         preset,
                         max_pools_to_initialize,
+                        factory_events,
                     } => liquidity::config::UniswapV3 {
                         max_pools_to_initialize,
+                        factory_events: factory_events
+                            .map(|config| uniswap_v3_factory_events(config, chain)),
                         ..match preset {
                             file::UniswapV3Preset::UniswapV3 => {
                                 liquidity::config::UniswapV3::uniswap_v3(&graph_api_base_url, chain)
@@ -179,10 +182,13 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                     file::UniswapV3Config::Manual {
                         router,
                         max_pools_to_initialize,
+                        factory_events,
                     } => liquidity::config::UniswapV3 {
                         router: router.into(),
                         max_pools_to_initialize,
                         graph_api_base_url: graph_api_base_url.clone(),
+                        factory_events: factory_events
+                            .map(|config| uniswap_v3_factory_events(config, chain)),
                     },
                 })
                 .collect(),
@@ -195,8 +201,10 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                     file::BalancerV2Config::Preset {
                         preset,
                         pool_deny_list,
+                        factory_events,
                     } => liquidity::config::BalancerV2 {
                         pool_deny_list: pool_deny_list.clone(),
+                        factory_events: factory_events.map(balancer_v2_factory_events),
                         ..match preset {
                             file::BalancerV2Preset::BalancerV2 => {
                                 liquidity::config::BalancerV2::balancer_v2(
@@ -215,6 +223,7 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                         liquidity_bootstrapping,
                         composable_stable,
                         pool_deny_list,
+                        factory_events,
                     } => liquidity::config::BalancerV2 {
                         vault: vault.into(),
                         weighted: weighted
@@ -236,9 +245,18 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                             .collect(),
                         pool_deny_list: pool_deny_list.clone(),
                         graph_api_base_url: graph_api_base_url.clone(),
+                        factory_events: factory_events.map(balancer_v2_factory_events),
                     },
                 })
                 .collect(),
+            curve: config
+                .liquidity
+                .curve
+                .iter()
+                .map(|config| liquidity::config::Curve {
+                    pools: config.pools.iter().copied().map(Into::into).collect(),
+                })
+                .collect(),
             zeroex: config
                 .liquidity
                 .zeroex
@@ -264,12 +282,12 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                         // disabled, otherwise driver would not even try to settle revertable
                         // settlements
                         mempool::Kind::Public(
-                            if config
-                                .submission
-                                .mempools
-                                .iter()
-                                .any(|pool| matches!(pool, file::Mempool::MevBlocker { .. }))
-                            {
+                            if config.submission.mempools.iter().any(|pool| {
+                                matches!(
+                                    pool,
+                                    file::Mempool::MevBlocker { .. } | file::Mempool::Bundle { .. }
+                                )
+                            }) {
                                 mempool::RevertProtection::Enabled
                             } else {
                                 mempool::RevertProtection::Disabled
@@ -287,6 +305,13 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                         additional_tip_percentage: *additional_tip_percentage,
                         use_soft_cancellations: *use_soft_cancellations,
                     },
+                    file::Mempool::Bundle {
+                        relays,
+                        signing_key,
+                    } => mempool::Kind::Bundle {
+                        relays: relays.to_owned(),
+                        signer: *signing_key,
+                    },
                 },
                 submission: match config.submission.logic {
                     file::Logic::Boundary => mempool::SubmissionLogic::Boundary,
@@ -294,8 +319,8 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                 },
             })
             .collect(),
-        simulator: match (config.tenderly, config.enso) {
-            (Some(config), None) => {
+        simulator: match (config.tenderly, config.enso, config.local_evm) {
+            (Some(config), None, None) => {
                 Some(simulator::Config::Tenderly(simulator::tenderly::Config {
                     url: config.url,
                     api_key: config.api_key,
@@ -305,12 +330,17 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
                     save_if_fails: config.save_if_fails,
                 }))
             }
-            (None, Some(config)) => Some(simulator::Config::Enso(simulator::enso::Config {
+            (None, Some(config), None) => Some(simulator::Config::Enso(simulator::enso::Config {
                 url: config.url,
                 network_block_interval: config.network_block_interval,
             })),
-            (None, None) => None,
-            (Some(_), Some(_)) => panic!("Cannot configure both Tenderly and Enso"),
+            (None, None, Some(config)) => {
+                Some(simulator::Config::Local(simulator::local::Config {
+                    network_block_interval: config.network_block_interval,
+                }))
+            }
+            (None, None, None) => None,
+            _ => panic!("Cannot configure more than one of Tenderly, Enso and the local EVM"),
         },
         contracts: blockchain::contracts::Addresses {
             settlement: config.contracts.gp_v2_settlement.map(Into::into),
@@ -320,3 +350,28 @@ pub async fn load(chain: eth::ChainId, path: &Path) -> infra::Config {
         disable_gas_simulation: config.disable_gas_simulation.map(Into::into),
     }
 }
+
+fn uniswap_v3_factory_events(
+    config: file::UniswapV3FactoryEventsConfig,
+    chain: eth::ChainId,
+) -> liquidity::config::UniswapV3FactoryEvents {
+    liquidity::config::UniswapV3FactoryEvents {
+        factory: config.factory.map(Into::into).unwrap_or_else(|| {
+            blockchain::contracts::deployment_address(
+                contracts::IUniswapV3Factory::raw_contract(),
+                chain,
+            )
+            .expect("no Uniswap V3 factory for current network")
+        }),
+        start_block: config.start_block,
+        checkpoint_path: config.checkpoint_path,
+    }
+}
+
+fn balancer_v2_factory_events(
+    config: file::BalancerV2FactoryEventsConfig,
+) -> liquidity::config::BalancerV2FactoryEvents {
+    liquidity::config::BalancerV2FactoryEvents {
+        start_block: config.start_block,
+    }
+}
@@ -4,8 +4,8 @@ use {
     reqwest::Url,
     serde::Deserialize,
     serde_with::serde_as,
-    solver::solver::Arn,
-    std::{collections::HashMap, time::Duration},
+    shared::arguments::Arn,
+    std::{collections::HashMap, path::PathBuf, time::Duration},
 };
 
 mod load;
@@ -45,6 +45,9 @@ struct Config {
     /// Use Enso for transaction simulation.
     enso: Option<EnsoConfig>,
 
+    /// Use an in-process EVM for transaction simulation.
+    local_evm: Option<LocalEvmConfig>,
+
     #[serde(rename = "solver")]
     solvers: Vec<SolverConfig>,
 
@@ -84,7 +87,8 @@ struct SubmissionConfig {
     max_confirm_time: Duration,
 
     /// The mempools to submit settlement transactions to. Can be the public
-    /// mempool of a node or the private MEVBlocker mempool.
+    /// mempool of a node, the private MEVBlocker mempool or block builder
+    /// relays accepting bundles.
     #[serde(rename = "mempool", default)]
     mempools: Vec<Mempool>,
 
@@ -120,6 +124,31 @@ enum Mempool {
         #[serde(default = "default_soft_cancellations_flag")]
         use_soft_cancellations: bool,
     },
+    #[serde(rename_all = "kebab-case")]
+    Bundle {
+        /// The block builder relays to send `eth_sendBundle` requests to.
+        relays: Vec<Url>,
+        /// The private key used for signing relay requests, required by some
+        /// relays such as Flashbots. This key only identifies the sender of
+        /// the bundles and doesn't need to hold any funds.
+        #[serde(default, deserialize_with = "deserialize_signing_key")]
+        signing_key: Option<secp256k1::SecretKey>,
+    },
+}
+
+/// Parses an optional private key, reporting invalid keys as configuration
+/// errors.
+fn deserialize_signing_key<'de, D>(
+    deserializer: D,
+) -> Result<Option<secp256k1::SecretKey>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<eth::H256>::deserialize(deserializer)?
+        .map(|key| {
+            secp256k1::SecretKey::from_slice(key.as_bytes()).map_err(serde::de::Error::custom)
+        })
+        .transpose()
 }
 
 fn default_additional_tip_percentage() -> f64 {
@@ -284,6 +313,15 @@ struct EnsoConfig {
     network_block_interval: Option<Duration>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct LocalEvmConfig {
+    /// How often the network produces a new block. If this is set,
+    /// transactions are simulated with the timestamp of the pending block.
+    #[serde(default, with = "humantime_serde")]
+    network_block_interval: Option<Duration>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 struct LiquidityConfig {
@@ -308,6 +346,10 @@ struct LiquidityConfig {
     #[serde(default)]
     balancer_v2: Vec<BalancerV2Config>,
 
+    /// Liquidity provided by Curve StableSwap pools.
+    #[serde(default)]
+    curve: Vec<CurveConfig>,
+
     /// Liquidity provided by 0x API.
     #[serde(default)]
     zeroex: Option<ZeroExConfig>,
@@ -387,6 +429,10 @@ enum UniswapV3Config {
         /// How many pools to initialize during start up.
         #[serde(default = "uniswap_v3::default_max_pools_to_initialize")]
         max_pools_to_initialize: usize,
+
+        /// Discover pools from factory events instead of the subgraph.
+        #[serde(default)]
+        factory_events: Option<UniswapV3FactoryEventsConfig>,
     },
 
     #[serde(rename_all = "kebab-case")]
@@ -397,9 +443,29 @@ enum UniswapV3Config {
         /// How many pools to initialize during start up.
         #[serde(default = "uniswap_v3::default_max_pools_to_initialize")]
         max_pools_to_initialize: usize,
+
+        /// Discover pools from factory events instead of the subgraph.
+        #[serde(default)]
+        factory_events: Option<UniswapV3FactoryEventsConfig>,
     },
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct UniswapV3FactoryEventsConfig {
+    /// Address of the Uniswap V3 compatible factory contract. Defaults to the
+    /// Uniswap V3 deployment of the current network.
+    factory: Option<eth::H160>,
+
+    /// The block to start indexing from when there is no checkpoint yet.
+    /// Defaults to the factory's deployment block if known.
+    start_block: Option<u64>,
+
+    /// The file the indexed pool state gets persisted to, so restarts don't
+    /// have to index all events again.
+    checkpoint_path: Option<PathBuf>,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 enum UniswapV3Preset {
@@ -422,6 +488,10 @@ enum BalancerV2Config {
         /// Deny listed Balancer V2 pools.
         #[serde(default)]
         pool_deny_list: Vec<eth::H256>,
+
+        /// Bootstrap pools from on-chain events instead of the subgraph.
+        #[serde(default)]
+        factory_events: Option<BalancerV2FactoryEventsConfig>,
     },
 
     #[serde(rename_all = "kebab-case")]
@@ -455,9 +525,21 @@ enum BalancerV2Config {
         /// Deny listed Balancer V2 pools.
         #[serde(default)]
         pool_deny_list: Vec<eth::H256>,
+
+        /// Bootstrap pools from on-chain events instead of the subgraph.
+        #[serde(default)]
+        factory_events: Option<BalancerV2FactoryEventsConfig>,
     },
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct BalancerV2FactoryEventsConfig {
+    /// The block to start scanning events from. Defaults to the Vault's
+    /// deployment block if known, and the genesis block otherwise.
+    start_block: Option<u64>,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 enum BalancerV2Preset {
@@ -474,6 +556,15 @@ enum Logic {
     Native,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct CurveConfig {
+    /// The addresses of the Curve StableSwap pools to fetch liquidity for.
+    /// CryptoSwap pools are not supported and make the driver fail on
+    /// startup.
+    pools: Vec<eth::H160>,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 struct ZeroExConfig {
@@ -3,7 +3,7 @@ use {
     derivative::Derivative,
     hex_literal::hex,
     reqwest::Url,
-    std::{collections::HashSet, time::Duration},
+    std::{collections::HashSet, path::PathBuf, time::Duration},
 };
 
 /// Configuration options for liquidity fetching.
@@ -29,6 +29,9 @@ pub struct Config {
     /// for.
     pub balancer_v2: Vec<BalancerV2>,
 
+    /// The collection of Curve StableSwap pools to fetch liquidity for.
+    pub curve: Vec<Curve>,
+
     /// 0x liquidity fetcher.
     pub zeroex: Option<ZeroEx>,
 }
@@ -147,6 +150,24 @@ pub struct UniswapV3 {
 
     /// The base URL used to connect to subgraph clients.
     pub graph_api_base_url: Url,
+
+    /// When set, pools are discovered from the factory's events instead of
+    /// the subgraph.
+    pub factory_events: Option<UniswapV3FactoryEvents>,
+}
+
+/// Options for discovering Uniswap V3 pools from factory events.
+#[derive(Clone, Debug)]
+pub struct UniswapV3FactoryEvents {
+    /// The address of the Uniswap V3 compatible factory contract.
+    pub factory: eth::ContractAddress,
+
+    /// The block to start indexing from when there is no checkpoint yet.
+    /// Defaults to the factory's deployment block if known.
+    pub start_block: Option<u64>,
+
+    /// The file the indexed pool state gets persisted to.
+    pub checkpoint_path: Option<PathBuf>,
 }
 
 impl UniswapV3 {
@@ -157,6 +178,7 @@ impl UniswapV3 {
             router: deployment_address(contracts::UniswapV3SwapRouter::raw_contract(), chain)?,
             max_pools_to_initialize: 100,
             graph_api_base_url: graph_api_base_url.clone(),
+            factory_events: None,
         })
     }
 }
@@ -191,6 +213,18 @@ pub struct BalancerV2 {
 
     /// The base URL used to connect to subgraph clients.
     pub graph_api_base_url: Url,
+
+    /// When set, pools are bootstrapped from the Vault's and factories' events
+    /// instead of the subgraph.
+    pub factory_events: Option<BalancerV2FactoryEvents>,
+}
+
+/// Options for bootstrapping Balancer V2 pools from on-chain events.
+#[derive(Clone, Debug)]
+pub struct BalancerV2FactoryEvents {
+    /// The block to start scanning events from. Defaults to the Vault's
+    /// deployment block if known.
+    pub start_block: Option<u64>,
 }
 
 impl BalancerV2 {
@@ -229,10 +263,20 @@ impl BalancerV2 {
             ]),
             pool_deny_list: Vec::new(),
             graph_api_base_url: graph_api_base_url.clone(),
+            factory_events: None,
         })
     }
 }
 
+/// Curve liquidity fetching options.
+#[derive(Clone, Debug)]
+pub struct Curve {
+    /// The addresses of the Curve StableSwap pools. Only plain pools whose
+    /// coins are all ERC20 tokens are supported. CryptoSwap pools are not
+    /// supported and are rejected.
+    pub pools: Vec<eth::ContractAddress>,
+}
+
 /// ZeroEx liquidity fetching options.
 #[derive(Clone, Derivative)]
 #[derivative(Debug)]
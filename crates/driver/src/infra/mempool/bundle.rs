@@ -0,0 +1,180 @@
+//! Submission of single transaction bundles to block builder relays.
+//!
+//! https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint
+
+use {
+    crate::domain::eth,
+    futures::future::join_all,
+    serde::Deserialize,
+    serde_json::json,
+    std::time::Duration,
+    web3::signing::{self, Key, SecretKeyRef},
+};
+
+/// How long to wait for a relay to respond. Bundles target a specific block,
+/// so a relay that doesn't respond within a fraction of the block time is not
+/// worth waiting for.
+const RELAY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A signed transaction that is submitted to relays as a single transaction
+/// bundle.
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    pub hash: eth::TxId,
+    raw: Vec<u8>,
+    /// Identifies the bundle across resubmissions, allowing it to be
+    /// cancelled with `eth_cancelBundle`.
+    replacement_uuid: String,
+}
+
+impl Bundle {
+    pub fn new(hash: eth::TxId, raw: Vec<u8>) -> Self {
+        // The transaction hash is unique per bundle and stays the same across
+        // resubmissions, so it is used to derive the replacement UUID.
+        let bytes = hex::encode(&hash.0.as_bytes()[..16]);
+        let replacement_uuid = format!(
+            "{}-{}-{}-{}-{}",
+            &bytes[..8],
+            &bytes[8..12],
+            &bytes[12..16],
+            &bytes[16..20],
+            &bytes[20..],
+        );
+        Self {
+            hash,
+            raw,
+            replacement_uuid,
+        }
+    }
+}
+
+/// The block builder relays that bundles are sent to.
+#[derive(Debug, Clone)]
+pub struct Relays {
+    client: reqwest::Client,
+    urls: Vec<reqwest::Url>,
+    signer: Option<secp256k1::SecretKey>,
+}
+
+impl Relays {
+    pub fn new(urls: Vec<reqwest::Url>, signer: Option<secp256k1::SecretKey>) -> Self {
+        Self {
+            client: reqwest::ClientBuilder::new()
+                .timeout(RELAY_TIMEOUT)
+                .build()
+                .unwrap(),
+            urls,
+            signer,
+        }
+    }
+
+    /// Sends the bundle to all relays for inclusion in the specified block.
+    /// Succeeds if at least one relay accepted the bundle.
+    pub async fn send(&self, bundle: &Bundle, block: eth::BlockNo) -> Result<(), Error> {
+        self.broadcast(
+            "eth_sendBundle",
+            json!({
+                "txs": [format!("0x{}", hex::encode(&bundle.raw))],
+                "blockNumber": format!("{:#x}", block.0),
+                "replacementUuid": bundle.replacement_uuid,
+            }),
+        )
+        .await
+    }
+
+    /// Asks all relays to stop considering the bundle for inclusion.
+    pub async fn cancel(&self, bundle: &Bundle) -> Result<(), Error> {
+        self.broadcast(
+            "eth_cancelBundle",
+            json!({ "replacementUuid": bundle.replacement_uuid }),
+        )
+        .await
+    }
+
+    async fn broadcast(&self, method: &str, params: serde_json::Value) -> Result<(), Error> {
+        let results = join_all(
+            self.urls
+                .iter()
+                .map(|url| self.request(url, method, params.clone())),
+        )
+        .await;
+
+        let mut last_err = Error::NoRelays;
+        for (url, result) in self.urls.iter().zip(results) {
+            match result {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    tracing::warn!(%url, ?err, method, "relay request failed");
+                    last_err = err;
+                }
+            }
+        }
+        Err(last_err)
+    }
+
+    async fn request(
+        &self,
+        url: &reqwest::Url,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<(), Error> {
+        let body = serde_json::to_vec(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": [params],
+        }))
+        .unwrap();
+
+        let mut request = self
+            .client
+            .post(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(signer) = &self.signer {
+            request = request.header("X-Flashbots-Signature", signature(signer, &body)?);
+        }
+        let response: Response = request
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        match response.error {
+            Some(err) => Err(Error::Relay(err)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Computes the `X-Flashbots-Signature` header value, which is the signer
+/// address and its EIP-191 signature of the hex encoded request body hash.
+fn signature(signer: &secp256k1::SecretKey, body: &[u8]) -> Result<String, Error> {
+    let key = SecretKeyRef::new(signer);
+    let message = format!("0x{}", hex::encode(signing::keccak256(body)));
+    let signature = key.sign_message(&signing::hash_message(message.as_bytes()).0)?;
+
+    let mut bytes = [0; 65];
+    bytes[..32].copy_from_slice(signature.r.as_bytes());
+    bytes[32..64].copy_from_slice(signature.s.as_bytes());
+    bytes[64] = signature.v as u8;
+    Ok(format!("{:?}:0x{}", key.address(), hex::encode(bytes)))
+}
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("no relays configured")]
+    NoRelays,
+    #[error("http error: {0:?}")]
+    Http(#[from] reqwest::Error),
+    #[error("relay error: {0}")]
+    Relay(serde_json::Value),
+    #[error("signing error: {0:?}")]
+    Signing(#[from] signing::SigningError),
+}
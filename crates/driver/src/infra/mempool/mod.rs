@@ -4,9 +4,11 @@ use {
         domain::{competition, eth, mempools},
         infra,
     },
-    ethcontract::dyns::DynWeb3,
+    ethcontract::{dyns::DynWeb3, transaction::Transaction},
 };
 
+mod bundle;
+
 pub use crate::boundary::mempool::{Config, GlobalTxPool, Kind, RevertProtection, SubmissionLogic};
 
 #[derive(Debug, Clone)]
@@ -39,6 +41,28 @@ impl Mempool {
 pub struct Inner {
     transport: DynWeb3,
     config: Config,
+    relays: Option<bundle::Relays>,
+}
+
+/// A transaction that was submitted to a mempool.
+#[derive(Debug, Clone)]
+pub enum Submission {
+    /// The transaction was sent to a node, which keeps propagating it until
+    /// it gets included.
+    Transaction(eth::TxId),
+    /// The transaction was sent as a bundle to block builder relays. Bundles
+    /// only target a single block, so they need to be resubmitted until they
+    /// get included.
+    Bundle(bundle::Bundle),
+}
+
+impl Submission {
+    pub fn hash(&self) -> &eth::TxId {
+        match self {
+            Self::Transaction(hash) => hash,
+            Self::Bundle(bundle) => &bundle.hash,
+        }
+    }
 }
 
 impl std::fmt::Display for Inner {
@@ -49,12 +73,22 @@ impl std::fmt::Display for Inner {
 
 impl Inner {
     pub fn new(config: Config, transport: DynWeb3) -> Self {
-        let transport = match &config.kind {
-            Kind::Public(_) => transport,
+        let (transport, relays) = match &config.kind {
+            Kind::Public(_) => (transport, None),
             // Flashbots Protect RPC fallback doesn't support buffered transport
-            Kind::MEVBlocker { url, .. } => unbuffered_web3_client(url),
+            Kind::MEVBlocker { url, .. } => (unbuffered_web3_client(url), None),
+            // The node is still used for signing transactions, bundles are
+            // sent to the relays directly.
+            Kind::Bundle { relays, signer } => (
+                transport,
+                Some(bundle::Relays::new(relays.clone(), *signer)),
+            ),
         };
-        Self { config, transport }
+        Self {
+            config,
+            transport,
+            relays,
+        }
     }
 
     /// Submits a transaction to the mempool. Returns optimistically as soon as
@@ -64,8 +98,8 @@ impl Inner {
         tx: eth::Tx,
         gas: competition::solution::settlement::Gas,
         solver: &infra::Solver,
-    ) -> Result<eth::TxId, mempools::Error> {
-        ethcontract::transaction::TransactionBuilder::new(self.transport.clone())
+    ) -> Result<Submission, mempools::Error> {
+        let builder = ethcontract::transaction::TransactionBuilder::new(self.transport.clone())
             .from(solver.account().clone())
             .to(tx.to.into())
             .gas_price(ethcontract::GasPrice::Eip1559 {
@@ -75,14 +109,66 @@ impl Inner {
             .data(tx.input.into())
             .value(tx.value.0)
             .gas(gas.limit.0)
-            .access_list(web3::types::AccessList::from(tx.access_list))
-            .resolve(ethcontract::transaction::ResolveCondition::Pending)
-            .send()
+            .access_list(web3::types::AccessList::from(tx.access_list));
+
+        if self.relays.is_none() {
+            return builder
+                .resolve(ethcontract::transaction::ResolveCondition::Pending)
+                .send()
+                .await
+                .map(|result| Submission::Transaction(eth::TxId(result.hash())))
+                .map_err(|err| mempools::Error::Other(anyhow::Error::from(err)));
+        }
+
+        let bundle = match builder
+            .build()
+            .await
+            .map_err(|err| mempools::Error::Other(anyhow::Error::from(err)))?
+        {
+            Transaction::Raw { bytes, hash } => bundle::Bundle::new(eth::TxId(hash), bytes.0),
+            Transaction::Request(_) => {
+                return Err(mempools::Error::Other(anyhow::anyhow!(
+                    "bundle submission requires a solver account that signs transactions locally"
+                )))
+            }
+        };
+        let submission = Submission::Bundle(bundle);
+        self.resubmit(&submission).await?;
+        Ok(submission)
+    }
+
+    /// Resubmits a pending transaction for the next block if the mempool
+    /// requires it. This is a no-op for transactions that were sent to a node.
+    pub async fn resubmit(&self, submission: &Submission) -> Result<(), mempools::Error> {
+        let (Some(relays), Submission::Bundle(bundle)) = (&self.relays, submission) else {
+            return Ok(());
+        };
+        let block = self
+            .transport
+            .eth()
+            .block_number()
+            .await
+            .map_err(|err| mempools::Error::Other(anyhow::Error::from(err)))?;
+        relays
+            .send(bundle, (block.as_u64() + 1).into())
             .await
-            .map(|result| eth::TxId(result.hash()))
             .map_err(|err| mempools::Error::Other(anyhow::Error::from(err)))
     }
 
+    /// Cancels a submitted bundle. Returns `false` if the submission isn't a
+    /// bundle, in which case it needs to be cancelled with a replacement
+    /// transaction.
+    pub async fn cancel_bundle(&self, submission: &Submission) -> Result<bool, mempools::Error> {
+        let (Some(relays), Submission::Bundle(bundle)) = (&self.relays, submission) else {
+            return Ok(false);
+        };
+        relays
+            .cancel(bundle)
+            .await
+            .map_err(|err| mempools::Error::Other(anyhow::Error::from(err)))?;
+        Ok(true)
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
@@ -91,6 +177,9 @@ impl Inner {
         match &self.config.kind {
             Kind::Public(_) => true,
             Kind::MEVBlocker { .. } => false,
+            // Bundles are sent without any reverting transaction hashes, so
+            // relays never include them if they revert.
+            Kind::Bundle { .. } => false,
         }
     }
 }
@@ -96,6 +96,7 @@ pub fn simulation_failed(
         simulator::Error::Revert(error) => notification::Kind::SimulationFailed(
             error.block,
             error.tx.clone(),
+            error.reason(),
             succeeded_at_least_once,
         ),
         simulator::Error::Other(error) => notification::Kind::DriverError(error.to_string()),
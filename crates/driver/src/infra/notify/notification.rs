@@ -10,6 +10,7 @@ type RequiredEther = Ether;
 type TokensUsed = BTreeSet<TokenAddress>;
 type TransactionHash = eth::TxId;
 type Transaction = eth::Tx;
+type RevertReason = Option<String>;
 pub type SimulationSucceededAtLeastOnce = bool;
 
 /// A notification sent to solvers in case of important events in the driver.
@@ -28,9 +29,15 @@ pub enum Kind {
     EmptySolution,
     /// Solution received from solver engine don't have unique id.
     DuplicatedSolutionId,
-    /// Failed simulation during competition. Last parameter is true
-    /// if has simulated at least once.
-    SimulationFailed(eth::BlockNo, Transaction, SimulationSucceededAtLeastOnce),
+    /// Failed simulation during competition. Includes the revert reason if
+    /// the simulator provides one. Last parameter is true if has simulated at
+    /// least once.
+    SimulationFailed(
+        eth::BlockNo,
+        Transaction,
+        RevertReason,
+        SimulationSucceededAtLeastOnce,
+    ),
     /// No valid score could be computed for the solution.
     ScoringFailed(ScoreKind),
     /// Solution aimed to internalize tokens that are not considered safe to
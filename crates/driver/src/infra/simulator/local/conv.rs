@@ -0,0 +1,34 @@
+//! Conversions between the driver's Ethereum primitives and [`revm`] ones.
+
+use {crate::domain::eth, revm::primitives};
+
+pub fn address(value: eth::H160) -> primitives::Address {
+    primitives::Address::from(value.0)
+}
+
+pub fn h160(value: primitives::Address) -> eth::H160 {
+    eth::H160(value.into_array())
+}
+
+pub fn u256(value: eth::U256) -> primitives::U256 {
+    primitives::U256::from_limbs(value.0)
+}
+
+pub fn eth_u256(value: primitives::U256) -> eth::U256 {
+    eth::U256(value.into_limbs())
+}
+
+pub fn access_list(value: eth::AccessList) -> Vec<(primitives::Address, Vec<primitives::U256>)> {
+    web3::types::AccessList::from(value)
+        .into_iter()
+        .map(|item| {
+            (
+                address(item.address),
+                item.storage_keys
+                    .into_iter()
+                    .map(|key| primitives::U256::from_be_bytes(key.0))
+                    .collect(),
+            )
+        })
+        .collect()
+}
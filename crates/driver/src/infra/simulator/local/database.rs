@@ -0,0 +1,175 @@
+//! A [`revm`] database that lazily fetches state from the node.
+
+use {
+    super::conv,
+    ethcontract::dyns::DynWeb3,
+    revm::primitives::{keccak256, AccountInfo, Address, Bytecode, B256, KECCAK_EMPTY, U256},
+    std::{
+        collections::{BTreeMap, BTreeSet, HashMap},
+        sync::{Arc, Mutex, MutexGuard},
+    },
+    tokio::runtime::Handle,
+    web3::types::{BlockId, BlockNumber},
+};
+
+/// State fetched from the node. The cache is shared between simulations and
+/// is only valid for a single block.
+#[derive(Debug, Default)]
+pub struct Cache {
+    block: u64,
+    accounts: HashMap<Address, AccountInfo>,
+    code: HashMap<B256, Bytecode>,
+    storage: HashMap<(Address, U256), U256>,
+    block_hashes: HashMap<u64, B256>,
+}
+
+impl Cache {
+    /// Discards all cached state if it was fetched at a different block.
+    fn at(&mut self, block: u64) {
+        if self.block != block {
+            *self = Self {
+                block,
+                ..Default::default()
+            };
+        }
+    }
+}
+
+/// A database for executing a single transaction on top of the state at a
+/// specific block.
+///
+/// Since [`revm`] executes synchronously, this should only be used from a
+/// blocking task. State that isn't cached yet is fetched by blocking on the
+/// specified runtime.
+pub struct Database {
+    web3: DynWeb3,
+    runtime: Handle,
+    block: u64,
+    cache: Arc<Mutex<Cache>>,
+    accessed: BTreeMap<Address, BTreeSet<U256>>,
+}
+
+impl Database {
+    pub fn new(web3: DynWeb3, runtime: Handle, block: u64, cache: Arc<Mutex<Cache>>) -> Self {
+        Self {
+            web3,
+            runtime,
+            block,
+            cache,
+            accessed: Default::default(),
+        }
+    }
+
+    /// The accounts and storage slots that were read during execution.
+    pub fn accessed(&self) -> &BTreeMap<Address, BTreeSet<U256>> {
+        &self.accessed
+    }
+
+    fn block_number(&self) -> Option<BlockNumber> {
+        Some(BlockNumber::Number(self.block.into()))
+    }
+
+    fn cache(&self) -> MutexGuard<'_, Cache> {
+        let mut cache = self.cache.lock().unwrap();
+        cache.at(self.block);
+        cache
+    }
+}
+
+impl revm::Database for Database {
+    type Error = Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Error> {
+        self.accessed.entry(address).or_default();
+        if let Some(info) = self.cache().accounts.get(&address) {
+            return Ok(Some(info.clone()));
+        }
+
+        let (eth, account, block) = (self.web3.eth(), conv::h160(address), self.block_number());
+        let (balance, nonce, code) = self.runtime.block_on(async {
+            futures::try_join!(
+                eth.balance(account, block),
+                eth.transaction_count(account, block),
+                eth.code(account, block),
+            )
+        })?;
+        let code_hash = if code.0.is_empty() {
+            KECCAK_EMPTY
+        } else {
+            keccak256(&code.0)
+        };
+        let code = Bytecode::new_raw(code.0.into());
+        let info = AccountInfo {
+            balance: conv::u256(balance),
+            nonce: nonce.as_u64(),
+            code_hash,
+            code: Some(code.clone()),
+        };
+
+        let mut cache = self.cache();
+        cache.accounts.insert(address, info.clone());
+        cache.code.insert(code_hash, code);
+        Ok(Some(info))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Error> {
+        if code_hash == KECCAK_EMPTY {
+            return Ok(Bytecode::new());
+        }
+        // The node can't be queried for code by its hash, but account code is
+        // always fetched and cached together with the account info.
+        self.cache()
+            .code
+            .get(&code_hash)
+            .cloned()
+            .ok_or(Error::MissingCode(code_hash))
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Error> {
+        self.accessed.entry(address).or_default().insert(index);
+        if let Some(value) = self.cache().storage.get(&(address, index)) {
+            return Ok(*value);
+        }
+
+        let value = self.runtime.block_on(self.web3.eth().storage(
+            conv::h160(address),
+            conv::eth_u256(index),
+            self.block_number(),
+        ))?;
+        let value = U256::from_be_bytes(value.0);
+
+        self.cache().storage.insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Error> {
+        let number = number.saturating_to::<u64>();
+        if let Some(hash) = self.cache().block_hashes.get(&number) {
+            return Ok(*hash);
+        }
+
+        let hash = self
+            .runtime
+            .block_on(
+                self.web3
+                    .eth()
+                    .block(BlockId::Number(BlockNumber::Number(number.into()))),
+            )?
+            .and_then(|block| block.hash)
+            .ok_or(Error::MissingBlock(number))?;
+        let hash = B256::from(hash.0);
+
+        self.cache().block_hashes.insert(number, hash);
+        Ok(hash)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("node error: {0:?}")]
+    Node(#[from] web3::Error),
+    #[error("missing block {0}")]
+    MissingBlock(u64),
+    #[error("missing code for hash {0}")]
+    MissingCode(B256),
+}
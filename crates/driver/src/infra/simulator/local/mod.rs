@@ -0,0 +1,387 @@
+use {
+    self::{
+        database::Database,
+        trace::{RevertPath, Tracer},
+    },
+    crate::{domain::eth, infra::blockchain::Ethereum},
+    ethabi::ParamType,
+    ethrpc::current_block::BlockInfo,
+    hex_literal::hex,
+    revm::{
+        inspector_handle_register,
+        primitives::{EVMError, ExecutionResult, TransactTo, U256},
+        Evm,
+    },
+    std::{
+        fmt,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+    thiserror::Error,
+};
+
+mod conv;
+mod database;
+mod trace;
+
+pub use trace::Call;
+
+/// The highest precompile address. Precompiles, as well as the zero address
+/// used as the block's coinbase, are always warm.
+const PRECOMPILES: u64 = 9;
+
+/// Executes transactions in-process with [`revm`], on top of state that is
+/// lazily fetched from the node at the current block and cached until the
+/// next block.
+#[derive(Debug, Clone)]
+pub(super) struct Local {
+    eth: Ethereum,
+    cache: Arc<Mutex<database::Cache>>,
+    network_block_interval: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The time between new blocks in the network. If this is set,
+    /// transactions are simulated with the timestamp of the pending block
+    /// instead of the latest one.
+    pub network_block_interval: Option<Duration>,
+}
+
+impl Local {
+    pub(super) fn new(config: Config, eth: Ethereum) -> Self {
+        Self {
+            eth,
+            cache: Default::default(),
+            network_block_interval: config.network_block_interval,
+        }
+    }
+
+    pub(super) async fn simulate(&self, tx: eth::Tx) -> Result<Simulation, Error> {
+        let block = *self.eth.current_block().borrow();
+        let timestamp = match self.network_block_interval {
+            Some(interval) => block.timestamp + interval.as_secs(),
+            None => block.timestamp,
+        };
+        let database = Database::new(
+            self.eth.web3().clone(),
+            tokio::runtime::Handle::current(),
+            block.number,
+            self.cache.clone(),
+        );
+        let chain = self.eth.network();
+
+        tokio::task::spawn_blocking(move || execute(database, chain, block, timestamp, tx))
+            .await
+            .map_err(|err| Error::Panic(err.to_string()))?
+    }
+}
+
+fn execute(
+    database: Database,
+    chain: eth::ChainId,
+    block: BlockInfo,
+    timestamp: u64,
+    tx: eth::Tx,
+) -> Result<Simulation, Error> {
+    let mut evm = Evm::builder()
+        .with_db(database)
+        .with_external_context(Tracer::default())
+        .modify_cfg_env(|cfg| cfg.chain_id = chain.into())
+        .modify_block_env(|env| {
+            env.number = U256::from(block.number);
+            env.timestamp = U256::from(timestamp);
+            env.gas_limit = conv::u256(block.gas_limit);
+            // Like `eth_estimateGas` without a gas price, the simulated
+            // transaction doesn't pay for gas. This way, the result does not
+            // depend on the balance of the submission account.
+            env.basefee = U256::ZERO;
+        })
+        .modify_tx_env(|env| {
+            env.gas_price = U256::ZERO;
+            env.gas_limit = block.gas_limit.low_u64();
+            env.caller = conv::address(tx.from.0);
+            env.transact_to = TransactTo::Call(conv::address(tx.to.0));
+            env.value = conv::u256(tx.value.0);
+            env.data = tx.input.0.clone().into();
+            env.access_list = conv::access_list(tx.access_list.clone());
+        })
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+    let result = evm.transact();
+    let context = evm.into_context();
+    let trace = context.external.into_trace();
+    let result = match result {
+        Ok(result) => result.result,
+        Err(EVMError::Database(err)) => return Err(Error::Database(err)),
+        Err(err) => {
+            // The transaction is invalid for the current state, for example
+            // because the sender doesn't have enough ETH for the value.
+            return Err(Error::Revert(Revert {
+                reason: format!("invalid transaction: {err:?}"),
+                trace,
+            }));
+        }
+    };
+
+    match result {
+        ExecutionResult::Success { gas_used, .. } => Ok(Simulation {
+            gas: gas_used.into(),
+            access_list: access_list(&context.evm.inner.db, &tx),
+        }),
+        ExecutionResult::Revert { output, .. } => Err(Error::Revert(Revert {
+            reason: revert_reason(&output),
+            trace,
+        })),
+        ExecutionResult::Halt { reason, .. } => Err(Error::Revert(Revert {
+            reason: format!("halted: {reason:?}"),
+            trace,
+        })),
+    }
+}
+
+/// Computes the access list for the state that was read while executing a
+/// transaction. Like `eth_createAccessList`, accounts which are always warm
+/// are only included if any of their storage was accessed.
+fn access_list(database: &Database, tx: &eth::Tx) -> eth::AccessList {
+    let warm = |address: eth::H160| {
+        address == tx.from.0
+            || address == tx.to.0
+            || address <= eth::H160::from_low_u64_be(PRECOMPILES)
+    };
+
+    let access_list: web3::types::AccessList = database
+        .accessed()
+        .iter()
+        .map(|(address, slots)| (conv::h160(*address), slots))
+        .filter(|(address, slots)| !slots.is_empty() || !warm(*address))
+        .map(|(address, slots)| web3::types::AccessListItem {
+            address,
+            storage_keys: slots
+                .iter()
+                .map(|slot| eth::H256(slot.to_be_bytes::<32>()))
+                .collect(),
+        })
+        .collect();
+    access_list.into()
+}
+
+/// Decodes the revert reason from the output of a reverted call.
+fn revert_reason(output: &[u8]) -> String {
+    const ERROR: [u8; 4] = hex!("08c379a0");
+    const PANIC: [u8; 4] = hex!("4e487b71");
+
+    if output.is_empty() {
+        return "execution reverted".to_owned();
+    }
+    let (selector, data) = output.split_at(output.len().min(4));
+    let decoded = if selector == ERROR {
+        ethabi::decode(&[ParamType::String], data)
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next()?.into_string())
+            .map(|message| format!("execution reverted: {message}"))
+    } else if selector == PANIC {
+        ethabi::decode(&[ParamType::Uint(256)], data)
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next()?.into_uint())
+            .map(|code| format!("panic: {code:#x}"))
+    } else {
+        None
+    };
+    decoded.unwrap_or_else(|| format!("execution reverted: 0x{}", hex::encode(output)))
+}
+
+#[derive(Debug)]
+pub struct Simulation {
+    pub gas: eth::Gas,
+    pub access_list: eth::AccessList,
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("state fetching error: {0}")]
+    Database(#[from] database::Error),
+    #[error(transparent)]
+    Revert(Revert),
+    #[error("simulation panicked: {0}")]
+    Panic(String),
+}
+
+/// A reverted simulation.
+#[derive(Debug)]
+pub struct Revert {
+    /// The decoded revert reason.
+    pub reason: String,
+    /// The trace of the executed calls.
+    pub trace: Option<Call>,
+}
+
+impl fmt::Display for Revert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.reason)?;
+        if let Some(trace) = &self.trace {
+            write!(f, " at {}", RevertPath(trace))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Revert {}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        ethcontract::transport::DynTransport,
+        ethrpc::mock::MockTransport,
+        serde_json::{json, Value},
+    };
+
+    const CONTRACT: eth::H160 = eth::H160([0x11; 20]);
+    const SENDER: eth::H160 = eth::H160([0x22; 20]);
+
+    /// Returns a mocked node where [`CONTRACT`] has the specified code and
+    /// every storage slot contains `42`.
+    fn node(code: Vec<u8>) -> MockTransport {
+        let transport = MockTransport::new();
+        transport
+            .mock()
+            .expect_execute()
+            .returning(move |method, params| {
+                let address = serde_json::from_value::<eth::H160>(params[0].clone()).unwrap();
+                Ok(match method.as_str() {
+                    "eth_getBalance" | "eth_getTransactionCount" => json!("0x0"),
+                    "eth_getCode" if address == CONTRACT => {
+                        json!(format!("0x{}", hex::encode(&code)))
+                    }
+                    "eth_getCode" => json!("0x"),
+                    "eth_getStorageAt" => json!(format!("0x{:064x}", 42)),
+                    _ => Value::Null,
+                })
+            });
+        transport
+    }
+
+    fn local_database(transport: MockTransport, cache: Arc<Mutex<database::Cache>>) -> Database {
+        Database::new(
+            web3::Web3::new(DynTransport::new(transport)),
+            tokio::runtime::Handle::current(),
+            1,
+            cache,
+        )
+    }
+
+    async fn simulate(code: Vec<u8>) -> Result<Simulation, Error> {
+        let database = local_database(node(code), Default::default());
+        let block = BlockInfo {
+            number: 1,
+            gas_limit: 30_000_000.into(),
+            ..Default::default()
+        };
+        let tx = eth::Tx {
+            from: SENDER.into(),
+            to: CONTRACT.into(),
+            value: eth::U256::zero().into(),
+            input: Default::default(),
+            access_list: Default::default(),
+        };
+        tokio::task::spawn_blocking(move || execute(database, eth::ChainId(1), block, 0, tx))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn simulates_gas_and_access_list() {
+        // PUSH1 1 SLOAD PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        let simulation = simulate(hex!("60015460005260206000f3").to_vec())
+            .await
+            .unwrap();
+
+        assert!(simulation.gas.0 > 21_000.into());
+        assert_eq!(
+            web3::types::AccessList::from(simulation.access_list),
+            vec![web3::types::AccessListItem {
+                address: CONTRACT,
+                storage_keys: vec![eth::H256::from_low_u64_be(1)],
+            }],
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_reverts_with_trace() {
+        // PUSH1 0 PUSH1 0 REVERT
+        let err = simulate(hex!("60006000fd").to_vec()).await.unwrap_err();
+
+        let Error::Revert(revert) = err else {
+            panic!("unexpected error {err:?}");
+        };
+        assert_eq!(revert.reason, "execution reverted");
+        let trace = revert.trace.unwrap();
+        assert_eq!(trace.from.0, SENDER);
+        assert_eq!(trace.to.0, CONTRACT);
+        assert!(!trace.success);
+    }
+
+    #[tokio::test]
+    async fn caches_state_and_code() {
+        let cache = Arc::default();
+        let mut database = local_database(node(hex!("00").to_vec()), Arc::clone(&cache));
+        let address = conv::address(CONTRACT);
+
+        let info = tokio::task::spawn_blocking(move || {
+            let info = revm::Database::basic(&mut database, address)
+                .unwrap()
+                .unwrap();
+            let code = revm::Database::code_by_hash(&mut database, info.code_hash).unwrap();
+            assert_eq!(code.original_bytes().to_vec(), hex!("00"));
+            info
+        })
+        .await
+        .unwrap();
+
+        // State is served from the cache without querying the node again.
+        let mut database = local_database(MockTransport::new(), cache);
+        tokio::task::spawn_blocking(move || {
+            let cached = revm::Database::basic(&mut database, address)
+                .unwrap()
+                .unwrap();
+            assert_eq!(cached.code_hash, info.code_hash);
+            assert!(matches!(
+                revm::Database::code_by_hash(&mut database, Default::default()),
+                Err(database::Error::MissingCode(_))
+            ));
+        })
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn decodes_revert_reasons() {
+        assert_eq!(revert_reason(&[]), "execution reverted");
+        assert_eq!(
+            revert_reason(
+                &[
+                    hex!("08c379a0").as_slice(),
+                    &ethabi::encode(&[ethabi::Token::String("boom".to_owned())]),
+                ]
+                .concat()
+            ),
+            "execution reverted: boom",
+        );
+        assert_eq!(
+            revert_reason(
+                &[
+                    hex!("4e487b71").as_slice(),
+                    &ethabi::encode(&[ethabi::Token::Uint(0x11.into())]),
+                ]
+                .concat()
+            ),
+            "panic: 0x11",
+        );
+        assert_eq!(
+            revert_reason(&hex!("deadbeef")),
+            "execution reverted: 0xdeadbeef"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Call tracing for locally simulated transactions.
+
+use {
+    super::conv,
+    crate::domain::eth,
+    revm::{
+        interpreter::{CallInputs, CallOutcome},
+        Database,
+        EvmContext,
+        Inspector,
+    },
+    std::fmt,
+};
+
+/// A call frame that was executed during a simulation.
+#[derive(Debug, Clone)]
+pub struct Call {
+    pub from: eth::Address,
+    pub to: eth::Address,
+    pub input: Vec<u8>,
+    pub output: Vec<u8>,
+    pub success: bool,
+    pub calls: Vec<Call>,
+}
+
+impl Call {
+    /// Returns the path from this call to the innermost call that caused it
+    /// to revert. Returns an empty path for successful calls.
+    pub fn revert_path(&self) -> Vec<&Call> {
+        let mut path = Vec::new();
+        let mut call = self;
+        while !call.success {
+            path.push(call);
+            match call.calls.iter().rev().find(|call| !call.success) {
+                Some(inner) => call = inner,
+                None => break,
+            }
+        }
+        path
+    }
+
+    fn selector(&self) -> Option<&[u8]> {
+        self.input.get(..4)
+    }
+}
+
+/// Displays the path to the innermost reverting call, e.g.
+/// `0x9008…ab41::0x13d79a0b > 0xa0b8…eb48::0xa9059cbb`.
+pub struct RevertPath<'a>(pub &'a Call);
+
+impl fmt::Display for RevertPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, call) in self.0.revert_path().into_iter().enumerate() {
+            if i > 0 {
+                f.write_str(" > ")?;
+            }
+            write!(f, "{:?}", call.to.0)?;
+            if let Some(selector) = call.selector() {
+                write!(f, "::0x{}", hex::encode(selector))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [`revm`] inspector that records the call tree of a transaction.
+#[derive(Debug, Default)]
+pub struct Tracer {
+    stack: Vec<Call>,
+    root: Option<Call>,
+}
+
+impl Tracer {
+    /// Returns the recorded top-level call.
+    pub fn into_trace(self) -> Option<Call> {
+        self.root
+    }
+}
+
+impl<DB: Database> Inspector<DB> for Tracer {
+    fn call(&mut self, _: &mut EvmContext<DB>, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.stack.push(Call {
+            from: conv::h160(inputs.context.caller).into(),
+            to: conv::h160(inputs.contract).into(),
+            input: inputs.input.to_vec(),
+            output: Default::default(),
+            success: false,
+            calls: Default::default(),
+        });
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _: &mut EvmContext<DB>,
+        _: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        if let Some(mut call) = self.stack.pop() {
+            call.output = outcome.output().to_vec();
+            call.success = outcome.instruction_result().is_ok();
+            match self.stack.last_mut() {
+                Some(parent) => parent.calls.push(call),
+                None => self.root = Some(call),
+            }
+        }
+        outcome
+    }
+}
@@ -7,6 +7,7 @@ use {
 };
 
 pub mod enso;
+pub mod local;
 pub mod tenderly;
 
 /// Ethereum transaction simulator.
@@ -25,6 +26,7 @@ pub struct Simulator {
 pub enum Config {
     Tenderly(tenderly::Config),
     Enso(enso::Config),
+    Local(local::Config),
 }
 
 impl Simulator {
@@ -66,6 +68,18 @@ impl Simulator {
         }
     }
 
+    /// Simulate transactions in-process using a local EVM, on top of state
+    /// that is fetched from the Ethereum RPC API.
+    pub fn local(config: local::Config, eth: Ethereum) -> Self {
+        let eth = eth.with_metric_label("localSimulator".into());
+        Self {
+            inner: Inner::Local(local::Local::new(config, eth.clone())),
+            eth,
+            disable_access_lists: false,
+            disable_gas: None,
+        }
+    }
+
     /// Disable access list simulation. Some environments, such as less popular
     /// blockchains, don't support access list simulation.
     pub fn disable_access_lists(&mut self) {
@@ -104,6 +118,13 @@ impl Simulator {
                 .create_access_list(tx.clone())
                 .await
                 .map_err(with(tx.clone(), block))?,
+            Inner::Local(local) => {
+                local
+                    .simulate(tx.clone())
+                    .await
+                    .map_err(with(tx.clone(), block))?
+                    .access_list
+            }
         };
         Ok(tx.access_list.merge(access_list))
     }
@@ -133,6 +154,14 @@ impl Simulator {
                 .measure("enso_simulate_gas")
                 .await
                 .map_err(with(tx, block))?,
+            Inner::Local(local) => {
+                local
+                    .simulate(tx.clone())
+                    .measure("local_simulate_gas")
+                    .await
+                    .map_err(with(tx, block))?
+                    .gas
+            }
         })
     }
 }
@@ -142,6 +171,7 @@ enum Inner {
     Tenderly(tenderly::Tenderly),
     Ethereum,
     Enso(enso::Enso),
+    Local(local::Local),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -152,6 +182,8 @@ pub enum SimulatorError {
     Blockchain(#[from] blockchain::Error),
     #[error("enso error: {0:?}")]
     Enso(#[from] enso::Error),
+    #[error("local simulation error: {0}")]
+    Local(#[from] local::Error),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -162,6 +194,18 @@ pub struct RevertError {
     pub block: eth::BlockNo,
 }
 
+impl RevertError {
+    /// A human readable explanation of why the transaction reverted, if the
+    /// simulator provides one.
+    pub fn reason(&self) -> Option<String> {
+        match &self.err {
+            SimulatorError::Enso(enso::Error::Revert(reason)) => Some(reason.clone()),
+            SimulatorError::Local(local::Error::Revert(revert)) => Some(revert.to_string()),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// If a transaction reverted, forward that transaction together with the
@@ -192,6 +236,8 @@ where
             }
             SimulatorError::Enso(enso::Error::Http(_)) => None,
             SimulatorError::Enso(enso::Error::Revert(_)) => Some(tx),
+            SimulatorError::Local(local::Error::Database(_) | local::Error::Panic(_)) => None,
+            SimulatorError::Local(local::Error::Revert(_)) => Some(tx),
         };
         match tx {
             Some(tx) => Error::Revert(RevertError { err, tx, block }),
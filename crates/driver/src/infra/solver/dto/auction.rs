@@ -2,7 +2,8 @@ use {
     crate::{
         domain::{
             competition::{self, order},
-            eth, liquidity,
+            eth,
+            liquidity,
         },
         util::{
             conv::{rational_to_big_decimal, u256::U256Ext},
@@ -47,6 +48,7 @@ impl Auction {
                 liquidity::Kind::UniswapV3(pool) => vec![pool.tokens.get().0, pool.tokens.get().1],
                 liquidity::Kind::BalancerV2Stable(pool) => pool.reserves.tokens().collect(),
                 liquidity::Kind::BalancerV2Weighted(pool) => pool.reserves.tokens().collect(),
+                liquidity::Kind::Curve(pool) => pool.reserves.tokens().collect(),
                 liquidity::Kind::Swapr(pool) => {
                     pool.base.reserves.iter().map(|r| r.token).collect()
                 }
@@ -180,6 +182,29 @@ impl Auction {
                             },
                         })
                     }
+                    liquidity::Kind::Curve(pool) => Liquidity::Curve(CurvePool {
+                        id: liquidity.id.into(),
+                        address: pool.address.into(),
+                        gas_estimate: liquidity.gas.into(),
+                        tokens: pool
+                            .reserves
+                            .iter()
+                            .map(|r| {
+                                (
+                                    r.asset.token.into(),
+                                    CurveReserve {
+                                        balance: r.asset.amount.into(),
+                                        decimals: r.decimals,
+                                    },
+                                )
+                            })
+                            .collect(),
+                        amplification_parameter: bigdecimal::BigDecimal::new(
+                            pool.amplification_parameter.0.to_big_int(),
+                            0,
+                        ),
+                        fee: bigdecimal::BigDecimal::new(pool.fee.as_raw().to_big_int(), 10),
+                    }),
                     liquidity::Kind::Swapr(pool) => {
                         Liquidity::ConstantProduct(ConstantProductPool {
                             id: liquidity.id.into(),
@@ -281,6 +306,7 @@ pub enum Liquidity {
     WeightedProduct(WeightedProductPool),
     Stable(StablePool),
     ConcentratedLiquidity(ConcentratedLiquidityPool),
+    Curve(CurvePool),
     LimitOrder(ForeignLimitOrder),
 }
 
@@ -390,6 +416,31 @@ pub struct ConcentratedLiquidityPool {
     pub fee: bigdecimal::BigDecimal,
 }
 
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurvePool {
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    pub id: usize,
+    pub address: eth::H160,
+    #[serde_as(as = "serialize::U256")]
+    pub gas_estimate: eth::U256,
+    pub tokens: IndexMap<eth::H160, CurveReserve>,
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    pub amplification_parameter: bigdecimal::BigDecimal,
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    pub fee: bigdecimal::BigDecimal,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurveReserve {
+    #[serde_as(as = "serialize::U256")]
+    pub balance: eth::U256,
+    pub decimals: u8,
+}
+
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -25,7 +25,7 @@ impl Notification {
             kind: match kind {
                 notify::Kind::Timeout => Kind::Timeout,
                 notify::Kind::EmptySolution => Kind::EmptySolution,
-                notify::Kind::SimulationFailed(block, tx, succeeded_once) => {
+                notify::Kind::SimulationFailed(block, tx, revert_reason, succeeded_once) => {
                     Kind::SimulationFailed {
                         block: block.0,
                         tx: Tx {
@@ -35,6 +35,7 @@ impl Notification {
                             value: tx.value.into(),
                             access_list: tx.access_list.into(),
                         },
+                        revert_reason,
                         succeeded_once,
                     }
                 }
@@ -105,6 +106,8 @@ pub enum Kind {
     SimulationFailed {
         block: BlockNo,
         tx: Tx,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        revert_reason: Option<String>,
         succeeded_once: bool,
     },
     ZeroScore,
@@ -120,6 +120,12 @@ fn simulator(config: &infra::Config, eth: &Ethereum) -> Simulator {
             },
             eth.to_owned(),
         ),
+        Some(infra::simulator::Config::Local(local)) => Simulator::local(
+            simulator::local::Config {
+                network_block_interval: local.network_block_interval.to_owned(),
+            },
+            eth.to_owned(),
+        ),
         None => Simulator::ethereum(eth.to_owned()),
     };
     if config.disable_access_list_simulation {
@@ -132,7 +138,7 @@ fn simulator(config: &infra::Config, eth: &Ethereum) -> Simulator {
 }
 
 async fn ethrpc(args: &cli::Args) -> blockchain::Rpc {
-    blockchain::Rpc::new(&args.ethrpc)
+    blockchain::Rpc::new(&args.ethrpc, &args.ethrpc_fallbacks, &args.multi_node())
         .await
         .expect("connect ethereum RPC")
 }
@@ -33,6 +33,24 @@ async fn matrix() {
     }
 }
 
+/// Checks that solutions are scored and settled when they are simulated with
+/// the local EVM.
+#[tokio::test]
+#[ignore]
+async fn local_evm_simulation() {
+    let test = tests::setup()
+        .name("local evm simulation")
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .local_evm()
+        .done()
+        .await;
+
+    test.solve().await.ok().default_score();
+    test.settle().await.ok().await.ab_order_executed().await;
+}
+
 /// Checks that settling without a solution returns an error.
 #[tokio::test]
 #[ignore]
@@ -73,6 +91,24 @@ async fn private_rpc_with_high_risk_solution() {
     err.kind("FailedToSubmit");
 }
 
+/// Checks that settlements can be submitted as bundles to block builder
+/// relays.
+#[tokio::test]
+#[ignore]
+async fn bundle_mempool() {
+    let test = tests::setup()
+        .name("bundle mempool")
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .mempools(vec![tests::setup::Mempool::Bundle])
+        .done()
+        .await;
+
+    test.solve().await.ok().default_score();
+    test.settle().await.ok().await.ab_order_executed().await;
+}
+
 // /// Checks that we can settle transactions that have a gas limit higher than
 // /// half the block size
 // #[tokio::test]
@@ -1,5 +1,5 @@
 use {
-    super::{blockchain::Blockchain, Mempool, Partial, Solver, Test},
+    super::{blockchain::Blockchain, relay::Relay, Mempool, Partial, Solver, Test},
     crate::{
         domain::competition::order,
         infra::time,
@@ -19,6 +19,7 @@ pub struct Config {
     /// temporary file will be created with reasonable values.
     pub config_file: Option<PathBuf>,
     pub enable_simulation: bool,
+    pub local_evm: bool,
     pub mempools: Vec<Mempool>,
 }
 
@@ -176,6 +177,14 @@ async fn create_config_file(
            "#
     };
     write!(file, "{simulation}").unwrap();
+    if config.local_evm {
+        write!(
+            file,
+            r#"[local-evm]
+               "#
+        )
+        .unwrap();
+    }
     write!(
         file,
         r#"[contracts]
@@ -214,6 +223,18 @@ async fn create_config_file(
                 )
                 .unwrap();
             }
+            Mempool::Bundle => {
+                let relay = Relay::new(&blockchain.web3_url).await;
+                write!(
+                    file,
+                    r#"[[submission.mempool]]
+                    mempool = "bundle"
+                    relays = ["http://{}"]
+                    "#,
+                    relay.addr,
+                )
+                .unwrap();
+            }
         }
     }
 
@@ -43,6 +43,7 @@ use {
 mod blockchain;
 mod driver;
 pub mod fee;
+mod relay;
 mod solver;
 
 #[derive(Debug, Clone, Copy)]
@@ -433,6 +434,8 @@ pub enum Mempool {
         /// Uses ethrpc node if None
         url: Option<String>,
     },
+    /// Bundles sent to a mock relay that forwards them to the ethrpc node.
+    Bundle,
 }
 
 /// Create a builder for the setup process.
@@ -447,6 +450,7 @@ pub fn setup() -> Setup {
         quote: Default::default(),
         solvers: vec![test_solver()],
         enable_simulation: true,
+        local_evm: false,
         settlement_address: Default::default(),
         mempools: vec![Mempool::Public],
     }
@@ -466,6 +470,9 @@ pub struct Setup {
     solvers: Vec<Solver>,
     /// Should simulation be enabled? True by default.
     enable_simulation: bool,
+    /// Should transactions be simulated with the local EVM instead of the
+    /// Ethereum RPC API?
+    local_evm: bool,
     /// Ensure the settlement contract is deployed on a specific address?
     settlement_address: Option<eth::H160>,
     /// Via which mempool the solutions should be submitted
@@ -727,6 +734,12 @@ impl Setup {
         self
     }
 
+    /// Simulate transactions with the local EVM.
+    pub fn local_evm(mut self) -> Self {
+        self.local_evm = true;
+        self
+    }
+
     /// Create the test: set up onchain contracts and pools, start a mock HTTP
     /// server for the solver and start the HTTP server for the driver.
     pub async fn done(self) -> Test {
@@ -796,6 +809,7 @@ impl Setup {
             &driver::Config {
                 config_file,
                 enable_simulation: self.enable_simulation,
+                local_evm: self.local_evm,
                 mempools: self.mempools,
             },
             &solvers_with_address,
@@ -1034,7 +1048,7 @@ impl<'a> SolveOk<'a> {
         assert_eq!(solutions.len(), 1);
         let solution = solutions[0].clone();
         assert!(solution.is_object());
-        assert_eq!(solution.as_object().unwrap().len(), 5);
+        assert_eq!(solution.as_object().unwrap().len(), 6);
         solution
     }
 
@@ -0,0 +1,48 @@
+use {serde_json::json, std::net::SocketAddr};
+
+/// A mock block builder relay. Transactions of received bundles are forwarded
+/// to the node straight away, as if a builder had included them in the next
+/// block.
+pub struct Relay {
+    pub addr: SocketAddr,
+}
+
+impl Relay {
+    pub async fn new(web3_url: &str) -> Self {
+        let web3 = web3::Web3::new(web3::transports::Http::new(web3_url).expect("valid URL"));
+        let app = axum::Router::new().route(
+            "/",
+            axum::routing::post(
+                move |axum::extract::Json(req): axum::extract::Json<serde_json::Value>| async move {
+                    let result = match req["method"].as_str() {
+                        Some("eth_sendBundle") => {
+                            let txs = req["params"][0]["txs"].as_array().expect("bundle txs");
+                            for tx in txs {
+                                let raw =
+                                    hex::decode(tx.as_str().unwrap().trim_start_matches("0x"))
+                                        .unwrap();
+                                // Bundles get resubmitted for every block until they
+                                // are included, so forwarding them can fail with
+                                // already known transactions.
+                                let _ = web3.eth().send_raw_transaction(raw.into()).await;
+                            }
+                            json!({ "bundleHash": format!("{:?}", web3::types::H256::zero()) })
+                        }
+                        Some("eth_cancelBundle") => json!(null),
+                        method => panic!("unexpected relay method {method:?}"),
+                    };
+                    axum::response::Json(json!({
+                        "jsonrpc": "2.0",
+                        "id": req["id"],
+                        "result": result,
+                    }))
+                },
+            ),
+        );
+        let server =
+            axum::Server::bind(&"0.0.0.0:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(async move { server.await.unwrap() });
+        Self { addr }
+    }
+}
@@ -298,7 +298,6 @@ async fn execute_test(
         vec![
             "--drivers=test_solver|http://localhost:11088/test_solver".to_string(),
             "--price-estimation-drivers=test_quoter|http://localhost:11088/test_solver".to_string(),
-            "--fee-policy-skip-market-orders=false".to_string(),
             fee_policy.to_string(),
         ],
     );
@@ -375,11 +374,11 @@ impl std::fmt::Display for FeePolicyKind {
                 max_volume_factor,
             } => write!(
                 f,
-                "--fee-policy-kind=surplus:{}:{}",
+                "--fee-policies=surplus:{}:{};class=limit",
                 factor, max_volume_factor
             ),
             FeePolicyKind::Volume { factor } => {
-                write!(f, "--fee-policy-kind=volume:{}", factor)
+                write!(f, "--fee-policies=volume:{};class=limit", factor)
             }
         }
     }
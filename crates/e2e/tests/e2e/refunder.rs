@@ -8,6 +8,7 @@ use {
     number::nonzero::U256 as NonZeroU256,
     refunder::refund_service::RefundService,
     sqlx::PgPool,
+    std::time::Duration,
 };
 
 #[tokio::test]
@@ -87,13 +88,15 @@ async fn refunder_tx(web3: Web3) {
 
     // Create the refund service and execute the refund tx
     let pg_pool = PgPool::connect_lazy("postgresql://").expect("failed to create database");
-    let mut refunder = RefundService::new(
+    let refunder = RefundService::new(
         pg_pool,
         web3,
-        onchain.contracts().ethflow.clone(),
+        vec![onchain.contracts().ethflow.clone()],
         validity_duration as i64 / 2,
         10u64,
         refunder.account().clone(),
+        800_000_000_000.,
+        Duration::from_secs(60),
     );
 
     assert_ne!(
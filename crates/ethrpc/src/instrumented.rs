@@ -139,7 +139,7 @@ impl BatchTransport for InstrumentedTransport {
     }
 }
 
-fn method_name(call: &Call) -> &str {
+pub(crate) fn method_name(call: &Call) -> &str {
     match call {
         Call::MethodCall(method) => &method.method,
         Call::Notification(notification) => &notification.method,
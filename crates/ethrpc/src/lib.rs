@@ -5,6 +5,7 @@ pub mod extensions;
 pub mod http;
 pub mod instrumented;
 pub mod mock;
+pub mod multi;
 pub mod multicall;
 
 use {
@@ -38,7 +39,7 @@ pub struct Config {
 impl Config {
     /// Returns the buffered transport configuration or `None` if batching is
     /// disabled.
-    fn into_buffered_configuration(self) -> Option<buffered::Configuration> {
+    fn buffered_configuration(&self) -> Option<buffered::Configuration> {
         match (
             self.ethrpc_max_batch_size,
             self.ethrpc_max_concurrent_requests,
@@ -72,7 +73,7 @@ pub fn web3(
 ) -> Web3 {
     let http = http_factory.cookie_store(true).build().unwrap();
     let http = HttpTransport::new(http, url.clone(), name.to_string());
-    let transport = match args.into_buffered_configuration() {
+    let transport = match args.buffered_configuration() {
         Some(config) => Web3Transport::new(BufferedTransport::with_config(http, config)),
         None => Web3Transport::new(http),
     };
@@ -80,6 +81,36 @@ pub fn web3(
     Web3::new(Web3Transport::new(instrumented))
 }
 
+/// Create a Web3 instance that spreads requests over multiple nodes. The nodes
+/// are specified in order of preference.
+pub fn multi_web3(
+    args: Config,
+    multi: multi::Configuration,
+    http_factory: reqwest::ClientBuilder,
+    urls: &[Url],
+    name: impl ToString,
+) -> Web3 {
+    let name = name.to_string();
+    let http = http_factory.cookie_store(true).build().unwrap();
+    let endpoints = urls
+        .iter()
+        .enumerate()
+        .map(|(i, url)| {
+            let label = format!("{name}_{i}");
+            let http = HttpTransport::new(http.clone(), url.clone(), label.clone());
+            let transport = match args.buffered_configuration() {
+                Some(config) => Web3Transport::new(BufferedTransport::with_config(http, config)),
+                None => Web3Transport::new(http),
+            };
+            let instrumented = instrumented::InstrumentedTransport::new(label.clone(), transport);
+            (label, Web3Transport::new(instrumented))
+        })
+        .collect();
+    let transport = Web3Transport::new(multi::MultiTransport::new(name.clone(), endpoints, multi));
+    let instrumented = instrumented::InstrumentedTransport::new(name, transport);
+    Web3::new(Web3Transport::new(instrumented))
+}
+
 /// Convenience method to create a transport from a URL.
 pub fn create_test_transport(url: &str) -> Web3Transport {
     let http_transport = HttpTransport::new(
@@ -0,0 +1,560 @@
+//! A `Transport` implementation that spreads requests over multiple nodes.
+//!
+//! Requests go to the first healthy node. A node is considered healthy if it
+//! does not lag too many blocks behind the most advanced node and did not fail
+//! too many of its recent requests. When a node can't be reached the request
+//! is retried on the next node. Latency sensitive requests can optionally be
+//! hedged by sending them to a second node when the first one takes too long
+//! to respond.
+
+use {
+    crate::instrumented::method_name,
+    ethcontract::{
+        jsonrpc::Call,
+        transport::DynTransport,
+        web3::{helpers, BatchTransport, Error as Web3Error, RequestId, Transport},
+    },
+    futures::{
+        future::{self, BoxFuture, Either},
+        FutureExt as _,
+    },
+    primitive_types::U64,
+    serde_json::Value,
+    std::{
+        collections::VecDeque,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+            Mutex,
+            Weak,
+        },
+        time::Duration,
+    },
+};
+
+/// Methods that are latency sensitive enough to be hedged.
+const HEDGED_METHODS: &[&str] = &["eth_call", "eth_estimateGas"];
+
+/// Number of most recent requests the error rate of a node is computed over.
+const ERROR_RATE_WINDOW: usize = 100;
+
+/// Multi node transport configuration.
+#[derive(Clone, Debug)]
+pub struct Configuration {
+    /// How many blocks a node can lag behind the most advanced node before it
+    /// is considered unhealthy.
+    pub max_block_lag: u64,
+    /// Share of failed recent requests above which a node is considered
+    /// unhealthy.
+    pub max_error_rate: f64,
+    /// How often the block height of every node gets checked.
+    pub health_check_interval: Duration,
+    /// How long to wait for a response to a latency sensitive request before
+    /// sending it to another node as well.
+    ///
+    /// Specifying `None` disables hedging.
+    pub hedge_delay: Option<Duration>,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self {
+            max_block_lag: 3,
+            max_error_rate: 0.5,
+            health_check_interval: Duration::from_secs(5),
+            hedge_delay: None,
+        }
+    }
+}
+
+#[derive(prometheus_metric_storage::MetricStorage, Clone, Debug)]
+#[metric(subsystem = "rpc")]
+struct Metrics {
+    /// Whether a node is currently considered healthy.
+    #[metric(labels("component", "endpoint"))]
+    endpoint_healthy: prometheus::IntGaugeVec,
+
+    /// Latest block reported by a node.
+    #[metric(labels("component", "endpoint"))]
+    endpoint_block: prometheus::IntGaugeVec,
+
+    /// Number of requests that got retried on a node after a previous node
+    /// failed.
+    #[metric(labels("component", "endpoint"))]
+    endpoint_failovers: prometheus::IntCounterVec,
+
+    /// Number of requests that got sent to a second node because the first
+    /// one was too slow to respond.
+    #[metric(labels("component"))]
+    hedged_requests: prometheus::IntCounterVec,
+}
+
+/// `Transport` implementation that sends requests to the healthiest of
+/// multiple nodes.
+#[derive(Clone, Debug)]
+pub struct MultiTransport(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    endpoints: Vec<Endpoint>,
+    config: Configuration,
+    label: String,
+    id: AtomicUsize,
+    metrics: &'static Metrics,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    label: String,
+    transport: DynTransport,
+    /// Latest block reported by the node. `None` if the node has not been
+    /// checked yet or the last check failed.
+    block: Mutex<Option<u64>>,
+    /// Outcomes of the most recent requests, `true` meaning the request failed.
+    failures: Mutex<VecDeque<bool>>,
+}
+
+type RpcResult = Result<Value, Web3Error>;
+
+impl MultiTransport {
+    /// Creates a new transport for the specified nodes in order of preference
+    /// and starts a background task that checks their health.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no nodes are specified.
+    pub fn new(
+        label: String,
+        endpoints: Vec<(String, DynTransport)>,
+        config: Configuration,
+    ) -> Self {
+        assert!(!endpoints.is_empty(), "at least one node is required");
+        let inner = Arc::new(Inner {
+            endpoints: endpoints
+                .into_iter()
+                .map(|(label, transport)| Endpoint {
+                    label,
+                    transport,
+                    block: Default::default(),
+                    failures: Default::default(),
+                })
+                .collect(),
+            config,
+            label,
+            id: AtomicUsize::new(0),
+            metrics: Metrics::instance(observe::metrics::get_storage_registry()).unwrap(),
+        });
+        tokio::task::spawn(Self::health_checks(Arc::downgrade(&inner)));
+        Self(inner)
+    }
+
+    /// Periodically checks the health of all nodes until the transport gets
+    /// dropped.
+    async fn health_checks(inner: Weak<Inner>) {
+        loop {
+            let Some(inner) = inner.upgrade() else {
+                break;
+            };
+            inner.check_health().await;
+            let interval = inner.config.health_check_interval;
+            drop(inner);
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+impl Inner {
+    async fn check_health(&self) {
+        future::join_all(self.endpoints.iter().map(|endpoint| async move {
+            let block = endpoint
+                .transport
+                .execute("eth_blockNumber", vec![])
+                .await
+                .and_then(|block| {
+                    serde_json::from_value::<U64>(block)
+                        .map_err(|err| Web3Error::Decoder(err.to_string()))
+                });
+            let block = match block {
+                Ok(block) => Some(block.as_u64()),
+                Err(err) => {
+                    tracing::warn!(endpoint = %endpoint.label, ?err, "node health check failed");
+                    None
+                }
+            };
+            endpoint.record(block.is_none());
+            *endpoint.block.lock().unwrap() = block;
+        }))
+        .await;
+
+        let best_block = self.best_block();
+        for endpoint in &self.endpoints {
+            let labels = [self.label.as_str(), endpoint.label.as_str()];
+            self.metrics
+                .endpoint_healthy
+                .with_label_values(&labels)
+                .set(endpoint.is_healthy(best_block, &self.config).into());
+            if let Some(block) = *endpoint.block.lock().unwrap() {
+                self.metrics
+                    .endpoint_block
+                    .with_label_values(&labels)
+                    .set(block.try_into().unwrap_or(i64::MAX));
+            }
+        }
+    }
+
+    fn best_block(&self) -> u64 {
+        self.endpoints
+            .iter()
+            .filter_map(|endpoint| *endpoint.block.lock().unwrap())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Indices of the nodes in the order they should be tried in. Healthy
+    /// nodes come first, otherwise the configured order is kept.
+    fn candidates(&self) -> Vec<usize> {
+        let best_block = self.best_block();
+        let (mut healthy, unhealthy): (Vec<_>, Vec<_>) = (0..self.endpoints.len())
+            .partition(|i| self.endpoints[*i].is_healthy(best_block, &self.config));
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    async fn send(&self, id: RequestId, call: Call) -> RpcResult {
+        let candidates = self.candidates();
+        if let (Some(delay), [first, second, ..]) = (self.config.hedge_delay, &candidates[..]) {
+            if HEDGED_METHODS.contains(&method_name(&call)) {
+                return self.send_hedged(id, call, (*first, *second), delay).await;
+            }
+        }
+
+        let mut result = Err(Web3Error::Unreachable);
+        for (attempt, i) in candidates.into_iter().enumerate() {
+            if attempt > 0 {
+                self.on_failover(i);
+            }
+            result = self.send_to(i, id, call.clone()).await;
+            if !matches!(&result, Err(err) if is_node_failure(err)) {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Sends the request to the first node and, if it did not respond within
+    /// `delay`, to the second node as well. Returns the first response that
+    /// is not a node failure.
+    async fn send_hedged(
+        &self,
+        id: RequestId,
+        call: Call,
+        (first, second): (usize, usize),
+        delay: Duration,
+    ) -> RpcResult {
+        let primary = self.send_to(first, id, call.clone());
+        let hedge = async {
+            tokio::time::sleep(delay).await;
+            self.metrics
+                .hedged_requests
+                .with_label_values(&[&self.label])
+                .inc();
+            self.send_to(second, id, call).await
+        };
+        futures::pin_mut!(primary, hedge);
+
+        let (result, other) = match future::select(primary, hedge).await {
+            Either::Left((result, hedge)) => (result, hedge.boxed()),
+            Either::Right((result, primary)) => (result, primary.boxed()),
+        };
+        match result {
+            Err(err) if is_node_failure(&err) => other.await,
+            result => result,
+        }
+    }
+
+    async fn send_batch(
+        &self,
+        requests: Vec<(RequestId, Call)>,
+    ) -> Result<Vec<RpcResult>, Web3Error> {
+        let mut result = Err(Web3Error::Unreachable);
+        for (attempt, i) in self.candidates().into_iter().enumerate() {
+            if attempt > 0 {
+                self.on_failover(i);
+            }
+            let endpoint = &self.endpoints[i];
+            result = endpoint.transport.send_batch(requests.clone()).await;
+            let failed = matches!(&result, Err(err) if is_node_failure(err));
+            endpoint.record(failed);
+            if !failed {
+                break;
+            }
+        }
+        result
+    }
+
+    async fn send_to(&self, i: usize, id: RequestId, call: Call) -> RpcResult {
+        let endpoint = &self.endpoints[i];
+        let result = endpoint.transport.send(id, call).await;
+        endpoint.record(matches!(&result, Err(err) if is_node_failure(err)));
+        result
+    }
+
+    fn on_failover(&self, i: usize) {
+        let endpoint = &self.endpoints[i];
+        tracing::debug!(endpoint = %endpoint.label, "failing over to next node");
+        self.metrics
+            .endpoint_failovers
+            .with_label_values(&[&self.label, &endpoint.label])
+            .inc();
+    }
+}
+
+impl Endpoint {
+    fn is_healthy(&self, best_block: u64, config: &Configuration) -> bool {
+        let Some(block) = *self.block.lock().unwrap() else {
+            return false;
+        };
+        best_block.saturating_sub(block) <= config.max_block_lag
+            && self.error_rate() <= config.max_error_rate
+    }
+
+    fn error_rate(&self) -> f64 {
+        let failures = self.failures.lock().unwrap();
+        if failures.is_empty() {
+            return 0.;
+        }
+        failures.iter().filter(|failed| **failed).count() as f64 / failures.len() as f64
+    }
+
+    fn record(&self, failed: bool) {
+        let mut failures = self.failures.lock().unwrap();
+        failures.push_back(failed);
+        while failures.len() > ERROR_RATE_WINDOW {
+            failures.pop_front();
+        }
+    }
+}
+
+/// Whether the error indicates a problem with the node rather than with the
+/// request itself. RPC errors (e.g. reverts) are valid responses that another
+/// node would respond with as well.
+fn is_node_failure(err: &Web3Error) -> bool {
+    matches!(
+        err,
+        Web3Error::Unreachable
+            | Web3Error::Transport(_)
+            | Web3Error::Io(_)
+            | Web3Error::Decoder(_)
+            | Web3Error::InvalidResponse(_)
+    )
+}
+
+impl Transport for MultiTransport {
+    type Out = BoxFuture<'static, RpcResult>;
+
+    fn prepare(&self, method: &str, params: Vec<Value>) -> (RequestId, Call) {
+        let id = self.0.id.fetch_add(1, Ordering::SeqCst);
+        (id, helpers::build_request(id, method, params))
+    }
+
+    fn send(&self, id: RequestId, call: Call) -> Self::Out {
+        let inner = self.0.clone();
+        async move { inner.send(id, call).await }.boxed()
+    }
+}
+
+impl BatchTransport for MultiTransport {
+    type Batch = BoxFuture<'static, Result<Vec<RpcResult>, Web3Error>>;
+
+    fn send_batch<R>(&self, requests: R) -> Self::Batch
+    where
+        R: IntoIterator<Item = (RequestId, Call)>,
+    {
+        let inner = self.0.clone();
+        let requests = requests.into_iter().collect();
+        async move { inner.send_batch(requests).await }.boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::mock::MockTransport,
+        ethcontract::web3::error::TransportError,
+        serde_json::json,
+    };
+
+    fn transport(nodes: Vec<MockTransport>, config: Configuration) -> MultiTransport {
+        MultiTransport::new(
+            "test".into(),
+            nodes
+                .into_iter()
+                .enumerate()
+                .map(|(i, node)| (i.to_string(), DynTransport::new(node)))
+                .collect(),
+            Configuration {
+                health_check_interval: Duration::from_secs(3600),
+                ..config
+            },
+        )
+    }
+
+    /// Node that answers every request with the same value after a delay.
+    #[derive(Clone, Debug)]
+    struct Delayed(Duration, Value);
+
+    impl Transport for Delayed {
+        type Out = BoxFuture<'static, RpcResult>;
+
+        fn prepare(&self, method: &str, params: Vec<Value>) -> (RequestId, Call) {
+            (0, helpers::build_request(0, method, params))
+        }
+
+        fn send(&self, _: RequestId, _: Call) -> Self::Out {
+            let Self(delay, value) = self.clone();
+            async move {
+                tokio::time::sleep(delay).await;
+                Ok(value)
+            }
+            .boxed()
+        }
+    }
+
+    impl BatchTransport for Delayed {
+        type Batch = BoxFuture<'static, Result<Vec<RpcResult>, Web3Error>>;
+
+        fn send_batch<T>(&self, requests: T) -> Self::Batch
+        where
+            T: IntoIterator<Item = (RequestId, Call)>,
+        {
+            let responses = requests
+                .into_iter()
+                .map(|(id, call)| self.send(id, call))
+                .collect::<Vec<_>>();
+            async move { Ok(futures::future::join_all(responses).await) }.boxed()
+        }
+    }
+
+    fn unreachable() -> Web3Error {
+        Web3Error::Transport(TransportError::Message("connection refused".into()))
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_next_node() {
+        let first = MockTransport::new();
+        first
+            .mock()
+            .expect_execute()
+            .returning(|_, _| Err(unreachable()));
+        let second = MockTransport::new();
+        second
+            .mock()
+            .expect_execute()
+            .returning(|_, _| Ok(json!("0x1")));
+
+        let transport = transport(vec![first, second], Default::default());
+        let result = transport.execute("eth_chainId", vec![]).await.unwrap();
+        assert_eq!(result, json!("0x1"));
+    }
+
+    #[tokio::test]
+    async fn does_not_fail_over_on_rpc_errors() {
+        let first = MockTransport::new();
+        first.mock().expect_execute().returning(|_, _| {
+            Err(Web3Error::Rpc(ethcontract::jsonrpc::Error::new(
+                ethcontract::jsonrpc::ErrorCode::ServerError(3),
+            )))
+        });
+        let second = MockTransport::new();
+        second
+            .mock()
+            .expect_execute()
+            .withf(|method, _| method == "eth_call")
+            .never();
+
+        let transport = transport(vec![first, second], Default::default());
+        let result = transport.execute("eth_call", vec![]).await;
+        assert!(matches!(result, Err(Web3Error::Rpc(_))));
+    }
+
+    #[tokio::test]
+    async fn prefers_healthy_nodes() {
+        let lagging = MockTransport::new();
+        lagging
+            .mock()
+            .expect_execute()
+            .returning(|_, _| Ok(json!("0x1")));
+        let synced = MockTransport::new();
+        synced
+            .mock()
+            .expect_execute()
+            .returning(|_, _| Ok(json!("0x10")));
+
+        let transport = transport(vec![lagging, synced], Default::default());
+        transport.0.check_health().await;
+        assert_eq!(transport.0.candidates(), vec![1, 0]);
+    }
+
+    #[tokio::test]
+    async fn hedges_slow_requests() {
+        let transport = MultiTransport::new(
+            "test".into(),
+            vec![
+                (
+                    "slow".into(),
+                    DynTransport::new(Delayed(Duration::from_secs(5), json!("0x1"))),
+                ),
+                (
+                    "fast".into(),
+                    DynTransport::new(Delayed(Duration::from_millis(10), json!("0x2"))),
+                ),
+            ],
+            Configuration {
+                health_check_interval: Duration::from_secs(3600),
+                hedge_delay: Some(Duration::from_millis(50)),
+                ..Default::default()
+            },
+        );
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            transport.execute("eth_call", vec![]),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(result, json!("0x2"));
+
+        // requests that aren't latency sensitive only go to the first node
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            transport.execute("eth_chainId", vec![]),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn fails_over_batches() {
+        let first = MockTransport::new();
+        first
+            .mock()
+            .expect_execute_batch()
+            .returning(|_| Err(unreachable()));
+        let second = MockTransport::new();
+        second
+            .mock()
+            .expect_execute_batch()
+            .returning(|requests| Ok(requests.iter().map(|_| Ok(json!(true))).collect()));
+
+        let transport = transport(vec![first, second], Default::default());
+        let requests = vec![
+            transport.prepare("eth_chainId", vec![]),
+            transport.prepare("eth_blockNumber", vec![]),
+        ];
+        let result = transport.send_batch(requests).await.unwrap();
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|result| result.is_ok()));
+    }
+}
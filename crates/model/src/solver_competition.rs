@@ -62,6 +62,19 @@ pub struct SolverSettlement {
     #[serde_as(as = "Option<BytesHex>")]
     #[derivative(Debug(format_with = "crate::format::debug_optional_bytes"))]
     pub uninternalized_call_data: Option<Vec<u8>>,
+    /// Whether this solution was chosen to settle the auction. An auction can
+    /// have multiple winners if their solutions don't overlap.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_winner: bool,
+    /// The score other solutions would have contributed in place of this
+    /// one had it not participated. Only set for winners.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<HexOrDecimalU256>")]
+    pub reference_score: Option<U256>,
+    /// Why this solution was passed over in favour of a lower ranked one, if
+    /// it was.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<String>,
 }
 
 #[serde_as]
@@ -177,6 +190,8 @@ mod tests {
                     ],
                     "callData": "0x13",
                     "uninternalizedCallData": "0x1314",
+                    "isWinner": true,
+                    "referenceScore": "0",
                 },
             ],
         });
@@ -220,6 +235,9 @@ mod tests {
                     ],
                     call_data: Some(vec![0x13]),
                     uninternalized_call_data: Some(vec![0x13, 0x14]),
+                    is_winner: true,
+                    reference_score: Some(0.into()),
+                    skip_reason: None,
                 }],
             },
         };
@@ -1,5 +1,11 @@
 use {
-    crate::{app_data, database::Postgres, orderbook::Orderbook, quoter::QuoteHandler},
+    crate::{
+        app_data,
+        database::Postgres,
+        order_events::OrderEvents,
+        orderbook::Orderbook,
+        quoter::QuoteHandler,
+    },
     shared::{
         api::{box_filter, error, finalize_router, ApiReply},
         price_estimation::native::NativePriceEstimating,
@@ -14,12 +20,16 @@ mod get_app_data;
 mod get_auction;
 mod get_native_price;
 mod get_order_by_uid;
+mod get_order_events;
+mod get_order_status;
 mod get_orders_by_tx;
 mod get_solver_competition;
+mod get_solver_rewards;
 mod get_total_surplus;
 mod get_trades;
 mod get_user_orders;
 mod post_order;
+mod post_orders;
 mod post_quote;
 mod put_app_data;
 mod version;
@@ -30,6 +40,7 @@ pub fn handle_all_routes(
     quotes: Arc<QuoteHandler>,
     app_data: Arc<app_data::Registry>,
     native_price_estimator: Arc<dyn NativePriceEstimating>,
+    order_events: Arc<OrderEvents>,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
     // Note that we add a string with endpoint's name to all responses.
     // This string will be used later to report metrics.
@@ -40,10 +51,33 @@ pub fn handle_all_routes(
             "v1/create_order",
             box_filter(post_order::post_order(orderbook.clone())),
         ),
+        (
+            "v1/create_orders",
+            box_filter(post_orders::filter(orderbook.clone())),
+        ),
         (
             "v1/get_order",
             box_filter(get_order_by_uid::get_order_by_uid(orderbook.clone())),
         ),
+        (
+            "v1/get_order_status",
+            box_filter(get_order_status::get(orderbook.clone())),
+        ),
+        (
+            "v1/get_order_events",
+            box_filter(get_order_events::get(database.clone())),
+        ),
+        (
+            "v1/stream_order_events",
+            box_filter(get_order_events::stream(
+                database.clone(),
+                order_events.clone(),
+            )),
+        ),
+        (
+            "v1/stream_owner_order_events",
+            box_filter(get_order_events::stream_by_owner(order_events)),
+        ),
         (
             "v1/get_trades",
             box_filter(get_trades::get_trades(database.clone())),
@@ -79,6 +113,12 @@ pub fn handle_all_routes(
                 database.clone(),
             ))),
         ),
+        (
+            "v1/solver_competition_history",
+            box_filter(get_solver_competition::get_history(Arc::new(
+                database.clone(),
+            ))),
+        ),
         ("v1/version", box_filter(version::version())),
         (
             "v1/get_native_price",
@@ -94,7 +134,11 @@ pub fn handle_all_routes(
         ),
         (
             "v1/get_total_surplus",
-            box_filter(get_total_surplus::get(database)),
+            box_filter(get_total_surplus::get(database.clone())),
+        ),
+        (
+            "v1/get_solver_rewards",
+            box_filter(get_solver_rewards::get(database)),
         ),
     ];
 
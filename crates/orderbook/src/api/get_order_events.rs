@@ -0,0 +1,216 @@
+use {
+    crate::{
+        database::{orders::OrderStoring, Postgres},
+        dto::{self, order_event::Label},
+        order_events::{OrderEvents, Subscription},
+    },
+    futures::{future, stream, Stream, StreamExt},
+    model::order::OrderUid,
+    primitive_types::H160,
+    std::{convert::Infallible, sync::Arc},
+    warp::{
+        hyper::StatusCode,
+        reply::{self, Response},
+        sse,
+        Filter,
+        Rejection,
+        Reply,
+    },
+};
+
+fn get_request() -> impl Filter<Extract = (OrderUid,), Error = Rejection> + Clone {
+    warp::path!("v1" / "orders" / OrderUid / "events").and(warp::get())
+}
+
+fn stream_request() -> impl Filter<Extract = (OrderUid,), Error = Rejection> + Clone {
+    warp::path!("v1" / "orders" / OrderUid / "events" / "stream").and(warp::get())
+}
+
+fn stream_by_owner_request() -> impl Filter<Extract = (H160,), Error = Rejection> + Clone {
+    warp::path!("v1" / "account" / H160 / "events" / "stream").and(warp::get())
+}
+
+pub fn get(db: Postgres) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    get_request().and_then(move |uid: OrderUid| {
+        let db = db.clone();
+        async move {
+            let response = match order_with_events(&db, &uid).await {
+                Ok((_, events)) => reply::json(&events).into_response(),
+                Err(response) => response,
+            };
+            Result::<_, Infallible>::Ok(response)
+        }
+    })
+}
+
+/// Streams all events of an order as server-sent events, starting with the
+/// ones that were already registered. The stream ends after an event after
+/// which the order can't change anymore.
+pub fn stream(
+    db: Postgres,
+    order_events: Arc<OrderEvents>,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    stream_request().and_then(move |uid: OrderUid| {
+        let db = db.clone();
+        let order_events = order_events.clone();
+        async move {
+            // Subscribe before fetching the registered events to not miss any
+            // events registered in between.
+            let Some(new) = order_events.subscribe(Subscription::Order(uid)) else {
+                return Result::<_, Infallible>::Ok(too_many_subscribers());
+            };
+            let (partially_fillable, registered) = match order_with_events(&db, &uid).await {
+                Ok(order) => order,
+                Err(response) => return Ok(response),
+            };
+            let new = {
+                let registered = registered.clone();
+                new.filter(move |event| future::ready(!registered.contains(event)))
+            };
+            let events = until_final(stream::iter(registered).chain(new), partially_fillable);
+            Ok(sse::reply(sse::keep_alive().stream(events.map(to_sse))).into_response())
+        }
+    })
+}
+
+/// Streams the events of all orders of an owner as server-sent events,
+/// starting with the ones registered after the subscription.
+pub fn stream_by_owner(
+    order_events: Arc<OrderEvents>,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    stream_by_owner_request().map(move |owner: H160| {
+        let Some(events) = order_events.subscribe(Subscription::Owner(owner)) else {
+            return too_many_subscribers();
+        };
+        let events = events.map(to_sse);
+        sse::reply(sse::keep_alive().stream(events)).into_response()
+    })
+}
+
+/// Fetches whether the order is partially fillable and its events, or the
+/// error response if that is not possible.
+async fn order_with_events(
+    db: &Postgres,
+    uid: &OrderUid,
+) -> Result<(bool, Vec<dto::OrderEvent>), Response> {
+    let result = async {
+        let Some(order) = db.single_order(uid).await? else {
+            return Ok(None);
+        };
+        let events = db.order_events(uid).await?;
+        anyhow::Ok(Some((order.data.partially_fillable, events)))
+    };
+    match result.await {
+        Ok(Some(order)) => Ok(order),
+        Ok(None) => Err(reply::with_status(
+            super::error("NotFound", "Order was not found"),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response()),
+        Err(err) => {
+            tracing::error!(?err, ?uid, "failed to fetch order events");
+            Err(shared::api::internal_error_reply().into_response())
+        }
+    }
+}
+
+fn too_many_subscribers() -> Response {
+    reply::with_status(
+        super::error("TooManySubscribers", "Too many event subscriptions"),
+        StatusCode::SERVICE_UNAVAILABLE,
+    )
+    .into_response()
+}
+
+/// Ends the stream after the first event after which no further events of
+/// the order are expected.
+fn until_final(
+    events: impl Stream<Item = dto::OrderEvent>,
+    partially_fillable: bool,
+) -> impl Stream<Item = dto::OrderEvent> {
+    let mut done = false;
+    events.take_while(move |event| {
+        let take = !done;
+        done = match event.label {
+            Label::Cancelled => true,
+            // Partially fillable orders can trade multiple times.
+            Label::Traded => !partially_fillable,
+            _ => false,
+        };
+        future::ready(take)
+    })
+}
+
+fn to_sse(event: dto::OrderEvent) -> Result<sse::Event, warp::Error> {
+    sse::Event::default().event("orderEvent").json_data(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        chrono::{TimeZone, Utc},
+        shared::addr,
+    };
+
+    #[tokio::test]
+    async fn requests() {
+        let uid = OrderUid([1; 56]);
+        let result = warp::test::request()
+            .path(&format!("/v1/orders/{uid}/events"))
+            .method("GET")
+            .filter(&get_request())
+            .await
+            .unwrap();
+        assert_eq!(result, uid);
+
+        let result = warp::test::request()
+            .path(&format!("/v1/orders/{uid}/events/stream"))
+            .method("GET")
+            .filter(&stream_request())
+            .await
+            .unwrap();
+        assert_eq!(result, uid);
+
+        let result = warp::test::request()
+            .path("/v1/account/0x0000000000000000000000000000000000000001/events/stream")
+            .method("GET")
+            .filter(&stream_by_owner_request())
+            .await
+            .unwrap();
+        assert_eq!(result, addr!("0000000000000000000000000000000000000001"));
+    }
+
+    #[tokio::test]
+    async fn stream_ends_after_final_event() {
+        let event = |seconds, label| dto::OrderEvent {
+            order_uid: OrderUid([1; 56]),
+            timestamp: Utc.timestamp_opt(seconds, 0).unwrap(),
+            label,
+            reason: None,
+        };
+        let events = vec![
+            event(1, Label::Created),
+            event(2, Label::Traded),
+            event(3, Label::Traded),
+            event(4, Label::Cancelled),
+            event(5, Label::Ready),
+        ];
+
+        let labels = |partially_fillable| {
+            until_final(stream::iter(events.clone()), partially_fillable)
+                .map(|event| event.label)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(false).await, vec![Label::Created, Label::Traded]);
+        assert_eq!(
+            labels(true).await,
+            vec![
+                Label::Created,
+                Label::Traded,
+                Label::Traded,
+                Label::Cancelled
+            ]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use {
+    crate::{dto::OrderStatusDetails, orderbook::Orderbook},
+    anyhow::Result,
+    model::order::OrderUid,
+    std::{convert::Infallible, sync::Arc},
+    warp::{hyper::StatusCode, reply, Filter, Rejection},
+};
+
+fn request() -> impl Filter<Extract = (OrderUid,), Error = Rejection> + Clone {
+    warp::path!("v1" / "orders" / OrderUid / "status").and(warp::get())
+}
+
+fn response(result: Result<Option<OrderStatusDetails>>) -> super::ApiReply {
+    match result {
+        Ok(Some(status)) => reply::with_status(reply::json(&status), StatusCode::OK),
+        Ok(None) => reply::with_status(
+            super::error("NotFound", "Order was not found"),
+            StatusCode::NOT_FOUND,
+        ),
+        Err(err) => {
+            tracing::error!(?err, "get_order_status");
+            shared::api::internal_error_reply()
+        }
+    }
+}
+
+pub fn get(
+    orderbook: Arc<Orderbook>,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |uid| {
+        let orderbook = orderbook.clone();
+        async move {
+            let result = orderbook.get_order_status(&uid).await;
+            Result::<_, Infallible>::Ok(response(result))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::dto::{
+            order_event::{FilterReason, Label},
+            order_status::{Competition, CurrentAuction, Solution},
+            OrderEvent,
+        },
+        chrono::{TimeZone, Utc},
+        model::order::OrderStatus,
+        primitive_types::H160,
+        serde_json::json,
+        shared::api::response_body,
+        warp::{test::request, Reply},
+    };
+
+    #[tokio::test]
+    async fn get_order_status_request() {
+        let uid = OrderUid([1; 56]);
+        let result = request()
+            .path(&format!("/v1/orders/{uid}/status"))
+            .method("GET")
+            .filter(&super::request())
+            .await
+            .unwrap();
+        assert_eq!(result, uid);
+    }
+
+    #[tokio::test]
+    async fn get_order_status_response() {
+        let uid = OrderUid([1; 56]);
+        let timestamp = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let status = OrderStatusDetails {
+            status: OrderStatus::Open,
+            latest_event: Some(OrderEvent {
+                order_uid: uid,
+                timestamp,
+                label: Label::Filtered,
+                reason: Some(FilterReason::MissingPrice),
+            }),
+            current_auction: Some(CurrentAuction {
+                id: 2,
+                includes_order: false,
+            }),
+            recent_competitions: vec![Competition {
+                auction_id: 1,
+                transaction_hash: None,
+                solutions: vec![Solution {
+                    solver: "solver".to_string(),
+                    solver_address: H160([2; 20]),
+                    ranking: 1,
+                    is_winner: false,
+                }],
+            }],
+        };
+        let response = response(Ok(Some(status))).into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_slice(&response_body(response).await).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "status": "open",
+                "latestEvent": {
+                    "orderUid": uid,
+                    "timestamp": timestamp,
+                    "label": "filtered",
+                    "reason": "missingPrice",
+                },
+                "currentAuction": {
+                    "id": 2,
+                    "includesOrder": false,
+                },
+                "recentCompetitions": [{
+                    "auctionId": 1,
+                    "transactionHash": null,
+                    "solutions": [{
+                        "solver": "solver",
+                        "solverAddress": H160([2; 20]),
+                        "ranking": 1,
+                        "isWinner": false,
+                    }],
+                }],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn get_order_status_response_non_existent() {
+        let response = response(Ok(None)).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,9 +1,15 @@
 use {
-    crate::solver_competition::{Identifier, LoadSolverCompetitionError, SolverCompetitionStoring},
+    crate::solver_competition::{
+        CompetitionFilter,
+        Identifier,
+        LoadSolverCompetitionError,
+        SolverCompetitionStoring,
+    },
     anyhow::Result,
     model::{auction::AuctionId, solver_competition::SolverCompetitionAPI},
-    primitive_types::H256,
+    primitive_types::{H160, H256},
     reqwest::StatusCode,
+    serde::Deserialize,
     std::{convert::Infallible, sync::Arc},
     warp::{
         reply::{with_status, Json, WithStatus},
@@ -27,6 +33,68 @@ fn request_hash() -> impl Filter<Extract = (Identifier,), Error = Rejection> + C
 fn request_latest() -> impl Filter<Extract = (), Error = Rejection> + Clone {
     warp::path!("v1" / "solver_competition" / "latest").and(warp::get())
 }
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+struct HistoryQuery {
+    solver: Option<H160>,
+    from_block: Option<u64>,
+    to_block: Option<u64>,
+    #[serde(default)]
+    winner_only: bool,
+    before_auction: Option<AuctionId>,
+    limit: Option<u64>,
+}
+
+impl HistoryQuery {
+    const DEFAULT_LIMIT: u64 = 10;
+    const MAX_LIMIT: u64 = 100;
+
+    fn validate(self) -> Result<Self, String> {
+        if matches!((self.from_block, self.to_block), (Some(from), Some(to)) if from > to) {
+            return Err("`from_block` must not be after `to_block`.".to_owned());
+        }
+        if self.winner_only && self.solver.is_none() {
+            return Err("`winner_only` requires a `solver`.".to_owned());
+        }
+        if !(1..=Self::MAX_LIMIT).contains(&self.limit()) {
+            return Err(format!("The pagination limit is [1,{}].", Self::MAX_LIMIT));
+        }
+        // The database stores these values as signed integers.
+        let max = i64::MAX as u64;
+        if [self.from_block, self.to_block]
+            .into_iter()
+            .flatten()
+            .any(|value| value > max)
+        {
+            return Err(format!(
+                "`from_block` and `to_block` must not be greater than {max}."
+            ));
+        }
+        Ok(self)
+    }
+
+    fn limit(&self) -> u64 {
+        self.limit.unwrap_or(Self::DEFAULT_LIMIT)
+    }
+
+    fn filter(&self) -> CompetitionFilter {
+        CompetitionFilter {
+            solver: self.solver,
+            from_block: self.from_block,
+            to_block: self.to_block,
+            winner_only: self.winner_only,
+        }
+    }
+}
+
+fn request_history(
+) -> impl Filter<Extract = (Result<HistoryQuery, String>,), Error = Rejection> + Clone {
+    warp::path!("v1" / "solver_competition")
+        .and(warp::get())
+        .and(warp::query::<HistoryQuery>())
+        .map(HistoryQuery::validate)
+}
+
 pub fn get(
     handler: Arc<dyn SolverCompetitionStoring>,
 ) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
@@ -54,6 +122,33 @@ pub fn get_latest(
     })
 }
 
+pub fn get_history(
+    handler: Arc<dyn SolverCompetitionStoring>,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request_history().and_then(move |query: Result<HistoryQuery, String>| {
+        let handler = handler.clone();
+        async move {
+            let query = match query {
+                Ok(query) => query,
+                Err(msg) => {
+                    let err = super::error("InvalidSolverCompetitionQuery", msg);
+                    return Result::<_, Infallible>::Ok(with_status(err, StatusCode::BAD_REQUEST));
+                }
+            };
+            let result = handler
+                .load_competitions(query.filter(), query.before_auction, query.limit())
+                .await;
+            Ok(match result {
+                Ok(competitions) => with_status(warp::reply::json(&competitions), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, ?query, "load solver competitions");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
 fn response(
     result: Result<SolverCompetitionAPI, crate::solver_competition::LoadSolverCompetitionError>,
 ) -> WithStatus<Json> {
@@ -75,6 +170,7 @@ mod tests {
     use {
         super::*,
         crate::solver_competition::MockSolverCompetitionStoring,
+        mockall::predicate::eq,
         warp::{test::request, Reply},
     };
 
@@ -111,4 +207,94 @@ mod tests {
         dbg!(&response);
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn history_request_ok() {
+        let filter = request_history();
+        let solver = H160([1; 20]);
+        let path = format!(
+            "/v1/solver_competition?solver=0x{solver:x}&from_block=1&to_block=2&winner_only=true&\
+             before_auction=3&limit=4"
+        );
+        let result = request()
+            .path(&path)
+            .method("GET")
+            .filter(&filter)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            Ok(HistoryQuery {
+                solver: Some(solver),
+                from_block: Some(1),
+                to_block: Some(2),
+                winner_only: true,
+                before_auction: Some(3),
+                limit: Some(4),
+            })
+        );
+
+        let result = request()
+            .path("/v1/solver_competition")
+            .method("GET")
+            .filter(&filter)
+            .await
+            .unwrap();
+        assert_eq!(result, Ok(HistoryQuery::default()));
+    }
+
+    #[tokio::test]
+    async fn history_request_err() {
+        let filter = request_history();
+        for query in [
+            "from_block=2&to_block=1",
+            "winner_only=true",
+            "limit=0",
+            "limit=101",
+            "from_block=9223372036854775808",
+        ] {
+            let path = format!("/v1/solver_competition?{query}");
+            let result = request().path(&path).filter(&filter).await.unwrap();
+            assert!(result.is_err(), "{query}");
+        }
+    }
+
+    #[tokio::test]
+    async fn history_response() {
+        let solver = H160([1; 20]);
+        let mut storage = MockSolverCompetitionStoring::new();
+        storage
+            .expect_load_competitions()
+            .with(
+                eq(CompetitionFilter {
+                    solver: Some(solver),
+                    from_block: Some(5),
+                    ..Default::default()
+                }),
+                eq(None),
+                eq(10),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+        let filter = get_history(Arc::new(storage));
+
+        let path = format!("/v1/solver_competition?solver=0x{solver:x}&from_block=5");
+        let response = request()
+            .path(&path)
+            .method("GET")
+            .filter(&filter)
+            .await
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = request()
+            .path("/v1/solver_competition?winner_only=true")
+            .method("GET")
+            .filter(&filter)
+            .await
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
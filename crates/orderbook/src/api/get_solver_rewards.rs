@@ -0,0 +1,120 @@
+use {
+    crate::{database::Postgres, dto::SolverRewards},
+    chrono::{DateTime, Utc},
+    primitive_types::H160,
+    serde::Deserialize,
+    shared::api::{error, ApiReply},
+    std::convert::Infallible,
+    warp::{hyper::StatusCode, reply::with_status, Filter, Rejection},
+};
+
+/// The longest accounting period rewards can be requested for at once, which
+/// bounds the number of rewards returned by a single request.
+const MAX_PERIOD_DAYS: i64 = 31;
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Query {
+    solver: H160,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+}
+
+impl Query {
+    fn validate(self) -> Result<Self, String> {
+        if self.from > self.to {
+            return Err("`from` must not be after `to`.".to_owned());
+        }
+        if self.to - self.from > chrono::Duration::days(MAX_PERIOD_DAYS) {
+            return Err(format!(
+                "The period must not be longer than {MAX_PERIOD_DAYS} days."
+            ));
+        }
+        Ok(self)
+    }
+}
+
+fn get_solver_rewards_request(
+) -> impl Filter<Extract = (Result<Query, String>,), Error = Rejection> + Clone {
+    warp::path!("v1" / "solver_rewards")
+        .and(warp::get())
+        .and(warp::query::<Query>())
+        .map(Query::validate)
+}
+
+pub fn get(db: Postgres) -> impl Filter<Extract = (ApiReply,), Error = Rejection> + Clone {
+    get_solver_rewards_request().and_then(move |query: Result<Query, String>| {
+        let db = db.clone();
+        async move {
+            let query = match query {
+                Ok(query) => query,
+                Err(msg) => {
+                    let err = error("InvalidSolverRewardsQuery", msg);
+                    return Result::<_, Infallible>::Ok(with_status(err, StatusCode::BAD_REQUEST));
+                }
+            };
+            let rewards = db.solver_rewards(&query.solver, query.from, query.to).await;
+            Ok(match rewards {
+                Ok(rewards) => with_status(
+                    warp::reply::json(&SolverRewards::new(
+                        query.solver,
+                        query.from,
+                        query.to,
+                        rewards,
+                    )),
+                    StatusCode::OK,
+                ),
+                Err(err) => {
+                    tracing::error!(?err, ?query, "failed to fetch solver rewards");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, warp::test::request};
+
+    #[tokio::test]
+    async fn get_solver_rewards_request_ok() {
+        let filter = get_solver_rewards_request();
+        let solver = H160([1; 20]);
+        let period = "from=2024-01-01T00:00:00Z&to=2024-02-01T00:00:00Z";
+        let path = format!("/v1/solver_rewards?solver=0x{solver:x}&{period}");
+        let result = request()
+            .path(&path)
+            .method("GET")
+            .filter(&filter)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            Ok(Query {
+                solver,
+                from: "2024-01-01T00:00:00Z".parse().unwrap(),
+                to: "2024-02-01T00:00:00Z".parse().unwrap(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn get_solver_rewards_request_err() {
+        let filter = get_solver_rewards_request();
+        let solver = H160([1; 20]);
+
+        let path = format!("/v1/solver_rewards?solver=0x{solver:x}&from=2024-01-01T00:00:00Z");
+        assert!(request().path(&path).filter(&filter).await.is_err());
+
+        let period = "from=2024-02-01T00:00:00Z&to=2024-01-01T00:00:00Z";
+        let path = format!("/v1/solver_rewards?solver=0x{solver:x}&{period}");
+        let result = request().path(&path).filter(&filter).await.unwrap();
+        assert!(result.is_err());
+
+        let period = "from=2024-01-01T00:00:00Z&to=2024-02-01T00:00:01Z";
+        let path = format!("/v1/solver_rewards?solver=0x{solver:x}&{period}");
+        let result = request().path(&path).filter(&filter).await.unwrap();
+        assert!(result.is_err());
+    }
+}
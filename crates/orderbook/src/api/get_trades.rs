@@ -1,22 +1,39 @@
 use {
     crate::database::{
-        trades::{TradeFilter, TradeRetrieving},
+        trades::{TradeCursor, TradeFilter, TradeRetrieving},
         Postgres,
     },
     anyhow::{Context, Result},
+    chrono::{DateTime, Utc},
     model::order::OrderUid,
-    primitive_types::H160,
+    primitive_types::{H160, H256},
     serde::Deserialize,
+    serde_with::{serde_as, DisplayFromStr},
     shared::api::{error, ApiReply},
     std::convert::Infallible,
     warp::{hyper::StatusCode, reply::with_status, Filter, Rejection},
 };
 
+const MIN_LIMIT: u64 = 1;
+const MAX_LIMIT: u64 = 1000;
+
+#[serde_as]
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct Query {
     pub order_uid: Option<OrderUid>,
     pub owner: Option<H160>,
+    pub sell_token: Option<H160>,
+    pub buy_token: Option<H160>,
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+    pub from_timestamp: Option<DateTime<Utc>>,
+    pub to_timestamp: Option<DateTime<Utc>>,
+    pub tx_hash: Option<H256>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub cursor: Option<TradeCursor>,
+    pub limit: Option<u64>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -29,15 +46,33 @@ impl Query {
         TradeFilter {
             order_uid: self.order_uid,
             owner: self.owner,
+            sell_token: self.sell_token,
+            buy_token: self.buy_token,
+            from_block: self.from_block,
+            to_block: self.to_block,
+            from_timestamp: self.from_timestamp,
+            to_timestamp: self.to_timestamp,
+            tx_hash: self.tx_hash,
+            after: self.cursor,
+            limit: self.limit,
         }
     }
 
     fn validate(&self) -> Result<TradeFilter, TradeFilterError> {
-        match (self.order_uid.as_ref(), self.owner.as_ref()) {
-            (Some(_), None) | (None, Some(_)) => Ok(self.trade_filter()),
-            _ => Err(TradeFilterError::InvalidFilter(
-                "Must specify exactly one of owner and order_uid.".to_owned(),
-            )),
+        match self.limit {
+            Some(limit) if !(MIN_LIMIT..=MAX_LIMIT).contains(&limit) => {
+                Err(TradeFilterError::InvalidFilter(format!(
+                    "The pagination limit is [{MIN_LIMIT},{MAX_LIMIT}]."
+                )))
+            }
+            // Without a limit all matching trades get returned which is only
+            // reasonable when the trades of a single owner or order are requested.
+            None if self.owner.is_none() && self.order_uid.is_none() => {
+                Err(TradeFilterError::InvalidFilter(
+                    "Must specify owner or order_uid when not paginating with a limit.".to_owned(),
+                ))
+            }
+            _ => Ok(self.trade_filter()),
         }
     }
 }
@@ -107,6 +142,44 @@ mod tests {
             .unwrap();
         assert_eq!(result.owner, None);
         assert_eq!(result.order_uid, Some(uid));
+
+        let path = format!("/v1/trades?owner=0x{owner:x}&orderUid={uid}");
+        let result = trade_filter(request().path(path.as_str()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.owner, Some(owner));
+        assert_eq!(result.order_uid, Some(uid));
+
+        let token = H160::from_slice(&hex!("0000000000000000000000000000000000000002"));
+        let tx_hash = H256([3; 32]);
+        let path = format!(
+            "/v1/trades?sellToken=0x{token:x}&buyToken=0x{token:x}&fromBlock=1&toBlock=2&\
+             fromTimestamp=2023-01-01T00:00:00Z&toTimestamp=2023-01-02T00:00:00Z&\
+             txHash=0x{tx_hash:x}&cursor=1-2&limit=10"
+        );
+        let result = trade_filter(request().path(path.as_str()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            result,
+            TradeFilter {
+                sell_token: Some(token),
+                buy_token: Some(token),
+                from_block: Some(1),
+                to_block: Some(2),
+                from_timestamp: Some("2023-01-01T00:00:00Z".parse().unwrap()),
+                to_timestamp: Some("2023-01-02T00:00:00Z".parse().unwrap()),
+                tx_hash: Some(tx_hash),
+                after: Some(TradeCursor {
+                    block_number: 1,
+                    log_index: 2,
+                }),
+                limit: Some(10),
+                ..Default::default()
+            }
+        );
     }
 
     #[tokio::test]
@@ -116,15 +189,20 @@ mod tests {
             request.method("GET").filter(&filter).await
         };
 
-        let owner = H160::from_slice(&hex!("0000000000000000000000000000000000000001"));
-        let uid = OrderUid([1u8; 56]);
-        let path = format!("/v1/trades?owner=0x{owner:x}&orderUid={uid}");
+        let path = "/v1/trades";
+        let result = trade_filter(request().path(path)).await.unwrap();
+        assert!(result.is_err());
 
+        let token = H160::from_slice(&hex!("0000000000000000000000000000000000000002"));
+        let path = format!("/v1/trades?sellToken=0x{token:x}");
         let result = trade_filter(request().path(path.as_str())).await.unwrap();
         assert!(result.is_err());
 
-        let path = "/v1/trades";
-        let result = trade_filter(request().path(path)).await.unwrap();
+        let path = format!("/v1/trades?sellToken=0x{token:x}&limit=1001");
+        let result = trade_filter(request().path(path.as_str())).await.unwrap();
         assert!(result.is_err());
+
+        let path = "/v1/trades?limit=10&cursor=1";
+        assert!(trade_filter(request().path(path)).await.is_err());
     }
 }
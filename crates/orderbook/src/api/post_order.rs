@@ -102,6 +102,10 @@ impl IntoWarpReply for AppDataValidationErrorWrapper {
                 error("InvalidAppData", format!("{:?}", err)),
                 StatusCode::BAD_REQUEST,
             ),
+            AppDataValidationError::InvalidPartnerFee(err) => with_status(
+                error("InvalidPartnerFee", err.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
             AppDataValidationError::Mismatch { provided, actual } => with_status(
                 error(
                     "AppDataHashMismatch",
@@ -268,6 +272,10 @@ impl IntoWarpReply for AddOrderError {
                 super::error("InvalidReplacement", err.to_string()),
                 StatusCode::UNAUTHORIZED,
             ),
+            err @ AddOrderError::ReplacementInBatch => reply::with_status(
+                super::error("ReplacementInBatch", err.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
         }
     }
 }
@@ -0,0 +1,127 @@
+use {
+    crate::orderbook::{AddOrderError, Orderbook},
+    model::{
+        order::{OrderCreation, OrderUid},
+        quote::QuoteId,
+    },
+    serde::Serialize,
+    shared::api::{error, extract_payload_with_max_size, ApiReply, IntoWarpReply},
+    std::{convert::Infallible, sync::Arc},
+    warp::{hyper::StatusCode, reply::with_status, Filter, Rejection, Reply},
+};
+
+/// Maximum number of orders that can be placed with a single request.
+const MAX_BATCH_SIZE: usize = 100;
+
+/// Large enough for a full batch of orders with app data.
+const MAX_JSON_BODY_PAYLOAD: u64 = 1024 * 1024;
+
+pub fn request() -> impl Filter<Extract = (Vec<OrderCreation>,), Error = Rejection> + Clone {
+    warp::path!("v1" / "orders" / "batch")
+        .and(warp::post())
+        .and(extract_payload_with_max_size(MAX_JSON_BODY_PAYLOAD))
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+enum OrderResult {
+    Created { uid: OrderUid },
+    Failed { error: serde_json::Value },
+}
+
+impl OrderResult {
+    async fn new(result: Result<(OrderUid, Option<QuoteId>), AddOrderError>) -> Self {
+        match result {
+            Ok((uid, _)) => Self::Created { uid },
+            Err(err) => {
+                // Reuse the error bodies of the single order endpoint.
+                let body = shared::api::response_body(err.into_warp_reply().into_response()).await;
+                Self::Failed {
+                    error: serde_json::from_slice(&body).unwrap_or_default(),
+                }
+            }
+        }
+    }
+}
+
+pub async fn response(
+    result: Result<Vec<Result<(OrderUid, Option<QuoteId>), AddOrderError>>, AddOrderError>,
+) -> ApiReply {
+    let results = match result {
+        Ok(results) => results,
+        Err(err) => return err.into_warp_reply(),
+    };
+    let mut body = Vec::with_capacity(results.len());
+    for result in results {
+        body.push(OrderResult::new(result).await);
+    }
+    with_status(warp::reply::json(&body), StatusCode::OK)
+}
+
+pub fn filter(
+    orderbook: Arc<Orderbook>,
+) -> impl Filter<Extract = (ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |orders: Vec<OrderCreation>| {
+        let orderbook = orderbook.clone();
+        async move {
+            if orders.len() > MAX_BATCH_SIZE {
+                let err = error(
+                    "TooManyOrders",
+                    format!("at most {MAX_BATCH_SIZE} orders can be placed at once"),
+                );
+                return Result::<_, Infallible>::Ok(with_status(err, StatusCode::BAD_REQUEST));
+            }
+            let result = orderbook.add_orders(orders).await;
+            match &result {
+                Ok(results) => tracing::debug!(?results, "order batch processed"),
+                Err(err) => tracing::debug!(?err, "error creating order batch"),
+            }
+            Ok(response(result).await)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json, shared::api::response_body, warp::test::request};
+
+    #[tokio::test]
+    async fn post_orders_request_ok() {
+        let filter = super::request();
+        let orders = vec![OrderCreation::default(), OrderCreation::default()];
+        let result = request()
+            .path("/v1/orders/batch")
+            .method("POST")
+            .header("content-type", "application/json")
+            .json(&orders)
+            .filter(&filter)
+            .await
+            .unwrap();
+        assert_eq!(result, orders);
+    }
+
+    #[tokio::test]
+    async fn post_orders_response() {
+        let response = response(Ok(vec![
+            Ok((OrderUid([1; 56]), Some(42))),
+            Err(AddOrderError::DuplicatedOrder),
+        ]))
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_slice(&response_body(response).await).unwrap();
+        assert_eq!(
+            body,
+            json!([
+                { "uid": OrderUid([1; 56]) },
+                {
+                    "error": {
+                        "errorType": "DuplicatedOrder",
+                        "description": "order already exists",
+                    },
+                },
+            ])
+        );
+    }
+}
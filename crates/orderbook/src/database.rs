@@ -1,8 +1,11 @@
 pub mod app_data;
 pub mod auctions;
+mod native_prices;
+pub mod order_events;
 pub mod orders;
 pub mod quotes;
 pub mod solver_competition;
+pub mod solver_rewards;
 pub mod total_surplus;
 pub mod trades;
 
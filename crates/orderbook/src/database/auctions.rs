@@ -1,4 +1,4 @@
-use {crate::dto, anyhow::Result};
+use {crate::dto, anyhow::Result, database::byte_array::ByteArray, model::order::OrderUid};
 
 impl super::Postgres {
     pub async fn most_recent_auction(&self) -> Result<Option<dto::AuctionWithId>> {
@@ -16,4 +16,20 @@ impl super::Postgres {
         let auction = dto::AuctionWithId { id, auction };
         Ok(Some(auction))
     }
+
+    pub async fn current_auction_includes_order(
+        &self,
+        uid: &OrderUid,
+    ) -> Result<Option<dto::order_status::CurrentAuction>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["current_auction_includes_order"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let auction =
+            database::auction::most_recent_includes_order(&mut ex, &ByteArray(uid.0)).await?;
+        Ok(auction
+            .map(|(id, includes_order)| dto::order_status::CurrentAuction { id, includes_order }))
+    }
 }
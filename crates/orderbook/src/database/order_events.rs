@@ -0,0 +1,59 @@
+use {
+    super::Postgres,
+    crate::{dto, order_events::Event},
+    anyhow::{Context, Result},
+    chrono::{DateTime, Utc},
+    database::byte_array::ByteArray,
+    model::order::OrderUid,
+    primitive_types::H160,
+};
+
+impl Postgres {
+    /// All events of an order.
+    pub async fn order_events(&self, uid: &OrderUid) -> Result<Vec<dto::OrderEvent>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["order_events"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let events = database::order_events::fetch_order_events(&mut ex, &ByteArray(uid.0), None)
+            .await
+            .context("fetch_order_events")?;
+        Ok(events.into_iter().map(Into::into).collect())
+    }
+
+    /// The most recent event of an order.
+    pub async fn latest_order_event(&self, uid: &OrderUid) -> Result<Option<dto::OrderEvent>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["latest_order_event"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let event = database::order_events::fetch_latest_order_event(&mut ex, &ByteArray(uid.0))
+            .await
+            .context("fetch_latest_order_event")?;
+        Ok(event.map(Into::into))
+    }
+
+    /// Events of all orders registered at or after the provided timestamp.
+    pub async fn order_events_since(&self, since: DateTime<Utc>) -> Result<Vec<Event>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["order_events_since"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let events = database::order_events::fetch_order_events_since(&mut ex, since)
+            .await
+            .context("fetch_order_events_since")?;
+        Ok(events
+            .into_iter()
+            .map(|event| Event {
+                owner: H160(event.owner.0),
+                event: event.event.into(),
+            })
+            .collect())
+    }
+}
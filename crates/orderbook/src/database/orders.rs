@@ -49,7 +49,7 @@ use {
         order_validation::{is_order_outside_market_price, Amounts, LimitOrderCounting},
     },
     sqlx::{types::BigDecimal, Connection, PgConnection},
-    std::convert::TryInto,
+    std::{collections::HashSet, convert::TryInto},
 };
 
 #[cfg_attr(test, mockall::automock)]
@@ -57,6 +57,12 @@ use {
 pub trait OrderStoring: Send + Sync {
     async fn insert_order(&self, order: &Order, quote: Option<Quote>)
         -> Result<(), InsertionError>;
+    /// Inserts all orders in a single transaction. Returns for every order
+    /// whether it got inserted or was skipped because it already exists.
+    async fn insert_orders(
+        &self,
+        orders: &[(Order, Option<Quote>)],
+    ) -> Result<Vec<bool>, InsertionError>;
     async fn cancel_orders(&self, order_uids: Vec<OrderUid>, now: DateTime<Utc>) -> Result<()>;
     async fn cancel_order(&self, order_uid: &OrderUid, now: DateTime<Utc>) -> Result<()>;
     async fn replace_order(
@@ -109,6 +115,7 @@ async fn cancel_order(
             order_uid: uid,
             timestamp: now,
             label: OrderEventLabel::Cancelled,
+            reason: None,
         },
     )
     .await?;
@@ -123,10 +130,57 @@ async fn insert_order(order: &Order, ex: &mut PgConnection) -> Result<(), Insert
             order_uid: ByteArray(order.metadata.uid.0),
             timestamp: Utc::now(),
             label: OrderEventLabel::Created,
+            reason: None,
         },
     )
     .await?;
-    let interactions = std::iter::empty()
+    let interactions = interactions_into(order);
+    let order = order_into(order);
+
+    database::orders::insert_order(ex, &order)
+        .await
+        .map_err(|err| {
+            if database::orders::is_duplicate_record_error(&err) {
+                InsertionError::DuplicatedRecord
+            } else {
+                InsertionError::DbError(err)
+            }
+        })?;
+    database::orders::insert_interactions(ex, &order.uid, &interactions)
+        .await
+        .map_err(InsertionError::DbError)?;
+
+    Ok(())
+}
+
+fn order_into(order: &Order) -> database::orders::Order {
+    database::orders::Order {
+        uid: ByteArray(order.metadata.uid.0),
+        owner: ByteArray(order.metadata.owner.0),
+        creation_timestamp: order.metadata.creation_date,
+        sell_token: ByteArray(order.data.sell_token.0),
+        buy_token: ByteArray(order.data.buy_token.0),
+        receiver: order.data.receiver.map(|h160| ByteArray(h160.0)),
+        sell_amount: u256_to_big_decimal(&order.data.sell_amount),
+        buy_amount: u256_to_big_decimal(&order.data.buy_amount),
+        valid_to: order.data.valid_to as i64,
+        app_data: ByteArray(order.data.app_data.0),
+        fee_amount: u256_to_big_decimal(&order.data.fee_amount),
+        kind: order_kind_into(order.data.kind),
+        class: order_class_into(&order.metadata.class),
+        partially_fillable: order.data.partially_fillable,
+        signature: order.signature.to_bytes(),
+        signing_scheme: signing_scheme_into(order.signature.scheme()),
+        settlement_contract: ByteArray(order.metadata.settlement_contract.0),
+        sell_token_balance: sell_token_source_into(order.data.sell_token_balance),
+        buy_token_balance: buy_token_destination_into(order.data.buy_token_balance),
+        full_fee_amount: u256_to_big_decimal(&order.metadata.full_fee_amount),
+        cancellation_timestamp: None,
+    }
+}
+
+fn interactions_into(order: &Order) -> Vec<database::orders::Interaction> {
+    std::iter::empty()
         .chain(
             order
                 .interactions
@@ -153,46 +207,7 @@ async fn insert_order(order: &Order, ex: &mut PgConnection) -> Result<(), Insert
                 execution,
             },
         )
-        .collect::<Vec<_>>();
-
-    let order = database::orders::Order {
-        uid: ByteArray(order.metadata.uid.0),
-        owner: ByteArray(order.metadata.owner.0),
-        creation_timestamp: order.metadata.creation_date,
-        sell_token: ByteArray(order.data.sell_token.0),
-        buy_token: ByteArray(order.data.buy_token.0),
-        receiver: order.data.receiver.map(|h160| ByteArray(h160.0)),
-        sell_amount: u256_to_big_decimal(&order.data.sell_amount),
-        buy_amount: u256_to_big_decimal(&order.data.buy_amount),
-        valid_to: order.data.valid_to as i64,
-        app_data: ByteArray(order.data.app_data.0),
-        fee_amount: u256_to_big_decimal(&order.data.fee_amount),
-        kind: order_kind_into(order.data.kind),
-        class: order_class_into(&order.metadata.class),
-        partially_fillable: order.data.partially_fillable,
-        signature: order.signature.to_bytes(),
-        signing_scheme: signing_scheme_into(order.signature.scheme()),
-        settlement_contract: ByteArray(order.metadata.settlement_contract.0),
-        sell_token_balance: sell_token_source_into(order.data.sell_token_balance),
-        buy_token_balance: buy_token_destination_into(order.data.buy_token_balance),
-        full_fee_amount: u256_to_big_decimal(&order.metadata.full_fee_amount),
-        cancellation_timestamp: None,
-    };
-
-    database::orders::insert_order(ex, &order)
-        .await
-        .map_err(|err| {
-            if database::orders::is_duplicate_record_error(&err) {
-                InsertionError::DuplicatedRecord
-            } else {
-                InsertionError::DbError(err)
-            }
-        })?;
-    database::orders::insert_interactions(ex, &order.uid, &interactions)
-        .await
-        .map_err(InsertionError::DbError)?;
-
-    Ok(())
+        .collect()
 }
 
 async fn insert_quote(
@@ -241,6 +256,49 @@ impl OrderStoring for Postgres {
         Ok(())
     }
 
+    async fn insert_orders(
+        &self,
+        orders: &[(Order, Option<Quote>)],
+    ) -> Result<Vec<bool>, InsertionError> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["insert_orders"])
+            .start_timer();
+
+        let mut ex = self.pool.begin().await?;
+
+        // Only the orders that actually got inserted (i.e. didn't exist already
+        // and weren't inserted concurrently) get their associated data stored.
+        let mut new_uids: HashSet<_> = database::orders::insert_new_orders(
+            &mut ex,
+            &orders
+                .iter()
+                .map(|(order, _)| order_into(order))
+                .collect::<Vec<_>>(),
+        )
+        .await?
+        .into_iter()
+        .collect();
+        let mut inserted = Vec::with_capacity(orders.len());
+        for (order, quote) in orders {
+            let uid = ByteArray(order.metadata.uid.0);
+            // Orders appearing multiple times in the batch are only stored once.
+            let new = new_uids.remove(&uid);
+            inserted.push(new);
+            if !new {
+                continue;
+            }
+            database::orders::insert_interactions(&mut ex, &uid, &interactions_into(order)).await?;
+            if let Some(quote) = quote {
+                insert_quote(&order.metadata.uid, quote, &mut ex).await?;
+            }
+            Self::insert_order_app_data(order, &mut ex).await?;
+        }
+
+        ex.commit().await?;
+        Ok(inserted)
+    }
+
     async fn cancel_orders(&self, order_uids: Vec<OrderUid>, now: DateTime<Utc>) -> Result<()> {
         let _timer = super::Metrics::get()
             .database_queries
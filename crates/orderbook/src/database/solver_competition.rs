@@ -1,14 +1,28 @@
 use {
     super::Postgres,
-    crate::solver_competition::{Identifier, LoadSolverCompetitionError, SolverCompetitionStoring},
+    crate::{
+        dto::{
+            order_status::{Competition, Solution},
+            solver_competition::{CompetitionSummary, Participant},
+        },
+        solver_competition::{
+            CompetitionFilter,
+            Identifier,
+            LoadSolverCompetitionError,
+            SolverCompetitionStoring,
+        },
+    },
     anyhow::{Context, Result},
-    database::byte_array::ByteArray,
+    database::{byte_array::ByteArray, settlement_scores::ScoresFilter},
     model::{
         auction::AuctionId,
-        solver_competition::{SolverCompetitionAPI, SolverCompetitionDB},
+        order::OrderUid,
+        solver_competition::{self, SolverCompetitionAPI, SolverCompetitionDB},
     },
-    primitive_types::H256,
+    number::conversions::big_decimal_to_u256,
+    primitive_types::{H160, H256},
     sqlx::types::JsonValue,
+    std::collections::HashMap,
 };
 
 fn deserialize_solver_competition(
@@ -25,6 +39,59 @@ fn deserialize_solver_competition(
     })
 }
 
+impl Postgres {
+    /// Up to `limit` competitions of the `max_auctions` most recent auctions
+    /// that contained the order, together with the solutions proposing to
+    /// execute it.
+    pub async fn recent_competitions_with_order(
+        &self,
+        uid: &OrderUid,
+        max_auctions: i64,
+        limit: i64,
+    ) -> Result<Vec<Competition>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["recent_competitions_with_order"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let rows = database::solver_competition::load_recent_by_order(
+            &mut ex,
+            &ByteArray(uid.0),
+            max_auctions,
+            limit,
+        )
+        .await
+        .context("solver_competition::load_recent_by_order")?;
+        rows.into_iter()
+            .map(|row| {
+                let competition: SolverCompetitionDB =
+                    serde_json::from_value(row.json).context("deserialize SolverCompetitionDB")?;
+                Ok(Competition {
+                    auction_id: row.id,
+                    transaction_hash: row.tx_hash.map(|hash| H256(hash.0)),
+                    solutions: competition
+                        .solutions
+                        .into_iter()
+                        .filter(|solution| {
+                            solution.orders.iter().any(|order| match order {
+                                solver_competition::Order::Colocated { id, .. }
+                                | solver_competition::Order::Legacy { id, .. } => id == uid,
+                            })
+                        })
+                        .map(|solution| Solution {
+                            solver: solution.solver,
+                            solver_address: solution.solver_address,
+                            ranking: solution.ranking,
+                            is_winner: solution.is_winner,
+                        })
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+}
+
 #[async_trait::async_trait]
 impl SolverCompetitionStoring for Postgres {
     async fn load_competition(
@@ -79,6 +146,79 @@ impl SolverCompetitionStoring for Postgres {
             })
             .ok_or(LoadSolverCompetitionError::NotFound)?
     }
+
+    async fn load_competitions(
+        &self,
+        filter: CompetitionFilter,
+        before: Option<AuctionId>,
+        limit: u64,
+    ) -> Result<Vec<CompetitionSummary>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["load_solver_competitions"])
+            .start_timer();
+
+        let filter = ScoresFilter {
+            solver: filter.solver.map(|solver| ByteArray(solver.0)),
+            from_block: filter.from_block.map(i64::try_from).transpose()?,
+            to_block: filter.to_block.map(i64::try_from).transpose()?,
+            winner_only: filter.winner_only,
+        };
+        let mut ex = self.pool.acquire().await?;
+        let scores = database::settlement_scores::fetch_filtered(
+            &mut ex,
+            &filter,
+            before,
+            limit.try_into()?,
+        )
+        .await
+        .context("settlement_scores::fetch_filtered")?;
+        let ids: Vec<_> = scores.iter().map(|score| score.auction_id).collect();
+        let mut competitions: HashMap<_, _> =
+            database::solver_competition::load_by_ids(&mut ex, &ids)
+                .await
+                .context("solver_competition::load_by_ids")?
+                .into_iter()
+                .map(|row| (row.id, row))
+                .collect();
+
+        scores
+            .into_iter()
+            .filter_map(|score| {
+                let row = competitions.remove(&score.auction_id)?;
+                Some((score, row))
+            })
+            .map(|(score, row)| {
+                let competition: SolverCompetitionDB =
+                    serde_json::from_value(row.json).context("deserialize SolverCompetitionDB")?;
+                Ok(CompetitionSummary {
+                    auction_id: score.auction_id,
+                    simulation_block: score
+                        .simulation_block
+                        .try_into()
+                        .context("simulation_block")?,
+                    block_deadline: score.block_deadline.try_into().context("block_deadline")?,
+                    winner: H160(score.winner.0),
+                    winning_score: big_decimal_to_u256(&score.winning_score)
+                        .context("winning_score")?,
+                    reference_score: big_decimal_to_u256(&score.reference_score)
+                        .context("reference_score")?,
+                    transaction_hash: row.tx_hash.map(|hash| H256(hash.0)),
+                    participants: competition
+                        .solutions
+                        .into_iter()
+                        .map(|solution| Participant {
+                            solver: solution.solver,
+                            solver_address: solution.solver_address,
+                            score: solution.score.map(|score| score.score()),
+                            ranking: solution.ranking,
+                            is_winner: solution.is_winner,
+                        })
+                        .collect(),
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
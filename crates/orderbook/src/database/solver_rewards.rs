@@ -0,0 +1,23 @@
+use {
+    anyhow::Result,
+    chrono::{DateTime, Utc},
+    database::{byte_array::ByteArray, solver_rewards::Reward},
+    primitive_types::H160,
+};
+
+impl super::Postgres {
+    pub async fn solver_rewards(
+        &self,
+        solver: &H160,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Reward>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["solver_rewards"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        Ok(database::solver_rewards::fetch(&mut ex, &ByteArray(solver.0), from, to).await?)
+    }
+}
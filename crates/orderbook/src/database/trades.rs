@@ -1,13 +1,18 @@
 use {
     crate::database::Postgres,
     anyhow::{Context, Result},
-    database::{byte_array::ByteArray, trades::TradesQueryRow},
+    chrono::{DateTime, Utc},
+    database::{byte_array::ByteArray, events::EventIndex, trades::TradesQueryRow},
     ethcontract::H160,
     futures::{stream::TryStreamExt, StreamExt},
     model::{order::OrderUid, trade::Trade},
     number::conversions::big_decimal_to_big_uint,
     primitive_types::H256,
-    std::convert::TryInto,
+    std::{
+        convert::TryInto,
+        fmt::{self, Display},
+        str::FromStr,
+    },
 };
 
 #[async_trait::async_trait]
@@ -20,6 +25,44 @@ pub trait TradeRetrieving: Send + Sync {
 pub struct TradeFilter {
     pub owner: Option<H160>,
     pub order_uid: Option<OrderUid>,
+    pub sell_token: Option<H160>,
+    pub buy_token: Option<H160>,
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+    pub from_timestamp: Option<DateTime<Utc>>,
+    pub to_timestamp: Option<DateTime<Utc>>,
+    pub tx_hash: Option<H256>,
+    /// Only return trades after the one this cursor points to.
+    pub after: Option<TradeCursor>,
+    /// Maximum number of trades to return.
+    pub limit: Option<u64>,
+}
+
+/// Points to a trade by its event index. Trades are ordered by their event
+/// index so the cursor of the last trade of a page can be used to fetch the
+/// next page. Its string representation is `<blockNumber>-<logIndex>`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TradeCursor {
+    pub block_number: u64,
+    pub log_index: u64,
+}
+
+impl FromStr for TradeCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (block_number, log_index) = s.split_once('-').context("missing separator")?;
+        Ok(Self {
+            block_number: block_number.parse().context("block number")?,
+            log_index: log_index.parse().context("log index")?,
+        })
+    }
+}
+
+impl Display for TradeCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.block_number, self.log_index)
+    }
 }
 
 #[async_trait::async_trait]
@@ -30,18 +73,36 @@ impl TradeRetrieving for Postgres {
             .with_label_values(&["trades"])
             .start_timer();
 
+        let db_filter = database::trades::TradesFilter {
+            owner: filter.owner.map(|owner| ByteArray(owner.0)),
+            order_uid: filter.order_uid.map(|uid| ByteArray(uid.0)),
+            sell_token: filter.sell_token.map(|token| ByteArray(token.0)),
+            buy_token: filter.buy_token.map(|token| ByteArray(token.0)),
+            from_block: filter.from_block.map(i64::try_from).transpose()?,
+            to_block: filter.to_block.map(i64::try_from).transpose()?,
+            from_timestamp: filter.from_timestamp,
+            to_timestamp: filter.to_timestamp,
+            tx_hash: filter.tx_hash.map(|hash| ByteArray(hash.0)),
+        };
+        let after = filter
+            .after
+            .map(|cursor| -> Result<_> {
+                Ok(EventIndex {
+                    block_number: cursor.block_number.try_into()?,
+                    log_index: cursor.log_index.try_into()?,
+                })
+            })
+            .transpose()?;
+        let limit = filter.limit.map(i64::try_from).transpose()?;
+
         let mut ex = self.pool.acquire().await?;
-        database::trades::trades(
-            &mut ex,
-            filter.owner.map(|owner| ByteArray(owner.0)).as_ref(),
-            filter.order_uid.map(|uid| ByteArray(uid.0)).as_ref(),
-        )
-        .map(|result| match result {
-            Ok(row) => trade_from(row),
-            Err(err) => Err(anyhow::Error::from(err)),
-        })
-        .try_collect()
-        .await
+        database::trades::trades(&mut ex, &db_filter, after, limit)
+            .map(|result| match result {
+                Ok(row) => trade_from(row),
+                Err(err) => Err(anyhow::Error::from(err)),
+            })
+            .try_collect()
+            .await
     }
 }
 
@@ -84,4 +145,16 @@ mod tests {
     fn convert_trade() {
         trade_from(TradesQueryRow::default()).unwrap();
     }
+
+    #[test]
+    fn cursor_roundtrip() {
+        let cursor = TradeCursor {
+            block_number: 17_000_000,
+            log_index: 42,
+        };
+        assert_eq!(cursor.to_string(), "17000000-42");
+        assert_eq!("17000000-42".parse::<TradeCursor>().unwrap(), cursor);
+        assert!("17000000".parse::<TradeCursor>().is_err());
+        assert!("17000000-".parse::<TradeCursor>().is_err());
+    }
 }
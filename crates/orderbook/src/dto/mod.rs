@@ -1,7 +1,15 @@
 pub mod auction;
 pub mod order;
+pub mod order_event;
+pub mod order_status;
+pub mod solver_competition;
+pub mod solver_rewards;
 
 pub use {
     auction::{Auction, AuctionId, AuctionWithId},
     order::Order,
+    order_event::OrderEvent,
+    order_status::OrderStatusDetails,
+    solver_competition::CompetitionSummary,
+    solver_rewards::SolverRewards,
 };
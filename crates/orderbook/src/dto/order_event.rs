@@ -0,0 +1,87 @@
+use {
+    chrono::{DateTime, Utc},
+    database::order_events::{OrderEventLabel, OrderFilterReason},
+    model::order::OrderUid,
+    serde::{Deserialize, Serialize},
+};
+
+/// A single event in the lifecycle of an order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderEvent {
+    pub order_uid: OrderUid,
+    pub timestamp: DateTime<Utc>,
+    pub label: Label,
+    /// Why the order was filtered or is invalid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<FilterReason>,
+}
+
+/// Replicates [`database::order_events::OrderEventLabel`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Label {
+    Created,
+    Ready,
+    Filtered,
+    Invalid,
+    Executing,
+    Considered,
+    Traded,
+    Cancelled,
+}
+
+/// Replicates [`database::order_events::OrderFilterReason`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FilterReason {
+    BannedUser,
+    InvalidSignature,
+    UnsupportedToken,
+    InsufficientBalance,
+    DustOrder,
+    MissingPrice,
+    OutOfMarket,
+    Other,
+}
+
+impl From<database::order_events::OrderEvent> for OrderEvent {
+    fn from(event: database::order_events::OrderEvent) -> Self {
+        Self {
+            order_uid: OrderUid(event.order_uid.0),
+            timestamp: event.timestamp,
+            label: event.label.into(),
+            reason: event.reason.map(Into::into),
+        }
+    }
+}
+
+impl From<OrderEventLabel> for Label {
+    fn from(label: OrderEventLabel) -> Self {
+        match label {
+            OrderEventLabel::Created => Self::Created,
+            OrderEventLabel::Ready => Self::Ready,
+            OrderEventLabel::Filtered => Self::Filtered,
+            OrderEventLabel::Invalid => Self::Invalid,
+            OrderEventLabel::Executing => Self::Executing,
+            OrderEventLabel::Considered => Self::Considered,
+            OrderEventLabel::Traded => Self::Traded,
+            OrderEventLabel::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<OrderFilterReason> for FilterReason {
+    fn from(reason: OrderFilterReason) -> Self {
+        match reason {
+            OrderFilterReason::BannedUser => Self::BannedUser,
+            OrderFilterReason::InvalidSignature => Self::InvalidSignature,
+            OrderFilterReason::UnsupportedToken => Self::UnsupportedToken,
+            OrderFilterReason::InsufficientBalance => Self::InsufficientBalance,
+            OrderFilterReason::DustOrder => Self::DustOrder,
+            OrderFilterReason::MissingPrice => Self::MissingPrice,
+            OrderFilterReason::OutOfMarket => Self::OutOfMarket,
+            OrderFilterReason::Other => Self::Other,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use {
+    super::{AuctionId, OrderEvent},
+    model::order::OrderStatus,
+    primitive_types::{H160, H256},
+    serde::Serialize,
+};
+
+/// Explains where an order currently stands in the settlement process.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderStatusDetails {
+    pub status: OrderStatus,
+    /// The most recent event in the lifecycle of the order, e.g. whether it
+    /// got filtered from the last auction.
+    pub latest_event: Option<OrderEvent>,
+    /// The auction that is currently being solved, if there is one.
+    pub current_auction: Option<CurrentAuction>,
+    /// The recent competitions whose auction contained the order, newest
+    /// first.
+    pub recent_competitions: Vec<Competition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentAuction {
+    pub id: AuctionId,
+    pub includes_order: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Competition {
+    pub auction_id: AuctionId,
+    pub transaction_hash: Option<H256>,
+    /// The solutions that proposed to execute the order.
+    pub solutions: Vec<Solution>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Solution {
+    pub solver: String,
+    pub solver_address: H160,
+    pub ranking: usize,
+    pub is_winner: bool,
+}
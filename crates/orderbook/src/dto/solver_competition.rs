@@ -0,0 +1,41 @@
+use {
+    super::AuctionId,
+    number::serialization::HexOrDecimalU256,
+    primitive_types::{H160, H256, U256},
+    serde::Serialize,
+    serde_with::serde_as,
+};
+
+/// A past solver competition together with the scores of its participants and
+/// the outcome of the winning settlement.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompetitionSummary {
+    pub auction_id: AuctionId,
+    /// The block the competing solutions were simulated at.
+    pub simulation_block: u64,
+    /// The block the winner has to settle the auction by.
+    pub block_deadline: u64,
+    pub winner: H160,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub winning_score: U256,
+    /// The score of the runner up, zero if there was none.
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub reference_score: U256,
+    /// The transaction that settled the auction on-chain, if it got settled.
+    pub transaction_hash: Option<H256>,
+    pub participants: Vec<Participant>,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Participant {
+    pub solver: String,
+    pub solver_address: H160,
+    #[serde_as(as = "Option<HexOrDecimalU256>")]
+    pub score: Option<U256>,
+    pub ranking: usize,
+    pub is_winner: bool,
+}
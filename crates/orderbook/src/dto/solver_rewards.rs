@@ -0,0 +1,71 @@
+use {
+    bigdecimal::BigDecimal,
+    chrono::{DateTime, Utc},
+    number::{conversions::big_decimal_to_u256, serialization::HexOrDecimalU256},
+    primitive_types::{H160, U256},
+    serde::Serialize,
+    serde_with::{serde_as, DisplayFromStr},
+};
+
+/// The rewards of a solver for the auctions whose deadline block is in an
+/// accounting period.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolverRewards {
+    pub solver: H160,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// The sum of all rewards in wei, negative if penalties outweigh rewards.
+    #[serde_as(as = "DisplayFromStr")]
+    pub total: BigDecimal,
+    pub auctions: Vec<AuctionReward>,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuctionReward {
+    pub auction_id: i64,
+    pub block_deadline: u64,
+    pub timestamp: DateTime<Utc>,
+    pub settled: bool,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub score: U256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub reference_score: U256,
+    /// The reward in wei, negative for penalties.
+    #[serde_as(as = "DisplayFromStr")]
+    pub reward: BigDecimal,
+}
+
+impl SolverRewards {
+    pub fn new(
+        solver: H160,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        rewards: Vec<database::solver_rewards::Reward>,
+    ) -> Self {
+        Self {
+            solver,
+            from,
+            to,
+            total: rewards.iter().map(|reward| &reward.reward).sum(),
+            auctions: rewards.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<database::solver_rewards::Reward> for AuctionReward {
+    fn from(reward: database::solver_rewards::Reward) -> Self {
+        Self {
+            auction_id: reward.auction_id,
+            block_deadline: reward.block_deadline.try_into().unwrap_or_default(),
+            timestamp: reward.block_timestamp,
+            settled: reward.settled,
+            score: big_decimal_to_u256(&reward.score).unwrap_or_default(),
+            reference_score: big_decimal_to_u256(&reward.reference_score).unwrap_or_default(),
+            reward: reward.reward,
+        }
+    }
+}
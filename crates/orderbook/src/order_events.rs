@@ -0,0 +1,259 @@
+//! Streams new order events to the API subscribers. A single task polls the
+//! database for new events, so the number of queries doesn't grow with the
+//! number of subscribers, and hands every subscriber only the events it asked
+//! for.
+
+use {
+    crate::{database::Postgres, dto},
+    chrono::{DateTime, Utc},
+    futures::{stream, Stream},
+    model::order::OrderUid,
+    primitive_types::H160,
+    std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+    tokio::sync::mpsc::{self, error::TrySendError},
+};
+
+/// How often the database gets checked for new events.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum number of concurrent subscriptions.
+const MAX_SUBSCRIBERS: usize = 1_000;
+
+/// How many events a subscriber can fall behind before its subscription ends.
+const CHANNEL_CAPACITY: usize = 1_000;
+
+/// An order event together with the owner of the order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Event {
+    pub owner: H160,
+    pub event: dto::OrderEvent,
+}
+
+/// The events a subscriber is interested in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Subscription {
+    /// The events of a single order.
+    Order(OrderUid),
+    /// The events of all orders of an owner.
+    Owner(H160),
+}
+
+impl Subscription {
+    fn matches(&self, event: &Event) -> bool {
+        match self {
+            Self::Order(uid) => event.event.order_uid == *uid,
+            Self::Owner(owner) => event.owner == *owner,
+        }
+    }
+}
+
+struct Subscriber {
+    subscription: Subscription,
+    sender: mpsc::Sender<dto::OrderEvent>,
+}
+
+pub struct OrderEvents {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+}
+
+impl OrderEvents {
+    /// Creates the dispatcher and spawns the task polling the database.
+    pub fn new(db: Postgres) -> Self {
+        let subscribers = Arc::new(Mutex::new(Vec::new()));
+        tokio::task::spawn(poll_forever(db, subscribers.clone()));
+        Self { subscribers }
+    }
+
+    /// Subscribes to the matching events registered from now on. Returns
+    /// `None` if there are too many subscribers already.
+    ///
+    /// The stream ends if the subscriber falls too far behind, so that clients
+    /// reconnect instead of silently missing events.
+    pub fn subscribe(
+        &self,
+        subscription: Subscription,
+    ) -> Option<impl Stream<Item = dto::OrderEvent> + Send + 'static> {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|subscriber| !subscriber.sender.is_closed());
+        if subscribers.len() >= MAX_SUBSCRIBERS {
+            return None;
+        }
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        subscribers.push(Subscriber {
+            subscription,
+            sender,
+        });
+        Some(stream::unfold(receiver, |mut receiver| async move {
+            let event = receiver.recv().await?;
+            Some((event, receiver))
+        }))
+    }
+}
+
+async fn poll_forever(db: Postgres, subscribers: Arc<Mutex<Vec<Subscriber>>>) {
+    let mut cursor = Cursor::new(Utc::now());
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let listening = {
+            let mut subscribers = subscribers.lock().unwrap();
+            subscribers.retain(|subscriber| !subscriber.sender.is_closed());
+            !subscribers.is_empty()
+        };
+        if !listening {
+            // Nobody is listening, so there is no need to query the events.
+            cursor = Cursor::new(Utc::now());
+            continue;
+        }
+        match db.order_events_since(cursor.timestamp).await {
+            Ok(events) => {
+                let events = cursor.advance(events);
+                dispatch(&mut subscribers.lock().unwrap(), &events);
+            }
+            Err(err) => tracing::warn!(?err, "failed to poll order events"),
+        }
+    }
+}
+
+/// Sends every subscriber the events matching its subscription. Subscribers
+/// that disconnected or fell too far behind get dropped, which ends their
+/// stream once the already queued events were consumed.
+fn dispatch(subscribers: &mut Vec<Subscriber>, events: &[Event]) {
+    subscribers.retain(|subscriber| {
+        events
+            .iter()
+            .filter(|event| subscriber.subscription.matches(event))
+            .all(|event| match subscriber.sender.try_send(event.event) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    tracing::debug!("order event subscriber fell behind");
+                    false
+                }
+                Err(TrySendError::Closed(_)) => false,
+            })
+    });
+}
+
+/// Keeps track of the events that were already broadcast. Multiple events can
+/// share a timestamp, so events get fetched including the latest timestamp
+/// seen so far and the ones that were already broadcast get skipped.
+#[derive(Debug)]
+struct Cursor {
+    timestamp: DateTime<Utc>,
+    seen: Vec<Event>,
+}
+
+impl Cursor {
+    fn new(timestamp: DateTime<Utc>) -> Self {
+        Self {
+            timestamp,
+            seen: Vec::new(),
+        }
+    }
+
+    /// Returns the events that were not seen before and moves the cursor past
+    /// them. Expects the events to be ordered by timestamp.
+    fn advance(&mut self, events: Vec<Event>) -> Vec<Event> {
+        let new: Vec<_> = events
+            .into_iter()
+            .filter(|event| !self.seen.contains(event))
+            .collect();
+        if let Some(latest) = new.last() {
+            if latest.event.timestamp > self.timestamp {
+                self.timestamp = latest.event.timestamp;
+                self.seen.clear();
+            }
+            self.seen.extend(
+                new.iter()
+                    .filter(|event| event.event.timestamp == self.timestamp),
+            );
+        }
+        new
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, chrono::TimeZone};
+
+    fn event(uid: u8, seconds: i64) -> Event {
+        Event {
+            owner: H160([1; 20]),
+            event: dto::OrderEvent {
+                order_uid: OrderUid([uid; 56]),
+                timestamp: Utc.timestamp_opt(seconds, 0).unwrap(),
+                label: dto::order_event::Label::Created,
+                reason: None,
+            },
+        }
+    }
+
+    #[test]
+    fn cursor_skips_seen_events() {
+        let mut cursor = Cursor::new(Utc.timestamp_opt(0, 0).unwrap());
+
+        assert_eq!(
+            cursor.advance(vec![event(1, 1), event(2, 2)]),
+            vec![event(1, 1), event(2, 2)]
+        );
+        assert_eq!(cursor.timestamp, event(2, 2).event.timestamp);
+
+        // Another event with the same timestamp as the latest one showed up.
+        assert_eq!(
+            cursor.advance(vec![event(2, 2), event(3, 2)]),
+            vec![event(3, 2)]
+        );
+        assert_eq!(cursor.advance(vec![event(2, 2), event(3, 2)]), vec![]);
+
+        assert_eq!(
+            cursor.advance(vec![event(2, 2), event(3, 2), event(4, 3)]),
+            vec![event(4, 3)]
+        );
+        assert_eq!(cursor.seen, vec![event(4, 3)]);
+    }
+
+    #[test]
+    fn dispatch_filters_per_subscriber() {
+        let subscriber = |subscription, capacity| {
+            let (sender, receiver) = mpsc::channel(capacity);
+            (
+                Subscriber {
+                    subscription,
+                    sender,
+                },
+                receiver,
+            )
+        };
+        let other_owner = Event {
+            owner: H160([2; 20]),
+            ..event(3, 3)
+        };
+        let (by_order, mut by_order_events) =
+            subscriber(Subscription::Order(OrderUid([1; 56])), 10);
+        let (by_owner, mut by_owner_events) = subscriber(Subscription::Owner(H160([1; 20])), 10);
+        let (lagging, _lagging_events) = subscriber(Subscription::Owner(H160([1; 20])), 1);
+        let (closed, _) = subscriber(Subscription::Owner(H160([2; 20])), 10);
+        let mut subscribers = vec![by_order, by_owner, lagging, closed];
+
+        dispatch(&mut subscribers, &[event(1, 1), event(2, 2), other_owner]);
+
+        let subscriptions = subscribers
+            .iter()
+            .map(|subscriber| subscriber.subscription)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            subscriptions,
+            vec![
+                Subscription::Order(OrderUid([1; 56])),
+                Subscription::Owner(H160([1; 20]))
+            ]
+        );
+        assert_eq!(by_order_events.try_recv().unwrap(), event(1, 1).event);
+        assert!(by_order_events.try_recv().is_err());
+        assert_eq!(by_owner_events.try_recv().unwrap(), event(1, 1).event);
+        assert_eq!(by_owner_events.try_recv().unwrap(), event(2, 2).event);
+        assert!(by_owner_events.try_recv().is_err());
+    }
+}
@@ -7,6 +7,7 @@ use {
     anyhow::{Context, Result},
     chrono::Utc,
     ethcontract::H256,
+    futures::future,
     model::{
         app_data::AppDataHash,
         order::{
@@ -33,6 +34,13 @@ use {
     thiserror::Error,
 };
 
+/// How many of the most recent auctions get searched for competitions
+/// containing an order when reporting its status.
+const STATUS_RECENT_AUCTIONS: i64 = 100;
+
+/// How many competitions containing an order get reported in its status.
+const STATUS_MAX_COMPETITIONS: i64 = 10;
+
 #[derive(prometheus_metric_storage::MetricStorage, Clone, Debug)]
 #[metric(subsystem = "orderbook")]
 struct Metrics {
@@ -100,6 +108,8 @@ pub enum AddOrderError {
     InvalidAppData(#[source] anyhow::Error),
     #[error("the new order is not a valid replacement for the old one")]
     InvalidReplacement,
+    #[error("orders replacing another order cannot be placed in a batch")]
+    ReplacementInBatch,
     #[error(
         "contract app data {contract_app_data:?} is associated with full app data {existing:?} \
          which is different from the provided {provided:?}"
@@ -228,6 +238,97 @@ impl Orderbook {
         }
     }
 
+    /// Validates and inserts multiple orders at once. The orders get
+    /// validated together so that they share balance and quote lookups and
+    /// all valid orders get inserted in a single transaction. Returns the
+    /// result for every order in the order of the payloads.
+    pub async fn add_orders(
+        &self,
+        payloads: Vec<OrderCreation>,
+    ) -> Result<Vec<Result<(OrderUid, Option<QuoteId>), AddOrderError>>, AddOrderError> {
+        let prepared = future::join_all(
+            payloads
+                .into_iter()
+                .map(|payload| self.prepare_batch_order(payload)),
+        )
+        .await;
+
+        let mut to_validate = Vec::new();
+        let prepared = prepared
+            .into_iter()
+            .map(|result| result.map(|prepared| to_validate.push(prepared)))
+            .collect::<Vec<_>>();
+        let mut validated = self
+            .order_validator
+            .validate_and_construct_orders(
+                to_validate,
+                &self.domain_separator,
+                self.settlement_contract,
+            )
+            .await
+            .into_iter();
+        let validated = prepared
+            .into_iter()
+            .map(|result| {
+                result?;
+                Ok(validated
+                    .next()
+                    .expect("one validation result per prepared order")?)
+            })
+            .collect::<Vec<Result<(Order, Option<Quote>), AddOrderError>>>();
+
+        let valid = validated
+            .iter()
+            .filter_map(|result| result.as_ref().ok().cloned())
+            .collect::<Vec<_>>();
+        let mut inserted = self
+            .database
+            .insert_orders(&valid)
+            .await
+            .map_err(|err| match err {
+                InsertionError::DbError(err) => AddOrderError::Database(err.into()),
+                err => AddOrderError::Database(anyhow::anyhow!("{err:?}")),
+            })?
+            .into_iter();
+
+        Ok(validated
+            .into_iter()
+            .map(|result| {
+                let (order, quote) = result?;
+                if !inserted.next().unwrap_or_default() {
+                    return Err(AddOrderError::DuplicatedOrder);
+                }
+                Metrics::on_order_operation(&order, OrderOperation::Created);
+                Ok((
+                    order.metadata.uid,
+                    quote.as_ref().and_then(|quote| quote.id),
+                ))
+            })
+            .collect())
+    }
+
+    /// Looks up the full app data of an order of a batch and makes sure that
+    /// it doesn't replace another order.
+    async fn prepare_batch_order(
+        &self,
+        payload: OrderCreation,
+    ) -> Result<(OrderCreation, Option<String>), AddOrderError> {
+        let full_app_data_override = match payload.app_data {
+            OrderCreationAppData::Hash { hash } => self.app_data.find(&hash).await?,
+            _ => None,
+        };
+
+        if self
+            .get_replaced_order(&payload, full_app_data_override.as_deref())
+            .await?
+            .is_some()
+        {
+            return Err(AddOrderError::ReplacementInBatch);
+        }
+
+        Ok((payload, full_app_data_override))
+    }
+
     /// Finds an order for cancellation.
     ///
     /// Returns an error if the order cannot be found or cannot be cancelled.
@@ -379,6 +480,32 @@ impl Orderbook {
         self.database.single_order(uid).await
     }
 
+    /// Explains where the order stands in the settlement process, or `None`
+    /// if the order doesn't exist.
+    pub async fn get_order_status(
+        &self,
+        uid: &OrderUid,
+    ) -> Result<Option<dto::OrderStatusDetails>> {
+        let Some(order) = self.database.single_order(uid).await? else {
+            return Ok(None);
+        };
+        let (latest_event, current_auction, recent_competitions) = futures::try_join!(
+            self.database.latest_order_event(uid),
+            self.database.current_auction_includes_order(uid),
+            self.database.recent_competitions_with_order(
+                uid,
+                STATUS_RECENT_AUCTIONS,
+                STATUS_MAX_COMPETITIONS
+            ),
+        )?;
+        Ok(Some(dto::OrderStatusDetails {
+            status: order.metadata.status,
+            latest_event,
+            current_auction,
+            recent_competitions,
+        }))
+    }
+
     pub async fn get_orders_for_tx(&self, hash: &H256) -> Result<Vec<Order>> {
         self.database.orders_for_tx(hash).await
     }
@@ -563,4 +690,83 @@ mod tests {
             .unwrap();
         assert_eq!(order_id, new_order_uid,);
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_add_orders_returns_result_per_order() {
+        // Orders with an even `valid_to` are rejected by the validator.
+        let mut order_validator = MockOrderValidating::new();
+        order_validator
+            .expect_validate_and_construct_orders()
+            .returning(|orders, _, _| {
+                orders
+                    .into_iter()
+                    .map(|(creation, _)| {
+                        if creation.valid_to % 2 == 0 {
+                            return Err(ValidationError::TooManyLimitOrders);
+                        }
+                        Ok((
+                            Order {
+                                metadata: OrderMetadata {
+                                    owner: creation.from.unwrap(),
+                                    uid: OrderUid::from_integer(creation.valid_to),
+                                    ..Default::default()
+                                },
+                                data: creation.data(),
+                                signature: creation.signature,
+                                ..Default::default()
+                            },
+                            Default::default(),
+                        ))
+                    })
+                    .collect()
+            });
+
+        let database = crate::database::Postgres::new("postgresql://").unwrap();
+        database::clear_DANGER(&database.pool).await.unwrap();
+        let app_data = Arc::new(app_data::Registry::new(
+            shared::app_data::Validator::new(8192),
+            database.clone(),
+            None,
+        ));
+        let orderbook = Orderbook {
+            database,
+            order_validator: Arc::new(order_validator),
+            domain_separator: Default::default(),
+            settlement_contract: H160([0xba; 20]),
+            app_data,
+        };
+
+        let order = |valid_to| OrderCreation {
+            valid_to,
+            from: Some(H160([1; 20])),
+            signature: Signature::PreSign,
+            app_data: OrderCreationAppData::Full {
+                full: "{}".to_string(),
+            },
+            ..Default::default()
+        };
+        let results = orderbook
+            .add_orders(vec![order(1), order(2), order(3), order(1)])
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[0].as_ref().unwrap(),
+            &(OrderUid::from_integer(1), None)
+        );
+        assert!(matches!(
+            results[1],
+            Err(AddOrderError::OrderValidation(
+                ValidationError::TooManyLimitOrders
+            ))
+        ));
+        assert_eq!(
+            results[2].as_ref().unwrap(),
+            &(OrderUid::from_integer(3), None)
+        );
+        // The same order is only inserted once.
+        assert!(matches!(results[3], Err(AddOrderError::DuplicatedOrder)));
+    }
 }
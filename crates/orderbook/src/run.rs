@@ -6,6 +6,7 @@ use {
         database::Postgres,
         ipfs::Ipfs,
         ipfs_app_data::IpfsAppData,
+        order_events::OrderEvents,
         orderbook::Orderbook,
         quoter::QuoteHandler,
     },
@@ -18,6 +19,7 @@ use {
     order_validation,
     shared::{
         account_balances,
+        arguments::PoolDiscovery,
         bad_token::{
             cache::CachingDetector,
             instrumented::InstrumentedBadTokenDetectorExt,
@@ -49,7 +51,7 @@ use {
                 BalancerPoolFetcher,
             },
             uniswap_v2::{pool_cache::PoolCache, UniV2BaselineSourceParameters},
-            uniswap_v3::pool_fetching::UniswapV3PoolFetcher,
+            uniswap_v3::pool_fetching::{FactoryEventsDiscovery, UniswapV3PoolFetcher},
             BaselineSource,
             PoolAggregator,
         },
@@ -75,7 +77,7 @@ pub async fn start(args: impl Iterator<Item = String>) {
 pub async fn run(args: Arguments) {
     let http_factory = HttpClientFactory::new(&args.http_client);
 
-    let web3 = shared::ethrpc::web3(
+    let web3 = shared::ethrpc::web3_with_fallbacks(
         &args.shared.ethrpc,
         &http_factory,
         &args.shared.node_url,
@@ -284,8 +286,8 @@ pub async fn run(args: Arguments) {
             .clone()
             .unwrap_or_else(|| BalancerFactoryKind::for_chain(chain_id));
         let contracts = BalancerContracts::new(&web3, factories).await.unwrap();
-        match BalancerPoolFetcher::new(
-            &args.shared.graph_api_base_url,
+        let fetcher = BalancerPoolFetcher::from_args(
+            &args.shared,
             chain_id,
             block_retriever.clone(),
             token_info_fetcher.clone(),
@@ -294,10 +296,9 @@ pub async fn run(args: Arguments) {
             http_factory.create(),
             web3.clone(),
             &contracts,
-            args.shared.balancer_pool_deny_list.clone(),
         )
-        .await
-        {
+        .await;
+        match fetcher {
             Ok(fetcher) => Some(Arc::new(fetcher)),
             Err(err) => {
                 tracing::error!(
@@ -313,16 +314,50 @@ pub async fn run(args: Arguments) {
         None
     };
     let uniswap_v3_pool_fetcher = if baseline_sources.contains(&BaselineSource::UniswapV3) {
-        match UniswapV3PoolFetcher::new(
-            &args.shared.graph_api_base_url,
-            chain_id,
-            web3.clone(),
-            http_factory.create(),
-            block_retriever,
-            args.shared.max_pools_to_initialize_cache,
-        )
-        .await
-        {
+        let fetcher = match args.shared.uniswap_v3_pool_discovery {
+            PoolDiscovery::Subgraph => {
+                UniswapV3PoolFetcher::new(
+                    &args.shared.graph_api_base_url,
+                    chain_id,
+                    web3.clone(),
+                    http_factory.create(),
+                    block_retriever,
+                    args.shared.max_pools_to_initialize_cache,
+                )
+                .await
+            }
+            PoolDiscovery::FactoryEvents => {
+                match args
+                    .shared
+                    .uniswap_v3_factory_address
+                    .or(uniswapv3_factory.as_ref().map(|factory| factory.address()))
+                {
+                    Some(factory) => {
+                        async {
+                            let config = FactoryEventsDiscovery::new(
+                                factory,
+                                args.shared.uniswap_v3_factory_start_block,
+                                args.shared.uniswap_v3_pool_checkpoint_path.clone(),
+                                chain_id,
+                            )
+                            .await?;
+                            UniswapV3PoolFetcher::from_factory_events(
+                                config,
+                                web3.clone(),
+                                block_retriever,
+                                token_info_fetcher.clone(),
+                            )
+                            .await
+                        }
+                        .await
+                    }
+                    None => Err(anyhow::anyhow!(
+                        "UniswapV3 factory is not deployed on this network"
+                    )),
+                }
+            }
+        };
+        match fetcher {
             Ok(fetcher) => Some(Arc::new(fetcher)),
             Err(err) => {
                 tracing::error!(
@@ -377,6 +412,7 @@ pub async fn run(args: Arguments) {
                 &args.order_quoting.price_estimation_legacy_solvers,
             ),
             args.fast_price_estimation_results_required,
+            Arc::new(postgres.clone()),
         )
         .unwrap();
     let price_estimator = price_estimator_factory
@@ -493,6 +529,8 @@ pub async fn run(args: Arguments) {
             .with_fast_quoter(fast_quoter),
     );
 
+    let order_events = Arc::new(OrderEvents::new(postgres.clone()));
+
     let (shutdown_sender, shutdown_receiver) = tokio::sync::oneshot::channel();
     let serve_api = serve_api(
         postgres,
@@ -504,6 +542,7 @@ pub async fn run(args: Arguments) {
             let _ = shutdown_receiver.await;
         },
         native_price_estimator,
+        order_events,
     );
 
     let mut metrics_address = args.bind_address;
@@ -570,6 +609,7 @@ fn serve_api(
     address: SocketAddr,
     shutdown_receiver: impl Future<Output = ()> + Send + 'static,
     native_price_estimator: Arc<dyn NativePriceEstimating>,
+    order_events: Arc<OrderEvents>,
 ) -> JoinHandle<()> {
     let filter = api::handle_all_routes(
         database,
@@ -577,6 +617,7 @@ fn serve_api(
         quotes,
         app_data,
         native_price_estimator,
+        order_events,
     )
     .boxed();
     tracing::info!(%address, "serving order book");
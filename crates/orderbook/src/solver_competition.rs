@@ -1,10 +1,11 @@
 //! Manage solver competition data received by the driver through a private spi.
 
 use {
+    crate::dto::CompetitionSummary,
     anyhow::Result,
     database::auction::AuctionId,
     model::solver_competition::SolverCompetitionAPI,
-    primitive_types::H256,
+    primitive_types::{H160, H256},
     thiserror::Error,
 };
 
@@ -13,6 +14,20 @@ pub enum Identifier {
     Transaction(H256),
 }
 
+/// Filters past solver competitions. Fields that are `None` don't restrict the
+/// result.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CompetitionFilter {
+    /// Solver that must have participated in the competition.
+    pub solver: Option<H160>,
+    /// First block (inclusive) the solutions can have been simulated at.
+    pub from_block: Option<u64>,
+    /// Last block (inclusive) the solutions can have been simulated at.
+    pub to_block: Option<u64>,
+    /// Only match competitions won by `solver`.
+    pub winner_only: bool,
+}
+
 /// Component used for saving and loading past solver competitions.
 #[cfg_attr(test, mockall::automock)]
 #[async_trait::async_trait]
@@ -32,6 +47,16 @@ pub trait SolverCompetitionStoring: Send + Sync {
     async fn load_latest_competition(
         &self,
     ) -> Result<SolverCompetitionAPI, crate::solver_competition::LoadSolverCompetitionError>;
+
+    /// Retrieves a page of the competitions that had a winner and match the
+    /// filter, newest first. Only competitions of auctions older than `before`
+    /// are returned.
+    async fn load_competitions(
+        &self,
+        filter: CompetitionFilter,
+        before: Option<AuctionId>,
+        limit: u64,
+    ) -> Result<Vec<CompetitionSummary>>;
 }
 
 /// Possible errors when loading a solver competition by ID.
@@ -1,7 +1,12 @@
 use {
     clap::Parser,
     ethcontract::H160,
-    shared::{arguments::display_option, ethrpc, http_client, logging_args_with_default_filter},
+    shared::{
+        arguments::{display_option, display_secret_option, Arn},
+        ethrpc,
+        http_client,
+        logging_args_with_default_filter,
+    },
     std::time::Duration,
     tracing::level_filters::LevelFilter,
     url::Url,
@@ -52,12 +57,35 @@ pub struct Arguments {
     #[clap(long, env)]
     pub chain_id: Option<u64>,
 
-    /// Address of the ethflow contract
-    #[clap(long, env)]
-    pub ethflow_contract: H160,
+    /// Addresses of the ethflow contracts whose orders get refunded
+    #[clap(
+        long,
+        env,
+        alias = "ethflow-contract",
+        use_value_delimiter = true,
+        required = true
+    )]
+    pub ethflow_contracts: Vec<H160>,
 
+    /// Private key of the account that sends the refunding transactions.
+    /// Either this or `refunder_kms_key_id` has to be specified.
     #[clap(long, env, hide_env_values = true)]
-    pub refunder_pk: String,
+    pub refunder_pk: Option<String>,
+
+    /// AWS KMS key ARN of the account that sends the refunding transactions.
+    /// Either this or `refunder_pk` has to be specified.
+    #[clap(long, env)]
+    pub refunder_kms_key_id: Option<Arn>,
+
+    /// The maximum gas price in Gwei the refunder is willing to pay. Fees of
+    /// pending refunding txs are not bumped beyond this.
+    #[clap(long, env, default_value = "800", value_parser = shared::arguments::wei_from_gwei)]
+    pub max_gas_price: f64,
+
+    /// Pending refunding txs get rebroadcast with bumped fees if they didn't
+    /// get mined within this time.
+    #[clap(long, env, default_value = "1m", value_parser = humantime::parse_duration)]
+    pub resubmission_interval: Duration,
 
     /// The port at which we serve our metrics
     #[clap(long, env, default_value = "9590")]
@@ -73,11 +101,14 @@ impl std::fmt::Display for Arguments {
             min_slippage_bps,
             node_url,
             chain_id,
-            ethflow_contract,
+            ethflow_contracts,
             metrics_port,
             logging,
             db_url,
             refunder_pk,
+            refunder_kms_key_id,
+            max_gas_price,
+            resubmission_interval,
         } = self;
 
         write!(f, "{}", http_client)?;
@@ -89,9 +120,15 @@ impl std::fmt::Display for Arguments {
         writeln!(f, "db_url: SECRET")?;
         writeln!(f, "node_url: {}", node_url)?;
         display_option(f, "chain_id", chain_id)?;
-        writeln!(f, "ethflow_contract: {:?}", ethflow_contract)?;
-        let _intentionally_ignored = refunder_pk;
-        writeln!(f, "refunder_pk: SECRET")?;
+        writeln!(f, "ethflow_contracts: {:?}", ethflow_contracts)?;
+        display_secret_option(f, "refunder_pk", refunder_pk)?;
+        display_option(
+            f,
+            "refunder_kms_key_id",
+            &refunder_kms_key_id.as_ref().map(|arn| &arn.0),
+        )?;
+        writeln!(f, "max_gas_price: {}", max_gas_price)?;
+        writeln!(f, "resubmission_interval: {:?}", resubmission_interval)?;
         writeln!(f, "metrics_port: {}", metrics_port)?;
         Ok(())
     }
@@ -34,7 +34,8 @@ pub async fn start(args: impl Iterator<Item = String>) {
 
 pub async fn run(args: arguments::Arguments) {
     let http_factory = HttpClientFactory::new(&args.http_client);
-    let web3 = shared::ethrpc::web3(&args.ethrpc, &http_factory, &args.node_url, "base");
+    let web3 =
+        shared::ethrpc::web3_with_fallbacks(&args.ethrpc, &http_factory, &args.node_url, "base");
     if let Some(expected_chain_id) = args.chain_id {
         let chain_id = web3
             .eth()
@@ -56,15 +57,31 @@ pub async fn run(args: arguments::Arguments) {
     });
     shared::metrics::serve_metrics(liveness.clone(), ([0, 0, 0, 0], args.metrics_port).into());
 
-    let ethflow_contract = CoWSwapEthFlow::at(&web3, args.ethflow_contract);
-    let refunder_account = Account::Offline(args.refunder_pk.parse::<PrivateKey>().unwrap(), None);
-    let mut refunder = RefundService::new(
+    let ethflow_contracts = args
+        .ethflow_contracts
+        .iter()
+        .map(|address| CoWSwapEthFlow::at(&web3, *address))
+        .collect();
+    let refunder_account = match (args.refunder_pk, args.refunder_kms_key_id) {
+        (Some(pk), None) => Account::Offline(pk.parse::<PrivateKey>().unwrap(), None),
+        (None, Some(key_id)) => {
+            let config = ethcontract::aws_config::load_from_env().await;
+            let account = ethcontract::transaction::kms::Account::new((&config).into(), &key_id.0)
+                .await
+                .unwrap_or_else(|_| panic!("Unable to load KMS account {:?}", key_id));
+            Account::Kms(account, None)
+        }
+        _ => panic!("exactly one of refunder_pk and refunder_kms_key_id must be specified"),
+    };
+    let refunder = RefundService::new(
         pg_pool,
         web3,
-        ethflow_contract,
+        ethflow_contracts,
         args.min_validity_duration.as_secs() as i64,
         args.min_slippage_bps,
         refunder_account,
+        args.max_gas_price,
+        args.resubmission_interval,
     );
     loop {
         tracing::info!("Staring a new refunding loop");
@@ -2,13 +2,22 @@ use {
     super::ethflow_order::{order_to_ethflow_data, EncodedEthflowOrder, EthflowOrder},
     crate::submitter::Submitter,
     anyhow::{anyhow, Context, Result},
+    chrono::Utc,
     contracts::CoWSwapEthFlow,
     database::{
+        byte_array::ByteArray,
         ethflow_orders::{read_order, refundable_orders, EthOrderPlacement},
+        ethflow_refund_transactions::{self, RefundTransaction, Status},
         orders::read_order as read_db_order,
         OrderUid,
     },
-    ethcontract::{Account, H160, H256},
+    ethcontract::{
+        web3::types::{BlockId, TransactionId, U64},
+        Account,
+        H160,
+        H256,
+        U256,
+    },
     ethrpc::{
         current_block::timestamp_of_current_block_in_seconds,
         Web3,
@@ -16,7 +25,10 @@ use {
         MAX_BATCH_SIZE,
     },
     futures::{stream, StreamExt},
+    gas_estimation::GasPrice1559,
+    number::conversions::{big_decimal_to_u256, u256_to_big_decimal},
     sqlx::PgPool,
+    std::{collections::HashSet, time::Duration},
 };
 
 pub const NO_OWNER: H160 = H160([0u8; 20]);
@@ -26,10 +38,13 @@ const MAX_NUMBER_OF_UIDS_PER_REFUND_TX: usize = 30;
 pub struct RefundService {
     pub db: PgPool,
     pub web3: Web3,
-    pub ethflow_contract: CoWSwapEthFlow,
+    pub ethflow_contracts: Vec<CoWSwapEthFlow>,
     pub min_validity_duration: i64,
     pub min_slippage: f64,
     pub submitter: Submitter,
+    /// Pending refunding txs get rebroadcast with bumped fees if they didn't
+    /// get mined within this interval.
+    pub resubmission_interval: Duration,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -40,33 +55,42 @@ enum RefundStatus {
 }
 
 impl RefundService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: PgPool,
         web3: Web3,
-        ethflow_contract: CoWSwapEthFlow,
+        ethflow_contracts: Vec<CoWSwapEthFlow>,
         min_validity_duration: i64,
         min_slippage_bps: u64,
         account: Account,
+        max_gas_price: f64,
+        resubmission_interval: Duration,
     ) -> Self {
         RefundService {
             db,
             web3: web3.clone(),
-            ethflow_contract: ethflow_contract.clone(),
+            ethflow_contracts,
             min_validity_duration,
             min_slippage: min_slippage_bps as f64 / 10000f64,
             submitter: Submitter {
                 web3: web3.clone(),
-                ethflow_contract,
                 account,
                 gas_estimator: Box::new(web3),
-                gas_parameters_of_last_tx: None,
-                nonce_of_last_submission: None,
+                max_gas_price,
             },
+            resubmission_interval,
         }
     }
 
-    pub async fn try_to_refund_all_eligble_orders(&mut self) -> Result<()> {
-        let refundable_order_uids = self.get_refundable_ethflow_orders_from_db().await?;
+    pub async fn try_to_refund_all_eligble_orders(&self) -> Result<()> {
+        // Orders that are refunded by pending txs must not be refunded again.
+        let pending_uids = self.update_pending_refunds().await?;
+        let refundable_order_uids = self
+            .get_refundable_ethflow_orders_from_db()
+            .await?
+            .into_iter()
+            .filter(|order| !pending_uids.contains(&order.uid))
+            .collect();
 
         let to_be_refunded_uids = self
             .identify_uids_refunding_status_via_web3_calls(refundable_order_uids)
@@ -102,12 +126,21 @@ impl RefundService {
         let mut batch = Web3CallBatch::new(self.web3.transport().clone());
         let futures = refundable_order_uids
             .iter()
-            .map(|eth_order_placement| {
+            .filter_map(|eth_order_placement| {
+                let Some(ethflow_contract) = self.ethflow_contract(&eth_order_placement.uid) else {
+                    tracing::debug!(
+                        uid = ?eth_order_placement.uid,
+                        "skipping order of unknown ethflow contract"
+                    );
+                    return None;
+                };
+                Some((eth_order_placement, ethflow_contract))
+            })
+            .map(|(eth_order_placement, ethflow_contract)| {
                 let order_hash: [u8; 32] = eth_order_placement.uid.0[0..32]
                     .try_into()
                     .expect("order_uid slice with incorrect length");
-                let order = self
-                    .ethflow_contract
+                let order = ethflow_contract
                     .orders(ethcontract::tokens::Bytes(order_hash))
                     .batch_call(&mut batch);
                 async move {
@@ -157,6 +190,14 @@ impl RefundService {
         Ok(to_be_refunded_uids)
     }
 
+    /// Returns the ethflow contract that placed the order, which is the owner
+    /// encoded in the order uid.
+    fn ethflow_contract(&self, uid: &OrderUid) -> Option<&CoWSwapEthFlow> {
+        self.ethflow_contracts
+            .iter()
+            .find(|contract| contract.address() == order_owner(uid))
+    }
+
     async fn get_ethflow_data_from_db(&self, uid: &OrderUid) -> Result<EthflowOrder> {
         let mut ex = self.db.acquire().await.context("acquire")?;
         let order = read_db_order(&mut ex, uid)
@@ -170,10 +211,30 @@ impl RefundService {
         Ok(order_to_ethflow_data(order, ethflow_order))
     }
 
-    async fn send_out_refunding_tx(&mut self, uids: Vec<OrderUid>) -> Result<()> {
-        if uids.is_empty() {
-            return Ok(());
+    async fn send_out_refunding_tx(&self, uids: Vec<OrderUid>) -> Result<()> {
+        // Orders of different ethflow contracts have to be refunded in separate
+        // transactions.
+        for ethflow_contract in &self.ethflow_contracts {
+            let uids: Vec<OrderUid> = uids
+                .iter()
+                .filter(|uid| order_owner(uid) == ethflow_contract.address())
+                .copied()
+                .collect();
+            if uids.is_empty() {
+                continue;
+            }
+            self.send_out_refunding_tx_for_contract(ethflow_contract, uids)
+                .await
+                .with_context(|| format!("ethflow contract {:?}", ethflow_contract.address()))?;
         }
+        Ok(())
+    }
+
+    async fn send_out_refunding_tx_for_contract(
+        &self,
+        ethflow_contract: &CoWSwapEthFlow,
+        uids: Vec<OrderUid>,
+    ) -> Result<()> {
         // only try to refund MAX_NUMBER_OF_UIDS_PER_REFUND_TX uids, in order to fit
         // into gas limit
         let uids: Vec<OrderUid> = uids
@@ -181,22 +242,83 @@ impl RefundService {
             .take(MAX_NUMBER_OF_UIDS_PER_REFUND_TX)
             .collect();
 
-        tracing::debug!("Trying to refund the following uids: {:?}", uids);
+        tracing::debug!(
+            contract = ?ethflow_contract.address(),
+            "Trying to refund the following uids: {:?}",
+            uids
+        );
+
+        let (uids, encoded_ethflow_orders): (Vec<_>, Vec<_>) =
+            self.encoded_ethflow_orders(&uids).await.into_iter().unzip();
+        if uids.is_empty() {
+            return Ok(());
+        }
+
+        let nonce = self.next_nonce().await?;
+        let gas_price = self.submitter.gas_price(None).await?;
+        let tx_hash = self
+            .submitter
+            .send(ethflow_contract, encoded_ethflow_orders, nonce, gas_price)
+            .await
+            .context("submission failed")?;
+        tracing::debug!(
+            ?tx_hash,
+            ?nonce,
+            "Sent tx to refund the orderuids {:?}",
+            uids
+        );
 
+        let now = Utc::now();
+        let mut ex = self.db.acquire().await?;
+        ethflow_refund_transactions::insert(
+            &mut ex,
+            &RefundTransaction {
+                sender: ByteArray(self.submitter.address().0),
+                nonce: nonce.as_u64().try_into()?,
+                ethflow_contract: ByteArray(ethflow_contract.address().0),
+                order_uids: uids,
+                tx_hashes: vec![ByteArray(tx_hash.0)],
+                max_fee_per_gas: u256_to_big_decimal(&U256::from_f64_lossy(
+                    gas_price.max_fee_per_gas,
+                )),
+                max_priority_fee_per_gas: u256_to_big_decimal(&U256::from_f64_lossy(
+                    gas_price.max_priority_fee_per_gas,
+                )),
+                created: now,
+                last_broadcast: now,
+                status: Status::Pending,
+                mined_tx_hash: None,
+                mined_block_number: None,
+            },
+        )
+        .await?;
+        Metrics::get()
+            .refund_transactions
+            .with_label_values(&["submitted"])
+            .inc();
+        Ok(())
+    }
+
+    /// Reads the orders from the db and encodes them for the refunding tx.
+    /// Orders that can't be read are skipped.
+    async fn encoded_ethflow_orders(
+        &self,
+        uids: &[OrderUid],
+    ) -> Vec<(OrderUid, EncodedEthflowOrder)> {
         let futures = uids.iter().map(|uid| {
-            let (uid, self_) = (*uid, &self);
+            let uid = *uid;
             async move {
-                self_
-                    .get_ethflow_data_from_db(&uid)
+                self.get_ethflow_data_from_db(&uid)
                     .await
+                    .map(|order| (uid, order.encode()))
                     .context(format!("uid {uid:?}"))
             }
         });
-        let encoded_ethflow_orders: Vec<EncodedEthflowOrder> = stream::iter(futures)
-            .buffer_unordered(10)
+        stream::iter(futures)
+            .buffered(10)
             .filter_map(|result| async {
                 match result {
-                    Ok(order) => Some(order.encode()),
+                    Ok(order) => Some(order),
                     Err(err) => {
                         tracing::error!(?err, "failed to get data from db");
                         None
@@ -204,9 +326,246 @@ impl RefundService {
                 }
             })
             .collect()
-            .await;
+            .await
+    }
+
+    /// The nonce for a new refunding tx, which comes after all txs that got
+    /// mined or are still pending.
+    async fn next_nonce(&self) -> Result<U256> {
+        let mined_nonce = self.submitter.mined_nonce().await?;
+        let mut ex = self.db.acquire().await?;
+        let pending =
+            ethflow_refund_transactions::pending(&mut ex, &ByteArray(self.submitter.address().0))
+                .await?;
+        Ok(pending
+            .iter()
+            .map(|tx| U256::from(tx.nonce) + 1)
+            .fold(mined_nonce, U256::max))
+    }
+
+    /// Updates the status of all pending refunding txs, rebroadcasting the ones
+    /// that didn't get mined in time. Returns the uids of the orders that are
+    /// still being refunded.
+    async fn update_pending_refunds(&self) -> Result<HashSet<OrderUid>> {
+        let pending = {
+            let mut ex = self.db.acquire().await?;
+            ethflow_refund_transactions::pending(&mut ex, &ByteArray(self.submitter.address().0))
+                .await?
+        };
+        if pending.is_empty() {
+            Metrics::get().pending_refund_transactions.set(0);
+            return Ok(Default::default());
+        }
 
-        self.submitter.submit(uids, encoded_ethflow_orders).await?;
+        // The nonce is fetched before the receipts, so that a tx that gets
+        // mined in between is not considered dropped.
+        let mined_nonce = self.submitter.mined_nonce().await?;
+        let mut pending_uids = HashSet::new();
+        let mut still_pending = 0;
+        for tx in pending {
+            let is_pending = self
+                .update_pending_refund(&tx, mined_nonce)
+                .await
+                .unwrap_or_else(|err| {
+                    tracing::warn!(?err, nonce = tx.nonce, "failed to update pending refund tx");
+                    true
+                });
+            if is_pending {
+                still_pending += 1;
+                pending_uids.extend(tx.order_uids);
+            }
+        }
+        Metrics::get()
+            .pending_refund_transactions
+            .set(still_pending);
+        Ok(pending_uids)
+    }
+
+    /// Updates the status of a pending refunding tx. Returns whether it is
+    /// still pending.
+    async fn update_pending_refund(
+        &self,
+        tx: &RefundTransaction,
+        mined_nonce: U256,
+    ) -> Result<bool> {
+        // Any of the broadcasts could have been mined, but most likely it is the
+        // latest one.
+        for tx_hash in tx.tx_hashes.iter().rev() {
+            let receipt = self.web3.eth().transaction_receipt(H256(tx_hash.0)).await?;
+            let Some(block_number) = receipt.and_then(|receipt| receipt.block_number) else {
+                continue;
+            };
+            tracing::debug!(?tx_hash, ?block_number, nonce = tx.nonce, "refund tx mined");
+            let mut ex = self.db.acquire().await?;
+            ethflow_refund_transactions::mark_mined(
+                &mut ex,
+                &tx.sender,
+                tx.nonce,
+                tx_hash,
+                block_number.as_u64().try_into()?,
+            )
+            .await?;
+            Metrics::get()
+                .refund_transactions
+                .with_label_values(&["mined"])
+                .inc();
+            if let Err(err) = self.observe_refund_latency(tx, block_number).await {
+                tracing::warn!(?err, "failed to observe refund latency");
+            }
+            return Ok(false);
+        }
+
+        if U256::from(tx.nonce) < mined_nonce {
+            // A different tx with the same nonce got mined, e.g. because the
+            // account was used by something else. The orders can be refunded
+            // again.
+            tracing::warn!(nonce = tx.nonce, "refund tx dropped");
+            let mut ex = self.db.acquire().await?;
+            ethflow_refund_transactions::mark_dropped(&mut ex, &tx.sender, tx.nonce).await?;
+            Metrics::get()
+                .refund_transactions
+                .with_label_values(&["dropped"])
+                .inc();
+            return Ok(false);
+        }
+
+        let latest = tx.tx_hashes.last().context("refund tx without hashes")?;
+        let in_mempool = self
+            .web3
+            .eth()
+            .transaction(TransactionId::Hash(H256(latest.0)))
+            .await?
+            .is_some();
+        let resubmission_due = (Utc::now() - tx.last_broadcast)
+            .to_std()
+            .is_ok_and(|elapsed| elapsed >= self.resubmission_interval);
+        if in_mempool && !resubmission_due {
+            return Ok(true);
+        }
+        self.rebroadcast(tx, in_mempool).await?;
+        Ok(true)
+    }
+
+    /// Broadcasts a pending refunding tx again with bumped fees.
+    async fn rebroadcast(&self, tx: &RefundTransaction, in_mempool: bool) -> Result<()> {
+        let to_f64 = |value| {
+            big_decimal_to_u256(value)
+                .context("invalid fee")
+                .map(|value| value.to_f64_lossy())
+        };
+        let previous = GasPrice1559 {
+            base_fee_per_gas: 0.,
+            max_fee_per_gas: to_f64(&tx.max_fee_per_gas)?,
+            max_priority_fee_per_gas: to_f64(&tx.max_priority_fee_per_gas)?,
+        };
+        let gas_price = self.submitter.gas_price(Some(previous)).await?;
+        // The node rejects replacements that don't bump the fees, so there is
+        // no point in rebroadcasting a tx that is still known to the node once
+        // the max gas price is reached.
+        if in_mempool && gas_price.max_fee_per_gas <= previous.max_fee_per_gas {
+            tracing::debug!(nonce = tx.nonce, "refund tx fees can't be bumped further");
+            return Ok(());
+        }
+
+        let address = H160(tx.ethflow_contract.0);
+        let ethflow_contract = self
+            .ethflow_contracts
+            .iter()
+            .find(|contract| contract.address() == address)
+            .cloned()
+            .unwrap_or_else(|| CoWSwapEthFlow::at(&self.web3, address));
+        let encoded_ethflow_orders = self
+            .encoded_ethflow_orders(&tx.order_uids)
+            .await
+            .into_iter()
+            .map(|(_, order)| order)
+            .collect();
+        let tx_hash = self
+            .submitter
+            .send(
+                &ethflow_contract,
+                encoded_ethflow_orders,
+                tx.nonce.into(),
+                gas_price,
+            )
+            .await
+            .context("rebroadcast failed")?;
+        tracing::debug!(
+            ?tx_hash,
+            nonce = tx.nonce,
+            ?gas_price,
+            "rebroadcast refund tx"
+        );
+
+        let mut ex = self.db.acquire().await?;
+        ethflow_refund_transactions::record_broadcast(
+            &mut ex,
+            &tx.sender,
+            tx.nonce,
+            &ByteArray(tx_hash.0),
+            &u256_to_big_decimal(&U256::from_f64_lossy(gas_price.max_fee_per_gas)),
+            &u256_to_big_decimal(&U256::from_f64_lossy(gas_price.max_priority_fee_per_gas)),
+            Utc::now(),
+        )
+        .await?;
+        Metrics::get()
+            .refund_transactions
+            .with_label_values(&["rebroadcast"])
+            .inc();
+        Ok(())
+    }
+
+    /// Records the time between the expiry of the refunded orders and the
+    /// inclusion of the refunding tx.
+    async fn observe_refund_latency(
+        &self,
+        tx: &RefundTransaction,
+        block_number: U64,
+    ) -> Result<()> {
+        let block = self
+            .web3
+            .eth()
+            .block(BlockId::Number(block_number.into()))
+            .await?
+            .context("missing block")?;
+        let timestamp = block.timestamp.as_u64() as i64;
+
+        let mut ex = self.db.acquire().await?;
+        for uid in &tx.order_uids {
+            if let Some(order) = read_order(&mut ex, uid).await? {
+                Metrics::get()
+                    .refund_latency_seconds
+                    .observe((timestamp - order.valid_to).max(0) as f64);
+            }
+        }
         Ok(())
     }
 }
+
+#[derive(prometheus_metric_storage::MetricStorage, Debug)]
+#[metric(subsystem = "refund_service")]
+struct Metrics {
+    /// Refunding txs by lifecycle event (submitted, rebroadcast, mined,
+    /// dropped).
+    #[metric(labels("event"))]
+    refund_transactions: prometheus::IntCounterVec,
+
+    /// Number of refunding txs that are not mined yet.
+    pending_refund_transactions: prometheus::IntGauge,
+
+    /// Time from the expiry of an order until its refund got mined.
+    #[metric(buckets(60, 300, 600, 1800, 3600, 7200, 21600, 86400))]
+    refund_latency_seconds: prometheus::Histogram,
+}
+
+impl Metrics {
+    fn get() -> &'static Self {
+        Metrics::instance(observe::metrics::get_storage_registry()).unwrap()
+    }
+}
+
+/// The owner of an order is encoded in its uid. For ethflow orders, this is
+/// the ethflow contract that placed the order.
+fn order_owner(uid: &OrderUid) -> H160 {
+    H160::from_slice(&uid.0[32..52])
+}
@@ -13,6 +13,9 @@ mod compat;
 /// The minimum valid empty app data JSON string.
 pub const EMPTY: &str = "{}";
 
+/// The maximum partner fee in basis points an order can specify.
+pub const MAX_PARTNER_FEE_BPS: u64 = 100;
+
 #[derive(Debug)]
 pub struct ValidatedAppData {
     pub hash: AppDataHash,
@@ -27,6 +30,7 @@ pub struct ProtocolAppData {
     pub hooks: Hooks,
     pub signer: Option<H160>,
     pub replaced_order: Option<ReplacedOrder>,
+    pub partner_fee: Option<PartnerFee>,
 }
 
 #[derive(Debug, Default, Deserialize, Eq, PartialEq)]
@@ -34,6 +38,29 @@ pub struct ReplacedOrder {
     pub uid: OrderUid,
 }
 
+/// A fee that an integrator charges on top of the protocol fee. It is taken as
+/// a share of the order's volume.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct PartnerFee {
+    pub bps: u64,
+    pub recipient: H160,
+}
+
+impl PartnerFee {
+    /// The share of the order's volume that is taken as a partner fee.
+    pub fn factor(&self) -> f64 {
+        self.bps as f64 / 10_000.
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PartnerFeeError {
+    #[error("partner fee of {0} bps exceeds the maximum of {MAX_PARTNER_FEE_BPS} bps")]
+    TooHigh(u64),
+    #[error("partner fee recipient must not be the zero address")]
+    ZeroRecipient,
+}
+
 #[derive(Clone)]
 pub struct Validator {
     size_limit: usize,
@@ -65,13 +92,10 @@ impl Validator {
         }
 
         let document = String::from_utf8(full_app_data.to_vec())?;
-        let root = serde_json::from_str::<Root>(&document).context("invalid app data json")?;
-        let protocol = root
-            .metadata
-            .or_else(|| root.backend.map(ProtocolAppData::from))
-            // If the key doesn't exist, default. Makes life easier for API
-            // consumers, who don't care about protocol app data.
-            .unwrap_or_default();
+        let protocol = parse(document.as_bytes())?;
+        if let Some(partner_fee) = &protocol.partner_fee {
+            validate_partner_fee(partner_fee)?;
+        }
 
         Ok(ValidatedAppData {
             hash: AppDataHash(app_data_hash::hash_full_app_data(full_app_data)),
@@ -81,6 +105,29 @@ impl Validator {
     }
 }
 
+/// Parses the protocol relevant fields of a full app data document without
+/// validating them.
+pub fn parse(full_app_data: &[u8]) -> Result<ProtocolAppData> {
+    let root = serde_json::from_slice::<Root>(full_app_data).context("invalid app data json")?;
+    let protocol = root
+        .metadata
+        .or_else(|| root.backend.map(ProtocolAppData::from))
+        // If the key doesn't exist, default. Makes life easier for API
+        // consumers, who don't care about protocol app data.
+        .unwrap_or_default();
+    Ok(protocol)
+}
+
+fn validate_partner_fee(partner_fee: &PartnerFee) -> Result<(), PartnerFeeError> {
+    if partner_fee.bps > MAX_PARTNER_FEE_BPS {
+        return Err(PartnerFeeError::TooHigh(partner_fee.bps));
+    }
+    if partner_fee.recipient.is_zero() {
+        return Err(PartnerFeeError::ZeroRecipient);
+    }
+    Ok(())
+}
+
 /// The root app data JSON object.
 ///
 /// App data JSON is organised in an object of the form
@@ -255,6 +302,47 @@ mod tests {
                 ..Default::default()
             },
         );
+
+        assert_app_data!(
+            r#"
+                {
+                    "appCode": "CoW Swap",
+                    "metadata": {
+                        "partnerFee": {
+                            "bps": 50,
+                            "recipient": "0x4242424242424242424242424242424242424242"
+                        }
+                    },
+                    "version": "0.9.0"
+                }
+            "#,
+            ProtocolAppData {
+                partner_fee: Some(PartnerFee {
+                    bps: 50,
+                    recipient: H160([0x42; 20]),
+                }),
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn invalid_partner_fee() {
+        let validator = Validator::default();
+
+        let too_high = r#"{"metadata":{"partnerFee":{"bps":101,"recipient":"0x4242424242424242424242424242424242424242"}}}"#;
+        let err = validator.validate(too_high.as_bytes()).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PartnerFeeError>(),
+            Some(PartnerFeeError::TooHigh(101))
+        ));
+
+        let zero_recipient = r#"{"metadata":{"partnerFee":{"bps":1,"recipient":"0x0000000000000000000000000000000000000000"}}}"#;
+        let err = validator.validate(zero_recipient.as_bytes()).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PartnerFeeError>(),
+            Some(PartnerFeeError::ZeroRecipient)
+        ));
     }
 
     #[test]
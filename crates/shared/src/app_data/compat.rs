@@ -13,6 +13,7 @@ impl From<BackendAppData> for ProtocolAppData {
             hooks: value.hooks,
             signer: None,
             replaced_order: None,
+            partner_fee: None,
         }
     }
 }
@@ -18,6 +18,7 @@ use {
     std::{
         fmt::{self, Display, Formatter},
         num::NonZeroU64,
+        path::PathBuf,
         str::FromStr,
         time::Duration,
     },
@@ -280,6 +281,30 @@ pub struct Arguments {
     #[clap(long, env, default_value = "100")]
     pub max_pools_to_initialize_cache: usize,
 
+    /// How UniswapV3 pools are discovered. `Subgraph` bootstraps pools and
+    /// their ticks from the subgraph, `FactoryEvents` indexes the factory's
+    /// `PoolCreated` events and reconstructs ticks from pool events, which
+    /// works on networks without a subgraph.
+    #[clap(long, env, default_value = "Subgraph", value_enum)]
+    pub uniswap_v3_pool_discovery: PoolDiscovery,
+
+    /// Override address of the UniswapV3 factory contract used for
+    /// `FactoryEvents` pool discovery.
+    #[clap(long, env)]
+    pub uniswap_v3_factory_address: Option<H160>,
+
+    /// The block to start indexing UniswapV3 factory events from when no
+    /// checkpoint exists yet. Defaults to the factory's deployment block if
+    /// known.
+    #[clap(long, env)]
+    pub uniswap_v3_factory_start_block: Option<u64>,
+
+    /// File to persist the UniswapV3 pools checkpoint to when using
+    /// `FactoryEvents` pool discovery. Without it all events are indexed
+    /// again on every restart.
+    #[clap(long, env)]
+    pub uniswap_v3_pool_checkpoint_path: Option<PathBuf>,
+
     /// The time between new blocks on the network.
     #[clap(long, env, value_parser = humantime::parse_duration)]
     pub network_block_interval: Option<Duration>,
@@ -296,6 +321,18 @@ pub struct Arguments {
     #[clap(long, env)]
     pub balancer_v2_vault_address: Option<H160>,
 
+    /// How BalancerV2 pools are bootstrapped. `Subgraph` seeds the pool
+    /// registries from the subgraph, `FactoryEvents` scans the Vault's and
+    /// factories' events instead, which works on networks without a subgraph.
+    #[clap(long, env, default_value = "Subgraph", value_enum)]
+    pub balancer_v2_pool_discovery: PoolDiscovery,
+
+    /// The block to start scanning BalancerV2 events from when using
+    /// `FactoryEvents` pool discovery. Defaults to the Vault's deployment
+    /// block.
+    #[clap(long, env)]
+    pub balancer_v2_factory_start_block: Option<u64>,
+
     /// Deprecate market orders (orders with positive signed fee) starting from
     /// date
     #[clap(long, env)]
@@ -311,6 +348,14 @@ pub enum CodeSimulatorKind {
     Web3ThenTenderly,
 }
 
+/// How liquidity pools get discovered.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, clap::ValueEnum)]
+#[clap(rename_all = "verbatim")]
+pub enum PoolDiscovery {
+    Subgraph,
+    FactoryEvents,
+}
+
 pub fn display_secret_option<T>(
     f: &mut Formatter<'_>,
     name: &str,
@@ -422,10 +467,16 @@ impl Display for Arguments {
             settlement_contract_address,
             native_token_address,
             balancer_v2_vault_address,
+            balancer_v2_pool_discovery,
+            balancer_v2_factory_start_block,
             custom_univ2_baseline_sources,
             paraswap_api_url,
             liquidity_fetcher_max_age_update,
             max_pools_to_initialize_cache,
+            uniswap_v3_pool_discovery,
+            uniswap_v3_factory_address,
+            uniswap_v3_factory_start_block,
+            uniswap_v3_pool_checkpoint_path,
             market_orders_deprecation_date,
         } = self;
 
@@ -496,6 +547,16 @@ impl Display for Arguments {
             "balancer_v2_vault_address",
             &balancer_v2_vault_address.map(|a| format!("{a:?}")),
         )?;
+        writeln!(
+            f,
+            "balancer_v2_pool_discovery: {:?}",
+            balancer_v2_pool_discovery
+        )?;
+        display_option(
+            f,
+            "balancer_v2_factory_start_block",
+            balancer_v2_factory_start_block,
+        )?;
         display_list(
             f,
             "custom_univ2_baseline_sources",
@@ -512,6 +573,28 @@ impl Display for Arguments {
             "max_pools_to_initialize_cache: {}",
             max_pools_to_initialize_cache
         )?;
+        writeln!(
+            f,
+            "uniswap_v3_pool_discovery: {:?}",
+            uniswap_v3_pool_discovery
+        )?;
+        display_option(
+            f,
+            "uniswap_v3_factory_address",
+            &uniswap_v3_factory_address.map(|a| format!("{a:?}")),
+        )?;
+        display_option(
+            f,
+            "uniswap_v3_factory_start_block",
+            uniswap_v3_factory_start_block,
+        )?;
+        display_option(
+            f,
+            "uniswap_v3_pool_checkpoint_path",
+            &uniswap_v3_pool_checkpoint_path
+                .as_ref()
+                .map(|path| path.display()),
+        )?;
         display_option(
             f,
             "market_orders_deprecation_date",
@@ -586,6 +669,25 @@ impl FromStr for LegacySolver {
     }
 }
 
+// Wrapper type for AWS ARN identifiers
+#[derive(Debug, Clone)]
+pub struct Arn(pub String);
+
+impl FromStr for Arn {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // Could be more strict here, but this should suffice to catch unintended
+        // configuration mistakes
+        ensure!(
+            s.starts_with("arn:aws:kms:"),
+            "Invalid ARN identifier: {}",
+            s
+        );
+        Ok(Self(s.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
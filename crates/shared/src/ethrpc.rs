@@ -34,6 +34,32 @@ pub struct Arguments {
     /// out an incomplete batch.
     #[clap(long, env, value_parser = humantime::parse_duration, default_value = "0s")]
     pub ethrpc_batch_delay: Duration,
+
+    /// Additional nodes the main node fails over to when it is unhealthy or
+    /// can't be reached. They are tried in the specified order.
+    #[clap(long, env, use_value_delimiter = true)]
+    pub ethrpc_fallback_urls: Vec<Url>,
+
+    /// How many blocks a node can lag behind the most advanced node before it
+    /// is considered unhealthy. Only used when fallback nodes are configured.
+    #[clap(long, env, default_value = "3")]
+    pub ethrpc_max_block_lag: u64,
+
+    /// Share of failed recent requests above which a node is considered
+    /// unhealthy. Only used when fallback nodes are configured.
+    #[clap(long, env, default_value = "0.5")]
+    pub ethrpc_max_error_rate: f64,
+
+    /// How often the health of the nodes gets checked. Only used when fallback
+    /// nodes are configured.
+    #[clap(long, env, value_parser = humantime::parse_duration, default_value = "5s")]
+    pub ethrpc_health_check_interval: Duration,
+
+    /// How long to wait for a response to `eth_call` and `eth_estimateGas`
+    /// requests before also sending them to the next node. Hedging is disabled
+    /// if not specified. Only used when fallback nodes are configured.
+    #[clap(long, env, value_parser = humantime::parse_duration)]
+    pub ethrpc_hedge_delay: Option<Duration>,
 }
 
 impl Display for Arguments {
@@ -42,6 +68,11 @@ impl Display for Arguments {
             ethrpc_max_batch_size,
             ethrpc_max_concurrent_requests,
             ethrpc_batch_delay,
+            ethrpc_fallback_urls,
+            ethrpc_max_block_lag,
+            ethrpc_max_error_rate,
+            ethrpc_health_check_interval,
+            ethrpc_hedge_delay,
         } = self;
 
         writeln!(f, "ethrpc_max_batch_size: {}", ethrpc_max_batch_size)?;
@@ -51,6 +82,15 @@ impl Display for Arguments {
             ethrpc_max_concurrent_requests
         )?;
         writeln!(f, "ethrpc_batch_delay: {:?}", ethrpc_batch_delay)?;
+        writeln!(f, "ethrpc_fallback_urls: {:?}", ethrpc_fallback_urls)?;
+        writeln!(f, "ethrpc_max_block_lag: {}", ethrpc_max_block_lag)?;
+        writeln!(f, "ethrpc_max_error_rate: {}", ethrpc_max_error_rate)?;
+        writeln!(
+            f,
+            "ethrpc_health_check_interval: {:?}",
+            ethrpc_health_check_interval
+        )?;
+        writeln!(f, "ethrpc_hedge_delay: {:?}", ethrpc_hedge_delay)?;
 
         Ok(())
     }
@@ -64,6 +104,16 @@ impl Arguments {
             ethrpc_batch_delay: self.ethrpc_batch_delay,
         }
     }
+
+    /// Configuration of the transport that fails over to the fallback nodes.
+    pub fn multi(&self) -> ethrpc::multi::Configuration {
+        ethrpc::multi::Configuration {
+            max_block_lag: self.ethrpc_max_block_lag,
+            max_error_rate: self.ethrpc_max_error_rate,
+            health_check_interval: self.ethrpc_health_check_interval,
+            hedge_delay: self.ethrpc_hedge_delay,
+        }
+    }
 }
 
 /// Create a Web3 instance.
@@ -76,3 +126,26 @@ pub fn web3(
     let http_builder = http_factory.builder();
     ethrpc::web3(args.ethrpc(), http_builder, url, name)
 }
+
+/// Create a Web3 instance for the main node that fails over to the configured
+/// fallback nodes.
+pub fn web3_with_fallbacks(
+    args: &Arguments,
+    http_factory: &HttpClientFactory,
+    url: &Url,
+    name: impl ToString,
+) -> Web3 {
+    if args.ethrpc_fallback_urls.is_empty() {
+        return web3(args, http_factory, url, name);
+    }
+    let urls = std::iter::once(url.clone())
+        .chain(args.ethrpc_fallback_urls.iter().cloned())
+        .collect::<Vec<_>>();
+    ethrpc::multi_web3(
+        args.ethrpc(),
+        args.multi(),
+        http_factory.builder(),
+        &urls,
+        name,
+    )
+}
@@ -13,7 +13,12 @@ use {
             QuoteSearchParameters,
         },
         price_estimation::{PriceEstimationError, Verification},
-        signature_validator::{SignatureCheck, SignatureValidating, SignatureValidationError},
+        signature_validator::{
+            Erc6492Signature,
+            SignatureCheck,
+            SignatureValidating,
+            SignatureValidationError,
+        },
         trade_finding,
     },
     anyhow::{anyhow, Result},
@@ -22,6 +27,7 @@ use {
     contracts::{HooksTrampoline, WETH9},
     database::onchain_broadcasted_orders::OnchainOrderPlacementError,
     ethcontract::{Bytes, H160, H256, U256},
+    futures::future,
     model::{
         app_data::AppDataHash,
         interaction::InteractionData,
@@ -38,6 +44,7 @@ use {
             OrderData,
             OrderKind,
             OrderMetadata,
+            OrderUid,
             SellTokenSource,
             VerificationError,
             BUY_ETH_ADDRESS,
@@ -47,7 +54,11 @@ use {
         time,
         DomainSeparator,
     },
-    std::{sync::Arc, time::Duration},
+    std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+        time::Duration,
+    },
 };
 
 #[mockall::automock]
@@ -101,6 +112,19 @@ pub trait OrderValidating: Send + Sync {
         settlement_contract: H160,
         full_app_data_override: Option<String>,
     ) -> Result<(Order, Option<Quote>), ValidationError>;
+
+    /// Like [`Self::validate_and_construct_order`] but for multiple orders
+    /// that get placed together, each with its `full_app_data_override`.
+    /// Balance and quote lookups are shared between the orders and limit
+    /// orders count towards their owner's limit of limit orders for all later
+    /// orders of the batch even though they are not in the database yet.
+    /// Returns the result for every order in the order of `orders`.
+    async fn validate_and_construct_orders(
+        &self,
+        orders: Vec<(OrderCreation, Option<String>)>,
+        domain_separator: &DomainSeparator,
+        settlement_contract: H160,
+    ) -> Vec<Result<(Order, Option<Quote>), ValidationError>>;
 }
 
 #[derive(Debug)]
@@ -128,6 +152,8 @@ pub enum AppDataValidationError {
         provided: AppDataHash,
         actual: AppDataHash,
     },
+    /// The partner fee specified in the app data is not allowed.
+    InvalidPartnerFee(crate::app_data::PartnerFeeError),
     Invalid(anyhow::Error),
 }
 
@@ -364,39 +390,41 @@ impl OrderValidator {
     }
 
     fn custom_interactions(&self, hooks: &Hooks) -> Interactions {
-        let to_interactions = |hooks: &[Hook]| -> Vec<InteractionData> {
-            if hooks.is_empty() {
-                vec![]
-            } else {
-                vec![InteractionData {
-                    target: self.hooks.address(),
-                    value: U256::zero(),
-                    call_data: self
-                        .hooks
-                        .execute(
-                            hooks
-                                .iter()
-                                .map(|hook| {
-                                    (
-                                        hook.target,
-                                        Bytes(hook.call_data.clone()),
-                                        hook.gas_limit.into(),
-                                    )
-                                })
-                                .collect(),
-                        )
-                        .tx
-                        .data
-                        .unwrap()
-                        .0,
-                }]
-            }
-        };
-
         Interactions {
-            pre: to_interactions(&hooks.pre),
-            post: to_interactions(&hooks.post),
+            pre: self.hooks_interactions(&hooks.pre),
+            post: self.hooks_interactions(&hooks.post),
+        }
+    }
+
+    /// Executes the hooks through the hooks trampoline so that they run with
+    /// their gas limit and without the privileges of the settlement contract.
+    /// The trampoline ignores reverting hooks.
+    fn hooks_interactions(&self, hooks: &[Hook]) -> Vec<InteractionData> {
+        if hooks.is_empty() {
+            return vec![];
         }
+        vec![InteractionData {
+            target: self.hooks.address(),
+            value: U256::zero(),
+            call_data: self
+                .hooks
+                .execute(
+                    hooks
+                        .iter()
+                        .map(|hook| {
+                            (
+                                hook.target,
+                                Bytes(hook.call_data.clone()),
+                                hook.gas_limit.into(),
+                            )
+                        })
+                        .collect(),
+                )
+                .tx
+                .data
+                .unwrap()
+                .0,
+        }]
     }
 }
 
@@ -462,7 +490,12 @@ impl OrderValidating for OrderValidator {
             let app_data = self
                 .app_data_validator
                 .validate(app_data.as_bytes())
-                .map_err(AppDataValidationError::Invalid)?;
+                .map_err(
+                    |err| match err.downcast::<crate::app_data::PartnerFeeError>() {
+                        Ok(err) => AppDataValidationError::InvalidPartnerFee(err),
+                        Err(err) => AppDataValidationError::Invalid(err),
+                    },
+                )?;
             Ok(app_data)
         };
 
@@ -513,9 +546,300 @@ impl OrderValidating for OrderValidator {
         settlement_contract: H160,
         full_app_data_override: Option<String>,
     ) -> Result<(Order, Option<Quote>), ValidationError> {
+        let order = self
+            .prepare_order(order, domain_separator, full_app_data_override)
+            .await?;
+        check_transfer(
+            order.signing_scheme,
+            self.balance_fetcher
+                .can_transfer(&order.balance_query(), order.min_balance)
+                .await,
+        )?;
+        let quote = get_or_create_quote(
+            &*self.quoter,
+            &order.quote_parameters,
+            order.creation.quote_id,
+        )
+        .await?;
+        check_fee(
+            &quote,
+            order.fee_to_check(),
+            self.market_orders_deprecation_date,
+        )?;
+        if order.is_limit_order(&quote) {
+            self.check_max_limit_orders(order.owner).await?;
+        }
+        Ok(order.construct(quote, settlement_contract))
+    }
+
+    async fn validate_and_construct_orders(
+        &self,
+        orders: Vec<(OrderCreation, Option<String>)>,
+        domain_separator: &DomainSeparator,
+        settlement_contract: H160,
+    ) -> Vec<Result<(Order, Option<Quote>), ValidationError>> {
+        let mut prepared =
+            future::join_all(orders.into_iter().map(|(order, full_app_data_override)| {
+                self.prepare_order(order, domain_separator, full_app_data_override)
+            }))
+            .await;
+
+        // Orders selling the same token of the same owner share a single
+        // transfer simulation for the largest required balance. Only if that
+        // fails are the orders checked individually to report the exact error.
+        let mut transfers = HashMap::<account_balances::Query, Vec<(usize, U256)>>::new();
+        for (index, order) in prepared.iter().enumerate() {
+            if let Ok(order) = order {
+                transfers
+                    .entry(order.balance_query())
+                    .or_default()
+                    .push((index, order.min_balance));
+            }
+        }
+        let transfers = future::join_all(transfers.into_iter().map(|(query, orders)| async move {
+            let max_balance = orders
+                .iter()
+                .map(|(_, balance)| *balance)
+                .max()
+                .unwrap_or_default();
+            match self.balance_fetcher.can_transfer(&query, max_balance).await {
+                Ok(()) => orders
+                    .into_iter()
+                    .map(|(index, _)| (index, Ok(())))
+                    .collect::<Vec<_>>(),
+                Err(err) if orders.len() == 1 => vec![(orders[0].0, Err(err))],
+                Err(_) => {
+                    future::join_all(orders.into_iter().map(|(index, balance)| {
+                        let query = &query;
+                        async move {
+                            (
+                                index,
+                                self.balance_fetcher.can_transfer(query, balance).await,
+                            )
+                        }
+                    }))
+                    .await
+                }
+            }
+        }))
+        .await;
+        for (index, result) in transfers.into_iter().flatten() {
+            if let Ok(order) = &prepared[index] {
+                if let Err(err) = check_transfer(order.signing_scheme, result) {
+                    prepared[index] = Err(err);
+                }
+            }
+        }
+
+        // Orders with the same quote parameters share a single quote lookup.
+        let mut quote_lookups = Vec::<(Option<i64>, QuoteSearchParameters)>::new();
+        let prepared = prepared
+            .into_iter()
+            .map(|order| {
+                let order = order?;
+                let lookup = (order.creation.quote_id, order.quote_parameters.clone());
+                let quote_index = match quote_lookups.iter().position(|other| *other == lookup) {
+                    Some(index) => index,
+                    None => {
+                        quote_lookups.push(lookup);
+                        quote_lookups.len() - 1
+                    }
+                };
+                Ok((order, quote_index))
+            })
+            .collect::<Vec<Result<_, ValidationError>>>();
+        let mut quotes = future::join_all(
+            quote_lookups
+                .iter()
+                .map(|(id, parameters)| get_or_create_quote(&*self.quoter, parameters, *id)),
+        )
+        .await
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+
+        let mut quoted = Vec::with_capacity(prepared.len());
+        for result in prepared {
+            let (order, quote_index) = match result {
+                Ok(result) => result,
+                Err(err) => {
+                    quoted.push(Err(err));
+                    continue;
+                }
+            };
+            let quote = match quotes[quote_index].take() {
+                Some(Ok(quote)) => {
+                    quotes[quote_index] = Some(Ok(quote.clone()));
+                    Ok(quote)
+                }
+                Some(Err(err)) => Err(err),
+                // The shared lookup already failed for an earlier order of
+                // the batch. Look the quote up again to get this order's error.
+                None => {
+                    get_or_create_quote(
+                        &*self.quoter,
+                        &order.quote_parameters,
+                        order.creation.quote_id,
+                    )
+                    .await
+                }
+            };
+            quoted.push(quote.and_then(|quote| {
+                check_fee(
+                    &quote,
+                    order.fee_to_check(),
+                    self.market_orders_deprecation_date,
+                )?;
+                Ok((order, quote))
+            }));
+        }
+
+        // The stored limit orders are counted once per owner. Limit orders of
+        // the batch count towards the owner's limit for all later orders.
+        let owners = quoted
+            .iter()
+            .filter_map(|result| {
+                let (order, quote) = result.as_ref().ok()?;
+                order.is_limit_order(quote).then_some(order.owner)
+            })
+            .collect::<HashSet<_>>();
+        let limit_orders = future::join_all(
+            owners
+                .into_iter()
+                .map(|owner| async move { (owner, self.limit_order_counter.count(owner).await) }),
+        )
+        .await
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+        let mut pending_limit_orders = HashMap::<H160, u64>::new();
+        quoted
+            .into_iter()
+            .map(|result| {
+                let (order, quote) = result?;
+                if order.is_limit_order(&quote) {
+                    let stored = match &limit_orders[&order.owner] {
+                        Ok(count) => *count,
+                        Err(err) => {
+                            return Err(ValidationError::Other(anyhow!(
+                                "failed to count limit orders: {err:?}"
+                            )))
+                        }
+                    };
+                    let pending = pending_limit_orders.entry(order.owner).or_default();
+                    if stored.saturating_add(*pending) >= self.max_limit_orders_per_user {
+                        return Err(ValidationError::TooManyLimitOrders);
+                    }
+                    *pending += 1;
+                }
+                Ok(order.construct(quote, settlement_contract))
+            })
+            .collect()
+    }
+}
+
+/// An order that passed all validations that don't depend on the owner's
+/// balance, the order's quote or the owner's other limit orders.
+struct PreparedOrder {
+    creation: OrderCreation,
+    full_app_data_override: Option<String>,
+    app_data: OrderAppData,
+    owner: H160,
+    uid: OrderUid,
+    data: OrderData,
+    signature: Signature,
+    signing_scheme: SigningScheme,
+    class: OrderClass,
+    quote_parameters: QuoteSearchParameters,
+    min_balance: U256,
+}
+
+impl PreparedOrder {
+    fn balance_query(&self) -> account_balances::Query {
+        account_balances::Query {
+            token: self.data.sell_token,
+            owner: self.owner,
+            source: self.data.sell_token_balance,
+            interactions: self.app_data.interactions.pre.clone(),
+        }
+    }
+
+    /// The fee that has to be covered by the quote. Only market orders pay a
+    /// signed fee.
+    fn fee_to_check(&self) -> Option<U256> {
+        (self.class == OrderClass::Market).then_some(self.data.fee_amount)
+    }
+
+    fn is_outside_market_price(&self, quote: &Quote) -> bool {
+        is_order_outside_market_price(
+            &Amounts {
+                sell: self.data.sell_amount,
+                buy: self.data.buy_amount,
+                fee: self.data.fee_amount,
+            },
+            &Amounts {
+                sell: quote.sell_amount,
+                buy: quote.buy_amount,
+                fee: quote.fee_amount,
+            },
+        )
+    }
+
+    /// Whether the order counts towards the owner's limit of limit orders.
+    /// Only orders that were placed as limit orders and are not "in-market"
+    /// count.
+    fn is_limit_order(&self, quote: &Quote) -> bool {
+        self.class != OrderClass::Market && self.is_outside_market_price(quote)
+    }
+
+    fn construct(self, quote: Quote, settlement_contract: H160) -> (Order, Option<Quote>) {
+        // Check if we need to re-classify the market order if it is outside the market
+        // price. We consider out-of-price orders as liquidity orders. See
+        // <https://github.com/cowprotocol/services/pull/301>.
+        let (class, quote) = match self.class {
+            OrderClass::Market if self.is_outside_market_price(&quote) => {
+                tracing::debug!(uid = %self.uid, owner = ?self.owner, "order being flagged as outside market price");
+                (OrderClass::Limit, Some(quote))
+            }
+            OrderClass::Market | OrderClass::Limit => (self.class, Some(quote)),
+            OrderClass::Liquidity => (OrderClass::Limit, None),
+        };
+
+        let order = Order {
+            metadata: OrderMetadata {
+                owner: self.owner,
+                creation_date: chrono::offset::Utc::now(),
+                uid: self.uid,
+                settlement_contract,
+                full_fee_amount: self.data.fee_amount,
+                class,
+                full_app_data: match self.creation.app_data {
+                    OrderCreationAppData::Both { full, .. }
+                    | OrderCreationAppData::Full { full } => Some(full),
+                    OrderCreationAppData::Hash { .. } => self.full_app_data_override,
+                },
+                ..Default::default()
+            },
+            signature: self.signature,
+            data: self.data,
+            interactions: self.app_data.interactions,
+        };
+
+        (order, quote)
+    }
+}
+
+impl OrderValidator {
+    /// Performs all validations of an order that don't depend on the owner's
+    /// balance, the order's quote or the owner's other limit orders.
+    async fn prepare_order(
+        &self,
+        order: OrderCreation,
+        domain_separator: &DomainSeparator,
+        full_app_data_override: Option<String>,
+    ) -> Result<PreparedOrder, ValidationError> {
         // Happens before signature verification because a miscalculated app data hash
         // by the API user would lead to being unable to validate the signature below.
-        let app_data = self.validate_app_data(&order.app_data, &full_app_data_override)?;
+        let mut app_data = self.validate_app_data(&order.app_data, &full_app_data_override)?;
         let app_data_signer = app_data.inner.protocol.signer;
 
         let owner = order.verify_owner(domain_separator, app_data_signer)?;
@@ -553,6 +877,36 @@ impl OrderValidating for OrderValidator {
             0u64
         };
 
+        // The settlement contract calls `isValidSignature` with the stored
+        // signature so ERC-6492 wrapped signatures get unwrapped. Counterfactual
+        // wallets get deployed by a hook before any other pre-interaction. The
+        // hook gets executed through the trampoline which ignores failures, so
+        // the settlement doesn't revert if the wallet got deployed in the
+        // meantime.
+        let mut deployment_gas = 0u64;
+        let signature = match &order.signature {
+            Signature::Eip1271(signature) if !self.eip1271_skip_creation_validation => {
+                match Erc6492Signature::decode(signature) {
+                    Some(wrapped) => {
+                        let code_size = self
+                            .code_fetcher
+                            .code_size(owner)
+                            .await
+                            .map_err(ValidationError::Other)?;
+                        if code_size == 0 {
+                            let deployment = wrapped.deployment_hook();
+                            deployment_gas = deployment.gas_limit;
+                            let interactions = self.hooks_interactions(&[deployment]);
+                            app_data.interactions.pre.splice(0..0, interactions);
+                        }
+                        Signature::Eip1271(wrapped.signature)
+                    }
+                    None => order.signature.clone(),
+                }
+            }
+            _ => order.signature.clone(),
+        };
+
         if data.buy_amount.is_zero() || data.sell_amount.is_zero() {
             return Err(ValidationError::ZeroAmount);
         }
@@ -584,171 +938,30 @@ impl OrderValidating for OrderValidator {
                 true,
                 verification_gas_limit,
             )?,
-            additional_gas: app_data.inner.protocol.hooks.gas_limit(),
+            additional_gas: app_data
+                .inner
+                .protocol
+                .hooks
+                .gas_limit()
+                .saturating_add(deployment_gas),
             verification,
         };
 
         let min_balance = minimum_balance(&data).ok_or(ValidationError::SellAmountOverflow)?;
 
-        // Fast path to check if transfer is possible with a single node query.
-        // If not, run extra queries for additional information.
-        match self
-            .balance_fetcher
-            .can_transfer(
-                &account_balances::Query {
-                    token: data.sell_token,
-                    owner,
-                    source: data.sell_token_balance,
-                    interactions: app_data.interactions.pre.clone(),
-                },
-                min_balance,
-            )
-            .await
-        {
-            Ok(_) => (),
-            Err(
-                TransferSimulationError::InsufficientAllowance
-                | TransferSimulationError::InsufficientBalance
-                | TransferSimulationError::TransferFailed,
-            ) if signing_scheme == SigningScheme::PreSign => {
-                // We have an exception for pre-sign orders where they do not
-                // require sufficient balance or allowance. The idea, is that
-                // this allows smart contracts to place orders bundled with
-                // other transactions that either produce the required balance
-                // or set the allowance. This would, for example, allow a Gnosis
-                // Safe to bundle the pre-signature transaction with a WETH wrap
-                // and WETH approval to the vault relayer contract.
-            }
-            Err(err) => match err {
-                TransferSimulationError::InsufficientAllowance => {
-                    return Err(ValidationError::InsufficientAllowance);
-                }
-                TransferSimulationError::InsufficientBalance => {
-                    return Err(ValidationError::InsufficientBalance);
-                }
-                TransferSimulationError::TransferFailed => {
-                    return Err(ValidationError::TransferSimulationFailed);
-                }
-                TransferSimulationError::Other(err) => {
-                    tracing::warn!("TransferSimulation failed: {:?}", err);
-                    return Err(ValidationError::TransferSimulationFailed);
-                }
-            },
-        }
-
-        // Check if we need to re-classify the market order if it is outside the market
-        // price. We consider out-of-price orders as liquidity orders. See
-        // <https://github.com/cowprotocol/services/pull/301>.
-        let (class, quote) = match class {
-            // This has to be here in order to keep the previous behaviour
-            OrderClass::Market => {
-                let quote = get_quote_and_check_fee(
-                    &*self.quoter,
-                    &quote_parameters,
-                    order.quote_id,
-                    Some(data.fee_amount),
-                    self.market_orders_deprecation_date,
-                )
-                .await?;
-                tracing::debug!(
-                    ?uid,
-                    ?order,
-                    ?quote,
-                    "checking if order is outside market price"
-                );
-                if is_order_outside_market_price(
-                    &Amounts {
-                        sell: data.sell_amount,
-                        buy: data.buy_amount,
-                        fee: data.fee_amount,
-                    },
-                    &Amounts {
-                        sell: quote.sell_amount,
-                        buy: quote.buy_amount,
-                        fee: quote.fee_amount,
-                    },
-                ) {
-                    tracing::debug!(%uid, ?owner, ?class, "order being flagged as outside market price");
-                    (OrderClass::Limit, Some(quote))
-                } else {
-                    (class, Some(quote))
-                }
-            }
-            OrderClass::Limit => {
-                let quote = get_quote_and_check_fee(
-                    &*self.quoter,
-                    &quote_parameters,
-                    order.quote_id,
-                    None,
-                    self.market_orders_deprecation_date,
-                )
-                .await?;
-                // If the order is not "In-Market", check for the limit orders
-                if is_order_outside_market_price(
-                    &Amounts {
-                        sell: data.sell_amount,
-                        buy: data.buy_amount,
-                        fee: data.fee_amount,
-                    },
-                    &Amounts {
-                        sell: quote.sell_amount,
-                        buy: quote.buy_amount,
-                        fee: quote.fee_amount,
-                    },
-                ) {
-                    self.check_max_limit_orders(owner).await?;
-                }
-                (class, Some(quote))
-            }
-            OrderClass::Liquidity => {
-                let quote = get_quote_and_check_fee(
-                    &*self.quoter,
-                    &quote_parameters,
-                    order.quote_id,
-                    None,
-                    self.market_orders_deprecation_date,
-                )
-                .await?;
-                // If the order is not "In-Market", check for the limit orders
-                if is_order_outside_market_price(
-                    &Amounts {
-                        sell: data.sell_amount,
-                        buy: data.buy_amount,
-                        fee: data.fee_amount,
-                    },
-                    &Amounts {
-                        sell: quote.sell_amount,
-                        buy: quote.buy_amount,
-                        fee: quote.fee_amount,
-                    },
-                ) {
-                    self.check_max_limit_orders(owner).await?;
-                }
-                (OrderClass::Limit, None)
-            }
-        };
-
-        let order = Order {
-            metadata: OrderMetadata {
-                owner,
-                creation_date: chrono::offset::Utc::now(),
-                uid,
-                settlement_contract,
-                full_fee_amount: data.fee_amount,
-                class,
-                full_app_data: match order.app_data {
-                    OrderCreationAppData::Both { full, .. }
-                    | OrderCreationAppData::Full { full } => Some(full),
-                    OrderCreationAppData::Hash { .. } => full_app_data_override,
-                },
-                ..Default::default()
-            },
-            signature: order.signature.clone(),
+        Ok(PreparedOrder {
+            creation: order,
+            full_app_data_override,
+            app_data,
+            owner,
+            uid,
             data,
-            interactions: app_data.interactions,
-        };
-
-        Ok((order, quote))
+            signature,
+            signing_scheme,
+            class,
+            quote_parameters,
+            min_balance,
+        })
     }
 }
 
@@ -839,18 +1052,65 @@ pub async fn get_quote_and_check_fee(
     market_orders_deprecation_date: Option<chrono::DateTime<chrono::Utc>>,
 ) -> Result<Quote, ValidationError> {
     let quote = get_or_create_quote(quoter, quote_search_parameters, quote_id).await?;
+    check_fee(&quote, fee_amount, market_orders_deprecation_date)?;
+    Ok(quote)
+}
 
+/// Verifies that the fee of an order is sufficient for its quote.
+///
+/// The fee is checked only if `fee_amount` is specified.
+fn check_fee(
+    quote: &Quote,
+    fee_amount: Option<U256>,
+    market_orders_deprecation_date: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<(), ValidationError> {
     match market_orders_deprecation_date {
         Some(date) if Utc::now() > date && fee_amount.is_some_and(|fee| !fee.is_zero()) => {
-            return Err(ValidationError::NonZeroFee);
+            Err(ValidationError::NonZeroFee)
         }
         None if fee_amount.is_some_and(|fee| fee < quote.fee_amount) => {
-            return Err(ValidationError::InsufficientFee);
+            Err(ValidationError::InsufficientFee)
         }
-        _ => (),
+        _ => Ok(()),
     }
+}
 
-    Ok(quote)
+/// Converts the result of a transfer simulation for an order's sell token into
+/// a validation result.
+fn check_transfer(
+    signing_scheme: SigningScheme,
+    result: Result<(), TransferSimulationError>,
+) -> Result<(), ValidationError> {
+    match result {
+        Ok(_) => Ok(()),
+        Err(
+            TransferSimulationError::InsufficientAllowance
+            | TransferSimulationError::InsufficientBalance
+            | TransferSimulationError::TransferFailed,
+        ) if signing_scheme == SigningScheme::PreSign => {
+            // We have an exception for pre-sign orders where they do not
+            // require sufficient balance or allowance. The idea, is that
+            // this allows smart contracts to place orders bundled with
+            // other transactions that either produce the required balance
+            // or set the allowance. This would, for example, allow a Gnosis
+            // Safe to bundle the pre-signature transaction with a WETH wrap
+            // and WETH approval to the vault relayer contract.
+            Ok(())
+        }
+        Err(TransferSimulationError::InsufficientAllowance) => {
+            Err(ValidationError::InsufficientAllowance)
+        }
+        Err(TransferSimulationError::InsufficientBalance) => {
+            Err(ValidationError::InsufficientBalance)
+        }
+        Err(TransferSimulationError::TransferFailed) => {
+            Err(ValidationError::TransferSimulationFailed)
+        }
+        Err(TransferSimulationError::Other(err)) => {
+            tracing::warn!("TransferSimulation failed: {:?}", err);
+            Err(ValidationError::TransferSimulationFailed)
+        }
+    }
 }
 
 /// Retrieves the quote for an order that is being created
@@ -959,7 +1219,7 @@ mod tests {
             bad_token::{MockBadTokenDetecting, TokenQuality},
             code_fetching::MockCodeFetching,
             order_quoting::MockOrderQuoting,
-            signature_validator::MockSignatureValidating,
+            signature_validator::{MockSignatureValidating, ERC6492_DEPLOYMENT_GAS_LIMIT},
         },
         anyhow::anyhow,
         chrono::Utc,
@@ -1528,6 +1788,94 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn post_validate_too_many_limit_orders_in_batch() {
+        let mut order_quoter = MockOrderQuoting::new();
+        let mut bad_token_detector = MockBadTokenDetecting::new();
+        let mut balance_fetcher = MockBalanceFetching::new();
+        // The orders of the batch share their quote and balance lookups.
+        order_quoter.expect_find_quote().times(1).returning(|_, _| {
+            Ok(Quote {
+                id: None,
+                data: Default::default(),
+                sell_amount: U256::from(1),
+                buy_amount: U256::from(1),
+                fee_amount: Default::default(),
+            })
+        });
+        bad_token_detector
+            .expect_detect()
+            .returning(|_| Ok(TokenQuality::Good));
+        balance_fetcher
+            .expect_can_transfer()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut signature_validating = MockSignatureValidating::new();
+        signature_validating
+            .expect_validate_signature_and_get_additional_gas()
+            .never();
+        let signature_validating = Arc::new(signature_validating);
+
+        const MAX_LIMIT_ORDERS_PER_USER: u64 = 2;
+
+        let mut limit_order_counter = MockLimitOrderCounting::new();
+        limit_order_counter
+            .expect_count()
+            .times(1)
+            .returning(|_| Ok(MAX_LIMIT_ORDERS_PER_USER - 1));
+
+        let validator = OrderValidator::new(
+            dummy_contract!(WETH9, [0xef; 20]),
+            Arc::new(order_validation::banned::Users::none()),
+            OrderValidPeriodConfiguration {
+                min: Duration::from_secs(1),
+                max_market: Duration::from_secs(100),
+                max_limit: Duration::from_secs(200),
+            },
+            false,
+            Arc::new(bad_token_detector),
+            dummy_contract!(HooksTrampoline, [0xcf; 20]),
+            Arc::new(order_quoter),
+            Arc::new(balance_fetcher),
+            signature_validating,
+            Arc::new(limit_order_counter),
+            MAX_LIMIT_ORDERS_PER_USER,
+            Arc::new(MockCodeFetching::new()),
+            Default::default(),
+            None,
+        );
+
+        let creation = OrderCreation {
+            valid_to: model::time::now_in_epoch_seconds() + 2,
+            sell_token: H160::from_low_u64_be(1),
+            buy_token: H160::from_low_u64_be(2),
+            buy_amount: U256::from(10),
+            sell_amount: U256::from(1),
+            signature: Signature::Eip712(EcdsaSignature::non_zero()),
+            app_data: OrderCreationAppData::Full {
+                full: "{}".to_string(),
+            },
+            ..Default::default()
+        };
+        let results = validator
+            .validate_and_construct_orders(
+                vec![(creation.clone(), None), (creation, None)],
+                &Default::default(),
+                Default::default(),
+            )
+            .await;
+        assert_eq!(results.len(), 2);
+        // The first limit order of the owner in the batch still fits.
+        assert!(results[0].is_ok(), "{:?}", results[0]);
+        // The second one exceeds the limit together with the stored orders.
+        assert!(
+            matches!(results[1], Err(ValidationError::TooManyLimitOrders)),
+            "{:?}",
+            results[1]
+        );
+    }
+
     #[tokio::test]
     async fn post_limit_does_not_apply_to_in_market_orders() {
         let mut order_quoter = MockOrderQuoting::new();
@@ -2042,6 +2390,174 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn post_validate_erc6492_signature() {
+        let mut order_quoter = MockOrderQuoting::new();
+        let mut bad_token_detector = MockBadTokenDetecting::new();
+        let mut balance_fetcher = MockBalanceFetching::new();
+        let mut signature_validator = MockSignatureValidating::new();
+        let mut code_fetcher = MockCodeFetching::new();
+        // The deployment gas needs to be accounted for in the quote.
+        order_quoter
+            .expect_find_quote()
+            .withf(|_, parameters| parameters.additional_gas == ERC6492_DEPLOYMENT_GAS_LIMIT)
+            .returning(|_, _| Ok(Default::default()));
+        bad_token_detector
+            .expect_detect()
+            .returning(|_| Ok(TokenQuality::Good));
+
+        let owner = H160([1; 20]);
+        let wrapped = Erc6492Signature {
+            factory: H160([2; 20]),
+            factory_calldata: vec![3; 68],
+            signature: vec![1, 2, 3],
+        };
+        let creation = OrderCreation {
+            valid_to: time::now_in_epoch_seconds() + 2,
+            sell_token: H160::from_low_u64_be(1),
+            buy_token: H160::from_low_u64_be(2),
+            buy_amount: U256::from(1),
+            sell_amount: U256::from(1),
+            fee_amount: U256::from(1),
+            from: Some(owner),
+            signature: Signature::Eip1271(wrapped.encode()),
+            app_data: OrderCreationAppData::Full {
+                full: "{}".to_string(),
+            },
+            ..Default::default()
+        };
+        let domain = DomainSeparator::default();
+
+        // The validator gets the wrapped signature and simulates the deployment
+        // itself.
+        signature_validator
+            .expect_validate_signature_and_get_additional_gas()
+            .with(eq(SignatureCheck {
+                signer: owner,
+                hash: signature::hashed_eip712_message(&domain, &creation.data().hash_struct()),
+                signature: wrapped.encode(),
+                interactions: vec![],
+            }))
+            .returning(|_| Ok(0u64));
+        code_fetcher
+            .expect_code_size()
+            .with(eq(owner))
+            .returning(|_| Ok(0));
+
+        // The wallet gets deployed through the hooks trampoline with a gas
+        // limit.
+        let hooks = dummy_contract!(HooksTrampoline, [0xcf; 20]);
+        let deployment = InteractionData {
+            target: hooks.address(),
+            value: U256::zero(),
+            call_data: hooks
+                .execute(vec![(
+                    wrapped.factory,
+                    Bytes(wrapped.factory_calldata.clone()),
+                    ERC6492_DEPLOYMENT_GAS_LIMIT.into(),
+                )])
+                .tx
+                .data
+                .unwrap()
+                .0,
+        };
+        balance_fetcher
+            .expect_can_transfer()
+            .withf({
+                let deployment = deployment.clone();
+                move |query, _| query.interactions == vec![deployment.clone()]
+            })
+            .returning(|_, _| Ok(()));
+        let mut limit_order_counter = MockLimitOrderCounting::new();
+        limit_order_counter.expect_count().returning(|_| Ok(0u64));
+        let validator = OrderValidator::new(
+            dummy_contract!(WETH9, [0xef; 20]),
+            Arc::new(order_validation::banned::Users::none()),
+            OrderValidPeriodConfiguration::any(),
+            false,
+            Arc::new(bad_token_detector),
+            hooks,
+            Arc::new(order_quoter),
+            Arc::new(balance_fetcher),
+            Arc::new(signature_validator),
+            Arc::new(limit_order_counter),
+            0,
+            Arc::new(code_fetcher),
+            Default::default(),
+            None,
+        );
+
+        let (order, _) = validator
+            .validate_and_construct_order(creation, &domain, Default::default(), None)
+            .await
+            .unwrap();
+        assert_eq!(order.signature, Signature::Eip1271(vec![1, 2, 3]));
+        assert_eq!(order.interactions.pre, vec![deployment]);
+    }
+
+    #[tokio::test]
+    async fn post_validate_erc6492_signature_skipping_creation_validation() {
+        let mut order_quoter = MockOrderQuoting::new();
+        let mut bad_token_detector = MockBadTokenDetecting::new();
+        let mut balance_fetcher = MockBalanceFetching::new();
+        order_quoter
+            .expect_find_quote()
+            .returning(|_, _| Ok(Default::default()));
+        bad_token_detector
+            .expect_detect()
+            .returning(|_| Ok(TokenQuality::Good));
+        balance_fetcher
+            .expect_can_transfer()
+            .withf(|query, _| query.interactions.is_empty())
+            .returning(|_, _| Ok(()));
+        let mut limit_order_counter = MockLimitOrderCounting::new();
+        limit_order_counter.expect_count().returning(|_| Ok(0u64));
+
+        let wrapped = Erc6492Signature {
+            factory: H160([2; 20]),
+            factory_calldata: vec![3; 68],
+            signature: vec![1, 2, 3],
+        };
+        let creation = OrderCreation {
+            valid_to: time::now_in_epoch_seconds() + 2,
+            sell_token: H160::from_low_u64_be(1),
+            buy_token: H160::from_low_u64_be(2),
+            buy_amount: U256::from(1),
+            sell_amount: U256::from(1),
+            fee_amount: U256::from(1),
+            from: Some(H160([1; 20])),
+            signature: Signature::Eip1271(wrapped.encode()),
+            app_data: OrderCreationAppData::Full {
+                full: "{}".to_string(),
+            },
+            ..Default::default()
+        };
+        // Neither the signature validator nor the code fetcher get called.
+        let validator = OrderValidator::new(
+            dummy_contract!(WETH9, [0xef; 20]),
+            Arc::new(order_validation::banned::Users::none()),
+            OrderValidPeriodConfiguration::any(),
+            true,
+            Arc::new(bad_token_detector),
+            dummy_contract!(HooksTrampoline, [0xcf; 20]),
+            Arc::new(order_quoter),
+            Arc::new(balance_fetcher),
+            Arc::new(MockSignatureValidating::new()),
+            Arc::new(limit_order_counter),
+            0,
+            Arc::new(MockCodeFetching::new()),
+            Default::default(),
+            None,
+        );
+
+        let (order, _) = validator
+            .validate_and_construct_order(creation, &Default::default(), Default::default(), None)
+            .await
+            .unwrap();
+        assert_eq!(order.signature, Signature::Eip1271(wrapped.encode()));
+        assert!(order.interactions.pre.is_empty());
+    }
+
     #[test]
     fn allows_insufficient_allowance_and_balance_for_presign_orders() {
         fn assert_allows_failed_transfer(
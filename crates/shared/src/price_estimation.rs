@@ -19,6 +19,7 @@ use {
         fmt::{self, Display, Formatter},
         future::Future,
         hash::Hash,
+        num::NonZeroUsize,
         str::FromStr,
         sync::Arc,
         time::{Duration, Instant},
@@ -267,6 +268,18 @@ pub struct Arguments {
     #[clap(long, env, default_value = "1")]
     pub native_price_cache_concurrent_requests: usize,
 
+    /// The maximum number of prices the native price cache holds. When
+    /// exceeded, the least recently requested prices get evicted. If not set
+    /// the cache is unbounded.
+    #[clap(long, env)]
+    pub native_price_cache_max_size: Option<NonZeroUsize>,
+
+    /// Persist cached native prices in the database and warm up the cache with
+    /// the persisted prices that are younger than
+    /// `--native-price-cache-max-age` on startup.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub native_price_cache_persistence: bool,
+
     /// The amount in native tokens atoms to use for price estimation. Should be
     /// reasonably large so that small pools do not influence the prices. If
     /// not set a reasonable default is used based on network id.
@@ -334,6 +347,8 @@ impl Display for Arguments {
             native_price_prefetch_time,
             native_price_cache_max_update_size,
             native_price_cache_concurrent_requests,
+            native_price_cache_max_size,
+            native_price_cache_persistence,
             amount_to_estimate_prices_with,
             balancer_sor_url,
             tenderly_save_successful_trade_simulations,
@@ -376,6 +391,16 @@ impl Display for Arguments {
             "native_price_cache_concurrent_requests: {}",
             native_price_cache_concurrent_requests
         )?;
+        display_option(
+            f,
+            "native_price_cache_max_size",
+            native_price_cache_max_size,
+        )?;
+        writeln!(
+            f,
+            "native_price_cache_persistence: {}",
+            native_price_cache_persistence
+        )?;
         display_option(
             f,
             "amount_to_estimate_prices_with: {}",
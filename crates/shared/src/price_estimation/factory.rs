@@ -7,7 +7,7 @@ use {
         http::{HttpPriceEstimator, HttpTradeFinder},
         instrumented::InstrumentedPriceEstimator,
         native::{self, NativePriceEstimator},
-        native_price_cache::CachingNativePriceEstimator,
+        native_price_cache::{CacheOptions, CachingNativePriceEstimator, NativePriceStoring},
         oneinch::OneInchPriceEstimator,
         paraswap::ParaswapPriceEstimator,
         sanitized::SanitizedPriceEstimator,
@@ -380,6 +380,7 @@ impl<'a> PriceEstimatorFactory<'a> {
         native: &[Vec<NativePriceEstimatorSource>],
         external: &[PriceEstimatorSource],
         results_required: NonZeroUsize,
+        storage: Arc<dyn NativePriceStoring>,
     ) -> Result<Arc<CachingNativePriceEstimator>> {
         anyhow::ensure!(
             self.args.native_price_cache_max_age > self.args.native_price_prefetch_time,
@@ -400,13 +401,17 @@ impl<'a> PriceEstimatorFactory<'a> {
             CompetitionEstimator::new(estimators, PriceRanking::MaxOutAmount)
                 .prefer_verified_estimates(self.args.prefer_verified_quotes)
                 .with_early_return(results_required);
-        let native_estimator = Arc::new(CachingNativePriceEstimator::new(
+        let native_estimator = Arc::new(CachingNativePriceEstimator::with_options(
             Box::new(competition_estimator),
             self.args.native_price_cache_max_age,
             self.args.native_price_cache_refresh,
             Some(self.args.native_price_cache_max_update_size),
             self.args.native_price_prefetch_time,
             self.args.native_price_cache_concurrent_requests,
+            CacheOptions {
+                max_size: self.args.native_price_cache_max_size,
+                storage: self.args.native_price_cache_persistence.then_some(storage),
+            },
         ));
         Ok(native_estimator)
     }
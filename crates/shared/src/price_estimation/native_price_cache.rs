@@ -1,12 +1,16 @@
 use {
     super::PriceEstimationError,
     crate::price_estimation::native::{NativePriceEstimateResult, NativePriceEstimating},
+    anyhow::Result,
+    chrono::{DateTime, Utc},
     futures::{FutureExt, StreamExt},
     indexmap::IndexSet,
+    lru::LruCache,
     primitive_types::H160,
     prometheus::{IntCounter, IntCounterVec, IntGauge},
     std::{
-        collections::{hash_map::Entry, HashMap},
+        collections::HashMap,
+        num::NonZeroUsize,
         sync::{Arc, Mutex, MutexGuard, Weak},
         time::{Duration, Instant},
     },
@@ -35,17 +39,74 @@ impl Metrics {
 /// Wrapper around `Box<dyn PriceEstimating>` which caches successful price
 /// estimates for some time and supports updating the cache in the background.
 ///
-/// The size of the underlying cache is unbounded.
+/// The size of the underlying cache is unbounded unless configured otherwise
+/// with [`CacheOptions::max_size`].
 ///
 /// Is an Arc internally.
 #[derive(Clone)]
 pub struct CachingNativePriceEstimator(Arc<Inner>);
 
 struct Inner {
-    cache: Mutex<HashMap<H160, CachedResult>>,
+    /// Cached prices ordered by when they were last requested. A bounded
+    /// cache evicts the least recently requested price on insertion.
+    cache: Mutex<LruCache<H160, CachedResult>>,
     high_priority: Mutex<IndexSet<H160>>,
     estimator: Box<dyn NativePriceEstimating>,
     max_age: Duration,
+    storage: Option<Arc<dyn NativePriceStoring>>,
+}
+
+/// Optional behaviour of the [`CachingNativePriceEstimator`].
+#[derive(Clone, Default)]
+pub struct CacheOptions {
+    /// Maximum number of cached prices. When exceeded, the least recently
+    /// requested prices get evicted.
+    pub max_size: Option<NonZeroUsize>,
+    /// Storage to which cached prices get persisted and from which the cache
+    /// gets warmed up on startup, so that a restart doesn't leave it empty.
+    pub storage: Option<Arc<dyn NativePriceStoring>>,
+}
+
+/// A native price as persisted by [`NativePriceStoring`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredNativePrice {
+    pub token: H160,
+    pub price: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<StoredNativePrice> for database::native_prices::NativePrice {
+    fn from(price: StoredNativePrice) -> Self {
+        Self {
+            token: database::byte_array::ByteArray(price.token.0),
+            price: price.price,
+            updated_at: price.updated_at,
+        }
+    }
+}
+
+impl From<database::native_prices::NativePrice> for StoredNativePrice {
+    fn from(price: database::native_prices::NativePrice) -> Self {
+        Self {
+            token: H160(price.token.0),
+            price: price.price,
+            updated_at: price.updated_at,
+        }
+    }
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait::async_trait]
+pub trait NativePriceStoring: Send + Sync {
+    /// Stores the prices, replacing previously stored prices of the same
+    /// tokens.
+    async fn store_native_prices(&self, prices: Vec<StoredNativePrice>) -> Result<()>;
+
+    /// Loads all prices that were updated after `updated_after`.
+    async fn load_native_prices(
+        &self,
+        updated_after: DateTime<Utc>,
+    ) -> Result<Vec<StoredNativePrice>>;
 }
 
 struct UpdateTask {
@@ -70,33 +131,31 @@ impl Inner {
     fn get_cached_price(
         token: H160,
         now: Instant,
-        cache: &mut MutexGuard<HashMap<H160, CachedResult>>,
+        cache: &mut MutexGuard<LruCache<H160, CachedResult>>,
         max_age: &Duration,
         create_missing_entry: bool,
     ) -> Option<CacheEntry> {
-        match cache.entry(token) {
-            Entry::Occupied(mut entry) => {
-                let entry = entry.get_mut();
-                entry.requested_at = now;
-                let is_recent = now.saturating_duration_since(entry.updated_at) < *max_age;
-                is_recent.then_some(entry.result.clone())
-            }
-            Entry::Vacant(entry) => {
-                if create_missing_entry {
-                    // Create an outdated cache entry so the background task keeping the cache warm
-                    // will fetch the price during the next maintenance cycle.
-                    // This should happen only for prices missing while building the auction.
-                    // Otherwise malicious actors could easily cause the cache size to blow up.
-                    let outdated_timestamp = now.checked_sub(*max_age).unwrap();
-                    entry.insert(CachedResult {
-                        result: Ok(0.),
-                        updated_at: outdated_timestamp,
-                        requested_at: now,
-                    });
-                }
-                None
-            }
+        if let Some(entry) = cache.get_mut(&token) {
+            entry.requested_at = now;
+            let is_recent = now.saturating_duration_since(entry.updated_at) < *max_age;
+            return is_recent.then_some(entry.result.clone());
         }
+        if create_missing_entry {
+            // Create an outdated cache entry so the background task keeping the cache warm
+            // will fetch the price during the next maintenance cycle.
+            // This should happen only for prices missing while building the auction.
+            // Otherwise malicious actors could easily cause the cache size to blow up.
+            let outdated_timestamp = now.checked_sub(*max_age).unwrap();
+            cache.push(
+                token,
+                CachedResult {
+                    result: Ok(0.),
+                    updated_at: outdated_timestamp,
+                    requested_at: now,
+                },
+            );
+        }
+        None
     }
 
     /// Checks cache for the given tokens one by one. If the price is already
@@ -129,8 +188,7 @@ impl Inner {
                 // update price in cache
                 if should_cache(&result) {
                     let now = Instant::now();
-                    let mut cache = self.cache.lock().unwrap();
-                    cache.insert(
+                    self.cache.lock().unwrap().push(
                         *token,
                         CachedResult {
                             result: result.clone(),
@@ -147,6 +205,84 @@ impl Inner {
             .boxed()
     }
 
+    /// Warms up the cache with the persisted prices that are still valid.
+    /// Prices that have been cached in the meantime are kept and persisted
+    /// prices only fill up the remaining space of the cache.
+    async fn load_persisted_prices(&self, storage: &dyn NativePriceStoring) {
+        let now = Utc::now();
+        let Ok(max_age) = chrono::Duration::from_std(self.max_age) else {
+            return;
+        };
+        let mut prices = match storage.load_native_prices(now - max_age).await {
+            Ok(prices) => prices,
+            Err(err) => {
+                tracing::warn!(?err, "failed to load persisted native prices");
+                return;
+            }
+        };
+
+        // Most recent prices first, so older ones are the first to be evicted.
+        prices.sort_unstable_by_key(|price| std::cmp::Reverse(price.updated_at));
+
+        let instant_now = Instant::now();
+        let mut cache = self.cache.lock().unwrap();
+        for price in &prices {
+            if cache.len() >= cache.cap().get() {
+                break;
+            }
+            if cache.contains(&price.token) {
+                continue;
+            }
+            let age = (now - price.updated_at).to_std().unwrap_or_default();
+            let Some(updated_at) = instant_now.checked_sub(age) else {
+                continue;
+            };
+            cache.push(
+                price.token,
+                CachedResult {
+                    result: Ok(price.price),
+                    updated_at,
+                    requested_at: updated_at,
+                },
+            );
+            // Persisted prices haven't been requested since the restart.
+            cache.demote(&price.token);
+        }
+        tracing::debug!(prices = prices.len(), "loaded persisted native prices");
+    }
+
+    /// Persists all successfully estimated prices that were updated after
+    /// `updated_after`.
+    async fn persist_prices(&self, storage: &dyn NativePriceStoring, updated_after: Instant) {
+        let now = Utc::now();
+        let instant_now = Instant::now();
+        let prices: Vec<_> = self
+            .cache
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, cached)| cached.updated_at > updated_after)
+            .filter_map(|(token, cached)| {
+                let price = *cached.result.as_ref().ok()?;
+                let age = chrono::Duration::from_std(
+                    instant_now.saturating_duration_since(cached.updated_at),
+                )
+                .ok()?;
+                Some(StoredNativePrice {
+                    token: *token,
+                    price,
+                    updated_at: now - age,
+                })
+            })
+            .collect();
+        if prices.is_empty() {
+            return;
+        }
+        if let Err(err) = storage.store_native_prices(prices).await {
+            tracing::warn!(?err, "failed to persist native prices");
+        }
+    }
+
     /// Tokens with highest priority first.
     fn sorted_tokens_to_update(&self, max_age: Duration, now: Instant) -> Vec<H160> {
         let mut outdated: Vec<_> = self
@@ -218,9 +354,21 @@ impl UpdateTask {
 
     /// Runs background updates until inner is no longer alive.
     async fn run(self) {
+        if let Some(inner) = self.inner.upgrade() {
+            if let Some(storage) = &inner.storage {
+                inner.load_persisted_prices(storage.as_ref()).await;
+            }
+        }
+        let mut persisted_until = Instant::now();
         while let Some(inner) = self.inner.upgrade() {
             let now = Instant::now();
             self.single_update(&inner).await;
+            if let Some(storage) = &inner.storage {
+                let persisted_at = Instant::now();
+                let storage = storage.as_ref();
+                inner.persist_prices(storage, persisted_until).await;
+                persisted_until = persisted_at;
+            }
             tokio::time::sleep(self.update_interval.saturating_sub(now.elapsed())).await;
         }
     }
@@ -242,11 +390,38 @@ impl CachingNativePriceEstimator {
         prefetch_time: Duration,
         concurrent_requests: usize,
     ) -> Self {
+        Self::with_options(
+            estimator,
+            max_age,
+            update_interval,
+            update_size,
+            prefetch_time,
+            concurrent_requests,
+            Default::default(),
+        )
+    }
+
+    /// Like [`CachingNativePriceEstimator::new`] but allows bounding the cache
+    /// size and persisting the cached prices.
+    pub fn with_options(
+        estimator: Box<dyn NativePriceEstimating>,
+        max_age: Duration,
+        update_interval: Duration,
+        update_size: Option<usize>,
+        prefetch_time: Duration,
+        concurrent_requests: usize,
+        options: CacheOptions,
+    ) -> Self {
+        let cache = match options.max_size {
+            Some(max_size) => LruCache::new(max_size),
+            None => LruCache::unbounded(),
+        };
         let inner = Arc::new(Inner {
             estimator,
-            cache: Default::default(),
+            cache: Mutex::new(cache),
             high_priority: Default::default(),
             max_age,
+            storage: options.storage,
         });
 
         let update_task = UpdateTask {
@@ -562,37 +737,167 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn evicts_least_recently_requested_prices() {
+        let mut inner = MockNativePriceEstimating::new();
+        inner
+            .expect_estimate_native_price()
+            .times(3)
+            .returning(|_| async { Ok(1.0) }.boxed());
+        // token 1 got evicted
+        inner
+            .expect_estimate_native_price()
+            .times(1)
+            .returning(|passed_token| {
+                assert_eq!(passed_token, token(1));
+                async { Ok(1.0) }.boxed()
+            });
+
+        let estimator = CachingNativePriceEstimator::with_options(
+            Box::new(inner),
+            Duration::from_secs(10),
+            Duration::MAX,
+            None,
+            Default::default(),
+            1,
+            CacheOptions {
+                max_size: NonZeroUsize::new(2),
+                storage: None,
+            },
+        );
+
+        for token in [token(0), token(1), token(0), token(2), token(0), token(1)] {
+            estimator.estimate_native_price(token).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn warms_up_cache_with_persisted_prices() {
+        let mut inner = MockNativePriceEstimating::new();
+        // only the outdated persisted price needs to be estimated
+        inner
+            .expect_estimate_native_price()
+            .times(1)
+            .returning(|passed_token| {
+                assert_eq!(passed_token, token(1));
+                async { Ok(2.0) }.boxed()
+            });
+        let mut storage = MockNativePriceStoring::new();
+        storage.expect_load_native_prices().times(1).returning(|_| {
+            Ok(vec![
+                StoredNativePrice {
+                    token: token(0),
+                    price: 1.0,
+                    updated_at: Utc::now(),
+                },
+                StoredNativePrice {
+                    token: token(1),
+                    price: 1.0,
+                    updated_at: Utc::now() - chrono::Duration::seconds(20),
+                },
+            ])
+        });
+        // the updated price gets persisted after the first maintenance cycle
+        let (persisted, wait_for_persistence) = tokio::sync::oneshot::channel();
+        let persisted = Mutex::new(Some(persisted));
+        storage
+            .expect_store_native_prices()
+            .times(1)
+            .withf(|prices| {
+                prices.len() == 1 && prices[0].token == token(1) && prices[0].price == 2.0
+            })
+            .returning(move |_| {
+                persisted.lock().unwrap().take().unwrap().send(()).unwrap();
+                Ok(())
+            });
+
+        let estimator = CachingNativePriceEstimator::with_options(
+            Box::new(inner),
+            Duration::from_secs(10),
+            Duration::MAX,
+            None,
+            Default::default(),
+            1,
+            CacheOptions {
+                max_size: None,
+                storage: Some(Arc::new(storage)),
+            },
+        );
+
+        wait_for_persistence.await.unwrap();
+
+        let result = estimator.estimate_native_price(token(0)).await;
+        assert_eq!(result.unwrap(), 1.0);
+        let result = estimator.estimate_native_price(token(1)).await;
+        assert_eq!(result.unwrap(), 2.0);
+    }
+
+    #[tokio::test]
+    async fn persisted_prices_only_fill_up_the_cache() {
+        let now = Instant::now();
+        let mut cache = LruCache::new(NonZeroUsize::new(2).unwrap());
+        cache.push(
+            token(0),
+            CachedResult {
+                result: Ok(1.),
+                updated_at: now,
+                requested_at: now,
+            },
+        );
+        let inner = Inner {
+            cache: Mutex::new(cache),
+            high_priority: Default::default(),
+            estimator: Box::new(MockNativePriceEstimating::new()),
+            max_age: Duration::from_secs(10),
+            storage: None,
+        };
+        let mut storage = MockNativePriceStoring::new();
+        storage.expect_load_native_prices().times(1).returning(|_| {
+            let price = |token, age| StoredNativePrice {
+                token,
+                price: 2.,
+                updated_at: Utc::now() - chrono::Duration::seconds(age),
+            };
+            Ok(vec![
+                price(token(0), 0),
+                price(token(1), 2),
+                price(token(2), 1),
+            ])
+        });
+
+        inner.load_persisted_prices(&storage).await;
+
+        let cache = inner.cache.lock().unwrap();
+        // the cached price is kept and only the most recent persisted price fits
+        assert_eq!(cache.peek(&token(0)).unwrap().result.as_ref().unwrap(), &1.);
+        assert!(cache.contains(&token(2)));
+        assert!(!cache.contains(&token(1)));
+        // the persisted price is the first to be evicted
+        assert_eq!(cache.peek_lru().unwrap().0, &token(2));
+    }
+
     #[test]
     fn outdated_entries_prioritized() {
         let t0 = H160::from_low_u64_be(0);
         let t1 = H160::from_low_u64_be(1);
         let now = Instant::now();
+        let mut cache = LruCache::unbounded();
+        for token in [t0, t1] {
+            cache.push(
+                token,
+                CachedResult {
+                    result: Ok(0.),
+                    updated_at: now,
+                    requested_at: now,
+                },
+            );
+        }
         let inner = Inner {
-            cache: Mutex::new(
-                [
-                    (
-                        t0,
-                        CachedResult {
-                            result: Ok(0.),
-                            updated_at: now,
-                            requested_at: now,
-                        },
-                    ),
-                    (
-                        t1,
-                        CachedResult {
-                            result: Ok(0.),
-                            updated_at: now,
-                            requested_at: now,
-                        },
-                    ),
-                ]
-                .into_iter()
-                .collect(),
-            ),
+            cache: Mutex::new(cache),
             high_priority: Default::default(),
             estimator: Box::new(MockNativePriceEstimating::new()),
             max_age: Default::default(),
+            storage: None,
         };
 
         let now = now + Duration::from_secs(1);
@@ -2,10 +2,11 @@ use {
     ethcontract::Bytes,
     ethrpc::Web3,
     hex_literal::hex,
-    model::interaction::InteractionData,
-    primitive_types::H160,
+    model::{interaction::InteractionData, order::Hook},
+    primitive_types::{H160, U256},
     std::sync::Arc,
     thiserror::Error,
+    web3::ethabi::{self, ParamType, Token},
 };
 
 mod simulation;
@@ -58,6 +59,79 @@ pub fn check_erc1271_result(result: Bytes<[u8; 4]>) -> Result<(), SignatureValid
     }
 }
 
+/// The gas limit for deploying a counterfactual wallet of an ERC-6492
+/// signature in a settlement.
+pub const ERC6492_DEPLOYMENT_GAS_LIMIT: u64 = 1_000_000;
+
+/// The suffix that marks a signature as ERC-6492 wrapped.
+const ERC6492_MAGIC_SUFFIX: [u8; 32] =
+    hex!("6492649264926492649264926492649264926492649264926492649264926492");
+
+/// A signature of a counterfactual smart contract wallet that isn't deployed
+/// yet. It contains the call that deploys the wallet so that the signature
+/// can be verified by deploying the wallet first.
+/// <https://eips.ethereum.org/EIPS/eip-6492>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Erc6492Signature {
+    pub factory: H160,
+    pub factory_calldata: Vec<u8>,
+    /// The ERC-1271 signature to verify once the wallet is deployed.
+    pub signature: Vec<u8>,
+}
+
+impl Erc6492Signature {
+    /// Decodes an ERC-6492 wrapped signature. Returns `None` if the signature
+    /// is not wrapped or malformed.
+    pub fn decode(signature: &[u8]) -> Option<Self> {
+        let wrapped = signature.strip_suffix(&ERC6492_MAGIC_SUFFIX)?;
+        let tokens = ethabi::decode(
+            &[ParamType::Address, ParamType::Bytes, ParamType::Bytes],
+            wrapped,
+        )
+        .ok()?;
+        match <[Token; 3]>::try_from(tokens).ok()? {
+            [Token::Address(factory), Token::Bytes(factory_calldata), Token::Bytes(signature)] => {
+                Some(Self {
+                    factory,
+                    factory_calldata,
+                    signature,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut signature = ethabi::encode(&[
+            Token::Address(self.factory),
+            Token::Bytes(self.factory_calldata.clone()),
+            Token::Bytes(self.signature.clone()),
+        ]);
+        signature.extend(ERC6492_MAGIC_SUFFIX);
+        signature
+    }
+
+    /// The interaction that deploys the wallet.
+    pub fn deployment(&self) -> InteractionData {
+        InteractionData {
+            target: self.factory,
+            value: U256::zero(),
+            call_data: self.factory_calldata.clone(),
+        }
+    }
+
+    /// The hook that deploys the wallet in a settlement. The factory call is
+    /// chosen by the user so it has to be executed through the hooks
+    /// trampoline like any other user hook.
+    pub fn deployment_hook(&self) -> Hook {
+        Hook {
+            target: self.factory,
+            call_data: self.factory_calldata.clone(),
+            gas_limit: ERC6492_DEPLOYMENT_GAS_LIMIT,
+        }
+    }
+}
+
 /// Contracts required for signature verification simulation.
 pub struct Contracts {
     pub chain_id: u64,
@@ -73,3 +147,46 @@ pub fn validator(web3: &Web3, contracts: Contracts) -> Arc<dyn SignatureValidati
         contracts.vault_relayer,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn erc6492_signature_roundtrip() {
+        let wrapped = Erc6492Signature {
+            factory: H160([1; 20]),
+            factory_calldata: vec![2; 68],
+            signature: vec![3; 65],
+        };
+        let encoded = wrapped.encode();
+        assert!(encoded.ends_with(&ERC6492_MAGIC_SUFFIX));
+        assert_eq!(Erc6492Signature::decode(&encoded), Some(wrapped.clone()));
+        assert_eq!(
+            wrapped.deployment(),
+            InteractionData {
+                target: H160([1; 20]),
+                value: U256::zero(),
+                call_data: vec![2; 68],
+            }
+        );
+        assert_eq!(
+            wrapped.deployment_hook(),
+            Hook {
+                target: H160([1; 20]),
+                call_data: vec![2; 68],
+                gas_limit: ERC6492_DEPLOYMENT_GAS_LIMIT,
+            }
+        );
+    }
+
+    #[test]
+    fn erc6492_signature_decode_rejects_unwrapped_signatures() {
+        assert_eq!(Erc6492Signature::decode(&[1; 65]), None);
+        assert_eq!(Erc6492Signature::decode(&ERC6492_MAGIC_SUFFIX), None);
+
+        let mut malformed = vec![0xff; 96];
+        malformed.extend(ERC6492_MAGIC_SUFFIX);
+        assert_eq!(Erc6492Signature::decode(&malformed), None);
+    }
+}
@@ -1,10 +1,11 @@
 //! An `eth_call` simulation based ERC-1271 signature verification
 //! implementation. This allows orders with ERC-1271 signatures to be used that
 //! only get setup as a pre-hook (such as creating a Composable CoW order with a
-//! Safe in a pre-interaction).
+//! Safe in a pre-interaction). ERC-6492 wrapped signatures of counterfactual
+//! wallets are verified by deploying the wallet in a pre-interaction first.
 
 use {
-    super::{SignatureCheck, SignatureValidating, SignatureValidationError},
+    super::{Erc6492Signature, SignatureCheck, SignatureValidating, SignatureValidationError},
     crate::ethcontract_error::EthcontractErrorType,
     anyhow::Result,
     ethcontract::Bytes,
@@ -14,6 +15,7 @@ use {
 };
 
 pub struct Validator {
+    web3: Web3,
     signatures: contracts::support::Signatures,
     settlement: H160,
     vault_relayer: H160,
@@ -24,6 +26,7 @@ impl Validator {
         let web3 = ethrpc::instrumented::instrument_with_label(web3, "signatureValidation".into());
         Self {
             signatures: contracts::support::Signatures::at(&web3, settlement),
+            web3,
             settlement,
             vault_relayer,
         }
@@ -33,6 +36,8 @@ impl Validator {
         &self,
         check: &SignatureCheck,
     ) -> Result<Simulation, SignatureValidationError> {
+        let check = self.unwrap_erc6492(check).await?;
+
         // We simulate the signature verification from the Settlement contract's
         // context. This allows us to check:
         // 1. How the pre-interactions would behave as part of the settlement
@@ -59,6 +64,36 @@ impl Validator {
         tracing::trace!(?check, ?simulation, "simulated signature");
         Ok(simulation)
     }
+
+    /// Replaces an ERC-6492 wrapped signature with the inner ERC-1271
+    /// signature. If the wallet is not deployed yet, its deployment gets
+    /// executed before all other pre-interactions.
+    async fn unwrap_erc6492(
+        &self,
+        check: &SignatureCheck,
+    ) -> Result<SignatureCheck, SignatureValidationError> {
+        let Some(wrapped) = Erc6492Signature::decode(&check.signature) else {
+            return Ok(check.clone());
+        };
+        let code = self
+            .web3
+            .eth()
+            .code(check.signer, None)
+            .await
+            .map_err(anyhow::Error::from)?;
+        let interactions = if code.0.is_empty() {
+            std::iter::once(wrapped.deployment())
+                .chain(check.interactions.iter().cloned())
+                .collect()
+        } else {
+            check.interactions.clone()
+        };
+        Ok(SignatureCheck {
+            signature: wrapped.signature,
+            interactions,
+            ..check.clone()
+        })
+    }
 }
 
 #[async_trait::async_trait]
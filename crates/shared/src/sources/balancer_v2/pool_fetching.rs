@@ -12,8 +12,8 @@ use {
         registry::Registry,
     },
     super::{
-        graph_api::{BalancerSubgraphClient, RegisteredPools},
-        pool_init::PoolInitializing,
+        graph_api::{BalancerSubgraphClient, PoolType, RegisteredPools},
+        pool_init::{OnChainPoolInitializer, PoolInitializing},
         pools::{
             common::{self, PoolInfoFetcher},
             stable,
@@ -26,6 +26,7 @@ use {
         swap::fixed_point::Bfp,
     },
     crate::{
+        arguments::PoolDiscovery,
         ethrpc::{Web3, Web3Transport},
         recent_block_cache::{Block, CacheConfig},
         token_info::TokenInfoFetching,
@@ -215,6 +216,23 @@ impl BalancerFactoryKind {
             _ => Default::default(),
         }
     }
+
+    /// Returns the kind of pools created by factories of this type.
+    pub fn pool_type(&self) -> PoolType {
+        match self {
+            Self::Weighted | Self::WeightedV3 | Self::WeightedV4 | Self::Weighted2Token => {
+                PoolType::Weighted
+            }
+            Self::StableV2 => PoolType::Stable,
+            Self::LiquidityBootstrapping | Self::NoProtocolFeeLiquidityBootstrapping => {
+                PoolType::LiquidityBootstrapping
+            }
+            Self::ComposableStable
+            | Self::ComposableStableV3
+            | Self::ComposableStableV4
+            | Self::ComposableStableV5 => PoolType::ComposableStable,
+        }
+    }
 }
 
 /// All balancer related contracts that we expect to exist.
@@ -297,8 +315,116 @@ impl BalancerPoolFetcher {
         contracts: &BalancerContracts,
         deny_listed_pool_ids: Vec<H256>,
     ) -> Result<Self> {
-        let web3 = ethrpc::instrumented::instrument_with_label(&web3, "balancerV2".into());
         let pool_initializer = BalancerSubgraphClient::for_chain(base_url, chain_id, client)?;
+        Self::with_initializer(
+            pool_initializer,
+            block_retriever,
+            token_infos,
+            config,
+            block_stream,
+            web3,
+            contracts,
+            deny_listed_pool_ids,
+        )
+        .await
+    }
+
+    /// Creates the pool fetcher for the pool discovery mechanism configured in
+    /// the shared arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn from_args(
+        args: &crate::arguments::Arguments,
+        chain_id: u64,
+        block_retriever: Arc<dyn BlockRetrieving>,
+        token_infos: Arc<dyn TokenInfoFetching>,
+        config: CacheConfig,
+        block_stream: CurrentBlockStream,
+        client: Client,
+        web3: Web3,
+        contracts: &BalancerContracts,
+    ) -> Result<Self> {
+        match args.balancer_v2_pool_discovery {
+            PoolDiscovery::Subgraph => {
+                Self::new(
+                    &args.graph_api_base_url,
+                    chain_id,
+                    block_retriever,
+                    token_infos,
+                    config,
+                    block_stream,
+                    client,
+                    web3,
+                    contracts,
+                    args.balancer_pool_deny_list.clone(),
+                )
+                .await
+            }
+            PoolDiscovery::FactoryEvents => {
+                Self::from_factory_events(
+                    args.balancer_v2_factory_start_block,
+                    block_retriever,
+                    token_infos,
+                    config,
+                    block_stream,
+                    web3,
+                    contracts,
+                    args.balancer_pool_deny_list.clone(),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Creates a pool fetcher that doesn't depend on the Balancer subgraph.
+    /// The registries are bootstrapped by scanning the Vault's
+    /// `PoolRegistered` and the factories' `PoolCreated` events, starting at
+    /// `start_block` or the Vault's deployment block if not specified.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn from_factory_events(
+        start_block: Option<u64>,
+        block_retriever: Arc<dyn BlockRetrieving>,
+        token_infos: Arc<dyn TokenInfoFetching>,
+        config: CacheConfig,
+        block_stream: CurrentBlockStream,
+        web3: Web3,
+        contracts: &BalancerContracts,
+        deny_listed_pool_ids: Vec<H256>,
+    ) -> Result<Self> {
+        let pool_initializer = OnChainPoolInitializer::new(
+            contracts.vault.clone(),
+            contracts
+                .factories
+                .iter()
+                .map(|(kind, instance)| (kind.pool_type(), instance.address()))
+                .collect(),
+            token_infos.clone(),
+            start_block,
+        );
+        Self::with_initializer(
+            pool_initializer,
+            block_retriever,
+            token_infos,
+            config,
+            block_stream,
+            web3,
+            contracts,
+            deny_listed_pool_ids,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn with_initializer(
+        pool_initializer: impl PoolInitializing,
+        block_retriever: Arc<dyn BlockRetrieving>,
+        token_infos: Arc<dyn TokenInfoFetching>,
+        config: CacheConfig,
+        block_stream: CurrentBlockStream,
+        web3: Web3,
+        contracts: &BalancerContracts,
+        deny_listed_pool_ids: Vec<H256>,
+    ) -> Result<Self> {
+        let web3 = ethrpc::instrumented::instrument_with_label(&web3, "balancerV2".into());
         let fetcher = Arc::new(Cache::new(
             create_aggregate_pool_fetcher(
                 web3,
@@ -507,6 +633,23 @@ mod tests {
         std::time::Duration,
     };
 
+    #[test]
+    fn factory_kinds_map_to_subgraph_pool_types() {
+        assert_eq!(
+            BalancerFactoryKind::Weighted2Token.pool_type(),
+            PoolType::Weighted
+        );
+        assert_eq!(BalancerFactoryKind::StableV2.pool_type(), PoolType::Stable);
+        assert_eq!(
+            BalancerFactoryKind::NoProtocolFeeLiquidityBootstrapping.pool_type(),
+            PoolType::LiquidityBootstrapping
+        );
+        assert_eq!(
+            BalancerFactoryKind::ComposableStableV5.pool_type(),
+            PoolType::ComposableStable
+        );
+    }
+
     #[test]
     fn can_extract_address_from_pool_id() {
         assert_eq!(
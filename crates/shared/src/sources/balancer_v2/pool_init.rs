@@ -4,15 +4,39 @@
 //! with existing data in order to reduce the "cold start" time of the service.
 
 use {
-    super::graph_api::{BalancerSubgraphClient, RegisteredPools},
-    anyhow::{anyhow, bail, Result},
-    contracts::BalancerV2Vault,
+    super::{
+        graph_api::{BalancerSubgraphClient, PoolData, PoolType, RegisteredPools, Token},
+        swap::fixed_point::Bfp,
+    },
+    crate::{event_handling::MAX_REORG_BLOCK_COUNT, token_info::TokenInfoFetching},
+    anyhow::{anyhow, bail, Context, Result},
+    contracts::{BalancerV2BasePoolFactory, BalancerV2Vault, BalancerV2WeightedPool},
     ethcontract::{
         common::{contract::Network, DeploymentInformation},
+        BlockNumber,
+        Bytes,
         Contract,
+        H160,
+        H256,
     },
+    futures::{stream, StreamExt, TryStreamExt},
+    std::{collections::HashMap, future::Future, sync::Arc, time::Duration},
 };
 
+/// The maximum number of blocks to query events for in a single request.
+const MAX_BLOCKS_PER_QUERY: u64 = 50_000;
+
+/// The maximum number of pools to fetch information for concurrently.
+const MAX_CONCURRENT_POOL_FETCHES: usize = 32;
+
+/// How often a chunk of events gets queried before the initialization fails.
+/// The scan spans many requests, so a single flaky one shouldn't throw away
+/// the progress of all the others.
+const MAX_QUERY_ATTEMPTS: usize = 3;
+
+/// How long to wait before querying a failed chunk of events again.
+const QUERY_RETRY_DELAY: Duration = Duration::from_secs(1);
+
 #[async_trait::async_trait]
 pub trait PoolInitializing: Send + Sync {
     async fn initialize_pools(&self) -> Result<RegisteredPools>;
@@ -44,6 +68,232 @@ impl PoolInitializing for EmptyPoolInitializer {
     }
 }
 
+/// A Balancer pool registry initializer that bootstraps the registered pools
+/// purely from on-chain data.
+///
+/// It scans the Vault's `PoolRegistered` events as well as the `PoolCreated`
+/// events of the configured factories up to a reorg safe block, which makes it
+/// usable on chains and forks without a Balancer subgraph.
+pub struct OnChainPoolInitializer {
+    vault: BalancerV2Vault,
+    factories: Vec<(PoolType, H160)>,
+    token_infos: Arc<dyn TokenInfoFetching>,
+    start_block: u64,
+}
+
+impl OnChainPoolInitializer {
+    /// Creates a new on-chain pool initializer. Events get scanned from
+    /// `start_block` if specified, otherwise from the Vault's deployment
+    /// block.
+    pub fn new(
+        vault: BalancerV2Vault,
+        factories: Vec<(PoolType, H160)>,
+        token_infos: Arc<dyn TokenInfoFetching>,
+        start_block: Option<u64>,
+    ) -> Self {
+        let start_block = start_block
+            .or_else(|| match vault.raw_instance().deployment_information() {
+                Some(DeploymentInformation::BlockNumber(block)) => Some(block),
+                _ => None,
+            })
+            .unwrap_or_default();
+        Self {
+            vault,
+            factories,
+            token_infos,
+            start_block,
+        }
+    }
+
+    /// Returns the IDs of all pools registered in the Vault by pool address.
+    async fn registered_pool_ids(&self, to_block: u64) -> Result<HashMap<H160, H256>> {
+        let events = query_in_chunks(self.start_block, to_block, |from, to| {
+            self.vault
+                .events()
+                .pool_registered()
+                .from_block(BlockNumber::Number(from.into()))
+                .to_block(BlockNumber::Number(to.into()))
+                .query()
+        })
+        .await
+        .context("failed to query PoolRegistered events")?;
+
+        Ok(events
+            .into_iter()
+            .map(|event| (event.data.pool_address, H256(event.data.pool_id.0)))
+            .collect())
+    }
+
+    /// Returns the addresses of all pools created by a factory.
+    async fn created_pools(&self, factory: H160, to_block: u64) -> Result<Vec<H160>> {
+        let factory = BalancerV2BasePoolFactory::at(&self.vault.raw_instance().web3(), factory);
+        let events = query_in_chunks(self.start_block, to_block, |from, to| {
+            factory
+                .events()
+                .pool_created()
+                .from_block(BlockNumber::Number(from.into()))
+                .to_block(BlockNumber::Number(to.into()))
+                .query()
+        })
+        .await
+        .with_context(|| {
+            format!(
+                "failed to query PoolCreated events of {:?}",
+                factory.address()
+            )
+        })?;
+
+        Ok(events.into_iter().map(|event| event.data.pool).collect())
+    }
+
+    /// Fetches the static pool data for a registered pool, in the same shape
+    /// as the subgraph would return it.
+    async fn pool_data(
+        &self,
+        pool_type: PoolType,
+        factory: H160,
+        address: H160,
+        id: H256,
+    ) -> Result<PoolData> {
+        let (tokens, _, _) = self
+            .vault
+            .get_pool_tokens(Bytes(id.0))
+            .call()
+            .await
+            .context("failed to get pool tokens")?;
+        let weights = match pool_type {
+            PoolType::Weighted => Some(
+                BalancerV2WeightedPool::at(&self.vault.raw_instance().web3(), address)
+                    .get_normalized_weights()
+                    .call()
+                    .await
+                    .context("failed to get pool weights")?,
+            ),
+            _ => None,
+        };
+        let token_infos = self.token_infos.get_token_infos(&tokens).await;
+
+        let tokens = tokens
+            .iter()
+            .enumerate()
+            .map(|(i, &token)| {
+                Ok(Token {
+                    address: token,
+                    decimals: token_infos
+                        .get(&token)
+                        .and_then(|info| info.decimals)
+                        .ok_or_else(|| anyhow!("missing decimals for token {:?}", token))?,
+                    weight: weights
+                        .as_ref()
+                        .map(|weights| {
+                            weights
+                                .get(i)
+                                .copied()
+                                .map(Bfp::from_wei)
+                                .context("pool weights and tokens mismatch")
+                        })
+                        .transpose()?,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(PoolData {
+            pool_type,
+            id,
+            address,
+            factory,
+            swap_enabled: true,
+            tokens,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PoolInitializing for OnChainPoolInitializer {
+    async fn initialize_pools(&self) -> Result<RegisteredPools> {
+        let current_block = self
+            .vault
+            .raw_instance()
+            .web3()
+            .eth()
+            .block_number()
+            .await?
+            .as_u64();
+        let fetched_block_number = current_block
+            .saturating_sub(MAX_REORG_BLOCK_COUNT)
+            .max(self.start_block);
+
+        let pool_ids = self.registered_pool_ids(fetched_block_number).await?;
+        let mut candidates = Vec::new();
+        for (pool_type, factory) in &self.factories {
+            for address in self.created_pools(*factory, fetched_block_number).await? {
+                // Factories may be deployed for a different Vault, so only
+                // consider pools that are registered in ours.
+                match pool_ids.get(&address) {
+                    Some(id) => candidates.push((*pool_type, *factory, address, *id)),
+                    None => tracing::debug!(
+                        pool = ?address, factory = ?factory,
+                        "skipping pool not registered in the vault",
+                    ),
+                }
+            }
+        }
+
+        let pools = stream::iter(candidates)
+            .map(|(pool_type, factory, address, id)| async move {
+                self.pool_data(pool_type, factory, address, id)
+                    .await
+                    .map_err(
+                        |err| tracing::warn!(pool = ?address, ?err, "failed to initialize pool"),
+                    )
+                    .ok()
+            })
+            .buffer_unordered(MAX_CONCURRENT_POOL_FETCHES)
+            .filter_map(futures::future::ready)
+            .collect::<Vec<_>>()
+            .await;
+        tracing::debug!(
+            block = %fetched_block_number, pools = %pools.len(),
+            "initialized registered pools from on-chain events",
+        );
+
+        Ok(RegisteredPools {
+            fetched_block_number,
+            pools,
+        })
+    }
+}
+
+/// Queries events over a block range by splitting it into chunks, since nodes
+/// usually limit the range a single `eth_getLogs` request may span. Failed
+/// chunks get retried a few times.
+async fn query_in_chunks<T, E, F, Fut>(from_block: u64, to_block: u64, query: F) -> Result<Vec<T>>
+where
+    F: Fn(u64, u64) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, E>>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let query = &query;
+    stream::iter((from_block..=to_block).step_by(MAX_BLOCKS_PER_QUERY as usize))
+        .then(|from| async move {
+            let to = (from + MAX_BLOCKS_PER_QUERY - 1).min(to_block);
+            let mut attempt = 1;
+            loop {
+                match query(from, to).await {
+                    Err(err) if attempt < MAX_QUERY_ATTEMPTS => {
+                        tracing::warn!(from, to, ?err, attempt, "failed to query events");
+                        attempt += 1;
+                        tokio::time::sleep(QUERY_RETRY_DELAY).await;
+                    }
+                    result => return result,
+                }
+            }
+        })
+        .try_concat()
+        .await
+        .map_err(Into::into)
+}
+
 #[async_trait::async_trait]
 impl PoolInitializing for BalancerSubgraphClient {
     async fn initialize_pools(&self) -> Result<RegisteredPools> {
@@ -82,7 +332,45 @@ async fn deployment_block(contract: &Contract, chain_id: u64) -> Result<u64> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, std::sync::Mutex};
+
+    #[tokio::test]
+    async fn query_in_chunks_retries_failed_chunks() {
+        let calls = Mutex::new(Vec::new());
+        let events = query_in_chunks(0, MAX_BLOCKS_PER_QUERY, |from, to| {
+            let mut calls = calls.lock().unwrap();
+            calls.push(from);
+            // The first query of the second chunk fails.
+            let result = if from > 0 && calls.iter().filter(|&&call| call == from).count() == 1 {
+                Err(std::fmt::Error)
+            } else {
+                Ok(vec![(from, to)])
+            };
+            async move { result }
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                (0, MAX_BLOCKS_PER_QUERY - 1),
+                (MAX_BLOCKS_PER_QUERY, MAX_BLOCKS_PER_QUERY)
+            ]
+        );
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![0, MAX_BLOCKS_PER_QUERY, MAX_BLOCKS_PER_QUERY]
+        );
+
+        let attempts = Mutex::new(0);
+        let result = query_in_chunks(0, 0, |_, _| {
+            *attempts.lock().unwrap() += 1;
+            async { Err::<Vec<()>, _>(std::fmt::Error) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(*attempts.lock().unwrap(), MAX_QUERY_ATTEMPTS);
+    }
 
     #[tokio::test]
     async fn initializes_empty_pools() {
@@ -101,4 +389,21 @@ mod tests {
         let initializer = EmptyPoolInitializer(999);
         assert!(initializer.initialize_pools().await.is_err());
     }
+
+    #[tokio::test]
+    async fn queries_events_in_chunks() {
+        let ranges = std::sync::Mutex::new(Vec::new());
+        let events = query_in_chunks(10, 120_000, |from, to| {
+            ranges.lock().unwrap().push((from, to));
+            async move { Ok::<_, std::io::Error>(vec![from]) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            ranges.into_inner().unwrap(),
+            [(10, 50_009), (50_010, 100_009), (100_010, 120_000)]
+        );
+        assert_eq!(events, [10, 50_010, 100_010]);
+    }
 }
@@ -0,0 +1,6 @@
+//! Curve StableSwap liquidity source implementation.
+//!
+//! CryptoSwap pools use a different invariant and dynamic fees. Neither their
+//! pricing nor their encoding is implemented, so configuring one is an error.
+
+pub mod pool_fetching;
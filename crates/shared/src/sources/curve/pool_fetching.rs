@@ -0,0 +1,377 @@
+use {
+    crate::{
+        ethrpc::Web3,
+        recent_block_cache::Block,
+        sources::uniswap_v2::pool_fetching::handle_contract_error,
+        token_info::TokenInfoFetching,
+    },
+    anyhow::{Context, Result},
+    contracts::{ICurveCryptoPool, ICurveLegacyPool, ICurvePool},
+    ethcontract::{errors::MethodError, BlockId, H160, U256},
+    futures::future,
+    model::TokenPair,
+    std::{collections::HashSet, sync::Arc},
+};
+
+/// Approximate gas used by a swap through a plain Curve StableSwap pool.
+pub const POOL_MEAN_GAS: u64 = 130_000;
+
+/// The denominator of the pool fee as returned by `fee()`, i.e. a fee of
+/// `FEE_DENOMINATOR` represents 100%.
+pub const FEE_DENOMINATOR: u64 = 10_000_000_000;
+
+/// The maximum number of coins a Curve StableSwap pool can hold.
+const MAX_COINS: usize = 8;
+
+#[async_trait::async_trait]
+#[cfg_attr(test, mockall::automock)]
+pub trait CurvePoolFetching: Send + Sync {
+    async fn fetch(&self, token_pairs: HashSet<TokenPair>, at_block: Block) -> Result<Vec<Pool>>;
+}
+
+/// The state of a Curve StableSwap pool.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pool {
+    pub address: H160,
+    /// The pool coins, ordered by their index in the pool contract.
+    pub tokens: Vec<TokenState>,
+    /// The amplification coefficient `A` of the pool.
+    pub amplification_parameter: U256,
+    /// The swap fee in units of [`FEE_DENOMINATOR`].
+    pub fee: U256,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TokenState {
+    pub address: H160,
+    pub balance: U256,
+    pub decimals: u8,
+}
+
+impl Pool {
+    /// Returns the index of the specified token in the pool contract.
+    pub fn index_of(&self, token: H160) -> Option<usize> {
+        self.tokens.iter().position(|state| state.address == token)
+    }
+
+    /// Returns all token pairs that can be traded with the pool.
+    pub fn token_pairs(&self) -> Vec<TokenPair> {
+        token_pairs(self.tokens.iter().map(|state| state.address))
+    }
+}
+
+/// Static information about a configured Curve pool that does not change
+/// between blocks.
+#[derive(Clone, Debug)]
+struct PoolInfo {
+    address: H160,
+    coins: Vec<(H160, u8)>,
+    coin_index: CoinIndex,
+}
+
+/// The type of the coin index argument of the pool's `coins` and `balances`
+/// getters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum CoinIndex {
+    Uint256,
+    /// Used by older pools.
+    Int128,
+}
+
+impl PoolInfo {
+    fn token_pairs(&self) -> Vec<TokenPair> {
+        token_pairs(self.coins.iter().map(|(coin, _)| *coin))
+    }
+}
+
+fn token_pairs(tokens: impl Iterator<Item = H160> + Clone) -> Vec<TokenPair> {
+    tokens
+        .clone()
+        .enumerate()
+        .flat_map(|(i, a)| {
+            tokens
+                .clone()
+                .skip(i + 1)
+                .filter_map(move |b| TokenPair::new(a, b))
+        })
+        .collect()
+}
+
+/// A pool fetcher for a fixed set of Curve StableSwap pools.
+pub struct CurvePoolFetcher {
+    web3: Web3,
+    pools: Vec<PoolInfo>,
+}
+
+impl CurvePoolFetcher {
+    /// Creates a new fetcher for the specified pool addresses, reading the
+    /// pool coins and their decimals once upfront.
+    ///
+    /// Pools containing coins without known decimals (such as native Ether)
+    /// are skipped. CryptoSwap pools are rejected since their pricing math is
+    /// not implemented.
+    pub async fn new(
+        web3: Web3,
+        pools: &[H160],
+        token_infos: Arc<dyn TokenInfoFetching>,
+    ) -> Result<Self> {
+        let mut infos = Vec::with_capacity(pools.len());
+        for &address in pools {
+            anyhow::ensure!(
+                !is_crypto_pool(&web3, address)
+                    .await
+                    .with_context(|| format!("failed to fetch type of Curve pool {address:?}"))?,
+                "Curve pool {address:?} is a CryptoSwap pool, only StableSwap pools are supported"
+            );
+            let (coins, coin_index) = fetch_coins(&web3, address)
+                .await
+                .with_context(|| format!("failed to fetch coins of Curve pool {address:?}"))?;
+            if coins.len() < 2 {
+                tracing::warn!(
+                    ?address,
+                    ?coins,
+                    "ignoring Curve pool with less than 2 coins"
+                );
+                continue;
+            }
+            let decimals = token_infos.get_token_infos(&coins).await;
+            let Some(coins) = coins
+                .iter()
+                .map(|coin| Some((*coin, decimals.get(coin)?.decimals?)))
+                .collect::<Option<Vec<_>>>()
+            else {
+                tracing::warn!(
+                    ?address,
+                    ?coins,
+                    "ignoring Curve pool with unknown decimals"
+                );
+                continue;
+            };
+            infos.push(PoolInfo {
+                address,
+                coins,
+                coin_index,
+            });
+        }
+
+        Ok(Self { web3, pools: infos })
+    }
+}
+
+/// CryptoSwap pools are told apart from StableSwap pools by their `gamma`
+/// parameter.
+async fn is_crypto_pool(web3: &Web3, address: H160) -> Result<bool> {
+    let pool = ICurveCryptoPool::at(web3, address);
+    Ok(handle_contract_error(pool.gamma().call().await)?.is_some())
+}
+
+/// Reads the coins of a Curve pool by querying `coins(i)` until the call
+/// reverts. Falls back to `int128` coin indices used by older pools if the
+/// pool has no coins at `uint256` indices.
+async fn fetch_coins(web3: &Web3, address: H160) -> Result<(Vec<H160>, CoinIndex)> {
+    let pool = ICurvePool::at(web3, address);
+    let mut coins = Vec::new();
+    for i in 0..MAX_COINS {
+        match handle_contract_error(pool.coins(i.into()).call().await)? {
+            Some(coin) => coins.push(coin),
+            None => break,
+        }
+    }
+    if !coins.is_empty() {
+        return Ok((coins, CoinIndex::Uint256));
+    }
+
+    let pool = ICurveLegacyPool::at(web3, address);
+    for i in 0..MAX_COINS {
+        match handle_contract_error(pool.coins(i as i128).call().await)? {
+            Some(coin) => coins.push(coin),
+            None => break,
+        }
+    }
+    Ok((coins, CoinIndex::Int128))
+}
+
+#[async_trait::async_trait]
+impl CurvePoolFetching for CurvePoolFetcher {
+    async fn fetch(&self, token_pairs: HashSet<TokenPair>, at_block: Block) -> Result<Vec<Pool>> {
+        let block = BlockId::Number(at_block.into());
+        let pools = self.pools.iter().filter(|pool| {
+            pool.token_pairs()
+                .iter()
+                .any(|pair| token_pairs.contains(pair))
+        });
+
+        let pools = future::try_join_all(pools.map(|pool| self.read_state(pool, block))).await?;
+        Ok(pools.into_iter().flatten().collect())
+    }
+}
+
+impl CurvePoolFetcher {
+    async fn read_state(&self, info: &PoolInfo, block: BlockId) -> Result<Option<Pool>> {
+        let pool = ICurvePool::at(&self.web3, info.address);
+        let fetch_balances =
+            future::join_all((0..info.coins.len()).map(|i| self.balance(info, i, block)));
+        let fetch_amplification_parameter = pool.a().block(block).call();
+        let fetch_fee = pool.fee().block(block).call();
+
+        let (balances, amplification_parameter, fee) =
+            futures::join!(fetch_balances, fetch_amplification_parameter, fetch_fee);
+        handle_results(info, balances, amplification_parameter, fee)
+    }
+
+    async fn balance(
+        &self,
+        info: &PoolInfo,
+        i: usize,
+        block: BlockId,
+    ) -> Result<U256, MethodError> {
+        match info.coin_index {
+            CoinIndex::Uint256 => {
+                ICurvePool::at(&self.web3, info.address)
+                    .balances(i.into())
+                    .block(block)
+                    .call()
+                    .await
+            }
+            CoinIndex::Int128 => {
+                ICurveLegacyPool::at(&self.web3, info.address)
+                    .balances(i as i128)
+                    .block(block)
+                    .call()
+                    .await
+            }
+        }
+    }
+}
+
+// Node errors should be bubbled up but contract errors should lead to the pool
+// being skipped.
+fn handle_results(
+    info: &PoolInfo,
+    balances: Vec<Result<U256, MethodError>>,
+    amplification_parameter: Result<U256, MethodError>,
+    fee: Result<U256, MethodError>,
+) -> Result<Option<Pool>> {
+    let balances = balances
+        .into_iter()
+        .map(handle_contract_error)
+        .collect::<Result<Option<Vec<_>>>>()?;
+    let amplification_parameter = handle_contract_error(amplification_parameter)?;
+    let fee = handle_contract_error(fee)?;
+
+    let (Some(balances), Some(amplification_parameter), Some(fee)) =
+        (balances, amplification_parameter, fee)
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(Pool {
+        address: info.address,
+        tokens: info
+            .coins
+            .iter()
+            .zip(balances)
+            .map(|(&(address, decimals), balance)| TokenState {
+                address,
+                balance,
+                decimals,
+            })
+            .collect(),
+        amplification_parameter,
+        fee,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::ethcontract_error};
+
+    fn pool_info() -> PoolInfo {
+        PoolInfo {
+            address: H160([0xc0; 20]),
+            coins: vec![(H160([1; 20]), 18), (H160([2; 20]), 6), (H160([3; 20]), 6)],
+            coin_index: CoinIndex::Uint256,
+        }
+    }
+
+    #[test]
+    fn token_pairs_cover_all_coins() {
+        let pairs = pool_info().token_pairs();
+        assert_eq!(
+            pairs,
+            vec![
+                TokenPair::new(H160([1; 20]), H160([2; 20])).unwrap(),
+                TokenPair::new(H160([1; 20]), H160([3; 20])).unwrap(),
+                TokenPair::new(H160([2; 20]), H160([3; 20])).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_pool_from_results() {
+        let info = pool_info();
+        let pool = handle_results(
+            &info,
+            vec![Ok(1_000.into()), Ok(2_000.into()), Ok(3_000.into())],
+            Ok(200.into()),
+            Ok(4_000_000.into()),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            pool,
+            Pool {
+                address: info.address,
+                tokens: vec![
+                    TokenState {
+                        address: H160([1; 20]),
+                        balance: 1_000.into(),
+                        decimals: 18,
+                    },
+                    TokenState {
+                        address: H160([2; 20]),
+                        balance: 2_000.into(),
+                        decimals: 6,
+                    },
+                    TokenState {
+                        address: H160([3; 20]),
+                        balance: 3_000.into(),
+                        decimals: 6,
+                    },
+                ],
+                amplification_parameter: 200.into(),
+                fee: 4_000_000.into(),
+            }
+        );
+        assert_eq!(pool.index_of(H160([3; 20])), Some(2));
+        assert_eq!(pool.index_of(H160([4; 20])), None);
+    }
+
+    #[test]
+    fn ignores_contract_errors() {
+        assert!(handle_results(
+            &pool_info(),
+            vec![
+                Ok(1_000.into()),
+                Err(ethcontract_error::testing_contract_error()),
+                Ok(3_000.into()),
+            ],
+            Ok(200.into()),
+            Ok(4_000_000.into()),
+        )
+        .unwrap()
+        .is_none());
+    }
+
+    #[test]
+    fn bubbles_up_node_errors() {
+        assert!(handle_results(
+            &pool_info(),
+            vec![Ok(1_000.into()), Ok(2_000.into()), Ok(3_000.into())],
+            Err(ethcontract_error::testing_node_error()),
+            Ok(4_000_000.into()),
+        )
+        .is_err());
+    }
+}
@@ -2,7 +2,9 @@ use {
     crate::event_handling::{EventRetrieving, EventStoring},
     anyhow::{Context, Result},
     contracts::{
-        uniswap_v3_pool::event_data::{Burn, Mint, Swap},
+        i_uniswap_v3_factory::event_data::PoolCreated,
+        uniswap_v3_pool::event_data::{Burn, Initialize, Mint, Swap},
+        IUniswapV3Factory,
         UniswapV3Pool,
     },
     ethcontract::{
@@ -17,7 +19,7 @@ use {
     },
     ethrpc::{current_block::RangeInclusive, Web3},
     hex_literal::hex,
-    std::collections::BTreeMap,
+    std::collections::{BTreeMap, HashSet},
 };
 
 const SWAP_TOPIC: [u8; 32] =
@@ -26,12 +28,20 @@ const BURN_TOPIC: [u8; 32] =
     hex!("0c396cd989a39f4459b5fa1aed6a9a8dcdbc45908acfd67e028cd568da98982c");
 const MINT_TOPIC: [u8; 32] =
     hex!("7a53080ba414158be7ec69b987b5fb7d07dee101fe85488f0853ae16239d0bde");
+const INITIALIZE_TOPIC: [u8; 32] =
+    hex!("98636036cb66a9c19a37435efc1e90142190214e8abeb821bdba3f2990dd4c95");
+const POOL_CREATED_TOPIC: [u8; 32] =
+    hex!("783cca1c0412dd0d695e784568c96da2e9c22ff989357a2e8b1d9b2b4e6b7118");
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum UniswapV3Event {
     Burn(Burn),
     Mint(Mint),
     Swap(Swap),
+    Initialize(Initialize),
+    /// Emitted by the factory, only indexed when discovering pools from
+    /// on-chain events.
+    PoolCreated(PoolCreated),
 }
 
 impl ParseLog for UniswapV3Event {
@@ -62,6 +72,22 @@ impl ParseLog for UniswapV3Event {
                             .expect("generated event decode"),
                     )?,
                 )),
+                H256(INITIALIZE_TOPIC) => Ok(UniswapV3Event::Initialize(
+                    log.clone().decode(
+                        UniswapV3Pool::raw_contract()
+                            .abi
+                            .event("Initialize")
+                            .expect("generated event decode"),
+                    )?,
+                )),
+                H256(POOL_CREATED_TOPIC) => Ok(UniswapV3Event::PoolCreated(
+                    log.clone().decode(
+                        IUniswapV3Factory::raw_contract()
+                            .abi
+                            .event("PoolCreated")
+                            .expect("generated event decode"),
+                    )?,
+                )),
                 _ => Err(ExecutionError::from(Error::InvalidData)),
             });
         if let Some(Ok(data)) = standard_event {
@@ -71,14 +97,23 @@ impl ParseLog for UniswapV3Event {
     }
 }
 
-pub struct UniswapV3PoolEventFetcher(pub Web3);
+pub struct UniswapV3PoolEventFetcher {
+    pub web3: Web3,
+    /// Whether to additionally index pool creations and initializations. This
+    /// is needed when pools are discovered from on-chain events instead of
+    /// the subgraph.
+    pub discover_pools: bool,
+}
 
 impl EventRetrieving for UniswapV3PoolEventFetcher {
     type Event = UniswapV3Event;
 
     fn get_events(&self) -> DynAllEventsBuilder<Self::Event> {
-        let mut events = DynAllEventsBuilder::new(self.0.clone(), H160::default(), None);
-        let events_signatures = vec![H256(SWAP_TOPIC), H256(BURN_TOPIC), H256(MINT_TOPIC)];
+        let mut events = DynAllEventsBuilder::new(self.web3.clone(), H160::default(), None);
+        let mut events_signatures = vec![H256(SWAP_TOPIC), H256(BURN_TOPIC), H256(MINT_TOPIC)];
+        if self.discover_pools {
+            events_signatures.extend([H256(INITIALIZE_TOPIC), H256(POOL_CREATED_TOPIC)]);
+        }
         events.filter = events
             .filter
             .address(vec![])
@@ -91,9 +126,49 @@ impl EventRetrieving for UniswapV3PoolEventFetcher {
 pub struct RecentEventsCache {
     /// (block number, event log index) used as a Key
     events: BTreeMap<(u64, usize), Event<UniswapV3Event>>,
+    /// Only set when pools are discovered from factory events, in which case
+    /// only the events of the factory and the pools it created are kept.
+    addresses: Option<PoolAddresses>,
+}
+
+#[derive(Debug)]
+struct PoolAddresses {
+    factory: H160,
+    pools: HashSet<H160>,
+}
+
+impl PoolAddresses {
+    /// Whether the event was emitted by the factory or one of its pools. Pools
+    /// created by the factory are remembered so that their events are kept
+    /// from then on.
+    fn keep(&mut self, event: &Event<UniswapV3Event>) -> bool {
+        let Some(meta) = &event.meta else {
+            return false;
+        };
+        match &event.data {
+            UniswapV3Event::PoolCreated(created) if meta.address == self.factory => {
+                self.pools.insert(created.pool);
+                true
+            }
+            UniswapV3Event::PoolCreated(_) => false,
+            _ => self.pools.contains(&meta.address),
+        }
+    }
 }
 
 impl RecentEventsCache {
+    /// Creates a cache that only keeps the events emitted by the factory and
+    /// the pools it created, starting with the already known pools.
+    pub fn for_factory(factory: H160, pools: impl IntoIterator<Item = H160>) -> Self {
+        Self {
+            events: Default::default(),
+            addresses: Some(PoolAddresses {
+                factory,
+                pools: pools.into_iter().collect(),
+            }),
+        }
+    }
+
     /// Removes all events up to the specified block, excluding the specified
     /// block.
     pub fn remove_events_older_than_block(&mut self, delete_up_to_block_number: u64) {
@@ -127,6 +202,11 @@ impl EventStoring<UniswapV3Event> for RecentEventsCache {
 
     async fn append_events(&mut self, events: Vec<Event<UniswapV3Event>>) -> Result<()> {
         for event in events {
+            if let Some(addresses) = &mut self.addresses {
+                if !addresses.keep(&event) {
+                    continue;
+                }
+            }
             let event_meta = event.meta.as_ref().context("event meta is empty")?;
             self.events
                 .insert((event_meta.block_number, event_meta.log_index), event);
@@ -173,7 +253,10 @@ mod tests {
             .map(|key| (key, build_event(key)))
             .collect();
 
-        let mut cache = RecentEventsCache { events };
+        let mut cache = RecentEventsCache {
+            events,
+            ..Default::default()
+        };
         cache.remove_events_older_than_block(2);
 
         assert_eq!(cache.events.keys().cloned().collect::<Vec<_>>(), keys[2..]);
@@ -193,7 +276,10 @@ mod tests {
             .map(|key| (key, build_event(key)))
             .collect();
 
-        let mut cache = RecentEventsCache { events };
+        let mut cache = RecentEventsCache {
+            events,
+            ..Default::default()
+        };
         cache.remove_events_newer_than_block(2);
 
         assert_eq!(cache.events.keys().cloned().collect::<Vec<_>>(), keys[..2]);
@@ -222,7 +308,10 @@ mod tests {
             .into_iter()
             .map(|key| (key, build_event(key)))
             .collect();
-        let cache = RecentEventsCache { events };
+        let cache = RecentEventsCache {
+            events,
+            ..Default::default()
+        };
 
         // test inside range
         let expected_events = keys[2..=5]
@@ -244,7 +333,10 @@ mod tests {
     #[tokio::test]
     async fn append_events_test() {
         let events = BTreeMap::from([((1, 0), build_event((1, 0))), ((1, 1), build_event((1, 1)))]);
-        let mut cache = RecentEventsCache { events };
+        let mut cache = RecentEventsCache {
+            events,
+            ..Default::default()
+        };
 
         let appended_events = vec![
             build_event((1, 2)),
@@ -277,8 +369,55 @@ mod tests {
             .into_iter()
             .map(|key| (key, build_event(key)))
             .collect();
-        let cache = RecentEventsCache { events };
+        let cache = RecentEventsCache {
+            events,
+            ..Default::default()
+        };
         let result = cache.last_event_block().await.unwrap();
         assert_eq!(result, 2);
     }
+
+    #[tokio::test]
+    async fn factory_cache_only_keeps_events_of_factory_pools() {
+        let (factory, known, created, other) = (
+            H160::from_low_u64_be(1),
+            H160::from_low_u64_be(2),
+            H160::from_low_u64_be(3),
+            H160::from_low_u64_be(4),
+        );
+        let event = |address, log_index, data| Event {
+            data,
+            meta: Some(EventMetadata {
+                address,
+                block_number: 1,
+                log_index,
+                ..Default::default()
+            }),
+        };
+        let pool_created = |pool| {
+            UniswapV3Event::PoolCreated(PoolCreated {
+                pool,
+                ..Default::default()
+            })
+        };
+        let swap = || UniswapV3Event::Swap(Swap::default());
+
+        let mut cache = RecentEventsCache::for_factory(factory, [known]);
+        cache
+            .append_events(vec![
+                event(known, 0, swap()),
+                event(created, 1, swap()),
+                event(other, 2, pool_created(other)),
+                event(factory, 3, pool_created(created)),
+                event(created, 4, swap()),
+                event(other, 5, swap()),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            cache.events.keys().cloned().collect::<Vec<_>>(),
+            [(1, 0), (1, 3), (1, 4)]
+        );
+    }
 }
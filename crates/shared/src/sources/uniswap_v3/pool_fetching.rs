@@ -7,8 +7,10 @@ use {
         event_handling::{EventHandler, EventStoring, MAX_REORG_BLOCK_COUNT},
         maintenance::Maintaining,
         recent_block_cache::Block,
+        token_info::TokenInfoFetching,
     },
     anyhow::{Context, Result},
+    contracts::IUniswapV3Factory,
     ethcontract::{Event, H160, U256},
     ethrpc::{
         current_block::{BlockRetrieving, RangeInclusive},
@@ -19,15 +21,19 @@ use {
     num::{rational::Ratio, BigInt, Zero},
     number::serialization::HexOrDecimalU256,
     reqwest::{Client, Url},
-    serde::Serialize,
+    serde::{Deserialize, Serialize},
     serde_with::{serde_as, DisplayFromStr},
     std::{
         collections::{BTreeMap, HashMap, HashSet},
         ops::Neg,
+        path::{Path, PathBuf},
         sync::{Arc, Mutex},
     },
 };
 
+/// Mean gas used by a swap, as estimated by <https://dune.com/queries/1044812>.
+const POOL_MEAN_GAS: u64 = 108_163;
+
 #[async_trait::async_trait]
 pub trait PoolFetching: Send + Sync {
     async fn fetch(
@@ -101,7 +107,7 @@ impl TryFrom<PoolData> for PoolInfo {
                 fee: Ratio::new(pool.fee_tier.as_u32(), 1_000_000u32),
             },
             gas_stats: PoolStats {
-                mean_gas: U256::from(108_163), // as estimated by https://dune.com/queries/1044812
+                mean_gas: U256::from(POOL_MEAN_GAS),
             },
         })
     }
@@ -109,6 +115,8 @@ impl TryFrom<PoolData> for PoolInfo {
 
 #[derive(Default)]
 struct PoolsCheckpoint {
+    /// H160 is pool id while TokenPair is a pair or tokens for each pool.
+    pools_by_token_pair: HashMap<TokenPair, HashSet<H160>>,
     /// Pools state.
     pools: HashMap<H160, PoolInfo>,
     /// Block number for which `pools` field was populated.
@@ -118,10 +126,27 @@ struct PoolsCheckpoint {
     missing_pools: HashSet<H160>,
 }
 
+impl PoolsCheckpoint {
+    /// Adds newly discovered pools to the checkpoint. Already known pools are
+    /// left untouched.
+    fn register_pools(&mut self, pools: Vec<PoolInfo>) -> Result<()> {
+        for pool in pools {
+            let pair =
+                TokenPair::new(pool.tokens[0].id, pool.tokens[1].id).context("cant create pair")?;
+            self.pools_by_token_pair
+                .entry(pair)
+                .or_default()
+                .insert(pool.address);
+            self.pools.entry(pool.address).or_insert(pool);
+        }
+        Ok(())
+    }
+}
+
 struct PoolsCheckpointHandler {
-    graph_api: UniV3SubgraphClient,
-    /// H160 is pool id while TokenPair is a pair or tokens for each pool.
-    pools_by_token_pair: HashMap<TokenPair, HashSet<H160>>,
+    /// Not set when pools are discovered from factory events, in which case
+    /// all known pools are always part of the checkpoint.
+    graph_api: Option<UniV3SubgraphClient>,
     /// Pools state on a specific block number in history considered reorg safe
     pools_checkpoint: Mutex<PoolsCheckpoint>,
 }
@@ -173,14 +198,14 @@ impl PoolsCheckpointHandler {
             .filter_map(|pool| Some((pool.id, pool.try_into().ok()?)))
             .collect::<HashMap<_, _>>();
         let pools_checkpoint = Mutex::new(PoolsCheckpoint {
+            pools_by_token_pair,
             pools,
             block_number: registered_pools.fetched_block_number,
             ..Default::default()
         });
 
         Ok(Self {
-            graph_api,
-            pools_by_token_pair,
+            graph_api: Some(graph_api),
             pools_checkpoint,
         })
     }
@@ -189,37 +214,41 @@ impl PoolsCheckpointHandler {
     /// exist in the checkpoint. For the ones that don't exist, flag as
     /// missing and expect to exist after the next maintenance run.
     fn get(&self, token_pairs: &HashSet<TokenPair>) -> (HashMap<H160, PoolInfo>, u64) {
-        let mut pool_ids = token_pairs
+        let mut pools_checkpoint = self.pools_checkpoint.lock().unwrap();
+        let pool_ids = token_pairs
             .iter()
-            .filter_map(|pair| self.pools_by_token_pair.get(pair))
+            .filter_map(|pair| pools_checkpoint.pools_by_token_pair.get(pair))
             .flatten()
-            .peekable();
+            .copied()
+            .collect::<Vec<_>>();
 
         tracing::trace!("get checkpoint for pool_ids: {:?}", pool_ids);
 
-        match pool_ids.peek() {
-            Some(_) => {
-                let mut pools_checkpoint = self.pools_checkpoint.lock().unwrap();
-                let (existing_pools, missing_pools): (HashMap<H160, PoolInfo>, Vec<H160>) =
-                    pool_ids.partition_map(|pool_id| match pools_checkpoint.pools.get(pool_id) {
-                        Some(entry) => Either::Left((*pool_id, entry.clone())),
-                        _ => Either::Right(pool_id),
-                    });
-                tracing::trace!(
-                    "cache hit: {:?}, cache miss: {:?}",
-                    existing_pools.keys(),
-                    missing_pools
-                );
-                pools_checkpoint.missing_pools.extend(missing_pools);
-                (existing_pools, pools_checkpoint.block_number)
-            }
-            None => Default::default(),
+        if pool_ids.is_empty() {
+            return Default::default();
         }
+
+        let (existing_pools, missing_pools): (HashMap<H160, PoolInfo>, Vec<H160>) = pool_ids
+            .into_iter()
+            .partition_map(|pool_id| match pools_checkpoint.pools.get(&pool_id) {
+                Some(entry) => Either::Left((pool_id, entry.clone())),
+                _ => Either::Right(pool_id),
+            });
+        tracing::trace!(
+            "cache hit: {:?}, cache miss: {:?}",
+            existing_pools.keys(),
+            missing_pools
+        );
+        pools_checkpoint.missing_pools.extend(missing_pools);
+        (existing_pools, pools_checkpoint.block_number)
     }
 
     /// Fetches state/ticks for missing pools and moves them from
     /// `missing_pools` to `pools`
     async fn update_missing_pools(&self) -> Result<()> {
+        let Some(graph_api) = &self.graph_api else {
+            return Ok(());
+        };
         let (missing_pools, block_number) = {
             let checkpoint = self.pools_checkpoint.lock().unwrap();
             (checkpoint.missing_pools.clone(), checkpoint.block_number)
@@ -228,8 +257,7 @@ impl PoolsCheckpointHandler {
 
         let pool_ids = missing_pools.into_iter().collect::<Vec<_>>();
         let start = std::time::Instant::now();
-        let pools = self
-            .graph_api
+        let pools = graph_api
             .get_pools_with_ticks_by_ids(&pool_ids, block_number)
             .await;
         tracing::debug!(
@@ -256,12 +284,128 @@ impl PoolsCheckpointHandler {
     }
 }
 
+/// Configuration for discovering pools by indexing the factory's
+/// `PoolCreated` events instead of querying the subgraph.
+#[derive(Clone, Debug)]
+pub struct FactoryEventsDiscovery {
+    /// The Uniswap V3 compatible factory contract.
+    pub factory: H160,
+    /// The block to start indexing from when no checkpoint exists, usually
+    /// the block in which the factory was deployed.
+    pub start_block: u64,
+    /// File the reorg safe checkpoint gets persisted to, so restarts don't
+    /// have to index all events from `start_block` again.
+    pub checkpoint_path: Option<PathBuf>,
+}
+
+impl FactoryEventsDiscovery {
+    /// Creates the configuration for indexing the given factory. Without a
+    /// start block indexing starts at the block the factory was deployed in,
+    /// which is only known for the Uniswap V3 deployments.
+    pub async fn new(
+        factory: H160,
+        start_block: Option<u64>,
+        checkpoint_path: Option<PathBuf>,
+        chain_id: u64,
+    ) -> Result<Self> {
+        let start_block = match start_block {
+            Some(start_block) => start_block,
+            None => {
+                let contract = IUniswapV3Factory::raw_contract();
+                anyhow::ensure!(
+                    crate::contracts::deployment(contract, chain_id)
+                        .is_ok_and(|deployment| deployment.address == factory),
+                    "deployment block of factory {factory:?} is unknown, configure the start block"
+                );
+                crate::contracts::deployment_block(contract, chain_id).await?
+            }
+        };
+        Ok(Self {
+            factory,
+            start_block,
+            checkpoint_path,
+        })
+    }
+}
+
+struct PoolDiscovery {
+    config: FactoryEventsDiscovery,
+    token_info: Arc<dyn TokenInfoFetching>,
+}
+
+impl PoolDiscovery {
+    /// Builds the initial state of all pools created by the factory in the
+    /// given events. Pools with tokens whose decimals can't be determined are
+    /// skipped.
+    async fn created_pools(&self, events: &[Event<UniswapV3Event>]) -> Result<Vec<PoolInfo>> {
+        let created = events
+            .iter()
+            .filter_map(|event| match &event.data {
+                // Anyone can emit an event with the same signature, so only
+                // trust the ones coming from the factory.
+                UniswapV3Event::PoolCreated(created)
+                    if event.meta.as_ref()?.address == self.config.factory =>
+                {
+                    Some(created)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if created.is_empty() {
+            return Ok(Default::default());
+        }
+
+        let tokens = created
+            .iter()
+            .flat_map(|created| [created.token_0, created.token_1])
+            .unique()
+            .collect::<Vec<_>>();
+        let decimals = futures::future::try_join_all(tokens.into_iter().map(|token| async move {
+            let info = self.token_info.get_token_info(token).await?;
+            Result::<_>::Ok((token, info.decimals))
+        }))
+        .await?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+        let token = |id: H160| {
+            Some(Token {
+                id,
+                decimals: decimals.get(&id).copied().flatten()?,
+            })
+        };
+
+        Ok(created
+            .into_iter()
+            .filter_map(|created| {
+                let (Some(token0), Some(token1)) = (token(created.token_0), token(created.token_1))
+                else {
+                    tracing::debug!(pool = ?created.pool, "skipping pool with unknown token decimals");
+                    return None;
+                };
+                Some(PoolInfo {
+                    address: created.pool,
+                    tokens: vec![token0, token1],
+                    state: PoolState {
+                        fee: Ratio::new(created.fee, 1_000_000u32),
+                        ..Default::default()
+                    },
+                    gas_stats: PoolStats {
+                        mean_gas: U256::from(POOL_MEAN_GAS),
+                    },
+                })
+            })
+            .collect())
+    }
+}
+
 pub struct UniswapV3PoolFetcher {
     /// Pools state on a specific block number in history considered reorg safe
     checkpoint: PoolsCheckpointHandler,
     /// Recent events used on top of pools_checkpoint to get the `latest_block`
     /// pools state.
     events: tokio::sync::Mutex<EventHandler<UniswapV3PoolEventFetcher, RecentEventsCache>>,
+    /// Set when pools are discovered from factory events.
+    discovery: Option<PoolDiscovery>,
 }
 
 impl UniswapV3PoolFetcher {
@@ -283,12 +427,66 @@ impl UniswapV3PoolFetcher {
 
         let events = tokio::sync::Mutex::new(EventHandler::new(
             block_retriever,
-            UniswapV3PoolEventFetcher(web3),
+            UniswapV3PoolEventFetcher {
+                web3,
+                discover_pools: false,
+            },
             RecentEventsCache::default(),
             Some(init_block),
         ));
 
-        Ok(Self { checkpoint, events })
+        Ok(Self {
+            checkpoint,
+            events,
+            discovery: None,
+        })
+    }
+
+    /// Creates a pool fetcher that doesn't depend on the subgraph. Pools are
+    /// discovered from the factory's `PoolCreated` events and their ticks are
+    /// reconstructed from `Mint` and `Burn` events.
+    ///
+    /// Newly created pools become available once their creation is considered
+    /// reorg safe.
+    pub async fn from_factory_events(
+        config: FactoryEventsDiscovery,
+        web3: Web3,
+        block_retriever: Arc<dyn BlockRetrieving>,
+        token_info: Arc<dyn TokenInfoFetching>,
+    ) -> Result<Self> {
+        let web3 = ethrpc::instrumented::instrument_with_label(&web3, "uniswapV3".into());
+        let checkpoint = match &config.checkpoint_path {
+            Some(path) => load_checkpoint(path).await?,
+            None => None,
+        }
+        .unwrap_or_else(|| PoolsCheckpoint {
+            block_number: config.start_block.saturating_sub(1),
+            ..Default::default()
+        });
+        tracing::debug!(
+            block = %checkpoint.block_number, pools = %checkpoint.pools.len(),
+            "initialized uniswap v3 pools checkpoint",
+        );
+
+        let init_block = block_retriever.block(checkpoint.block_number).await?;
+        let events = tokio::sync::Mutex::new(EventHandler::new(
+            block_retriever,
+            UniswapV3PoolEventFetcher {
+                web3,
+                discover_pools: true,
+            },
+            RecentEventsCache::for_factory(config.factory, checkpoint.pools.keys().copied()),
+            Some(init_block),
+        ));
+
+        Ok(Self {
+            checkpoint: PoolsCheckpointHandler {
+                graph_api: None,
+                pools_checkpoint: Mutex::new(checkpoint),
+            },
+            events,
+            discovery: Some(PoolDiscovery { config, token_info }),
+        })
     }
 
     /// Moves the checkpoint to the block `latest_block - MAX_REORG_BLOCK_COUNT`
@@ -306,18 +504,27 @@ impl UniswapV3PoolFetcher {
         );
 
         if new_checkpoint_block > old_checkpoint_block {
-            {
+            let changed = {
                 let block_range =
                     RangeInclusive::try_new(old_checkpoint_block + 1, new_checkpoint_block)?;
                 let events = self.events.lock().await.store().get_events(block_range);
+                let created_pools = match &self.discovery {
+                    Some(discovery) => discovery.created_pools(&events).await?,
+                    None => Default::default(),
+                };
+                // Only the block number moves forward without any events, which
+                // is not worth persisting as these blocks are cheap to index again.
+                let changed = !events.is_empty() || !created_pools.is_empty();
                 let mut checkpoint = self.checkpoint.pools_checkpoint.lock().unwrap();
+                checkpoint.register_pools(created_pools)?;
                 append_events(&mut checkpoint.pools, events);
                 checkpoint.block_number = new_checkpoint_block;
                 tracing::debug!(
                     "checkpoint block number updated to {}",
                     checkpoint.block_number
                 );
-            }
+                changed
+            };
 
             // clear events with block number lower than `new_checkpoint_block`
             self.events
@@ -325,11 +532,125 @@ impl UniswapV3PoolFetcher {
                 .await
                 .store_mut()
                 .remove_events_older_than_block(new_checkpoint_block);
+
+            if let Some(path) = self
+                .discovery
+                .as_ref()
+                .and_then(|discovery| discovery.config.checkpoint_path.as_ref())
+                .filter(|_| changed)
+            {
+                let persisted =
+                    PersistedCheckpoint::from(&*self.checkpoint.pools_checkpoint.lock().unwrap());
+                if let Err(err) = store_checkpoint(path, &persisted).await {
+                    tracing::warn!(?err, "failed to persist uniswap v3 pools checkpoint");
+                }
+            }
         }
         Ok(())
     }
 }
 
+/// Serialization format of a persisted [`PoolsCheckpoint`].
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedCheckpoint {
+    block_number: u64,
+    pools: Vec<PersistedPool>,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedPool {
+    address: H160,
+    tokens: Vec<Token>,
+    /// Fee in hundredths of a basis point.
+    fee: u32,
+    #[serde_as(as = "HexOrDecimalU256")]
+    sqrt_price: U256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    liquidity: U256,
+    #[serde_as(as = "DisplayFromStr")]
+    tick: BigInt,
+    #[serde_as(as = "BTreeMap<DisplayFromStr, DisplayFromStr>")]
+    liquidity_net: BTreeMap<BigInt, BigInt>,
+}
+
+impl From<&PoolsCheckpoint> for PersistedCheckpoint {
+    fn from(checkpoint: &PoolsCheckpoint) -> Self {
+        Self {
+            block_number: checkpoint.block_number,
+            pools: checkpoint
+                .pools
+                .values()
+                .map(|pool| PersistedPool {
+                    address: pool.address,
+                    tokens: pool.tokens.clone(),
+                    fee: pool.state.fee.numer() * (1_000_000 / pool.state.fee.denom()),
+                    sqrt_price: pool.state.sqrt_price,
+                    liquidity: pool.state.liquidity,
+                    tick: pool.state.tick.clone(),
+                    liquidity_net: pool.state.liquidity_net.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<PersistedCheckpoint> for PoolsCheckpoint {
+    type Error = anyhow::Error;
+
+    fn try_from(persisted: PersistedCheckpoint) -> Result<Self> {
+        let mut checkpoint = PoolsCheckpoint {
+            block_number: persisted.block_number,
+            ..Default::default()
+        };
+        checkpoint.register_pools(
+            persisted
+                .pools
+                .into_iter()
+                .map(|pool| PoolInfo {
+                    address: pool.address,
+                    tokens: pool.tokens,
+                    state: PoolState {
+                        sqrt_price: pool.sqrt_price,
+                        liquidity: pool.liquidity,
+                        tick: pool.tick,
+                        liquidity_net: pool.liquidity_net,
+                        fee: Ratio::new(pool.fee, 1_000_000u32),
+                    },
+                    gas_stats: PoolStats {
+                        mean_gas: U256::from(POOL_MEAN_GAS),
+                    },
+                })
+                .collect(),
+        )?;
+        Ok(checkpoint)
+    }
+}
+
+/// Loads a persisted checkpoint, returns `None` if there is none yet.
+async fn load_checkpoint(path: &Path) -> Result<Option<PoolsCheckpoint>> {
+    let content = match tokio::fs::read(path).await {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("failed to read {path:?}")),
+    };
+    let persisted: PersistedCheckpoint =
+        serde_json::from_slice(&content).with_context(|| format!("failed to parse {path:?}"))?;
+    Ok(Some(persisted.try_into()?))
+}
+
+/// Atomically replaces the persisted checkpoint, so a crash while writing
+/// never leaves a corrupted file behind.
+async fn store_checkpoint(path: &Path, checkpoint: &PersistedCheckpoint) -> Result<()> {
+    let content = serde_json::to_vec(checkpoint)?;
+    let tmp = path.with_extension("tmp");
+    tokio::fs::write(&tmp, content).await?;
+    tokio::fs::rename(&tmp, path).await?;
+    Ok(())
+}
+
 #[async_trait::async_trait]
 impl PoolFetching for UniswapV3PoolFetcher {
     async fn fetch(
@@ -462,6 +783,12 @@ fn append_events(pools: &mut HashMap<H160, PoolInfo>, events: Vec<Event<UniswapV
                     pool.liquidity = swap.liquidity.into();
                     pool.sqrt_price = swap.sqrt_price_x96;
                 }
+                UniswapV3Event::Initialize(initialize) => {
+                    pool.tick = BigInt::from(initialize.tick);
+                    pool.sqrt_price = initialize.sqrt_price_x96;
+                }
+                // pools are registered before applying events
+                UniswapV3Event::PoolCreated(_) => {}
             }
         }
     }
@@ -496,8 +823,14 @@ impl Maintaining for UniswapV3PoolFetcher {
 mod tests {
     use {
         super::*,
-        crate::ethrpc,
-        contracts::uniswap_v3_pool::event_data::{Burn, Mint, Swap},
+        crate::{
+            ethrpc,
+            token_info::{MockTokenInfoFetching, TokenInfo},
+        },
+        contracts::{
+            i_uniswap_v3_factory::event_data::PoolCreated,
+            uniswap_v3_pool::event_data::{Burn, Initialize, Mint, Swap},
+        },
         ethcontract::EventMetadata,
         serde_json::json,
         std::{ops::Sub, str::FromStr},
@@ -721,6 +1054,175 @@ mod tests {
         );
     }
 
+    #[test]
+    fn append_events_test_initialize() {
+        let address = H160::from_low_u64_be(1);
+        let pool = PoolInfo {
+            address,
+            ..Default::default()
+        };
+        let mut pools = HashMap::from([(address, pool)]);
+
+        let event = Event {
+            data: UniswapV3Event::Initialize(Initialize {
+                sqrt_price_x96: 1.into(),
+                tick: -3,
+            }),
+            meta: Some(EventMetadata {
+                address,
+                ..Default::default()
+            }),
+        };
+        append_events(&mut pools, vec![event]);
+
+        assert_eq!(pools[&address].state.tick, BigInt::from(-3));
+        assert_eq!(pools[&address].state.sqrt_price, U256::from(1));
+        assert_eq!(pools[&address].state.liquidity, U256::zero());
+    }
+
+    #[tokio::test]
+    async fn created_pools_only_from_factory() {
+        let factory = H160::from_low_u64_be(1);
+        let (token0, token1, unknown) = (
+            H160::from_low_u64_be(2),
+            H160::from_low_u64_be(3),
+            H160::from_low_u64_be(4),
+        );
+        let created = |emitter: u64, token_1: H160, pool: u64| Event {
+            data: UniswapV3Event::PoolCreated(PoolCreated {
+                token_0: token0,
+                token_1,
+                fee: 3000,
+                tick_spacing: 60,
+                pool: H160::from_low_u64_be(pool),
+            }),
+            meta: Some(EventMetadata {
+                address: H160::from_low_u64_be(emitter),
+                ..Default::default()
+            }),
+        };
+
+        let mut token_info = MockTokenInfoFetching::new();
+        token_info.expect_get_token_info().returning(move |token| {
+            Ok(TokenInfo {
+                decimals: (token != unknown).then_some(18),
+                symbol: None,
+            })
+        });
+        let discovery = PoolDiscovery {
+            config: FactoryEventsDiscovery {
+                factory,
+                start_block: 0,
+                checkpoint_path: None,
+            },
+            token_info: Arc::new(token_info),
+        };
+
+        let pools = discovery
+            .created_pools(&[
+                created(1, token1, 10),
+                // not emitted by the factory
+                created(5, token1, 11),
+                // token without decimals
+                created(1, unknown, 12),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            pools,
+            vec![PoolInfo {
+                address: H160::from_low_u64_be(10),
+                tokens: vec![
+                    Token {
+                        id: token0,
+                        decimals: 18,
+                    },
+                    Token {
+                        id: token1,
+                        decimals: 18,
+                    },
+                ],
+                state: PoolState {
+                    fee: Ratio::new(3000, 1_000_000),
+                    ..Default::default()
+                },
+                gas_stats: PoolStats {
+                    mean_gas: U256::from(POOL_MEAN_GAS),
+                },
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn factory_start_block_defaults_to_deployment() {
+        let factory = IUniswapV3Factory::raw_contract().networks["1"].address;
+        let config = FactoryEventsDiscovery::new(factory, None, None, 1)
+            .await
+            .unwrap();
+        assert_eq!(config.start_block, 12369621);
+
+        let config = FactoryEventsDiscovery::new(factory, Some(42), None, 1)
+            .await
+            .unwrap();
+        assert_eq!(config.start_block, 42);
+
+        let other = H160::from_low_u64_be(1);
+        assert!(FactoryEventsDiscovery::new(other, None, None, 1)
+            .await
+            .is_err());
+        assert_eq!(
+            FactoryEventsDiscovery::new(other, Some(42), None, 1)
+                .await
+                .unwrap()
+                .start_block,
+            42
+        );
+    }
+
+    #[test]
+    fn persisted_checkpoint_roundtrip() {
+        let pool = PoolInfo {
+            address: H160::from_low_u64_be(1),
+            tokens: vec![
+                Token {
+                    id: H160::from_low_u64_be(2),
+                    decimals: 6,
+                },
+                Token {
+                    id: H160::from_low_u64_be(3),
+                    decimals: 18,
+                },
+            ],
+            state: PoolState {
+                sqrt_price: 4.into(),
+                liquidity: 5.into(),
+                tick: BigInt::from(-6),
+                liquidity_net: BTreeMap::from([
+                    (BigInt::from(-60), BigInt::from(5)),
+                    (BigInt::from(60), BigInt::from(-5)),
+                ]),
+                fee: Ratio::new(500, 1_000_000),
+            },
+            gas_stats: PoolStats {
+                mean_gas: U256::from(POOL_MEAN_GAS),
+            },
+        };
+        let mut checkpoint = PoolsCheckpoint {
+            block_number: 42,
+            ..Default::default()
+        };
+        checkpoint.register_pools(vec![pool.clone()]).unwrap();
+
+        let serialized = serde_json::to_vec(&PersistedCheckpoint::from(&checkpoint)).unwrap();
+        let persisted: PersistedCheckpoint = serde_json::from_slice(&serialized).unwrap();
+        let restored = PoolsCheckpoint::try_from(persisted).unwrap();
+
+        assert_eq!(restored.block_number, 42);
+        assert_eq!(restored.pools, HashMap::from([(pool.address, pool)]));
+        assert_eq!(restored.pools_by_token_pair, checkpoint.pools_by_token_pair);
+    }
+
     #[tokio::test]
     #[ignore]
     async fn uniswap_v3_pool_fetcher_constructor_test() {
@@ -733,14 +1235,9 @@ mod tests {
                 .await
                 .unwrap();
 
-        assert!(!fetcher.checkpoint.pools_by_token_pair.is_empty());
-        assert!(!fetcher
-            .checkpoint
-            .pools_checkpoint
-            .lock()
-            .unwrap()
-            .pools
-            .is_empty());
+        let checkpoint = fetcher.checkpoint.pools_checkpoint.lock().unwrap();
+        assert!(!checkpoint.pools_by_token_pair.is_empty());
+        assert!(!checkpoint.pools.is_empty());
     }
 
     #[tokio::test]
@@ -0,0 +1,61 @@
+use {
+    contracts::ICurvePool,
+    ethcontract::Bytes,
+    primitive_types::U256,
+    shared::interaction::{EncodedInteraction, Interaction},
+};
+
+/// A swap of an exact input amount through a Curve StableSwap pool. The
+/// output tokens are always sent to the caller (i.e. the settlement contract).
+#[derive(Debug)]
+pub struct CurveInteraction {
+    pub pool: ICurvePool,
+    pub index_in: usize,
+    pub index_out: usize,
+    pub amount_in: U256,
+    pub min_amount_out: U256,
+}
+
+impl Interaction for CurveInteraction {
+    fn encode(&self) -> EncodedInteraction {
+        let method = self.pool.exchange(
+            self.index_in as i128,
+            self.index_out as i128,
+            self.amount_in,
+            self.min_amount_out,
+        );
+        let calldata = method.tx.data.expect("no calldata").0;
+        (self.pool.address(), 0.into(), Bytes(calldata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, contracts::dummy_contract, hex_literal::hex, primitive_types::H160};
+
+    #[test]
+    fn encode_curve_exchange() {
+        let pool = dummy_contract!(ICurvePool, H160::from_low_u64_be(1));
+        let interaction = CurveInteraction {
+            pool: pool.clone(),
+            index_in: 2,
+            index_out: 0,
+            amount_in: 3.into(),
+            min_amount_out: 4.into(),
+        };
+
+        let (target, value, call) = interaction.encode();
+        assert_eq!(target, pool.address());
+        assert_eq!(value, U256::zero());
+        assert_eq!(
+            call.0,
+            hex!(
+                "3df02124"
+                "0000000000000000000000000000000000000000000000000000000000000002"
+                "0000000000000000000000000000000000000000000000000000000000000000"
+                "0000000000000000000000000000000000000000000000000000000000000003"
+                "0000000000000000000000000000000000000000000000000000000000000004"
+            )
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod balancer_v2;
+pub mod curve;
 pub mod order_converter;
 pub mod slippage;
 pub mod uniswap_v2;
@@ -30,6 +31,7 @@ use {
                 },
                 swap::fixed_point::Bfp,
             },
+            curve::pool_fetching::Pool as CurvePool,
             uniswap_v2::pool_fetching::Pool,
             uniswap_v3::pool_fetching::PoolInfo,
         },
@@ -47,6 +49,7 @@ pub enum Liquidity {
     BalancerStable(StablePoolOrder),
     LimitOrder(LimitOrder),
     Concentrated(ConcentratedLiquidity),
+    Curve(CurvePoolOrder),
 }
 
 impl Liquidity {
@@ -60,6 +63,7 @@ impl Liquidity {
                 .map(|pair| vec![pair])
                 .unwrap_or_default(),
             Liquidity::Concentrated(amm) => vec![amm.tokens],
+            Liquidity::Curve(amm) => amm.pool.token_pairs(),
         }
     }
 
@@ -71,6 +75,7 @@ impl Liquidity {
             Liquidity::BalancerStable(amm) => Some(amm.address),
             Liquidity::LimitOrder(_) => None,
             Liquidity::Concentrated(amm) => Some(amm.pool.address),
+            Liquidity::Curve(amm) => Some(amm.pool.address),
         }
     }
 }
@@ -436,6 +441,30 @@ impl Settleable for ConcentratedLiquidity {
     }
 }
 
+/// Curve StableSwap pool with an arbitrary number of pegged coins.
+#[derive(Clone)]
+#[cfg_attr(test, derive(Derivative))]
+#[cfg_attr(test, derivative(PartialEq))]
+pub struct CurvePoolOrder {
+    pub pool: CurvePool,
+    #[cfg_attr(test, derivative(PartialEq = "ignore"))]
+    pub settlement_handling: Arc<dyn SettlementHandling<Self>>,
+}
+
+impl std::fmt::Debug for CurvePoolOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Curve pool {:?}", self.pool.address)
+    }
+}
+
+impl Settleable for CurvePoolOrder {
+    type Execution = AmmOrderExecution;
+
+    fn settlement_handling(&self) -> &dyn SettlementHandling<Self> {
+        &*self.settlement_handling
+    }
+}
+
 #[cfg(test)]
 impl Default for ConstantProductOrder {
     fn default() -> Self {
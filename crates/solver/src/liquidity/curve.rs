@@ -0,0 +1,185 @@
+use {
+    super::{AmmOrderExecution, CurvePoolOrder, SettlementHandling},
+    crate::{
+        interactions::{
+            allowances::{AllowanceManager, AllowanceManaging, Allowances, Approval},
+            CurveInteraction,
+        },
+        liquidity::Liquidity,
+        liquidity_collector::LiquidityCollecting,
+        settlement::SettlementEncoder,
+    },
+    anyhow::{Context, Result},
+    contracts::{GPv2Settlement, ICurvePool},
+    model::TokenPair,
+    primitive_types::H160,
+    shared::{
+        ethrpc::Web3,
+        http_solver::model::TokenAmount,
+        recent_block_cache::Block,
+        sources::curve::pool_fetching::CurvePoolFetching,
+    },
+    std::{collections::HashSet, sync::Arc},
+};
+
+pub struct CurveLiquidity {
+    web3: Web3,
+    pool_fetcher: Arc<dyn CurvePoolFetching>,
+    settlement_allowances: Box<dyn AllowanceManaging>,
+}
+
+impl CurveLiquidity {
+    pub fn new(
+        web3: Web3,
+        settlement: &GPv2Settlement,
+        pool_fetcher: Arc<dyn CurvePoolFetching>,
+    ) -> Self {
+        let settlement_allowances =
+            Box::new(AllowanceManager::new(web3.clone(), settlement.address()));
+        Self {
+            web3,
+            pool_fetcher,
+            settlement_allowances,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LiquidityCollecting for CurveLiquidity {
+    async fn get_liquidity(
+        &self,
+        pairs: HashSet<TokenPair>,
+        at_block: Block,
+    ) -> Result<Vec<Liquidity>> {
+        let mut result = Vec::new();
+        for pool in self.pool_fetcher.fetch(pairs, at_block).await? {
+            let tokens = pool.tokens.iter().map(|token| token.address).collect();
+            let allowances = self
+                .settlement_allowances
+                .get_allowances(tokens, pool.address)
+                .await?;
+
+            result.push(Liquidity::Curve(CurvePoolOrder {
+                settlement_handling: Arc::new(SettlementHandler {
+                    pool: ICurvePool::at(&self.web3, pool.address),
+                    tokens: pool.tokens.iter().map(|token| token.address).collect(),
+                    allowances,
+                }),
+                pool,
+            }));
+        }
+        Ok(result)
+    }
+}
+
+pub struct SettlementHandler {
+    pool: ICurvePool,
+    /// The pool coins, ordered by their index in the pool contract.
+    tokens: Vec<H160>,
+    allowances: Allowances,
+}
+
+impl SettlementHandler {
+    pub fn new(pool: ICurvePool, tokens: Vec<H160>, allowances: Allowances) -> Self {
+        Self {
+            pool,
+            tokens,
+            allowances,
+        }
+    }
+
+    /// Curve pools only support swapping an exact input amount, so the swap
+    /// sells the entire `input_max` and expects at least `output` in return.
+    pub fn settle(
+        &self,
+        input_max: TokenAmount,
+        output: TokenAmount,
+    ) -> Result<(Option<Approval>, CurveInteraction)> {
+        let index_in = self.index_of(input_max.token)?;
+        let index_out = self.index_of(output.token)?;
+        let approval = self.allowances.approve_token_or_default(input_max.clone());
+
+        Ok((
+            approval,
+            CurveInteraction {
+                pool: self.pool.clone(),
+                index_in,
+                index_out,
+                amount_in: input_max.amount,
+                min_amount_out: output.amount,
+            },
+        ))
+    }
+
+    fn index_of(&self, token: H160) -> Result<usize> {
+        self.tokens
+            .iter()
+            .position(|&coin| coin == token)
+            .with_context(|| format!("token {token:?} not traded by Curve pool"))
+    }
+}
+
+impl SettlementHandling<CurvePoolOrder> for SettlementHandler {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn encode(&self, execution: AmmOrderExecution, encoder: &mut SettlementEncoder) -> Result<()> {
+        let (approval, swap) = self.settle(execution.input_max, execution.output)?;
+        if let Some(approval) = approval {
+            encoder.append_to_execution_plan_internalizable(
+                Arc::new(approval),
+                execution.internalizable,
+            );
+        }
+        encoder.append_to_execution_plan_internalizable(Arc::new(swap), execution.internalizable);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, contracts::dummy_contract, maplit::hashmap};
+
+    #[test]
+    fn settles_by_coin_index() {
+        let tokens = vec![
+            H160::from_low_u64_be(1),
+            H160::from_low_u64_be(2),
+            H160::from_low_u64_be(3),
+        ];
+        let pool = dummy_contract!(ICurvePool, H160::from_low_u64_be(42));
+        let handler = SettlementHandler::new(
+            pool.clone(),
+            tokens.clone(),
+            Allowances::new(pool.address(), hashmap! { tokens[2] => 100.into() }),
+        );
+
+        let (approval, swap) = handler
+            .settle(
+                TokenAmount::new(tokens[2], 100),
+                TokenAmount::new(tokens[0], 99),
+            )
+            .unwrap();
+        assert_eq!(approval, None);
+        assert_eq!(swap.index_in, 2);
+        assert_eq!(swap.index_out, 0);
+        assert_eq!(swap.amount_in, 100.into());
+        assert_eq!(swap.min_amount_out, 99.into());
+
+        let (approval, _) = handler
+            .settle(
+                TokenAmount::new(tokens[1], 100),
+                TokenAmount::new(tokens[0], 99),
+            )
+            .unwrap();
+        assert_ne!(approval, None);
+
+        assert!(handler
+            .settle(
+                TokenAmount::new(H160::from_low_u64_be(4), 100),
+                TokenAmount::new(tokens[0], 99),
+            )
+            .is_err());
+    }
+}
@@ -77,6 +77,7 @@ pub enum Liquidity {
     WeightedProduct(WeightedProductPool),
     Stable(StablePool),
     ConcentratedLiquidity(ConcentratedLiquidityPool),
+    Curve(CurvePool),
     LimitOrder(ForeignLimitOrder),
 }
 
@@ -175,6 +176,28 @@ pub struct ConcentratedLiquidityPool {
     pub fee: BigDecimal,
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CurvePool {
+    pub id: String,
+    pub address: H160,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub gas_estimate: U256,
+    pub tokens: HashMap<H160, CurveReserve>,
+    pub amplification_parameter: BigDecimal,
+    pub fee: BigDecimal,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CurveReserve {
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub balance: U256,
+    pub decimals: u8,
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -29,6 +29,8 @@ pub enum Kind {
     SimulationFailed {
         block: BlockNo,
         tx: Tx,
+        #[serde(default)]
+        revert_reason: Option<String>,
         succeeded_once: bool,
     },
     ZeroScore,
@@ -19,6 +19,7 @@ pub fn to_domain(
                 block,
                 tx,
                 succeeded_once,
+                ..
             } => notification::Kind::SimulationFailed(
                 *block,
                 eth::Tx {
@@ -76,6 +76,7 @@ pub fn to_domain(auction: &Auction) -> Result<auction::Auction, Error> {
                 Liquidity::ConcentratedLiquidity(liquidity) => {
                     concentrated_liquidity_pool::to_domain(liquidity)
                 }
+                Liquidity::Curve(liquidity) => curve_pool::to_domain(liquidity),
                 Liquidity::LimitOrder(liquidity) => Ok(foreign_limit_order::to_domain(liquidity)),
             })
             .try_collect()?,
@@ -236,6 +237,43 @@ mod concentrated_liquidity_pool {
     }
 }
 
+mod curve_pool {
+    use super::*;
+
+    pub fn to_domain(pool: &CurvePool) -> Result<liquidity::Liquidity, Error> {
+        let reserves = liquidity::curve::Reserves::new(
+            pool.tokens
+                .iter()
+                .map(|(address, token)| liquidity::curve::Reserve {
+                    asset: eth::Asset {
+                        token: eth::TokenAddress(*address),
+                        amount: token.balance,
+                    },
+                    decimals: token.decimals,
+                })
+                .collect(),
+        )
+        .ok_or("invalid curve pool reserves")?;
+
+        Ok(liquidity::Liquidity {
+            id: liquidity::Id(pool.id.clone()),
+            address: pool.address,
+            gas: eth::Gas(pool.gas_estimate),
+            state: liquidity::State::Curve(liquidity::curve::Pool {
+                reserves,
+                amplification_parameter: conv::decimal_to_rational(&pool.amplification_parameter)
+                    .filter(|a| *a.denom() == 1.into())
+                    .map(|a| *a.numer())
+                    .ok_or("invalid curve amplification parameter")?,
+                fee: conv::decimal_to_rational(&pool.fee)
+                    .as_ref()
+                    .and_then(liquidity::curve::Fee::new)
+                    .ok_or("invalid curve pool fee")?,
+            }),
+        })
+    }
+}
+
 mod foreign_limit_order {
     use super::*;
 
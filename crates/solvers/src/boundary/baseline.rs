@@ -187,6 +187,30 @@ fn to_boundary_amms(liquidity: &[liquidity::Liquidity]) -> HashMap<TokenPair, Ve
                         }
                     }
                 }
+                liquidity::State::Concentrated(pool) => {
+                    let token_pair = to_boundary_token_pair(&pool.tokens);
+                    amms.entry(token_pair).or_default().push(Amm {
+                        id: liquidity.id.clone(),
+                        token_pair,
+                        pool: Pool::Concentrated {
+                            pool: pool.clone(),
+                            gas: liquidity.gas,
+                        },
+                    });
+                }
+                liquidity::State::Curve(pool) => {
+                    for pair in pool.reserves.token_pairs() {
+                        let token_pair = to_boundary_token_pair(&pair);
+                        amms.entry(token_pair).or_default().push(Amm {
+                            id: liquidity.id.clone(),
+                            token_pair,
+                            pool: Pool::Curve {
+                                pool: pool.clone(),
+                                gas: liquidity.gas,
+                            },
+                        });
+                    }
+                }
                 // The baseline solver does not currently support other AMMs.
                 _ => {}
             };
@@ -206,6 +230,14 @@ enum Pool {
     ConstantProduct(boundary::liquidity::constant_product::Pool),
     WeightedProduct(boundary::liquidity::weighted_product::Pool),
     Stable(boundary::liquidity::stable::Pool),
+    Concentrated {
+        pool: liquidity::concentrated::Pool,
+        gas: eth::Gas,
+    },
+    Curve {
+        pool: liquidity::curve::Pool,
+        gas: eth::Gas,
+    },
 }
 
 impl BaselineSolvable for Amm {
@@ -214,6 +246,26 @@ impl BaselineSolvable for Amm {
             Pool::ConstantProduct(pool) => pool.get_amount_out(out_token, input),
             Pool::WeightedProduct(pool) => pool.get_amount_out(out_token, input),
             Pool::Stable(pool) => pool.get_amount_out(out_token, input),
+            Pool::Concentrated { pool, .. } => {
+                let (amount, token) = input;
+                if self.token_pair.other(&token)? != out_token {
+                    return None;
+                }
+                pool.output(&eth::Asset {
+                    token: eth::TokenAddress(token),
+                    amount,
+                })
+            }
+            Pool::Curve { pool, .. } => {
+                let (amount, token) = input;
+                pool.output(
+                    &eth::Asset {
+                        token: eth::TokenAddress(token),
+                        amount,
+                    },
+                    eth::TokenAddress(out_token),
+                )
+            }
         }
     }
 
@@ -222,6 +274,26 @@ impl BaselineSolvable for Amm {
             Pool::ConstantProduct(pool) => pool.get_amount_in(in_token, out),
             Pool::WeightedProduct(pool) => pool.get_amount_in(in_token, out),
             Pool::Stable(pool) => pool.get_amount_in(in_token, out),
+            Pool::Concentrated { pool, .. } => {
+                let (amount, token) = out;
+                if self.token_pair.other(&token)? != in_token {
+                    return None;
+                }
+                pool.input(&eth::Asset {
+                    token: eth::TokenAddress(token),
+                    amount,
+                })
+            }
+            Pool::Curve { pool, .. } => {
+                let (amount, token) = out;
+                pool.input(
+                    &eth::Asset {
+                        token: eth::TokenAddress(token),
+                        amount,
+                    },
+                    eth::TokenAddress(in_token),
+                )
+            }
         }
     }
 
@@ -230,6 +302,9 @@ impl BaselineSolvable for Amm {
             Pool::ConstantProduct(pool) => pool.gas_cost(),
             Pool::WeightedProduct(pool) => pool.gas_cost(),
             Pool::Stable(pool) => pool.gas_cost(),
+            Pool::Concentrated { gas, .. } | Pool::Curve { gas, .. } => {
+                gas.0.try_into().unwrap_or(usize::MAX)
+            }
         }
     }
 }
@@ -340,6 +340,8 @@ fn to_boundary_auction(
                     to_big_rational(&state.fee.0),
                 )
             }
+            // Curve pools are not supported by the legacy solver API.
+            liquidity::State::Curve(_) => continue,
             liquidity::State::LimitOrder(state) => {
                 let index = mapping.orders.len();
                 mapping.orders.push(Order::Liquidity(liquidity, state));
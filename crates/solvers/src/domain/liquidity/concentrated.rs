@@ -4,6 +4,8 @@ use {
     std::collections::BTreeMap,
 };
 
+mod math;
+
 /// State for a UniswapV3-like concentrated liquidity pool.
 #[derive(Clone, Debug)]
 pub struct Pool {
@@ -15,6 +17,149 @@ pub struct Pool {
     pub fee: Fee,
 }
 
+impl Pool {
+    /// Computes the amount of the other token that is received for swapping
+    /// exactly `input` into the pool. Returns `None` if the pool does not have
+    /// enough liquidity to execute the swap.
+    pub fn output(&self, input: &eth::Asset) -> Option<U256> {
+        let zero_for_one = self.zero_for_one(input.token)?;
+        let swap = self.swap(zero_for_one, input.amount, true)?;
+        (swap.amount_in == input.amount).then_some(swap.amount_out)
+    }
+
+    /// Computes the amount of the other token that needs to be swapped into
+    /// the pool in order to receive exactly `output`. Returns `None` if the
+    /// pool does not have enough liquidity to execute the swap.
+    pub fn input(&self, output: &eth::Asset) -> Option<U256> {
+        let zero_for_one = !self.zero_for_one(output.token)?;
+        let swap = self.swap(zero_for_one, output.amount, false)?;
+        (swap.amount_out == output.amount).then_some(swap.amount_in)
+    }
+
+    /// Returns whether or not the specified token is `token0` of the pool or
+    /// `None` if the token is not traded by the pool.
+    fn zero_for_one(&self, token: eth::TokenAddress) -> Option<bool> {
+        let (token0, token1) = self.tokens.get();
+        if token == token0 {
+            Some(true)
+        } else if token == token1 {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Simulates a swap the same way the Uniswap V3 pool contract does,
+    /// crossing initialized ticks until the specified amount is exhausted.
+    fn swap(&self, zero_for_one: bool, amount: U256, exact_input: bool) -> Option<Swap> {
+        let fee = self.fee.pips()?;
+        let tick_spacing = self.fee.tick_spacing()?;
+        let sqrt_price_limit = if zero_for_one {
+            math::min_sqrt_ratio() + U256::one()
+        } else {
+            math::max_sqrt_ratio() - U256::one()
+        };
+
+        let mut remaining = amount;
+        let mut swap = Swap::default();
+        let mut sqrt_price = self.sqrt_price.0;
+        let mut tick = self.tick.0;
+        let mut liquidity = self.liquidity.0;
+
+        while !remaining.is_zero() && sqrt_price != sqrt_price_limit {
+            let (tick_next, initialized) =
+                self.next_initialized_tick_within_one_word(tick, tick_spacing, zero_for_one);
+            let tick_next = tick_next.clamp(math::MIN_TICK, math::MAX_TICK);
+            let sqrt_price_next = math::sqrt_ratio_at_tick(tick_next)?;
+
+            let target = if (zero_for_one && sqrt_price_next < sqrt_price_limit)
+                || (!zero_for_one && sqrt_price_next > sqrt_price_limit)
+            {
+                sqrt_price_limit
+            } else {
+                sqrt_price_next
+            };
+            let step = math::compute_swap_step(
+                sqrt_price,
+                target,
+                liquidity,
+                remaining,
+                exact_input,
+                fee,
+            )?;
+            sqrt_price = step.sqrt_price_next;
+
+            let amount_in = step.amount_in.checked_add(step.fee_amount)?;
+            if exact_input {
+                remaining = remaining.checked_sub(amount_in)?;
+            } else {
+                remaining = remaining.checked_sub(step.amount_out)?;
+            }
+            swap.amount_in = swap.amount_in.checked_add(amount_in)?;
+            swap.amount_out = swap.amount_out.checked_add(step.amount_out)?;
+
+            if sqrt_price == sqrt_price_next {
+                if initialized {
+                    let liquidity_net = self.liquidity_net.get(&Tick(tick_next))?.0;
+                    let liquidity_net = if zero_for_one {
+                        liquidity_net.checked_neg()?
+                    } else {
+                        liquidity_net
+                    };
+                    liquidity = liquidity.checked_add_signed(liquidity_net)?;
+                }
+                tick = if zero_for_one {
+                    tick_next - 1
+                } else {
+                    tick_next
+                };
+            }
+        }
+
+        Some(swap)
+    }
+
+    /// Equivalent to `TickBitmap.nextInitializedTickWithinOneWord` where the
+    /// bitmap is derived from the ticks with net liquidity.
+    fn next_initialized_tick_within_one_word(
+        &self,
+        tick: i32,
+        tick_spacing: i32,
+        lte: bool,
+    ) -> (i32, bool) {
+        let compressed = tick.div_euclid(tick_spacing);
+        if lte {
+            let word_start = (compressed - compressed.rem_euclid(256)) * tick_spacing;
+            match self
+                .liquidity_net
+                .range(Tick(word_start)..=Tick(compressed * tick_spacing))
+                .next_back()
+            {
+                Some((next, _)) => (next.0, true),
+                None => (word_start, false),
+            }
+        } else {
+            let compressed = compressed + 1;
+            let word_end = (compressed + 255 - compressed.rem_euclid(256)) * tick_spacing;
+            match self
+                .liquidity_net
+                .range(Tick(compressed * tick_spacing)..=Tick(word_end))
+                .next()
+            {
+                Some((next, _)) => (next.0, true),
+                None => (word_end, false),
+            }
+        }
+    }
+}
+
+/// The total amounts swapped by a concentrated liquidity pool.
+#[derive(Debug, Default)]
+struct Swap {
+    amount_in: U256,
+    amount_out: U256,
+}
+
 /// A compressed representation of the current exchange rate between the tokens
 /// belonging to a pool.
 ///
@@ -48,3 +193,28 @@ pub struct LiquidityNet(pub i128);
 /// added by the uniswap DAO.
 #[derive(Clone, Debug)]
 pub struct Fee(pub eth::Rational);
+
+impl Fee {
+    /// Returns the fee in hundredths of a bip, which is the representation
+    /// used by the pool contracts. Returns `None` if the fee can't be exactly
+    /// represented this way.
+    fn pips(&self) -> Option<u32> {
+        let scaled = self.0.numer().checked_mul(1_000_000.into())?;
+        if !(scaled % self.0.denom()).is_zero() {
+            return None;
+        }
+        (scaled / self.0.denom()).try_into().ok()
+    }
+
+    /// Returns the tick spacing that the Uniswap V3 factory enables for the
+    /// fee tier of the pool.
+    fn tick_spacing(&self) -> Option<i32> {
+        match self.pips()? {
+            100 => Some(1),
+            500 => Some(10),
+            3000 => Some(60),
+            10000 => Some(200),
+            _ => None,
+        }
+    }
+}
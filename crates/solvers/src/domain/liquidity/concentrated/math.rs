@@ -0,0 +1,380 @@
+//! Port of the Uniswap V3 `TickMath`, `SqrtPriceMath` and `SwapMath`
+//! libraries. All functions return `None` where the Solidity implementation
+//! would revert.
+//!
+//! <https://github.com/Uniswap/v3-core/tree/main/contracts/libraries>
+
+use ethereum_types::{U256, U512};
+
+/// The minimum tick that can be used on any pool.
+pub const MIN_TICK: i32 = -887272;
+/// The maximum tick that can be used on any pool.
+pub const MAX_TICK: i32 = -MIN_TICK;
+
+/// The minimum value that can be returned from [`sqrt_ratio_at_tick`].
+pub fn min_sqrt_ratio() -> U256 {
+    U256::from(4295128739_u64)
+}
+
+/// The maximum value that can be returned from [`sqrt_ratio_at_tick`].
+pub fn max_sqrt_ratio() -> U256 {
+    U256::from_dec_str("1461446703485210103287273052203988822378723970342").unwrap()
+}
+
+/// Denominator of the fee expressed in hundredths of a bip.
+const FEE_DENOMINATOR: u32 = 1_000_000;
+
+/// The Q64.96 fixed point resolution.
+const RESOLUTION: usize = 96;
+
+fn q96() -> U256 {
+    U256::one() << RESOLUTION
+}
+
+/// Calculates `sqrt(1.0001^tick) * 2^96`.
+pub fn sqrt_ratio_at_tick(tick: i32) -> Option<U256> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return None;
+    }
+    let abs_tick = tick.unsigned_abs();
+
+    let factor = |hex: &str| U256::from_str_radix(hex, 16).unwrap();
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        factor("fffcb933bd6fad37aa2d162d1a594001")
+    } else {
+        U256::one() << 128
+    };
+    for (bit, hex) in [
+        (0x2, "fff97272373d413259a46990580e213a"),
+        (0x4, "fff2e50f5f656932ef12357cf3c7fdcc"),
+        (0x8, "ffe5caca7e10e4e61c3624eaa0941cd0"),
+        (0x10, "ffcb9843d60f6159c9db58835c926644"),
+        (0x20, "ff973b41fa98c081472e6896dfb254c0"),
+        (0x40, "ff2ea16466c96a3843ec78b326b52861"),
+        (0x80, "fe5dee046a99a2a811c461f1969c3053"),
+        (0x100, "fcbe86c7900a88aedcffc83b479aa3a4"),
+        (0x200, "f987a7253ac413176f2b074cf7815e54"),
+        (0x400, "f3392b0822b70005940c7a398e4b70f3"),
+        (0x800, "e7159475a2c29b7443b29c7fa6e889d9"),
+        (0x1000, "d097f3bdfd2022b8845ad8f792aa5825"),
+        (0x2000, "a9f746462d870fdf8a65dc1f90e061e5"),
+        (0x4000, "70d869a156d2a1b890bb3df62baf32f7"),
+        (0x8000, "31be135f97d08fd981231505542fcfa6"),
+        (0x10000, "9aa508b5b7a84e1c677de54f3e99bc9"),
+        (0x20000, "5d6af8dedb81196699c329225ee604"),
+        (0x40000, "2216e584f5fa1ea926041bedfe98"),
+        (0x80000, "48a170391f7dc42444e8fa2"),
+    ] {
+        if abs_tick & bit != 0 {
+            ratio = (ratio * factor(hex)) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Round up to go from a Q128.128 to a Q128.96 number. This ensures that
+    // the result is always consistent with `getTickAtSqrtRatio`.
+    let round_up = !(ratio % (U256::one() << 32)).is_zero();
+    Some((ratio >> 32) + U256::from(round_up as u8))
+}
+
+/// Computes `a * b / denominator` with full precision, rounding down.
+fn mul_div(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let result = a.full_mul(b) / U512::from(denominator);
+    U256::try_from(result).ok()
+}
+
+/// Computes `a * b / denominator` with full precision, rounding up.
+fn mul_div_rounding_up(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    let result = mul_div(a, b, denominator)?;
+    if (a.full_mul(b) % U512::from(denominator)).is_zero() {
+        Some(result)
+    } else {
+        result.checked_add(U256::one())
+    }
+}
+
+/// Computes `a / b`, rounding up.
+fn div_rounding_up(a: U256, b: U256) -> Option<U256> {
+    if b.is_zero() {
+        return None;
+    }
+    let (quotient, remainder) = a.div_mod(b);
+    Some(quotient + U256::from(!remainder.is_zero() as u8))
+}
+
+fn fits_in_u160(value: U256) -> bool {
+    value.bits() <= 160
+}
+
+/// Computes the amount of `token0` between two prices for the given
+/// liquidity.
+pub fn amount0_delta(
+    sqrt_ratio_a: U256,
+    sqrt_ratio_b: U256,
+    liquidity: u128,
+    round_up: bool,
+) -> Option<U256> {
+    let (sqrt_ratio_a, sqrt_ratio_b) = if sqrt_ratio_a > sqrt_ratio_b {
+        (sqrt_ratio_b, sqrt_ratio_a)
+    } else {
+        (sqrt_ratio_a, sqrt_ratio_b)
+    };
+    if sqrt_ratio_a.is_zero() {
+        return None;
+    }
+
+    let numerator1 = U256::from(liquidity) << RESOLUTION;
+    let numerator2 = sqrt_ratio_b - sqrt_ratio_a;
+    if round_up {
+        div_rounding_up(
+            mul_div_rounding_up(numerator1, numerator2, sqrt_ratio_b)?,
+            sqrt_ratio_a,
+        )
+    } else {
+        Some(mul_div(numerator1, numerator2, sqrt_ratio_b)? / sqrt_ratio_a)
+    }
+}
+
+/// Computes the amount of `token1` between two prices for the given
+/// liquidity.
+pub fn amount1_delta(
+    sqrt_ratio_a: U256,
+    sqrt_ratio_b: U256,
+    liquidity: u128,
+    round_up: bool,
+) -> Option<U256> {
+    let (sqrt_ratio_a, sqrt_ratio_b) = if sqrt_ratio_a > sqrt_ratio_b {
+        (sqrt_ratio_b, sqrt_ratio_a)
+    } else {
+        (sqrt_ratio_a, sqrt_ratio_b)
+    };
+
+    let liquidity = U256::from(liquidity);
+    let difference = sqrt_ratio_b - sqrt_ratio_a;
+    if round_up {
+        mul_div_rounding_up(liquidity, difference, q96())
+    } else {
+        mul_div(liquidity, difference, q96())
+    }
+}
+
+fn next_sqrt_price_from_amount0_rounding_up(
+    sqrt_price: U256,
+    liquidity: u128,
+    amount: U256,
+    add: bool,
+) -> Option<U256> {
+    if amount.is_zero() {
+        return Some(sqrt_price);
+    }
+    let numerator1 = U256::from(liquidity) << RESOLUTION;
+
+    if add {
+        if let Some(product) = amount.checked_mul(sqrt_price) {
+            if let Some(denominator) = numerator1.checked_add(product) {
+                return mul_div_rounding_up(numerator1, sqrt_price, denominator);
+            }
+        }
+        div_rounding_up(numerator1, (numerator1 / sqrt_price).checked_add(amount)?)
+    } else {
+        let product = amount.checked_mul(sqrt_price)?;
+        if numerator1 <= product {
+            return None;
+        }
+        let next = mul_div_rounding_up(numerator1, sqrt_price, numerator1 - product)?;
+        fits_in_u160(next).then_some(next)
+    }
+}
+
+fn next_sqrt_price_from_amount1_rounding_down(
+    sqrt_price: U256,
+    liquidity: u128,
+    amount: U256,
+    add: bool,
+) -> Option<U256> {
+    let liquidity = U256::from(liquidity);
+    if add {
+        let quotient = if fits_in_u160(amount) {
+            (amount << RESOLUTION).checked_div(liquidity)?
+        } else {
+            mul_div(amount, q96(), liquidity)?
+        };
+        let next = sqrt_price.checked_add(quotient)?;
+        fits_in_u160(next).then_some(next)
+    } else {
+        let quotient = if fits_in_u160(amount) {
+            div_rounding_up(amount << RESOLUTION, liquidity)?
+        } else {
+            mul_div_rounding_up(amount, q96(), liquidity)?
+        };
+        sqrt_price
+            .checked_sub(quotient)
+            .filter(|next| !next.is_zero())
+    }
+}
+
+fn next_sqrt_price_from_input(
+    sqrt_price: U256,
+    liquidity: u128,
+    amount_in: U256,
+    zero_for_one: bool,
+) -> Option<U256> {
+    if sqrt_price.is_zero() || liquidity == 0 {
+        return None;
+    }
+    if zero_for_one {
+        next_sqrt_price_from_amount0_rounding_up(sqrt_price, liquidity, amount_in, true)
+    } else {
+        next_sqrt_price_from_amount1_rounding_down(sqrt_price, liquidity, amount_in, true)
+    }
+}
+
+fn next_sqrt_price_from_output(
+    sqrt_price: U256,
+    liquidity: u128,
+    amount_out: U256,
+    zero_for_one: bool,
+) -> Option<U256> {
+    if sqrt_price.is_zero() || liquidity == 0 {
+        return None;
+    }
+    if zero_for_one {
+        next_sqrt_price_from_amount1_rounding_down(sqrt_price, liquidity, amount_out, false)
+    } else {
+        next_sqrt_price_from_amount0_rounding_up(sqrt_price, liquidity, amount_out, false)
+    }
+}
+
+/// The result of swapping within a single tick range.
+#[derive(Debug)]
+pub struct SwapStep {
+    pub sqrt_price_next: U256,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub fee_amount: U256,
+}
+
+/// Computes the result of swapping some amount in, or out, given the
+/// parameters of the swap. `amount_remaining` is an input amount when
+/// `exact_input` is set and an output amount otherwise. `fee` is expressed in
+/// hundredths of a bip.
+pub fn compute_swap_step(
+    sqrt_price_current: U256,
+    sqrt_price_target: U256,
+    liquidity: u128,
+    amount_remaining: U256,
+    exact_input: bool,
+    fee: u32,
+) -> Option<SwapStep> {
+    let zero_for_one = sqrt_price_current >= sqrt_price_target;
+    let fee_complement = U256::from(FEE_DENOMINATOR.checked_sub(fee)?);
+
+    let mut amount_in = U256::zero();
+    let mut amount_out = U256::zero();
+    let sqrt_price_next = if exact_input {
+        let amount_remaining_less_fee = mul_div(
+            amount_remaining,
+            fee_complement,
+            U256::from(FEE_DENOMINATOR),
+        )?;
+        amount_in = if zero_for_one {
+            amount0_delta(sqrt_price_target, sqrt_price_current, liquidity, true)?
+        } else {
+            amount1_delta(sqrt_price_current, sqrt_price_target, liquidity, true)?
+        };
+        if amount_remaining_less_fee >= amount_in {
+            sqrt_price_target
+        } else {
+            next_sqrt_price_from_input(
+                sqrt_price_current,
+                liquidity,
+                amount_remaining_less_fee,
+                zero_for_one,
+            )?
+        }
+    } else {
+        amount_out = if zero_for_one {
+            amount1_delta(sqrt_price_target, sqrt_price_current, liquidity, false)?
+        } else {
+            amount0_delta(sqrt_price_current, sqrt_price_target, liquidity, false)?
+        };
+        if amount_remaining >= amount_out {
+            sqrt_price_target
+        } else {
+            next_sqrt_price_from_output(
+                sqrt_price_current,
+                liquidity,
+                amount_remaining,
+                zero_for_one,
+            )?
+        }
+    };
+
+    let max = sqrt_price_target == sqrt_price_next;
+    if zero_for_one {
+        if !(max && exact_input) {
+            amount_in = amount0_delta(sqrt_price_next, sqrt_price_current, liquidity, true)?;
+        }
+        if !(max && !exact_input) {
+            amount_out = amount1_delta(sqrt_price_next, sqrt_price_current, liquidity, false)?;
+        }
+    } else {
+        if !(max && exact_input) {
+            amount_in = amount1_delta(sqrt_price_current, sqrt_price_next, liquidity, true)?;
+        }
+        if !(max && !exact_input) {
+            amount_out = amount0_delta(sqrt_price_current, sqrt_price_next, liquidity, false)?;
+        }
+    }
+
+    // Cap the output amount to not exceed the remaining output amount.
+    if !exact_input && amount_out > amount_remaining {
+        amount_out = amount_remaining;
+    }
+
+    let fee_amount = if exact_input && sqrt_price_next != sqrt_price_target {
+        // We didn't reach the target, so take the remainder of the maximum
+        // input as fee.
+        amount_remaining - amount_in
+    } else {
+        mul_div_rounding_up(amount_in, U256::from(fee), fee_complement)?
+    };
+
+    Some(SwapStep {
+        sqrt_price_next,
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqrt_ratio_at_tick_bounds() {
+        assert_eq!(sqrt_ratio_at_tick(MIN_TICK), Some(min_sqrt_ratio()));
+        assert_eq!(sqrt_ratio_at_tick(MAX_TICK), Some(max_sqrt_ratio()));
+        assert_eq!(sqrt_ratio_at_tick(MIN_TICK - 1), None);
+        assert_eq!(sqrt_ratio_at_tick(MAX_TICK + 1), None);
+    }
+
+    #[test]
+    fn sqrt_ratio_at_tick_values() {
+        assert_eq!(sqrt_ratio_at_tick(0), Some(q96()));
+        assert_eq!(
+            sqrt_ratio_at_tick(50).unwrap(),
+            U256::from_dec_str("79426470787362580746886972461").unwrap()
+        );
+        assert_eq!(
+            sqrt_ratio_at_tick(-50).unwrap(),
+            U256::from_dec_str("79030349367926598376800521322").unwrap()
+        );
+    }
+}
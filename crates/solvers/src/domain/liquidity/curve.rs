@@ -0,0 +1,117 @@
+use {
+    crate::domain::{eth, liquidity},
+    ethereum_types::U256,
+    itertools::Itertools as _,
+};
+
+mod math;
+
+/// State for a Curve StableSwap pool.
+#[derive(Clone, Debug)]
+pub struct Pool {
+    pub reserves: Reserves,
+    pub amplification_parameter: U256,
+    pub fee: Fee,
+}
+
+impl Pool {
+    /// Computes the amount of `out_token` that is received for swapping
+    /// exactly `input` into the pool. Returns `None` if either token is not
+    /// traded by the pool or the swap would revert.
+    pub fn output(&self, input: &eth::Asset, out_token: eth::TokenAddress) -> Option<U256> {
+        let (i, j) = self.indices(input.token, out_token)?;
+        self.with_math(|pool| pool.dy(i, j, input.amount))
+    }
+
+    /// Computes the amount of `in_token` that needs to be swapped into the
+    /// pool in order to receive at least `output`. Returns `None` if either
+    /// token is not traded by the pool or the swap would revert.
+    pub fn input(&self, output: &eth::Asset, in_token: eth::TokenAddress) -> Option<U256> {
+        let (i, j) = self.indices(in_token, output.token)?;
+        self.with_math(|pool| {
+            let dx = pool.dx(i, j, output.amount)?;
+            (pool.dy(i, j, dx)? >= output.amount).then_some(dx)
+        })
+    }
+
+    fn indices(&self, a: eth::TokenAddress, b: eth::TokenAddress) -> Option<(usize, usize)> {
+        let index = |token| self.reserves.0.iter().position(|r| r.asset.token == token);
+        let (i, j) = (index(a)?, index(b)?);
+        (i != j).then_some((i, j))
+    }
+
+    fn with_math<T>(&self, f: impl FnOnce(&math::Pool) -> Option<T>) -> Option<T> {
+        let multipliers = self
+            .reserves
+            .iter()
+            .map(|r| math::precision_multiplier(r.decimals))
+            .collect::<Option<Vec<_>>>()?;
+        let xp = self
+            .reserves
+            .iter()
+            .zip(&multipliers)
+            .map(|(r, m)| r.asset.amount.checked_mul(*m))
+            .collect::<Option<Vec<_>>>()?;
+
+        f(&math::Pool {
+            xp: &xp,
+            multipliers: &multipliers,
+            amplification_parameter: self.amplification_parameter,
+            fee: self.fee.0,
+        })
+    }
+}
+
+/// A representation of Curve pool reserves.
+#[derive(Clone, Debug)]
+pub struct Reserves(Vec<Reserve>);
+
+impl Reserves {
+    /// Returns a new reserve instance for specified reserve entries. Returns
+    /// `None` if there are fewer than two or duplicate entries for a token.
+    pub fn new(reserves: Vec<Reserve>) -> Option<Self> {
+        if reserves.len() < 2 || !reserves.iter().map(|r| r.asset.token).all_unique() {
+            return None;
+        }
+        Some(Self(reserves))
+    }
+
+    /// Returns an iterator over the token reserves.
+    pub fn iter(&self) -> impl Iterator<Item = Reserve> + '_ {
+        self.0.iter().cloned()
+    }
+
+    /// Returns an iterator over the tokens pairs handled by the pool reserves.
+    pub fn token_pairs(&self) -> impl Iterator<Item = liquidity::TokenPair> + '_ {
+        self.0
+            .iter()
+            .tuple_combinations()
+            .map(|(a, b)| liquidity::TokenPair::new(a.asset.token, b.asset.token).expect("a != b"))
+    }
+}
+
+/// A Curve pool token reserve.
+#[derive(Clone, Debug)]
+pub struct Reserve {
+    pub asset: eth::Asset,
+    pub decimals: u8,
+}
+
+/// A Curve swap fee, in units of `1e-10` as used by the pool contracts.
+#[derive(Clone, Copy, Debug)]
+pub struct Fee(pub U256);
+
+impl Fee {
+    /// Creates a fee from its fractional representation. Returns `None` if the
+    /// fee can't be exactly represented in pool contract units or is 100% or
+    /// more.
+    pub fn new(fee: &eth::Rational) -> Option<Self> {
+        let denominator = U256::from(math::FEE_DENOMINATOR);
+        let scaled = fee.numer().checked_mul(denominator)?;
+        if !(scaled % fee.denom()).is_zero() {
+            return None;
+        }
+        let fee = scaled / fee.denom();
+        (fee < denominator).then_some(Self(fee))
+    }
+}
@@ -0,0 +1,254 @@
+//! Port of the Curve StableSwap `get_D`, `get_y` and `get_dy` pool functions.
+//! All functions return `None` where the Vyper implementation would revert.
+//!
+//! <https://github.com/curvefi/curve-contract/blob/master/contracts/pools/3pool/StableSwap3Pool.vy>
+
+use ethereum_types::U256;
+
+/// The fee denominator used by Curve pools, i.e. a fee of `FEE_DENOMINATOR`
+/// represents 100%.
+pub const FEE_DENOMINATOR: u64 = 10_000_000_000;
+
+/// The number of decimals that balances are normalized to.
+const PRECISION_DECIMALS: u8 = 18;
+
+/// The maximum number of Newton iterations before giving up on convergence.
+const MAX_ITERATIONS: usize = 255;
+
+/// The normalized state of a StableSwap pool.
+pub struct Pool<'a> {
+    /// The pool balances, normalized to 18 decimals.
+    pub xp: &'a [U256],
+    /// The multipliers used for normalizing each coin balance.
+    pub multipliers: &'a [U256],
+    pub amplification_parameter: U256,
+    pub fee: U256,
+}
+
+/// Returns the multiplier for normalizing balances of a coin with the
+/// specified decimals to 18 decimals. Returns `None` for coins with more than
+/// 18 decimals, which Curve pools do not support.
+pub fn precision_multiplier(decimals: u8) -> Option<U256> {
+    let exponent = PRECISION_DECIMALS.checked_sub(decimals)?;
+    Some(U256::exp10(exponent.into()))
+}
+
+impl Pool<'_> {
+    fn n_coins(&self) -> U256 {
+        self.xp.len().into()
+    }
+
+    /// Computes the StableSwap invariant `D` for the specified normalized
+    /// balances.
+    fn d(&self, xp: &[U256]) -> Option<U256> {
+        let n = self.n_coins();
+        let s = xp
+            .iter()
+            .try_fold(U256::zero(), |sum, x| sum.checked_add(*x))?;
+        if s.is_zero() {
+            return Some(U256::zero());
+        }
+
+        let ann = self.amplification_parameter.checked_mul(n)?;
+        let mut d = s;
+        for _ in 0..MAX_ITERATIONS {
+            let mut d_p = d;
+            for x in xp {
+                d_p = d_p.checked_mul(d)?.checked_div(x.checked_mul(n)?)?;
+            }
+            let d_prev = d;
+            let numerator = ann
+                .checked_mul(s)?
+                .checked_add(d_p.checked_mul(n)?)?
+                .checked_mul(d)?;
+            let denominator = ann
+                .checked_sub(1.into())?
+                .checked_mul(d)?
+                .checked_add(n.checked_add(1.into())?.checked_mul(d_p)?)?;
+            d = numerator.checked_div(denominator)?;
+            if abs_diff(d, d_prev) <= U256::one() {
+                return Some(d);
+            }
+        }
+        None
+    }
+
+    /// Computes the new normalized balance of coin `j` such that the invariant
+    /// is preserved when the normalized balance of coin `i` becomes `x`.
+    fn y(&self, i: usize, j: usize, x: U256) -> Option<U256> {
+        let n = self.n_coins();
+        if i == j || i >= self.xp.len() || j >= self.xp.len() {
+            return None;
+        }
+
+        let d = self.d(self.xp)?;
+        let ann = self.amplification_parameter.checked_mul(n)?;
+        let mut c = d;
+        let mut s = U256::zero();
+        for (k, xp) in self.xp.iter().enumerate() {
+            let x = if k == i {
+                x
+            } else if k == j {
+                continue;
+            } else {
+                *xp
+            };
+            s = s.checked_add(x)?;
+            c = c.checked_mul(d)?.checked_div(x.checked_mul(n)?)?;
+        }
+        c = c.checked_mul(d)?.checked_div(ann.checked_mul(n)?)?;
+        let b = s.checked_add(d.checked_div(ann)?)?;
+
+        let mut y = d;
+        for _ in 0..MAX_ITERATIONS {
+            let y_prev = y;
+            y = y
+                .checked_mul(y)?
+                .checked_add(c)?
+                .checked_div(y.checked_mul(2.into())?.checked_add(b)?.checked_sub(d)?)?;
+            if abs_diff(y, y_prev) <= U256::one() {
+                return Some(y);
+            }
+        }
+        None
+    }
+
+    /// Computes the amount of coin `j` received for swapping `dx` of coin `i`,
+    /// net of fees.
+    pub fn dy(&self, i: usize, j: usize, dx: U256) -> Option<U256> {
+        let (xp_i, xp_j) = (*self.xp.get(i)?, *self.xp.get(j)?);
+        let (multiplier_i, multiplier_j) = (*self.multipliers.get(i)?, *self.multipliers.get(j)?);
+
+        let x = xp_i.checked_add(dx.checked_mul(multiplier_i)?)?;
+        let y = self.y(i, j, x)?;
+        let dy = xp_j
+            .checked_sub(y)?
+            .checked_sub(1.into())?
+            .checked_div(multiplier_j)?;
+        let fee = self.fee.checked_mul(dy)? / U256::from(FEE_DENOMINATOR);
+        dy.checked_sub(fee)
+    }
+
+    /// Computes the amount of coin `i` that needs to be swapped in order to
+    /// receive at least `dy` of coin `j`, net of fees.
+    ///
+    /// Note that plain StableSwap pools do not implement this on-chain, so
+    /// this is the inverse of [`Pool::dy`] rounded in favour of the pool.
+    pub fn dx(&self, i: usize, j: usize, dy: U256) -> Option<U256> {
+        let (xp_i, xp_j) = (*self.xp.get(i)?, *self.xp.get(j)?);
+        let (multiplier_i, multiplier_j) = (*self.multipliers.get(i)?, *self.multipliers.get(j)?);
+
+        let fee_denominator = U256::from(FEE_DENOMINATOR);
+        let dy = div_rounding_up(
+            dy.checked_mul(fee_denominator)?,
+            fee_denominator.checked_sub(self.fee)?,
+        )?
+        .checked_add(1.into())?;
+        let y = xp_j.checked_sub(dy.checked_add(1.into())?.checked_mul(multiplier_j)?)?;
+        let x = self.y(j, i, y)?;
+        div_rounding_up(x.checked_sub(xp_i)?, multiplier_i)?.checked_add(1.into())
+    }
+}
+
+fn abs_diff(a: U256, b: U256) -> U256 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// Computes `a / b`, rounding up.
+fn div_rounding_up(a: U256, b: U256) -> Option<U256> {
+    if b.is_zero() {
+        return None;
+    }
+    let (quotient, remainder) = a.div_mod(b);
+    Some(quotient + U256::from(!remainder.is_zero() as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool<'a>(xp: &'a [U256], multipliers: &'a [U256]) -> Pool<'a> {
+        Pool {
+            xp,
+            multipliers,
+            amplification_parameter: 2000.into(),
+            fee: 1_000_000.into(),
+        }
+    }
+
+    fn normalize(balances: &[U256], decimals: &[u8]) -> (Vec<U256>, Vec<U256>) {
+        let multipliers = decimals
+            .iter()
+            .map(|d| precision_multiplier(*d).unwrap())
+            .collect::<Vec<_>>();
+        let xp = balances
+            .iter()
+            .zip(&multipliers)
+            .map(|(b, m)| b * m)
+            .collect();
+        (xp, multipliers)
+    }
+
+    #[test]
+    fn dy_matches_reference_implementation() {
+        // 3pool-like DAI/USDC/USDT balances.
+        let (xp, multipliers) = normalize(
+            &[
+                U256::exp10(18) * 33_000_000,
+                U256::exp10(6) * 35_000_000,
+                U256::exp10(6) * 40_000_000,
+            ],
+            &[18, 6, 6],
+        );
+        let pool = pool(&xp, &multipliers);
+
+        assert_eq!(
+            pool.dy(0, 1, U256::exp10(18) * 1_000),
+            Some(999_931_441.into())
+        );
+        assert_eq!(
+            pool.dy(2, 0, U256::exp10(6) * 1_000),
+            Some(U256::from_dec_str("999803652372089066070").unwrap())
+        );
+        assert_eq!(
+            pool.dy(1, 2, U256::exp10(6) * 10_000_000),
+            Some(9_998_327_247_719_u64.into())
+        );
+    }
+
+    #[test]
+    fn dx_is_sufficient_for_dy() {
+        let (xp, multipliers) = normalize(
+            &[
+                U256::exp10(18) * 33_000_000,
+                U256::exp10(6) * 35_000_000,
+                U256::exp10(6) * 40_000_000,
+            ],
+            &[18, 6, 6],
+        );
+        let pool = pool(&xp, &multipliers);
+
+        for (i, j, dy) in [
+            (0, 1, U256::exp10(6) * 1_000),
+            (1, 0, U256::exp10(18) * 1_000),
+            (2, 1, U256::exp10(6) * 5_000_000),
+        ] {
+            let dx = pool.dx(i, j, dy).unwrap();
+            assert!(pool.dy(i, j, dx).unwrap() >= dy);
+        }
+    }
+
+    #[test]
+    fn cannot_drain_pool() {
+        let (xp, multipliers) =
+            normalize(&[U256::exp10(18) * 1_000, U256::exp10(6) * 1_000], &[18, 6]);
+        let pool = pool(&xp, &multipliers);
+
+        assert_eq!(pool.dx(0, 1, U256::exp10(6) * 1_000), None);
+        assert_eq!(precision_multiplier(19), None);
+    }
+}
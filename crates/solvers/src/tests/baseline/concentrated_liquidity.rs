@@ -0,0 +1,229 @@
+//! Test cases that verify that the baseline solver can route orders through
+//! concentrated liquidity (Uniswap V3) pools, including swaps that cross
+//! initialized ticks.
+//!
+//! The pool is made up of two positions, one in the range `[97800, 98160]`
+//! and a more concentrated one in the range `[97920, 98040]`. Both orders are
+//! large enough to move the price past tick 97920, where the concentrated
+//! position's liquidity is no longer active.
+
+use {crate::tests, serde_json::json};
+
+#[tokio::test]
+async fn sell() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::File("config/example.baseline.toml".into()),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                    "decimals": 18,
+                    "symbol": "WETH",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "1412206645170290748",
+                    "trusted": true
+                },
+                "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                    "decimals": 18,
+                    "symbol": "COW",
+                    "referencePrice": "55780483870967",
+                    "availableBalance": "740264138483556450389",
+                    "trusted": true
+                }
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                    "sellAmount": "6000000000000000000",
+                    "buyAmount": "100000000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "concentratedLiquidity",
+                    "id": "0",
+                    "address": "0x4b5Ab61593A2401B1075b90c04cBCDD3F87CE011",
+                    "router": "0xe592427a0aece92de3edee1f18e0157c05861564",
+                    "gasEstimate": "110000",
+                    "tokens": [
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                        "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB"
+                    ],
+                    "liquidity": "250000000000000000000000",
+                    "tick": 97980,
+                    "sqrtPrice": "10626295218125376682983597137168",
+                    "liquidityNet": {
+                        "97800": "50000000000000000000000",
+                        "97920": "200000000000000000000000",
+                        "98040": "-200000000000000000000000",
+                        "98160": "-50000000000000000000000"
+                    },
+                    "fee": "0.003"
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z"
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "107259773734462000602446",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "6000000000000000000"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "6000000000000000000"
+                    }
+                ],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                        "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "inputAmount": "6000000000000000000",
+                        "outputAmount": "107259773734462000602446"
+                    }
+                ],
+                "score": {
+                    "kind": "riskAdjusted",
+                    "successProbability": 0.5,
+                }
+            }]
+        }),
+    );
+}
+
+#[tokio::test]
+async fn buy() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::File("config/example.baseline.toml".into()),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                    "decimals": 18,
+                    "symbol": "WETH",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "1412206645170290748",
+                    "trusted": true
+                },
+                "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                    "decimals": 18,
+                    "symbol": "COW",
+                    "referencePrice": "55780483870967",
+                    "availableBalance": "740264138483556450389",
+                    "trusted": true
+                }
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                    "sellAmount": "7000000000000000000",
+                    "buyAmount": "110000000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "buy",
+                    "partiallyFillable": false,
+                    "class": "market",
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "concentratedLiquidity",
+                    "id": "0",
+                    "address": "0x4b5Ab61593A2401B1075b90c04cBCDD3F87CE011",
+                    "router": "0xe592427a0aece92de3edee1f18e0157c05861564",
+                    "gasEstimate": "110000",
+                    "tokens": [
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                        "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB"
+                    ],
+                    "liquidity": "250000000000000000000000",
+                    "tick": 97980,
+                    "sqrtPrice": "10626295218125376682983597137168",
+                    "liquidityNet": {
+                        "97800": "50000000000000000000000",
+                        "97920": "200000000000000000000000",
+                        "98040": "-200000000000000000000000",
+                        "98160": "-50000000000000000000000"
+                    },
+                    "fee": "0.003"
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z"
+        }))
+        .await;
+
+    // Note that the interaction receives slightly more than the buy order's
+    // amount because of rounding, the difference stays in the settlement
+    // contract.
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "110000000000000000000000",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "6154083957639232535"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "110000000000000000000000"
+                    }
+                ],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                        "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "inputAmount": "6154083957639232535",
+                        "outputAmount": "110000000000000000000223"
+                    }
+                ],
+                "score": {
+                    "kind": "riskAdjusted",
+                    "successProbability": 0.5,
+                }
+            }]
+        }),
+    );
+}
@@ -0,0 +1,225 @@
+//! Test cases that verify that the baseline solver can route orders through
+//! Curve StableSwap pools with coins of different decimals.
+
+use {crate::tests, serde_json::json};
+
+#[tokio::test]
+async fn sell() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::File("config/example.baseline.toml".into()),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0x6B175474E89094C44Da98b954EedeAC495271d0F": {
+                    "decimals": 18,
+                    "symbol": "DAI",
+                    "referencePrice": "1000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48": {
+                    "decimals": 6,
+                    "symbol": "USDC",
+                    "referencePrice": "1000000000000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                }
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0x6B175474E89094C44Da98b954EedeAC495271d0F",
+                    "buyToken": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                    "sellAmount": "1000000000000000000000",
+                    "buyAmount": "999000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "curve",
+                    "id": "0",
+                    "address": "0xbEbc44782C7dB0a1A60Cb6fe97d0b483032FF1C7",
+                    "gasEstimate": "130000",
+                    "tokens": {
+                        "0x6B175474E89094C44Da98b954EedeAC495271d0F": {
+                            "balance": "33000000000000000000000000",
+                            "decimals": 18
+                        },
+                        "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48": {
+                            "balance": "35000000000000",
+                            "decimals": 6
+                        },
+                        "0xdAC17F958D2ee523a2206206994597C13D831ec7": {
+                            "balance": "40000000000000",
+                            "decimals": 6
+                        }
+                    },
+                    "amplificationParameter": "2000",
+                    "fee": "0.0001"
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z"
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0x6b175474e89094c44da98b954eedeac495271d0f": "999931441",
+                    "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48": "1000000000000000000000"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "1000000000000000000000"
+                    }
+                ],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0x6b175474e89094c44da98b954eedeac495271d0f",
+                        "outputToken": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                        "inputAmount": "1000000000000000000000",
+                        "outputAmount": "999931441"
+                    }
+                ],
+                "score": {
+                    "kind": "riskAdjusted",
+                    "successProbability": 0.5,
+                }
+            }]
+        }),
+    );
+}
+
+#[tokio::test]
+async fn buy() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::File("config/example.baseline.toml".into()),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0x6B175474E89094C44Da98b954EedeAC495271d0F": {
+                    "decimals": 18,
+                    "symbol": "DAI",
+                    "referencePrice": "1000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48": {
+                    "decimals": 6,
+                    "symbol": "USDC",
+                    "referencePrice": "1000000000000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                }
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0x6B175474E89094C44Da98b954EedeAC495271d0F",
+                    "buyToken": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                    "sellAmount": "1001000000000000000000",
+                    "buyAmount": "1000000000",
+                    "feeAmount": "0",
+                    "kind": "buy",
+                    "partiallyFillable": false,
+                    "class": "market",
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "curve",
+                    "id": "0",
+                    "address": "0xbEbc44782C7dB0a1A60Cb6fe97d0b483032FF1C7",
+                    "gasEstimate": "130000",
+                    "tokens": {
+                        "0x6B175474E89094C44Da98b954EedeAC495271d0F": {
+                            "balance": "33000000000000000000000000",
+                            "decimals": 18
+                        },
+                        "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48": {
+                            "balance": "35000000000000",
+                            "decimals": 6
+                        },
+                        "0xdAC17F958D2ee523a2206206994597C13D831ec7": {
+                            "balance": "40000000000000",
+                            "decimals": 6
+                        }
+                    },
+                    "amplificationParameter": "2000",
+                    "fee": "0.0001"
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z"
+        }))
+        .await;
+
+    // Note that the interaction receives slightly more than the buy order's
+    // amount because of rounding, the difference stays in the settlement
+    // contract.
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0x6b175474e89094c44da98b954eedeac495271d0f": "1000000000",
+                    "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48": "1000068566704291403998"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "1000000000"
+                    }
+                ],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0x6b175474e89094c44da98b954eedeac495271d0f",
+                        "outputToken": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                        "inputAmount": "1000068566704291403998",
+                        "outputAmount": "1000000003"
+                    }
+                ],
+                "score": {
+                    "kind": "riskAdjusted",
+                    "successProbability": 0.5,
+                }
+            }]
+        }),
+    );
+}
@@ -2,6 +2,8 @@
 
 mod bal_liquidity;
 mod buy_order_rounding;
+mod concentrated_liquidity;
+mod curve_liquidity;
 mod direct_swap;
 mod internalization;
 mod partial_fill;
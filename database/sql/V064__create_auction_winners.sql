@@ -0,0 +1,14 @@
+-- An auction can have multiple winners whose solutions don't overlap. Each winner gets
+-- its own reference score, i.e. the score other solutions would have contributed to the
+-- auction in place of the winner's solution had the winner not participated.
+CREATE TABLE auction_winners (
+  auction_id bigint NOT NULL,
+  -- The solver that won the auction.
+  winner bytea NOT NULL,
+  -- The score of the winning solution.
+  score numeric(78,0) NOT NULL,
+  -- The score other solutions would have contributed in place of the winner's solution.
+  reference_score numeric(78,0) NOT NULL,
+
+  PRIMARY KEY (auction_id, winner)
+);
@@ -0,0 +1,9 @@
+-- Native prices cached by the autopilot and orderbook. Persisting them allows warming up the
+-- in-memory cache after a restart.
+CREATE TABLE native_prices (
+  token bytea PRIMARY KEY,
+  -- The price of one atom of the token in atoms of the native token.
+  price double precision NOT NULL,
+  -- When the price was estimated.
+  updated_at timestamptz NOT NULL
+);
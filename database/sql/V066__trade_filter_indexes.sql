@@ -0,0 +1,9 @@
+-- Trades can be filtered by the time they got settled. Trades don't store a timestamp themselves
+-- but the block timestamp of the settlement they belong to gets recorded when the settlement is
+-- indexed. It is only known for settlements indexed after this migration.
+ALTER TABLE settlements ADD COLUMN block_timestamp timestamptz;
+CREATE INDEX settlements_block_timestamp ON settlements USING BTREE (block_timestamp);
+
+-- Trades can be filtered by their tokens. Filtering by the sell token is already covered by the
+-- `order_quoting_parameters` index.
+CREATE INDEX order_buy_token ON orders USING BTREE (buy_token);
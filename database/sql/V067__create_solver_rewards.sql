@@ -0,0 +1,22 @@
+-- Rewards of the winners of an auction, computed by the autopilot once the deadline for settling
+-- the auction has passed (see CIP-20). Winners that settled their solution in time get rewarded
+-- with the difference between their score and the reference score. Winners that failed to settle
+-- get penalised by the reference score. Both are capped by the configured score cap.
+CREATE TABLE solver_rewards (
+  auction_id bigint NOT NULL,
+  solver bytea NOT NULL,
+  -- The last block in which the solver had to settle the auction.
+  block_deadline bigint NOT NULL,
+  -- The timestamp of the deadline block which determines the accounting period of the reward.
+  block_timestamp timestamptz NOT NULL,
+  -- Whether the solver settled its solution before the deadline.
+  settled boolean NOT NULL,
+  score numeric(78,0) NOT NULL,
+  reference_score numeric(78,0) NOT NULL,
+  -- The reward in wei, negative for penalties.
+  reward numeric(78,0) NOT NULL,
+
+  PRIMARY KEY (auction_id, solver)
+);
+
+CREATE INDEX solver_rewards_solver_block_timestamp ON solver_rewards USING BTREE (solver, block_timestamp);
@@ -0,0 +1,30 @@
+-- Tracks the refunding transactions the refunder sent until they get mined or dropped. Refunds
+-- only show up in `ethflow_refunds` once they are indexed on-chain, so this table allows the
+-- refunder to keep track of in-flight transactions across restarts. It can't be named
+-- `ethflow_refunds` because that table already exists (V045) and is owned by the event indexer.
+CREATE TYPE RefundTransactionStatus AS ENUM ('pending', 'mined', 'dropped');
+
+CREATE TABLE ethflow_refund_transactions (
+  -- The account that sent the transaction.
+  sender bytea NOT NULL,
+  nonce bigint NOT NULL,
+  ethflow_contract bytea NOT NULL,
+  -- The orders the transaction refunds.
+  order_uids bytea[] NOT NULL,
+  -- The hashes of all broadcasts of the transaction, the latest one last. Each rebroadcast with
+  -- higher fees results in a new hash.
+  tx_hashes bytea[] NOT NULL,
+  -- The fees of the latest broadcast.
+  max_fee_per_gas numeric(78,0) NOT NULL,
+  max_priority_fee_per_gas numeric(78,0) NOT NULL,
+  created timestamptz NOT NULL,
+  last_broadcast timestamptz NOT NULL,
+  status RefundTransactionStatus NOT NULL,
+  -- The hash and block of the broadcast that got mined.
+  mined_tx_hash bytea,
+  mined_block_number bigint,
+
+  PRIMARY KEY (sender, nonce)
+);
+
+CREATE INDEX ethflow_refund_transactions_pending ON ethflow_refund_transactions USING BTREE (sender) WHERE status = 'pending';
@@ -0,0 +1,25 @@
+-- Conditional orders registered with the ComposableCoW contract through `ConditionalOrderCreated`
+-- events. The autopilot periodically asks the contract for the currently tradeable order of each
+-- active conditional order and posts it to the orderbook.
+CREATE TYPE ConditionalOrderStatus AS ENUM ('active', 'done');
+
+CREATE TABLE conditional_orders (
+  block_number bigint NOT NULL,
+  log_index bigint NOT NULL,
+  -- The smart contract wallet owning the orders.
+  owner bytea NOT NULL,
+  -- The parameters identifying the conditional order.
+  handler bytea NOT NULL,
+  salt bytea NOT NULL,
+  static_input bytea NOT NULL,
+  status ConditionalOrderStatus NOT NULL,
+  -- The conditional order does not get polled before this block and timestamp.
+  next_poll_block bigint,
+  next_poll_timestamp bigint,
+  -- The last order that got posted for the conditional order.
+  last_order_uid bytea,
+
+  PRIMARY KEY (block_number, log_index)
+);
+
+CREATE INDEX conditional_orders_active ON conditional_orders USING BTREE (next_poll_block) WHERE status = 'active';
@@ -0,0 +1,14 @@
+-- Records why an order was filtered from an auction or considered invalid. Only set for `filtered`
+-- and `invalid` events.
+CREATE TYPE OrderFilterReason AS ENUM (
+  'banned_user',
+  'invalid_signature',
+  'unsupported_token',
+  'insufficient_balance',
+  'dust_order',
+  'missing_price',
+  'out_of_market',
+  'other'
+);
+
+ALTER TABLE order_events ADD COLUMN reason OrderFilterReason;
@@ -0,0 +1,5 @@
+-- Past solver competitions can be filtered by the block the solutions were simulated at, by the
+-- winning solver and by the solvers that participated in them.
+CREATE INDEX CONCURRENTLY settlement_scores_simulation_block ON settlement_scores USING BTREE (simulation_block);
+CREATE INDEX CONCURRENTLY settlement_scores_winner ON settlement_scores USING BTREE (winner, auction_id);
+CREATE INDEX CONCURRENTLY solver_competitions_solutions ON solver_competitions USING GIN ((json->'solutions') jsonb_path_ops);
@@ -0,0 +1,6 @@
+-- Add `partner` policy fee kind. Partner fees are taken like volume fees but are paid out to the
+-- recipient requested in the order's app data.
+ALTER TYPE PolicyKind ADD VALUE 'partner';
+
+ALTER TABLE fee_policies
+    ADD COLUMN partner_recipient bytea;
@@ -0,0 +1,3 @@
+-- Number of consecutive polls of a conditional order that failed unexpectedly. Conditional orders
+-- that keep failing stop getting polled.
+ALTER TABLE conditional_orders ADD COLUMN failed_polls integer NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- The orderbook regularly polls the events of all orders registered since its last poll to stream
+-- them to subscribers. The table is large, so the index gets built without locking it.
+CREATE INDEX CONCURRENTLY order_events_by_timestamp ON order_events USING BTREE (timestamp);
@@ -0,0 +1,3 @@
+-- The status of an order is explained by the recent competitions whose auction contained it. The
+-- default operator class is used because the lookup checks for a key with `?`.
+CREATE INDEX CONCURRENTLY solver_competitions_auction_orders ON solver_competitions USING GIN ((json->'auction'->'orders'));